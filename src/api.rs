@@ -3,12 +3,24 @@
 //! This module provides REST API endpoints and request handling:
 //! - HTTP/2 server using Axum framework
 //! - Request handlers for ingest, query, and export operations
+//! - Synchronous address and credential lookups against the corpus
 //! - Output formatters (JSON, CSV, JSONL, text) with field classification
+//! - Streaming upload storage in the working directory
+//! - Per-client rate limits and daily byte quotas
+//! - Signed webhook callbacks when jobs finish
+//! - Readiness checks of the database, working directory and services
+//! - OpenAPI 3 document generated from the route handlers
 //! - Authentication and authorization middleware
 
 pub mod handlers;
+pub mod lookup;
+pub mod openapi;
 pub mod output;
+pub mod rate_limit;
+pub mod readiness;
 pub mod server;
+pub mod upload;
+pub mod webhook;
 
 pub use handlers::{export_db, generate_tables, import_db, ingest, server, stats, status};
 pub use output::OutputFormatter;
@@ -1,21 +1,141 @@
 //! Command handlers for Dumptruck CLI.
 
-use std::{path::Path, sync::Arc};
+use std::{
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, PoisonError},
+};
 
 use crate::{
-	api::output::{
-		CsvFormatter, DetailedRowFinding, Detection, IngestResult, JsonFormatter, JsonlFormatter,
-		OutputFormatter, PiiDetectionSummary, TextFormatter, write_output,
+	api::{
+		output::{
+			CsvFormatter, DetailedRowFinding, Detection, IngestResult, JsonFormatter,
+			JsonlFormatter, OutputFormatter, PiiDetectionSummary, TextFormatter, write_output,
+		},
+		rate_limit::RateLimiter,
+		readiness,
+		server::AppState,
+		upload::ResumableUploads,
+	},
+	cli::{IngestArgs, InputFormat, OutputFormat, ServerArgs, StatusArgs},
+	core::{
+		config::{Config, ConfigStore},
+		metrics,
+		secure_deletion::{SecureDeletionConfig, secure_delete_file},
 	},
-	cli::{IngestArgs, OutputFormat, ServerArgs, StatusArgs},
+	deploy::pipeline,
 	detection,
-	ingest::adapters::FormatAdapter,
-	storage::working_copy::WorkingCopyManager,
+	ingest::{
+		archive::{self, ExtractedFile, ExtractionLimits},
+		compression::CompressionInfo,
+		safe_ingest::{self, FileScan},
+		sqlite,
+		streaming::{
+			RowSource, RowStream, StreamStats, StreamingComboParser, StreamingCsvParser,
+			StreamingJsonRows, StreamingSqlDumpParser, StreamingSqliteReader, StreamingTsvParser,
+			TableStream,
+		},
+	},
+	storage::{
+		FsStorage, SqliteStorage, StorageAdapter,
+		job_queue::{Job, JobQueue, JobStatus},
+		working_copy::WorkingCopyManager,
+	},
 };
+use tokio_util::sync::CancellationToken;
+
+/// Number of rows processed between progress updates and cancellation checks
+const PROGRESS_BATCH_ROWS: usize = 1000;
+
+/// Rows with detections kept in a result's detailed findings; later rows are only counted
+///
+/// Bounds the memory a run needs and the size of the result stored with a job,
+/// however many rows a file has.
+const MAX_DETAILED_FINDINGS: usize = 10_000;
+
+/// How long an idle worker waits before checking the queue again without a wakeup
+const WORKER_IDLE_POLL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How often the server looks for resumable uploads that have expired
+const UPLOAD_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Storage adapter shared with the blocking threads that write to it
+type SharedStorage = Arc<Mutex<Box<dyn StorageAdapter + Send>>>;
+
+/// Storage backend that ingested rows are persisted to
+enum StorageTarget {
+	/// SQLite database at the given path
+	Database(String),
+	/// Append-only CSV file at the given path
+	Filesystem(PathBuf),
+}
+
+impl StorageTarget {
+	/// Open a storage adapter for this target
+	fn open(&self) -> Result<SharedStorage, String> {
+		let storage = match self {
+			StorageTarget::Database(path) => SqliteStorage::new(path, None)
+				.map(|s| Box::new(s) as Box<dyn StorageAdapter + Send>)
+				.map_err(|e| format!("Failed to open database {}: {}", path, e)),
+			StorageTarget::Filesystem(path) => FsStorage::new(path.clone())
+				.map(|s| Box::new(s) as Box<dyn StorageAdapter + Send>)
+				.map_err(|e| format!("Failed to open storage file {:?}: {}", path, e)),
+		}?;
+		Ok(Arc::new(Mutex::new(storage)))
+	}
+}
+
+/// Run synchronous detection and storage work on the blocking thread pool
+///
+/// Writes block on SQLite (up to the busy timeout when another process holds the
+/// lock), and a batch of rows takes a while to analyze; on the async workers a
+/// few large jobs would stall the API, event streams and metrics.
+async fn with_storage<T, F>(storage: &SharedStorage, f: F) -> T
+where
+	T: Send + 'static,
+	F: FnOnce(&mut (dyn StorageAdapter + Send)) -> T + Send + 'static,
+{
+	let storage = storage.clone();
+	let task = tokio::task::spawn_blocking(move || {
+		let mut storage = storage.lock().unwrap_or_else(PoisonError::into_inner);
+		f(storage.as_mut())
+	});
+	match task.await {
+		Ok(value) => value,
+		Err(e) => std::panic::resume_unwind(e.into_panic()),
+	}
+}
 
 /// Configuration and manager setup for ingest operations
 struct IngestContext {
-	working_copy_mgr: WorkingCopyManager,
+	working_copy_mgr: Arc<WorkingCopyManager>,
+	config: Arc<Config>,
+	storage: StorageTarget,
+	format: Option<InputFormat>,
+	verbose: u32,
+	/// Receives (rows processed, total rows) updates while a file is processed
+	progress: Option<tokio::sync::watch::Sender<(usize, usize)>>,
+	/// Evidence file ID recorded before ingest (defaults to the file's SHA-256)
+	file_id: Option<String>,
+	/// Stops the run between row batches when the job is cancelled
+	cancel: Option<CancellationToken>,
+	/// The input is an upload the job owns in the working directory, ingested in place
+	owns_input: bool,
+}
+
+impl IngestContext {
+	/// Publish a progress update if anyone is listening
+	fn report_progress(&self, rows_processed: usize, total_rows: usize) {
+		if let Some(tx) = &self.progress {
+			let _ = tx.send((rows_processed, total_rows));
+		}
+	}
+
+	/// Whether the job this run belongs to has been cancelled
+	fn is_cancelled(&self) -> bool {
+		self.cancel
+			.as_ref()
+			.is_some_and(CancellationToken::is_cancelled)
+	}
 }
 
 /// Statistics aggregated across files during ingest
@@ -26,9 +146,50 @@ struct IngestStats {
 	hashed_credentials: usize,
 	weak_passwords: usize,
 	pii_summary: PiiDetectionSummary,
+	/// Findings of the first [`MAX_DETAILED_FINDINGS`] rows with detections
 	detailed_findings: Vec<DetailedRowFinding>,
+	rows_with_findings: usize,
 	metadata: Vec<String>,
 	errors: Vec<String>,
+	/// First storage error worth retrying later, such as a locked database
+	transient_error: Option<String>,
+	/// The run stopped early because its job was cancelled
+	cancelled: bool,
+}
+
+impl IngestStats {
+	/// Record a storage error, remembering it if a retry could succeed
+	fn record_storage_error(&mut self, err_msg: String, error: &std::io::Error) {
+		if self.transient_error.is_none() && is_transient_storage_error(error) {
+			self.transient_error = Some(err_msg.clone());
+		}
+		self.errors.push(err_msg);
+	}
+
+	/// Build the reportable result from the aggregated statistics
+	fn to_result(&self) -> IngestResult {
+		let mut metadata = self.metadata.clone();
+		if self.rows_with_findings > self.detailed_findings.len() {
+			metadata.push(format!(
+				"Detailed findings limited to the first {} of {} rows with detections",
+				self.detailed_findings.len(),
+				self.rows_with_findings
+			));
+		}
+
+		IngestResult {
+			rows_processed: self.total_rows,
+			unique_addresses: self.unique_addresses,
+			hashed_credentials_detected: self.hashed_credentials,
+			weak_passwords_found: self.weak_passwords,
+			breached_addresses: 0,
+			pii_summary: Some(self.pii_summary.clone()),
+			detailed_findings: self.detailed_findings.clone(),
+			rows_with_findings: self.rows_with_findings,
+			metadata,
+			errors: self.errors.clone(),
+		}
+	}
 }
 
 /// Handle the ingest command
@@ -43,27 +204,28 @@ pub async fn ingest(args: IngestArgs) -> Result<(), String> {
 	}
 
 	let ctx = setup_ingest_context(&args)?;
+	let storage = ctx.storage.open()?;
 	let mut stats = IngestStats::default();
 
 	for file_path in &files {
-		process_single_file(&ctx, file_path, &args, &mut stats).await;
+		process_single_file(&ctx, file_path, &storage, &mut stats).await;
 	}
 
 	finalize_ingest(&args, &stats).await
 }
 
-/// Set up ingest context (config and working directory)
+/// Set up ingest context (config, working directory and storage)
 fn setup_ingest_context(args: &IngestArgs) -> Result<IngestContext, String> {
 	let config_path = args.config.as_ref().and_then(|p| p.to_str());
-	let config = crate::core::config::Config::load_with_search(config_path, args.verbose >= 2)
+	let config = Config::load_with_search(config_path, args.verbose >= 2)
 		.map_err(|e| format!("Failed to load configuration: {}", e))?;
 
 	let working_dir = if let Some(dir) = &args.working_dir {
 		dir.clone()
 	} else if let Some(config_path) = &config.working_directory.path {
-		std::path::PathBuf::from(config_path)
+		PathBuf::from(config_path)
 	} else {
-		std::path::PathBuf::from("/tmp/dumptruck")
+		PathBuf::from("/tmp/dumptruck")
 	};
 
 	let working_copy_mgr =
@@ -74,25 +236,58 @@ fn setup_ingest_context(args: &IngestArgs) -> Result<IngestContext, String> {
 		eprintln!("[INFO] Working directory initialized: {:?}", working_dir);
 	}
 
-	Ok(IngestContext { working_copy_mgr })
+	let storage = if args.filesystem {
+		let path = args
+			.storage_path
+			.clone()
+			.ok_or_else(|| "--storage-path is required with --filesystem".to_string())?;
+		StorageTarget::Filesystem(path)
+	} else {
+		StorageTarget::Database(
+			args.database
+				.clone()
+				.unwrap_or_else(get_default_database_path),
+		)
+	};
+
+	Ok(IngestContext {
+		working_copy_mgr: Arc::new(working_copy_mgr),
+		config: Arc::new(config),
+		storage,
+		format: args.format,
+		verbose: args.verbose as u32,
+		progress: None,
+		file_id: None,
+		cancel: None,
+		owns_input: false,
+	})
 }
 
 /// Process a single file through the ingest pipeline
+///
+/// Compressed files are extracted first and every file inside is ingested on its own.
 async fn process_single_file(
 	ctx: &IngestContext,
-	file_path: &std::path::Path,
-	args: &IngestArgs,
+	file_path: &Path,
+	storage: &SharedStorage,
 	stats: &mut IngestStats,
 ) {
-	if args.verbose >= 1 {
+	if ctx.verbose >= 1 {
 		eprintln!("[INFO] Processing file: {:?}", file_path);
 	}
 
-	let working_copy_path = match ctx.working_copy_mgr.create_working_copy(file_path) {
+	// Uploads the job owns already live in the isolated working directory
+	let working_copy = if ctx.owns_input {
+		Ok(file_path.to_path_buf())
+	} else {
+		ctx.working_copy_mgr.create_working_copy_unique(file_path)
+	};
+
+	let working_copy_path = match working_copy {
 		Ok(path) => path,
 		Err(e) => {
 			let err_msg = format!("Failed to create working copy for {:?}: {}", file_path, e);
-			if args.verbose >= 1 {
+			if ctx.verbose >= 1 {
 				eprintln!("[ERROR] {}", err_msg);
 			}
 			stats.errors.push(err_msg);
@@ -100,21 +295,169 @@ async fn process_single_file(
 		}
 	};
 
-	if args.verbose >= 2 {
+	let compression = match CompressionInfo::detect(&working_copy_path) {
+		Ok(info) => info,
+		Err(e) => {
+			let err_msg = format!("Failed to inspect file {:?}: {}", working_copy_path, e);
+			if ctx.verbose >= 1 {
+				eprintln!("[ERROR] {}", err_msg);
+			}
+			stats.errors.push(err_msg);
+			return;
+		}
+	};
+
+	if compression.is_compressed() {
+		process_archive(ctx, file_path, &working_copy_path, storage, stats).await;
+	} else {
+		let source = IngestSource {
+			path: &working_copy_path,
+			name: file_path,
+			file_id: ctx.file_id.clone(),
+			member: None,
+		};
+		ingest_text_file(ctx, &source, storage, stats).await;
+	}
+
+	if stats.cancelled {
+		abandon_working_copy(ctx, &working_copy_path, stats);
+	}
+}
+
+/// A text file to run through the detection and storage pipeline
+struct IngestSource<'a> {
+	/// Contents to read, inside the working directory
+	path: &'a Path,
+	/// Name used for format detection and messages
+	name: &'a Path,
+	/// Evidence file ID to record rows under (defaults to the content's SHA-256)
+	file_id: Option<String>,
+	/// Archive member the file was extracted from
+	member: Option<&'a ExtractedFile>,
+}
+
+/// Extract a compressed file into the working directory and ingest every file inside it
+async fn process_archive(
+	ctx: &IngestContext,
+	file_path: &Path,
+	working_copy_path: &Path,
+	storage: &SharedStorage,
+	stats: &mut IngestStats,
+) {
+	let name = file_path
+		.file_name()
+		.map(|n| n.to_string_lossy().to_string())
+		.unwrap_or_else(|| file_path.display().to_string());
+	let limits = ExtractionLimits {
+		max_total_bytes: ctx.config.working_directory.max_extracted_bytes,
+		max_ratio: ctx.config.working_directory.max_compression_ratio,
+		..ExtractionLimits::default()
+	};
+
+	let source = working_copy_path.to_path_buf();
+	let dest_dir = ctx.working_copy_mgr.working_dir().to_path_buf();
+	let archive_name = name.clone();
+	let extraction = tokio::task::spawn_blocking(move || {
+		archive::extract(&source, &archive_name, &dest_dir, &limits)
+	})
+	.await
+	.map_err(|e| e.to_string())
+	.and_then(|result| result.map_err(|e| e.to_string()));
+
+	let extraction = match extraction {
+		Ok(extraction) => extraction,
+		Err(e) => {
+			let err_msg = format!("Failed to extract archive {:?}: {}", file_path, e);
+			if ctx.verbose >= 1 {
+				eprintln!("[ERROR] {}", err_msg);
+			}
+			stats.errors.push(err_msg);
+			return;
+		}
+	};
+
+	if ctx.verbose >= 1 {
 		eprintln!(
-			"[DEBUG] Reading file contents from working copy: {:?}",
-			working_copy_path
+			"[INFO] Extracted {} file(s) from {:?}",
+			extraction.files.len(),
+			file_path
 		);
 	}
+	for reason in &extraction.skipped {
+		if ctx.verbose >= 1 {
+			eprintln!("[WARN] Skipped archive member {}", reason);
+		}
+		stats
+			.errors
+			.push(format!("Skipped archive member {}", reason));
+	}
+
+	for member in &extraction.files {
+		if ctx.is_cancelled() {
+			stats.cancelled = true;
+			break;
+		}
+
+		let provenance = member.provenance();
+		// Members of a recorded evidence file get IDs derived from it
+		let file_id = ctx.file_id.as_ref().map(|id| {
+			let chain = provenance
+				.split_once(archive::MEMBER_SEPARATOR)
+				.map_or(provenance.as_str(), |(_, chain)| chain);
+			format!("{}{}{}", id, archive::MEMBER_SEPARATOR, chain)
+		});
+		let source = IngestSource {
+			path: &member.path,
+			name: Path::new(&provenance),
+			file_id,
+			member: Some(member),
+		};
+		ingest_text_file(ctx, &source, storage, stats).await;
+		if stats.transient_error.is_some() {
+			break;
+		}
+	}
 
-	let result =
-		crate::ingest::safe_ingest::safe_read_file(&working_copy_path, args.verbose as u32).await;
+	let dir = extraction.dir.clone();
+	let removed = tokio::task::spawn_blocking(move || archive::remove_extraction(&dir))
+		.await
+		.map_err(|e| e.to_string())
+		.and_then(|result| result.map_err(|e| e.to_string()));
+	if let Err(e) = removed {
+		let err_msg = format!(
+			"Failed to remove extracted files in {:?}: {}",
+			extraction.dir, e
+		);
+		if ctx.verbose >= 1 {
+			eprintln!("[ERROR] {}", err_msg);
+		}
+		stats.errors.push(err_msg);
+	}
+}
 
-	let (content, safety_analysis) = match result {
-		Ok((c, _had_utf8_errors, analysis)) => (c, analysis),
+/// Read, parse and store one uncompressed file
+///
+/// The file is scanned and parsed in a streaming fashion, so memory use does not grow with its size.
+async fn ingest_text_file(
+	ctx: &IngestContext,
+	source: &IngestSource<'_>,
+	storage: &SharedStorage,
+	stats: &mut IngestStats,
+) {
+	if ctx.verbose >= 2 {
+		eprintln!("[DEBUG] Scanning file from working copy: {:?}", source.path);
+	}
+
+	if ctx.is_cancelled() {
+		stats.cancelled = true;
+		return;
+	}
+
+	let scan = match safe_ingest::scan_file(source.path, ctx.verbose).await {
+		Ok(scan) => scan,
 		Err(e) => {
-			let err_msg = format!("Failed to read file {:?}: {}", working_copy_path, e);
-			if args.verbose >= 1 {
+			let err_msg = format!("Failed to read file {:?}: {}", source.name, e);
+			if ctx.verbose >= 1 {
 				eprintln!("[ERROR] {}", err_msg);
 			}
 			stats.errors.push(err_msg);
@@ -122,354 +465,815 @@ async fn process_single_file(
 		}
 	};
 
-	if args.verbose >= 2 {
-		eprintln!("[DEBUG] File read complete, size: {} bytes", content.len());
+	if ctx.verbose >= 2 {
+		eprintln!(
+			"[DEBUG] File scan complete, size: {} bytes",
+			scan.safety.file_size
+		);
 	}
 
-	if safety_analysis.is_binary {
-		if args.verbose >= 1 {
+	// SQLite databases are binary but are read through SQLite itself
+	let is_sqlite = match sqlite::is_sqlite_database(source.path) {
+		Ok(is_sqlite) => is_sqlite,
+		Err(e) => {
+			let err_msg = format!("Failed to read file {:?}: {}", source.name, e);
+			if ctx.verbose >= 1 {
+				eprintln!("[ERROR] {}", err_msg);
+			}
+			stats.errors.push(err_msg);
+			return;
+		}
+	};
+
+	if scan.safety.is_binary && !is_sqlite {
+		if ctx.verbose >= 1 {
 			eprintln!(
 				"[WARN] Binary file detected in {:?} ({:.0}% confidence) - skipping",
-				working_copy_path, safety_analysis.binary_confidence
+				source.name, scan.safety.binary_confidence
 			);
 		}
 		stats.errors.push(format!(
 			"Cannot process file {:?}: Binary file detected ({:.0}% confidence)",
-			working_copy_path, safety_analysis.binary_confidence
+			source.name, scan.safety.binary_confidence
 		));
 		return;
 	}
 
-	let format_str = if let Some(fmt) = args.format {
+	let format_str = if is_sqlite {
+		"sqlite".to_string()
+	} else if let Some(fmt) = ctx.format {
 		fmt.to_string()
 	} else {
-		detect_format_from_path(file_path)
+		detect_format_from_path(source.name)
 	};
 
-	if args.verbose >= 2 {
+	if ctx.verbose >= 2 {
 		eprintln!("[DEBUG] Detected format: {}", format_str);
 		eprintln!("[INFO] Parsing {} format file...", format_str);
 	}
 
-	process_format(&format_str, &content, file_path, args, stats).await;
-}
-
-/// Process file based on detected format
-async fn process_format(
-	format_str: &str,
-	content: &str,
-	file_path: &std::path::Path,
-	args: &IngestArgs,
-	stats: &mut IngestStats,
-) {
-	match format_str {
-		"csv" => process_csv(content, file_path, args, stats),
-		"tsv" => process_tsv(content, file_path, args, stats),
-		"json" => process_json(content, file_path, args, stats),
-		"xml" => process_xml(content, file_path, args, stats).await,
-		_ => {
-			let err_msg = format!("Unsupported format: {}", format_str);
-			if args.verbose >= 1 {
+	let rows = match open_rows(&format_str, source.path, source.name, ctx).await {
+		Ok(rows) => rows,
+		Err(err_msg) => {
+			if ctx.verbose >= 1 {
 				eprintln!("[ERROR] {}", err_msg);
 			}
 			stats.errors.push(err_msg);
+			return;
+		}
+	};
+
+	if ctx.is_cancelled() {
+		stats.cancelled = true;
+		return;
+	}
+
+	let original_filename = match source.member {
+		Some(member) => member.provenance(),
+		None => source
+			.name
+			.file_name()
+			.map(|n| n.to_string_lossy().to_string())
+			.unwrap_or_else(|| source.name.display().to_string()),
+	};
+
+	// Link the member's rows back to the archive chain it came from
+	let member_event = source.member.map(|member| {
+		vec![
+			"__archive_member__".to_string(),
+			member.archive.clone(),
+			member.member.clone(),
+		]
+	});
+	let file_size = scan.safety.file_size;
+	let evidence_id = source.file_id.clone();
+	let stored = with_storage(storage, move |storage| {
+		let file_id =
+			store_file_record(storage, &original_filename, &scan, evidence_id.as_deref())?;
+		if let Some(event) = member_event {
+			pipeline::store_with_file(storage, &event, &file_id)?;
+		}
+		Ok::<_, std::io::Error>(file_id)
+	})
+	.await;
+	let file_id = match stored {
+		Ok(file_id) => file_id,
+		Err(e) => {
+			let err_msg = format!("Failed to store file metadata for {:?}: {}", source.name, e);
+			if ctx.verbose >= 1 {
+				eprintln!("[ERROR] {}", err_msg);
+			}
+			stats.record_storage_error(err_msg, &e);
+			return;
+		}
+	};
+
+	match rows {
+		FileRows::Stream(mut stream) => {
+			let file = FileRun::new(source.name, &file_id, file_size);
+			process_stream(&mut *stream, file, ctx, storage, stats).await;
+			report_skipped_rows(stream.stats(), source.name, stats);
+		}
+		FileRows::Tables(mut tables) => {
+			process_tables(&mut tables, source.name, &file_id, ctx, storage, stats).await;
+			report_skipped_rows(tables.stats(), source.name, stats);
+		}
+		FileRows::Parsed(rows) => {
+			let headers = extract_headers(&rows, ctx);
+			process_rows(&rows, &headers, source.name, &file_id, ctx, storage, stats).await;
 		}
 	}
 }
 
-/// Process CSV format
-fn process_csv(
-	content: &str,
-	file_path: &std::path::Path,
-	args: &IngestArgs,
-	stats: &mut IngestStats,
-) {
-	let adapter = crate::ingest::adapters::CsvAdapter::new();
-	let rows = adapter.parse(content);
+/// Stop a cancelled run and shred its working copy
+///
+/// The working copy is a copy this run made or an upload the job owns; anything
+/// that does not resolve into the working directory is never deleted.
+fn abandon_working_copy(ctx: &IngestContext, working_copy_path: &Path, stats: &mut IngestStats) {
+	stats.cancelled = true;
+	if !ctx.working_copy_mgr.contains(working_copy_path) {
+		let err_msg = format!(
+			"Refusing to remove {:?}: not inside the working directory",
+			working_copy_path
+		);
+		if ctx.verbose >= 1 {
+			eprintln!("[ERROR] {}", err_msg);
+		}
+		stats.errors.push(err_msg);
+		return;
+	}
+	if ctx.verbose >= 1 {
+		eprintln!(
+			"[INFO] Ingest cancelled; removing working copy {:?}",
+			working_copy_path
+		);
+	}
 
-	if args.verbose >= 1 {
-		eprintln!("[INFO] CSV parsing complete: {} rows parsed", rows.len());
+	let config = SecureDeletionConfig {
+		log_deletions: ctx.verbose >= 2,
+		..SecureDeletionConfig::default()
+	};
+	if let Err(e) = secure_delete_file(working_copy_path, config) {
+		let err_msg = format!(
+			"Failed to remove working copy {:?}: {}",
+			working_copy_path, e
+		);
+		if ctx.verbose >= 1 {
+			eprintln!("[ERROR] {}", err_msg);
+		}
+		stats.errors.push(err_msg);
 	}
+}
 
-	let headers = extract_headers(&rows, args);
-	process_rows(&rows, &headers, file_path, args, stats);
+/// Rows of a file, streamed from disk or parsed in memory
+enum FileRows {
+	Stream(Box<RowStream>),
+	/// SQL dumps and SQLite databases, with one dataset per table
+	Tables(TableStream),
+	/// Formats without a streaming parser (XML)
+	Parsed(Vec<Vec<String>>),
 }
 
-/// Process TSV format
-fn process_tsv(
+/// Open a file's rows based on the detected format
+///
+/// JSON documents are validated here, before anything from the file is stored.
+async fn open_rows(
+	format_str: &str,
+	path: &Path,
+	file_path: &Path,
+	ctx: &IngestContext,
+) -> Result<FileRows, String> {
+	let stream = match format_str {
+		"csv" => StreamingCsvParser::new(path).await.map(RowStream::Csv),
+		"tsv" => StreamingTsvParser::new(path).await.map(RowStream::Tsv),
+		// Combo lists usually come as plain text files
+		"combo" | "txt" => StreamingComboParser::new(path).await.map(RowStream::Combo),
+		"sql" => {
+			return StreamingSqlDumpParser::new(path)
+				.await
+				.map(|dump| FileRows::Tables(TableStream::Sql(Box::new(dump))))
+				.map_err(|e| format!("Failed to read file {:?}: {}", file_path, e));
+		}
+		// Only chosen for files with the SQLite header
+		"sqlite" => {
+			return StreamingSqliteReader::open(path)
+				.await
+				.map(|reader| FileRows::Tables(TableStream::Sqlite(reader)))
+				.map_err(|e| format!("Failed to open SQLite database {:?}: {}", file_path, e));
+		}
+		"json" | "jsonl" | "ndjson" => {
+			if ctx.verbose >= 2 {
+				eprintln!("[DEBUG] Scanning JSON structure with universal parser...");
+			}
+			let rows = StreamingJsonRows::open(path, format_str != "json")
+				.await
+				.map_err(|e| format!("Failed to parse JSON from {:?}: {}", file_path, e))?;
+			if !rows.has_rows() {
+				return Err(format!("No data rows found in JSON file {:?}", file_path));
+			}
+			Ok(RowStream::Json(rows))
+		}
+		"xml" => {
+			let (content, _had_utf8_errors, _analysis) =
+				safe_ingest::safe_read_file(path, ctx.verbose)
+					.await
+					.map_err(|e| format!("Failed to read file {:?}: {}", file_path, e))?;
+			return parse_xml(&content, file_path, ctx).map(FileRows::Parsed);
+		}
+		_ => return Err(format!("Unsupported format: {}", format_str)),
+	};
+
+	stream
+		.map(|stream| FileRows::Stream(Box::new(stream)))
+		.map_err(|e| format!("Failed to read file {:?}: {}", file_path, e))
+}
+
+/// Parse XML format
+fn parse_xml(
 	content: &str,
-	file_path: &std::path::Path,
-	args: &IngestArgs,
-	stats: &mut IngestStats,
-) {
-	if args.verbose >= 2 {
-		eprintln!("[DEBUG] Starting TSV parsing...");
+	file_path: &Path,
+	ctx: &IngestContext,
+) -> Result<Vec<Vec<String>>, String> {
+	if ctx.verbose >= 2 {
+		eprintln!("[DEBUG] Starting XML parsing with universal parser...");
 	}
 
-	let rows: Vec<Vec<String>> = content
-		.lines()
-		.map(|line| line.split('\t').map(|s| s.to_string()).collect())
-		.collect();
+	let rows = crate::ingest::universal_parser::xml_to_rows(content)
+		.map_err(|e| format!("Failed to parse XML structure from {:?}: {}", file_path, e))?;
 
-	if args.verbose >= 1 {
-		eprintln!("[INFO] TSV parsing complete: {} rows parsed", rows.len());
+	if rows.is_empty() {
+		return Err(format!("No data rows found in XML file {:?}", file_path));
 	}
 
-	let headers = extract_headers(&rows, args);
-	process_rows(&rows, &headers, file_path, args, stats);
+	if ctx.verbose >= 1 {
+		eprintln!("[INFO] XML parsing complete: {} rows parsed", rows.len());
+	}
+
+	Ok(rows)
 }
 
-/// Process JSON format
-fn process_json(
-	content: &str,
-	file_path: &std::path::Path,
-	args: &IngestArgs,
+/// Record the file's hashes and metadata in storage, returning its file ID
+///
+/// Uses `file_id` when evidence was already recorded for the file, otherwise its SHA-256.
+fn store_file_record(
+	storage: &mut (dyn StorageAdapter + Send),
+	original_filename: &str,
+	scan: &FileScan,
+	file_id: Option<&str>,
+) -> std::io::Result<String> {
+	let file_id = file_id.map_or_else(|| scan.sha256.clone(), str::to_string);
+
+	storage.insert_file_metadata(
+		&file_id,
+		original_filename,
+		&scan.sha256,
+		scan.safety.file_size as i64,
+	)?;
+
+	let meta = vec![
+		"__file_hash__".to_string(),
+		scan.md5.clone(),
+		scan.sha256.clone(),
+	];
+	pipeline::store_with_file(storage, &meta, &file_id)?;
+
+	Ok(file_id)
+}
+
+/// Whether a storage error is likely to clear up on retry (busy or locked database, timeouts)
+fn is_transient_storage_error(error: &std::io::Error) -> bool {
+	if let Some(sqlite_error) = error
+		.get_ref()
+		.and_then(|inner| inner.downcast_ref::<rusqlite::Error>())
+	{
+		return matches!(
+			sqlite_error.sqlite_error_code(),
+			Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+		);
+	}
+
+	matches!(
+		error.kind(),
+		std::io::ErrorKind::TimedOut
+			| std::io::ErrorKind::Interrupted
+			| std::io::ErrorKind::WouldBlock
+			| std::io::ErrorKind::ConnectionRefused
+			| std::io::ErrorKind::ConnectionReset
+	)
+}
+
+/// Extract headers from rows if present
+fn extract_headers(rows: &[Vec<String>], ctx: &IngestContext) -> Option<Vec<String>> {
+	let headers = pipeline::detect_header(rows);
+	if headers.is_some() && ctx.verbose >= 2 {
+		eprintln!("[DEBUG] First row detected as header");
+	}
+	headers
+}
+
+/// Per-file state while its rows go through the pipeline
+#[derive(Default)]
+struct FileRun {
+	file_path: PathBuf,
+	file_id: String,
+	headers: Option<Vec<String>>,
+	/// Size of the file, for estimating its row count while streaming
+	file_size: usize,
+	/// Rows handed to the pipeline so far, including the header row
+	rows_seen: usize,
+	/// The header row holds column names from the parser, such as a SQL table's,
+	/// rather than being detected in the file's first row
+	synthetic_header: bool,
+	/// Rows the file is expected to contain, for progress and cancellation records
+	expected_rows: usize,
+	unique_addresses: usize,
+	hashed_credentials: usize,
+	weak_passwords: usize,
+	/// Writing stopped after the first storage failure
+	storage_failed: bool,
+}
+
+impl FileRun {
+	fn new(file_path: &Path, file_id: &str, file_size: usize) -> Self {
+		FileRun {
+			file_path: file_path.to_path_buf(),
+			file_id: file_id.to_string(),
+			headers: None,
+			file_size,
+			rows_seen: 0,
+			synthetic_header: false,
+			expected_rows: 0,
+			unique_addresses: 0,
+			hashed_credentials: 0,
+			weak_passwords: 0,
+			storage_failed: false,
+		}
+	}
+
+	/// Data rows of the file that went through the pipeline
+	fn rows_processed(&self) -> usize {
+		self.without_header(self.rows_seen)
+	}
+
+	/// Data rows the file is expected to contain
+	fn rows_expected(&self) -> usize {
+		self.without_header(self.expected_rows)
+	}
+
+	/// A count of rows less the header row, which is not data
+	fn without_header(&self, rows: usize) -> usize {
+		if self.headers.is_some() {
+			rows.saturating_sub(1)
+		} else {
+			rows
+		}
+	}
+
+	/// Extrapolate the file's row count from the share of its bytes read so far
+	fn estimate_rows(&mut self, rows_read: usize, bytes_read: u64) {
+		let file_size = self.file_size as u64;
+		self.expected_rows = if bytes_read == 0 || bytes_read >= file_size {
+			rows_read
+		} else {
+			let estimate = rows_read as u128 * file_size as u128 / bytes_read as u128;
+			usize::try_from(estimate)
+				.unwrap_or(usize::MAX)
+				.max(rows_read)
+		};
+	}
+}
+
+/// Stream a file's rows through the pipeline in batches of [`PROGRESS_BATCH_ROWS`]
+async fn process_stream<S: RowSource + Send>(
+	stream: &mut S,
+	mut file: FileRun,
+	ctx: &IngestContext,
+	storage: &SharedStorage,
 	stats: &mut IngestStats,
 ) {
-	if args.verbose >= 2 {
-		eprintln!("[DEBUG] Starting JSON parsing with universal parser...");
+	if ctx.verbose >= 2 {
+		eprintln!(
+			"[DEBUG] Starting detection pipeline on {:?}",
+			file.file_path
+		);
 	}
 
-	match serde_json::from_str::<serde_json::Value>(content) {
-		Ok(json_value) => {
-			let rows = crate::ingest::universal_parser::json_to_rows(&json_value);
-
-			if rows.is_empty() {
-				let err_msg = format!("No data rows found in JSON file {:?}", file_path);
-				if args.verbose >= 1 {
-					eprintln!("[ERROR] {}", err_msg);
+	loop {
+		let mut batch = Vec::with_capacity(PROGRESS_BATCH_ROWS);
+		let mut read_error = None;
+		while batch.len() < PROGRESS_BATCH_ROWS {
+			match stream.next_row().await {
+				Ok(Some(row)) => batch.push(row),
+				Ok(None) => break,
+				Err(e) => {
+					read_error = Some(e);
+					break;
 				}
-				stats.errors.push(err_msg);
-				return;
-			}
-
-			if args.verbose >= 1 {
-				eprintln!("[INFO] JSON parsing complete: {} rows parsed", rows.len());
 			}
+		}
 
-			let headers = extract_headers(&rows, args);
-			process_rows(&rows, &headers, file_path, args, stats);
+		if file.rows_seen == 0 {
+			file.headers = if file.synthetic_header {
+				batch.first().cloned()
+			} else {
+				extract_headers(&batch, ctx)
+			};
 		}
-		Err(e) => {
-			let err_msg = format!("Failed to parse JSON from {:?}: {}", file_path, e);
-			if args.verbose >= 1 {
+		let batch_len = batch.len();
+		file.estimate_rows(file.rows_seen + batch_len, stream.stats().bytes_read);
+		if batch_len > 0 && !process_batch(&mut file, batch, ctx, storage, stats).await {
+			return;
+		}
+
+		if let Some(e) = read_error {
+			let err_msg = format!(
+				"Failed to read rows after row {} from {:?}: {}",
+				file.rows_seen, file.file_path, e
+			);
+			if ctx.verbose >= 1 {
 				eprintln!("[ERROR] {}", err_msg);
 			}
 			stats.errors.push(err_msg);
+			break;
+		}
+		if batch_len < PROGRESS_BATCH_ROWS {
+			break;
 		}
 	}
+
+	if ctx.verbose >= 1 {
+		eprintln!(
+			"[INFO] Parsing complete: {} rows parsed from {:?}",
+			file.rows_processed(),
+			file.file_path
+		);
+	}
+
+	finish_file(file, ctx, stats);
 }
 
-/// Process XML format
-async fn process_xml(
-	content: &str,
-	file_path: &std::path::Path,
-	args: &IngestArgs,
+/// Stream each table of a SQL dump or SQLite database through the pipeline as its own dataset
+///
+/// Rows are stored under the file's ID, each table's after a
+/// `__source_table__` event naming it.
+async fn process_tables(
+	tables: &mut TableStream,
+	file_path: &Path,
+	file_id: &str,
+	ctx: &IngestContext,
+	storage: &SharedStorage,
 	stats: &mut IngestStats,
 ) {
-	if args.verbose >= 2 {
-		eprintln!("[DEBUG] Starting XML parsing with universal parser...");
-	}
-
-	match crate::ingest::universal_parser::xml_to_rows(content) {
-		Ok(rows) => {
-			if rows.is_empty() {
-				let err_msg = format!("No data rows found in XML file {:?}", file_path);
-				if args.verbose >= 1 {
+	let mut tables_read = 0;
+	loop {
+		let table = match tables.next_table().await {
+			Ok(Some(table)) => table,
+			Ok(None) => break,
+			Err(e) => {
+				let err_msg = format!("Failed to read tables from {:?}: {}", file_path, e);
+				if ctx.verbose >= 1 {
 					eprintln!("[ERROR] {}", err_msg);
 				}
 				stats.errors.push(err_msg);
-				return;
-			}
-
-			if args.verbose >= 1 {
-				eprintln!("[INFO] XML parsing complete: {} rows parsed", rows.len());
+				break;
 			}
+		};
+		tables_read += 1;
 
-			let headers = extract_headers(&rows, args);
-			process_rows(&rows, &headers, file_path, args, stats);
+		if ctx.verbose >= 2 {
+			eprintln!(
+				"[DEBUG] Reading table {} ({} columns) from {:?}",
+				table.name,
+				table.columns.len(),
+				file_path
+			);
 		}
-		Err(e) => {
-			let err_msg = format!("Failed to parse XML structure from {:?}: {}", file_path, e);
-			if args.verbose >= 1 {
+		let event = vec!["__source_table__".to_string(), table.name.clone()];
+		let table_file_id = file_id.to_string();
+		let recorded = with_storage(storage, move |storage| {
+			pipeline::store_with_file(storage, &event, &table_file_id)
+		})
+		.await;
+		if let Err(e) = recorded {
+			let err_msg = format!(
+				"Failed to record table {} from {:?}: {}",
+				table.name, file_path, e
+			);
+			if ctx.verbose >= 1 {
 				eprintln!("[ERROR] {}", err_msg);
 			}
-			stats.errors.push(err_msg);
+			stats.record_storage_error(err_msg, &e);
+			return;
+		}
+
+		// Table sizes are not known up front, so progress counts rows without an estimate
+		let table_path = PathBuf::from(format!("{}#{}", file_path.display(), table.name));
+		let mut file = FileRun::new(&table_path, file_id, 0);
+		file.synthetic_header = true;
+		process_stream(tables, file, ctx, storage, stats).await;
+		if stats.cancelled || stats.transient_error.is_some() {
+			return;
 		}
 	}
-}
 
-/// Extract headers from rows if present
-fn extract_headers(rows: &[Vec<String>], args: &IngestArgs) -> Option<Vec<String>> {
-	if rows.is_empty() {
-		return None;
+	if tables_read == 0 {
+		stats
+			.errors
+			.push(format!("No table rows found in {:?}", file_path));
 	}
+}
 
-	let first = &rows[0];
-	if first.iter().any(|c| c.chars().any(|ch| ch.is_alphabetic())) {
-		if args.verbose >= 2 {
-			eprintln!("[DEBUG] First row detected as header");
-		}
-		Some(first.clone())
-	} else {
-		None
+/// Report rows a streaming parser could not parse
+fn report_skipped_rows(parse_stats: &StreamStats, file_path: &Path, stats: &mut IngestStats) {
+	for warning in &parse_stats.warnings {
+		stats
+			.errors
+			.push(format!("Skipped row in {:?}: {}", file_path, warning));
+	}
+	let unreported = parse_stats.rows_failed as usize - parse_stats.warnings.len();
+	if unreported > 0 {
+		stats.errors.push(format!(
+			"Skipped {} more unparseable rows in {:?}",
+			unreported, file_path
+		));
 	}
 }
 
-/// Process rows through detection pipeline
-fn process_rows(
+/// Process rows through the detection, normalization and storage pipeline
+async fn process_rows(
 	rows: &[Vec<String>],
 	headers: &Option<Vec<String>>,
-	file_path: &std::path::Path,
-	args: &IngestArgs,
+	file_path: &Path,
+	file_id: &str,
+	ctx: &IngestContext,
+	storage: &SharedStorage,
 	stats: &mut IngestStats,
 ) {
-	if args.verbose >= 2 {
+	if ctx.verbose >= 2 {
 		eprintln!("[DEBUG] Starting detection pipeline on {} rows", rows.len());
 	}
 
-	let mut detections = Vec::new();
-	let row_start_idx = if headers.is_some() { 1 } else { 0 };
+	let mut file = FileRun::new(file_path, file_id, 0);
+	file.headers = headers.clone();
+	file.expected_rows = rows.len();
+	ctx.report_progress(stats.total_rows, stats.total_rows + file.rows_expected());
 
-	for (idx, row) in rows.iter().enumerate() {
-		if idx == 0 && headers.is_some() {
-			continue;
+	for batch in rows.chunks(PROGRESS_BATCH_ROWS) {
+		if !process_batch(&mut file, batch.to_vec(), ctx, storage, stats).await {
+			return;
 		}
-		let detection = detection::analyzer::detect_row(row, headers.as_deref(), idx);
-		detections.push(detection);
 	}
 
-	if args.verbose >= 2 {
-		eprintln!(
-			"[DEBUG] Detection pipeline complete on {} rows",
-			detections.len()
+	finish_file(file, ctx, stats);
+}
+
+/// Detect, store and tally one batch of a file's rows
+///
+/// Returns false when the job was cancelled before the batch was processed.
+async fn process_batch(
+	file: &mut FileRun,
+	batch: Vec<Vec<String>>,
+	ctx: &IngestContext,
+	storage: &SharedStorage,
+	stats: &mut IngestStats,
+) -> bool {
+	if ctx.is_cancelled() {
+		mark_cancelled_rows(file, ctx, storage, stats).await;
+		return false;
+	}
+
+	// The run's state moves to the blocking thread for the batch and back again
+	let mut owned_file = std::mem::take(file);
+	let mut owned_stats = std::mem::take(stats);
+	let config = ctx.config.clone();
+	let verbose = ctx.verbose;
+	(*file, *stats) = with_storage(storage, move |storage| {
+		store_batch(
+			&mut owned_file,
+			&batch,
+			&config,
+			verbose,
+			storage,
+			&mut owned_stats,
 		);
+		(owned_file, owned_stats)
+	})
+	.await;
+
+	ctx.report_progress(
+		stats.total_rows + file.rows_processed(),
+		stats.total_rows + file.rows_expected().max(file.rows_processed()),
+	);
+	true
+}
+
+/// Run one batch of rows through detection and storage on the current thread
+fn store_batch(
+	file: &mut FileRun,
+	batch: &[Vec<String>],
+	config: &Config,
+	verbose: u32,
+	storage: &mut (dyn StorageAdapter + Send),
+	stats: &mut IngestStats,
+) {
+	let mut detections = Vec::with_capacity(batch.len());
+	for (offset, row) in batch.iter().enumerate() {
+		let idx = file.rows_seen + offset;
+		if idx == 0 && file.headers.is_some() {
+			continue;
+		}
+		let detection = detection::analyzer::detect_row(row, file.headers.as_deref(), idx);
+		metrics::metrics().pipeline_rows.inc();
+		record_findings(idx, &detection, stats);
+		detections.push(detection);
+
+		// Stop writing after the first storage failure but keep analyzing the file
+		if !file.storage_failed
+			&& let Err(e) = pipeline::store_normalized_row(
+				storage,
+				row,
+				idx,
+				file.headers.as_deref(),
+				&file.file_id,
+				config,
+			) {
+			let err_msg = format!(
+				"Failed to store row {} from {:?}: {}",
+				idx, file.file_path, e
+			);
+			if verbose >= 1 {
+				eprintln!("[ERROR] {}", err_msg);
+			}
+			stats.record_storage_error(err_msg, &e);
+			file.storage_failed = true;
+		}
 	}
 
 	let detection_stats = detection::analyzer::aggregate_results(&detections);
+	file.unique_addresses += detection_stats.unique_addresses;
+	file.hashed_credentials += detection_stats.hashed_credentials_detected;
+	file.weak_passwords += detection_stats.weak_passwords_found;
+	file.rows_seen += batch.len();
+}
 
-	// Track PII detections and capture detailed findings
-	let mut rows_with_pii = vec![false; detections.len()];
-	for (idx, detection) in detections.iter().enumerate() {
-		let row_number = row_start_idx + idx; // User-friendly 1-based row number
-
-		// Create detailed findings for this row if it has detections
-		if !detection.pii_findings.is_empty() {
-			let mut row_detections = Vec::new();
-			for finding in &detection.pii_findings {
-				row_detections.push(Detection {
-					column: finding.column_name.clone(),
-					value: finding.value.clone(),
-					detection_type: finding.pii_type.to_string(),
-				});
-
-				// Update summary counts
-				match &finding.pii_type {
-					detection::npi_detection::PiiType::Email => {
-						stats.pii_summary.emails = stats.pii_summary.emails.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::PhoneNumber => {
-						stats.pii_summary.phone_numbers =
-							stats.pii_summary.phone_numbers.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::IpAddress
-					| detection::npi_detection::PiiType::IpV4Address
-					| detection::npi_detection::PiiType::IpV6Address => {
-						stats.pii_summary.ip_addresses =
-							stats.pii_summary.ip_addresses.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::SocialSecurityNumber => {
-						stats.pii_summary.social_security_numbers =
-							stats.pii_summary.social_security_numbers.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::NationalId => {
-						stats.pii_summary.national_ids =
-							stats.pii_summary.national_ids.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::CreditCardNumber => {
-						stats.pii_summary.credit_cards =
-							stats.pii_summary.credit_cards.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::Name => {
-						stats.pii_summary.names = stats.pii_summary.names.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::MailingAddress => {
-						stats.pii_summary.mailing_addresses =
-							stats.pii_summary.mailing_addresses.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::IBAN
-					| detection::npi_detection::PiiType::SWIFTCode
-					| detection::npi_detection::PiiType::RoutingNumber
-					| detection::npi_detection::PiiType::BankAccount => {
-						stats.pii_summary.bank_identifiers =
-							stats.pii_summary.bank_identifiers.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::CryptoAddress => {
-						stats.pii_summary.crypto_addresses =
-							stats.pii_summary.crypto_addresses.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					detection::npi_detection::PiiType::DigitalWalletToken => {
-						stats.pii_summary.digital_wallets =
-							stats.pii_summary.digital_wallets.saturating_add(1);
-						rows_with_pii[idx] = true;
-					}
-					_ => {}
-				}
-			}
+/// Track a row's PII detections and capture its detailed findings
+fn record_findings(
+	row_number: usize,
+	detection: &detection::analyzer::DetectionResult,
+	stats: &mut IngestStats,
+) {
+	if detection.pii_findings.is_empty() {
+		return;
+	}
 
-			stats.detailed_findings.push(DetailedRowFinding {
-				row_number,
-				detections: row_detections,
+	stats.rows_with_findings += 1;
+	let keep_details = stats.detailed_findings.len() < MAX_DETAILED_FINDINGS;
+	let mut row_detections = Vec::new();
+	for finding in &detection.pii_findings {
+		metrics::metrics()
+			.pii_findings
+			.with_label_values(&[finding.pii_type.to_string()])
+			.inc();
+		if keep_details {
+			row_detections.push(Detection {
+				column: finding.column_name.clone(),
+				value: finding.value.clone(),
+				detection_type: finding.pii_type.to_string(),
 			});
 		}
+
+		// Update summary counts
+		match &finding.pii_type {
+			detection::npi_detection::PiiType::Email => {
+				stats.pii_summary.emails = stats.pii_summary.emails.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::PhoneNumber => {
+				stats.pii_summary.phone_numbers = stats.pii_summary.phone_numbers.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::IpAddress
+			| detection::npi_detection::PiiType::IpV4Address
+			| detection::npi_detection::PiiType::IpV6Address => {
+				stats.pii_summary.ip_addresses = stats.pii_summary.ip_addresses.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::SocialSecurityNumber => {
+				stats.pii_summary.social_security_numbers =
+					stats.pii_summary.social_security_numbers.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::NationalId => {
+				stats.pii_summary.national_ids = stats.pii_summary.national_ids.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::CreditCardNumber => {
+				stats.pii_summary.credit_cards = stats.pii_summary.credit_cards.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::Name => {
+				stats.pii_summary.names = stats.pii_summary.names.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::MailingAddress => {
+				stats.pii_summary.mailing_addresses =
+					stats.pii_summary.mailing_addresses.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::IBAN
+			| detection::npi_detection::PiiType::SWIFTCode
+			| detection::npi_detection::PiiType::RoutingNumber
+			| detection::npi_detection::PiiType::BankAccount => {
+				stats.pii_summary.bank_identifiers =
+					stats.pii_summary.bank_identifiers.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::CryptoAddress => {
+				stats.pii_summary.crypto_addresses =
+					stats.pii_summary.crypto_addresses.saturating_add(1);
+			}
+			detection::npi_detection::PiiType::DigitalWalletToken => {
+				stats.pii_summary.digital_wallets =
+					stats.pii_summary.digital_wallets.saturating_add(1);
+			}
+			_ => {}
+		}
+	}
+
+	if keep_details {
+		stats.detailed_findings.push(DetailedRowFinding {
+			row_number,
+			detections: row_detections,
+		});
 	}
+}
 
-	stats.total_rows += rows.len();
-	stats.unique_addresses += detection_stats.unique_addresses;
-	stats.hashed_credentials += detection_stats.hashed_credentials_detected;
-	stats.weak_passwords += detection_stats.weak_passwords_found;
+/// Add a fully processed file's totals to the run statistics
+fn finish_file(file: FileRun, ctx: &IngestContext, stats: &mut IngestStats) {
+	let rows_processed = file.rows_processed();
+	stats.total_rows += rows_processed;
+	stats.unique_addresses += file.unique_addresses;
+	stats.hashed_credentials += file.hashed_credentials;
+	stats.weak_passwords += file.weak_passwords;
+	ctx.report_progress(stats.total_rows, stats.total_rows);
 
-	if args.verbose >= 2 {
+	if ctx.verbose >= 2 {
 		eprintln!("[DEBUG] Detection results for file:");
-		eprintln!(
-			"[DEBUG]   Unique addresses: {}",
-			detection_stats.unique_addresses
-		);
-		eprintln!(
-			"[DEBUG]   Hashed credentials: {}",
-			detection_stats.hashed_credentials_detected
-		);
-		eprintln!(
-			"[DEBUG]   Weak passwords: {}",
-			detection_stats.weak_passwords_found
-		);
+		eprintln!("[DEBUG]   Unique addresses: {}", file.unique_addresses);
+		eprintln!("[DEBUG]   Hashed credentials: {}", file.hashed_credentials);
+		eprintln!("[DEBUG]   Weak passwords: {}", file.weak_passwords);
 		eprintln!(
 			"[DEBUG]   Rows with detections: {}",
-			stats.detailed_findings.len()
+			stats.rows_with_findings
 		);
 	}
 
 	stats.metadata.push(format!(
 		"Processed {} rows from {} | Unique addresses: {}, Hashed credentials: {}, Weak \
 		 passwords: {}",
-		rows.len(),
-		file_path.display(),
-		detection_stats.unique_addresses,
-		detection_stats.hashed_credentials_detected,
-		detection_stats.weak_passwords_found
+		rows_processed,
+		file.file_path.display(),
+		file.unique_addresses,
+		file.hashed_credentials,
+		file.weak_passwords
 	));
 }
 
+/// Record that a file's rows were only partly stored because its job was cancelled
+///
+/// Rows already written keep their file ID; the `__ingest_cancelled__` event
+/// stored under the same file ID marks them as an incomplete import.
+async fn mark_cancelled_rows(
+	file: &FileRun,
+	ctx: &IngestContext,
+	storage: &SharedStorage,
+	stats: &mut IngestStats,
+) {
+	let (rows_done, total_rows) = (file.rows_processed(), file.rows_expected());
+	stats.cancelled = true;
+	stats.total_rows += rows_done;
+	stats.metadata.push(format!(
+		"Cancelled after {} of {} rows from {}; stored rows are marked incomplete under file \
+		 ID {}",
+		rows_done,
+		total_rows,
+		file.file_path.display(),
+		file.file_id
+	));
+
+	let marker = vec![
+		"__ingest_cancelled__".to_string(),
+		rows_done.to_string(),
+		total_rows.to_string(),
+	];
+	let file_id = file.file_id.clone();
+	let marked = with_storage(storage, move |storage| {
+		pipeline::store_with_file(storage, &marker, &file_id)
+	})
+	.await;
+	if let Err(e) = marked {
+		let err_msg = format!(
+			"Failed to mark rows from {:?} as incomplete: {}",
+			file.file_path, e
+		);
+		if ctx.verbose >= 1 {
+			eprintln!("[ERROR] {}", err_msg);
+		}
+		stats.errors.push(err_msg);
+	}
+}
+
 /// Finalize ingest and format output
 async fn finalize_ingest(args: &IngestArgs, stats: &IngestStats) -> Result<(), String> {
 	if args.verbose >= 1 {
@@ -488,17 +1292,7 @@ async fn finalize_ingest(args: &IngestArgs, stats: &IngestStats) -> Result<(), S
 		eprintln!("[DEBUG] Formatting output results...");
 	}
 
-	let result = IngestResult {
-		rows_processed: stats.total_rows,
-		unique_addresses: stats.unique_addresses,
-		hashed_credentials_detected: stats.hashed_credentials,
-		weak_passwords_found: stats.weak_passwords,
-		breached_addresses: 0,
-		pii_summary: Some(stats.pii_summary.clone()),
-		detailed_findings: stats.detailed_findings.clone(),
-		metadata: stats.metadata.clone(),
-		errors: stats.errors.clone(),
-	};
+	let result = stats.to_result();
 
 	if args.verbose >= 2 {
 		eprintln!("[DEBUG] Creating {:?} formatter...", args.output_format);
@@ -571,7 +1365,7 @@ pub async fn status(args: StatusArgs) -> Result<(), String> {
 			eprintln!("[DEBUG] Checking Ollama at: {}", ollama_url);
 		}
 
-		match check_ollama(&ollama_url).await {
+		match readiness::check_ollama(&ollama_url, readiness::PROBE_TIMEOUT).await {
 			Ok(_) => {
 				println!("✓ Ollama: OK");
 				checks_passed += 1;
@@ -593,7 +1387,7 @@ pub async fn status(args: StatusArgs) -> Result<(), String> {
 			eprintln!("[DEBUG] Checking database at: {}", db_url);
 		}
 
-		match check_database(&db_url).await {
+		match readiness::check_database(&db_url) {
 			Ok(_) => {
 				println!("✓ Database: OK");
 				checks_passed += 1;
@@ -612,7 +1406,7 @@ pub async fn status(args: StatusArgs) -> Result<(), String> {
 			eprintln!("[DEBUG] Checking HIBP API");
 		}
 
-		match check_hibp(api_key.as_deref()).await {
+		match readiness::check_hibp(api_key.as_deref(), readiness::PROBE_TIMEOUT).await {
 			Ok(_) => {
 				println!("✓ HIBP: OK");
 				checks_passed += 1;
@@ -644,65 +1438,6 @@ pub async fn status(args: StatusArgs) -> Result<(), String> {
 	}
 }
 
-/// Check Ollama connectivity
-async fn check_ollama(url: &str) -> Result<(), String> {
-	// Simple HTTP health check to Ollama's API endpoint
-	let client = reqwest::Client::new();
-	match client
-		.get(format!("{}/api/tags", url))
-		.timeout(std::time::Duration::from_secs(5))
-		.send()
-		.await
-	{
-		Ok(response) => {
-			if response.status().is_success() {
-				Ok(())
-			} else {
-				Err(format!("HTTP {}", response.status()))
-			}
-		}
-		Err(e) => Err(format!("Connection failed: {}", e)),
-	}
-}
-
-/// Check database connectivity
-async fn check_database(db_path: &str) -> Result<(), String> {
-	// Validate SQLite database file exists and is accessible
-	let path = std::path::Path::new(db_path);
-	if path.exists() {
-		match std::fs::metadata(path) {
-			Ok(metadata) if metadata.is_file() => Ok(()),
-			Ok(_) => Err(format!("Database path is not a file: {}", db_path)),
-			Err(e) => Err(format!("Cannot access database file: {}", e)),
-		}
-	} else {
-		// SQLite will create the file if it doesn't exist, so this is acceptable
-		Ok(())
-	}
-}
-
-/// Check HIBP API connectivity
-async fn check_hibp(_api_key: Option<&str>) -> Result<(), String> {
-	// Simple connectivity check to HIBP API
-	let client = reqwest::Client::new();
-	match client
-		.get("https://haveibeenpwned.com/api/v3/breaches")
-		.timeout(std::time::Duration::from_secs(5))
-		.send()
-		.await
-	{
-		Ok(response) => {
-			// HIBP returns 401 without API key, which is still a valid response
-			if response.status().is_client_error() || response.status().is_success() {
-				Ok(())
-			} else {
-				Err(format!("HTTP {}", response.status()))
-			}
-		}
-		Err(e) => Err(format!("Connection failed: {}", e)),
-	}
-}
-
 /// Detect file format from file extension
 fn detect_format_from_path(path: &Path) -> String {
 	path.extension()
@@ -714,13 +1449,11 @@ fn detect_format_from_path(path: &Path) -> String {
 /// Background job processor worker
 async fn process_jobs(
 	worker_id: usize,
-	queue: Arc<crate::storage::job_queue::JobQueue>,
-	verbose: u32,
+	state: Arc<AppState>,
 	shutdown_rx: &mut tokio::sync::broadcast::Receiver<()>,
 ) {
-	use std::time::Duration;
-
-	use crate::storage::job_queue::JobStatus;
+	let verbose = state.verbose;
+	let queue = state.job_queue.clone();
 
 	loop {
 		// Check for shutdown signal
@@ -731,98 +1464,274 @@ async fn process_jobs(
 			return;
 		}
 
-		// Check for jobs in the queue
-		let (jobs, _total) = queue.list_jobs(0, 100).await;
-
-		// Find first queued job
-		let queued_job: Option<&crate::storage::job_queue::Job> =
-			jobs.iter().find(|j| j.status == JobStatus::Queued);
-
-		if let Some(job) = queued_job {
-			let job_id = job.id.clone();
-			let filename = job.filename.clone();
+		match queue.claim_next(worker_id).await {
+			Ok(Some(job)) => {
+				let Some(filename) = job.source_path().map(str::to_string) else {
+					if verbose >= 1 {
+						eprintln!(
+							"[ERROR] Worker {} job {} has no server-side input file",
+							worker_id, job.id
+						);
+					}
+					let _ = queue
+						.update_job(&job.id, |j| {
+							j.fail("Job has no server-side input file".to_string())
+						})
+						.await;
+					continue;
+				};
+
+				if verbose >= 2 {
+					eprintln!(
+						"[DEBUG] Worker {} claimed job {} ({})",
+						worker_id, job.id, filename
+					);
+				}
 
-			if verbose >= 2 {
-				eprintln!(
-					"[DEBUG] Worker {} claiming job {} ({})",
-					worker_id, job_id, filename
-				);
+				metrics::metrics().workers_busy.inc();
+				process_single_job(&state, &job.id, &filename, job.file_id, worker_id).await;
+				metrics::metrics().workers_busy.dec();
 			}
-
-			// Mark job as processing
-			if queue
-				.update_job(&job_id, |j| {
-					j.status = JobStatus::Processing;
-					j.started_at = Some(chrono::Utc::now());
-					Ok(())
-				})
-				.await
-				.is_ok()
-			{
-				// Process the job
-				process_single_job(&queue, &job_id, &filename, worker_id, verbose).await;
+			Ok(None) => {
+				// Idle until a job is enqueued or shutdown is requested
+				tokio::select! {
+					_ = queue.wait_for_job(WORKER_IDLE_POLL) => {}
+					_ = shutdown_rx.recv() => {
+						if verbose >= 2 {
+							eprintln!("[DEBUG] Worker {} received shutdown signal", worker_id);
+						}
+						return;
+					}
+				}
+			}
+			Err(e) => {
+				if verbose >= 1 {
+					eprintln!("[ERROR] Worker {} could not claim a job: {}", worker_id, e);
+				}
+				tokio::time::sleep(WORKER_IDLE_POLL).await;
 			}
-		} else {
-			// No jobs available, sleep briefly
-			tokio::time::sleep(Duration::from_millis(100)).await;
 		}
 	}
 }
 
-/// Process a single job
+/// Process a single job through the ingest pipeline
 async fn process_single_job(
-	queue: &Arc<crate::storage::job_queue::JobQueue>,
+	state: &Arc<AppState>,
 	job_id: &str,
-	_filename: &str,
+	filename: &str,
+	file_id: Option<String>,
 	worker_id: usize,
-	verbose: u32,
 ) {
-	use crate::storage::job_queue::JobStatus;
+	let verbose = state.verbose;
+	let queue = state.job_queue.clone();
 
 	if verbose >= 2 {
 		eprintln!("[DEBUG] Worker {} processing job {}", worker_id, job_id);
 	}
 
-	// Simulate processing with a small delay
-	tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+	// Subscribe before checking the status so a cancellation cannot slip in between
+	let cancel = CancellationToken::new();
+	let events = queue.subscribe();
+	let job = queue.get_job(job_id).await.ok();
+	if job
+		.as_ref()
+		.is_some_and(|job| job.status == JobStatus::Cancelled)
+	{
+		cancel.cancel();
+	}
+	// Only a file stored by the upload handlers is the job's own to ingest in place and remove
+	let owns_input = job.is_some_and(|job| job.file_path.as_deref() == Some(filename));
+	let watcher = tokio::spawn(watch_for_cancellation(
+		queue.clone(),
+		events,
+		job_id.to_string(),
+		cancel.clone(),
+	));
 
-	// Mark job as completed
-	let result = queue
-		.update_job(job_id, |j| {
-			j.status = JobStatus::Completed;
-			j.completed_at = Some(chrono::Utc::now());
-			j.progress_percentage = 100;
-			j.rows_processed = j.file_size_bytes as usize / 100; // Mock: estimate rows from file size
-			Ok(())
-		})
-		.await;
+	let (progress_tx, mut progress_rx) = tokio::sync::watch::channel((0usize, 0usize));
+	let ctx = IngestContext {
+		working_copy_mgr: state.working_copy_mgr.clone(),
+		config: state.config.current(),
+		storage: StorageTarget::Database(state.database_path.clone()),
+		format: None,
+		verbose,
+		progress: Some(progress_tx),
+		file_id,
+		cancel: Some(cancel),
+		owns_input,
+	};
+
+	// Forward pipeline progress to the job until the context is dropped
+	let progress_queue = queue.clone();
+	let progress_job_id = job_id.to_string();
+	let forwarder = tokio::spawn(async move {
+		while progress_rx.changed().await.is_ok() {
+			let (rows, total) = *progress_rx.borrow_and_update();
+			let _ = progress_queue
+				.update_job(&progress_job_id, move |j| {
+					if j.status == JobStatus::Processing {
+						j.update_progress(rows, total);
+					}
+					Ok(())
+				})
+				.await;
+		}
+	});
+
+	let outcome = run_ingest_job(&ctx, Path::new(filename)).await;
+	drop(ctx);
+	let _ = forwarder.await;
+	watcher.abort();
+
+	let result = match outcome {
+		Ok(ingest_result) => {
+			queue
+				.update_job(job_id, |j| j.complete_with_result(ingest_result))
+				.await
+		}
+		Err(JobFailure::Transient(e)) => {
+			if verbose >= 1 {
+				eprintln!(
+					"[WARN] Worker {} job {} hit a transient error: {}",
+					worker_id, job_id, e
+				);
+			}
+			queue
+				.update_job(job_id, |j| j.retry_or_dead_letter(e))
+				.await
+		}
+		Err(JobFailure::Permanent(e)) => {
+			if verbose >= 1 {
+				eprintln!("[ERROR] Worker {} job {} failed: {}", worker_id, job_id, e);
+			}
+			queue.update_job(job_id, |j| j.fail(e)).await
+		}
+		Err(JobFailure::Cancelled) => {
+			// The job already carries its final status
+			if verbose >= 1 {
+				eprintln!(
+					"[INFO] Worker {} stopped cancelled job {}",
+					worker_id, job_id
+				);
+			}
+			return;
+		}
+	};
 
 	match result {
-		Ok(_) => {
+		Ok(job) => {
 			if verbose >= 2 {
-				eprintln!("[DEBUG] Worker {} completed job {}", worker_id, job_id);
+				eprintln!(
+					"[DEBUG] Worker {} finished job {} ({})",
+					worker_id, job_id, job.status
+				);
+			}
+			// A job that will be retried still needs its upload
+			if job.status.is_terminal() {
+				remove_job_upload(&job, verbose);
 			}
 		}
 		Err(e) => {
+			// The job was cancelled or removed while it was being processed
 			if verbose >= 2 {
 				eprintln!(
-					"[DEBUG] Worker {} error updating job {}: {}",
+					"[DEBUG] Worker {} could not record outcome of job {}: {}",
 					worker_id, job_id, e
 				);
 			}
-			// Try to mark as failed
-			let _ = queue
-				.update_job(job_id, |j| {
-					j.status = JobStatus::Failed;
-					j.completed_at = Some(chrono::Utc::now());
-					j.error_message = Some(format!("Processing error: {}", e));
-					Ok(())
-				})
-				.await;
 		}
 	}
 }
 
+/// Securely delete the upload a job owns once it is no longer needed
+///
+/// Only `job.file_path`, which the upload handlers set to a file they stored in
+/// the working directory, is removed; files queued from the import directory
+/// are left alone.
+pub(crate) fn remove_job_upload(job: &Job, verbose: u32) {
+	let Some(path) = job.file_path.as_deref().map(Path::new) else {
+		return;
+	};
+	if !path.exists() {
+		return;
+	}
+
+	let config = SecureDeletionConfig {
+		log_deletions: verbose >= 2,
+		..SecureDeletionConfig::default()
+	};
+	if let Err(e) = secure_delete_file(path, config)
+		&& verbose >= 1
+	{
+		eprintln!(
+			"[ERROR] Failed to remove upload {:?} of job {}: {}",
+			path, job.id, e
+		);
+	}
+}
+
+/// Why a server job did not complete
+#[derive(Debug)]
+enum JobFailure {
+	/// Worth retrying later, e.g. the database was locked
+	Transient(String),
+	/// Retrying would fail the same way
+	Permanent(String),
+	/// The job was cancelled while it ran
+	Cancelled,
+}
+
+/// Cancel `token` once the queue reports that the job was cancelled
+async fn watch_for_cancellation(
+	queue: Arc<JobQueue>,
+	mut events: tokio::sync::broadcast::Receiver<Job>,
+	job_id: String,
+	token: CancellationToken,
+) {
+	use tokio::sync::broadcast::error::RecvError;
+
+	loop {
+		let cancelled = match events.recv().await {
+			Ok(job) => job.id == job_id && job.status == JobStatus::Cancelled,
+			// Missed updates may have included the cancellation
+			Err(RecvError::Lagged(_)) => {
+				matches!(queue.get_job(&job_id).await, Ok(job) if job.status == JobStatus::Cancelled)
+			}
+			Err(RecvError::Closed) => return,
+		};
+		if cancelled {
+			token.cancel();
+			return;
+		}
+	}
+}
+
+/// Run the ingest pipeline for a single server job
+///
+/// Fails only when nothing could be processed or a transient storage error
+/// interrupted the run; other per-row problems are reported in the result's errors.
+async fn run_ingest_job(ctx: &IngestContext, file_path: &Path) -> Result<IngestResult, JobFailure> {
+	// The database being unavailable is an environment problem, not a problem with the job
+	let storage = ctx.storage.open().map_err(JobFailure::Transient)?;
+	let mut stats = IngestStats::default();
+
+	process_single_file(ctx, file_path, &storage, &mut stats).await;
+
+	if stats.cancelled {
+		return Err(JobFailure::Cancelled);
+	}
+
+	if let Some(err_msg) = stats.transient_error {
+		return Err(JobFailure::Transient(err_msg));
+	}
+
+	if stats.total_rows == 0 && !stats.errors.is_empty() {
+		return Err(JobFailure::Permanent(stats.errors.join("; ")));
+	}
+
+	Ok(stats.to_result())
+}
+
 /// Set up signal handlers for graceful shutdown (SIGTERM, SIGINT on Unix; Ctrl-C on Windows)
 fn setup_signal_handler(verbose: u32) -> Result<tokio::sync::broadcast::Sender<()>, String> {
 	let (shutdown_tx, _) = tokio::sync::broadcast::channel::<()>(1);
@@ -892,12 +1801,14 @@ fn setup_signal_handler(verbose: u32) -> Result<tokio::sync::broadcast::Sender<(
 
 /// Handle the server command
 pub async fn server(args: ServerArgs) -> Result<(), String> {
-	use std::sync::Arc;
-
 	use crate::{
-		api::server::{AppState, create_app},
+		api::{server::create_app, webhook::WebhookDispatcher},
+		core::config::parse_bind_address,
 		deploy::ServiceManager,
-		network::oauth::OAuthProvider,
+		network::{
+			oauth::{OAuthProvider, TokenValidation},
+			tls::{TlsSettings, build_server_config},
+		},
 		storage::job_queue::JobQueue,
 	};
 
@@ -909,9 +1820,8 @@ pub async fn server(args: ServerArgs) -> Result<(), String> {
 	}
 
 	// Load configuration file
-	let config =
-		crate::core::config::Config::load_with_search(args.config.as_deref(), args.verbose >= 2)
-			.map_err(|e| format!("Failed to load configuration: {}", e))?;
+	let config = Config::load_with_search(args.config.as_deref(), args.verbose >= 2)
+		.map_err(|e| format!("Failed to load configuration: {}", e))?;
 
 	// Get OAuth settings - CLI args override config file
 	let oauth_client_id = args
@@ -934,25 +1844,162 @@ pub async fn server(args: ServerArgs) -> Result<(), String> {
 		);
 	}
 
-	// Initialize OAuth provider
+	// Initialize OAuth provider and how it validates incoming tokens
+	let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+	let validation = TokenValidation {
+		issuer: non_empty(&config.oauth.issuer),
+		audience: non_empty(&config.oauth.audience),
+		jwks_uri: non_empty(&config.oauth.jwks_uri),
+		introspection_endpoint: non_empty(&config.oauth.introspection_endpoint),
+		jwks_cache_ttl_secs: config.oauth.jwks_cache_ttl_secs,
+		leeway_secs: config.oauth.leeway_secs,
+	};
+
+	if validation.jwks_uri.is_none() && validation.introspection_endpoint.is_none() {
+		return Err(
+			"Missing token validation configuration. Set oauth.jwks_uri or oauth.introspection_endpoint."
+				.to_string(),
+		);
+	}
+
+	if args.verbose >= 1 && (validation.issuer.is_none() || validation.audience.is_none()) {
+		eprintln!(
+			"[WARN] oauth.issuer or oauth.audience not set; those token claims are not checked"
+		);
+	}
+
 	let oauth = OAuthProvider::new(
 		oauth_client_id,
 		oauth_client_secret,
 		oauth_token_endpoint,
 		args.oauth_scope,
-	);
+	)
+	.with_validation(validation);
 
 	if args.verbose >= 2 {
 		eprintln!("[DEBUG] OAuth 2.0 provider initialized");
 	}
 
+	// Working directory for job working copies; fail fast if it is unusable
+	let working_dir = config
+		.working_directory
+		.path
+		.clone()
+		.unwrap_or_else(|| "/tmp/dumptruck".to_string());
+	let working_copy_mgr = WorkingCopyManager::new(
+		Path::new(&working_dir),
+		config.working_directory.verify_noexec,
+		args.verbose as u32,
+	)
+	.map_err(|e| format!("Failed to initialize working directory: {}", e))?;
+
+	let database_path = args
+		.database
+		.clone()
+		.unwrap_or_else(get_default_database_path);
+	let max_upload_bytes = args
+		.max_upload_bytes
+		.unwrap_or(config.server.max_upload_bytes);
+
+	if args.verbose >= 2 {
+		eprintln!("[DEBUG] Working directory: {}", working_dir);
+		eprintln!("[DEBUG] Job results stored in database: {}", database_path);
+		eprintln!("[DEBUG] Maximum upload size: {} bytes", max_upload_bytes);
+	}
+
+	// Resumable upload sessions persist in the working directory across restarts
+	let uploads = ResumableUploads::new(working_copy_mgr.working_dir(), max_upload_bytes)
+		.map_err(|e| format!("Failed to initialize upload directory: {}", e))?;
+
+	// Create or upgrade the storage schema before the readiness probe reports on it
+	SqliteStorage::new(&database_path, None)
+		.map_err(|e| format!("Failed to open database {}: {}", database_path, e))?;
+
+	// Jobs are kept in the database so queued and running work survives restarts
+	let job_queue = JobQueue::open_sqlite(&database_path)
+		.map_err(|e| format!("Failed to open job queue: {}", e))?;
+	let (requeued, failed) = job_queue
+		.recover_interrupted()
+		.await
+		.map_err(|e| format!("Failed to recover interrupted jobs: {}", e))?;
+	if args.verbose >= 1 && requeued + failed > 0 {
+		eprintln!(
+			"[INFO] Recovered interrupted jobs: {} re-queued, {} failed",
+			requeued, failed
+		);
+	}
+
+	// Listen addresses - CLI args override config
+	let bind_entries = if args.bind.is_empty() {
+		&config.server.bind_addresses
+	} else {
+		&args.bind
+	};
+	let addrs = bind_entries
+		.iter()
+		.map(|entry| parse_bind_address(entry, args.port))
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|e| e.to_string())?;
+	if addrs.is_empty() {
+		return Err("No bind addresses configured".to_string());
+	}
+
+	// TLS settings - CLI args override config
+	let tls_settings = TlsSettings {
+		cert_path: args
+			.cert
+			.clone()
+			.or_else(|| config.server.tls.cert_path.clone())
+			.unwrap_or_else(|| "/etc/tls/tls.crt".to_string()),
+		key_path: args
+			.key
+			.clone()
+			.or_else(|| config.server.tls.key_path.clone())
+			.unwrap_or_else(|| "/etc/tls/tls.key".to_string()),
+		client_ca_path: args
+			.client_ca
+			.clone()
+			.or_else(|| config.server.tls.client_ca_path.clone()),
+		client_auth_optional: config.server.tls.client_auth_optional,
+	};
+	let tls_reload_interval_secs = config.server.tls.reload_interval_secs;
+
 	// Create application state
-	let job_queue = Arc::new(JobQueue::new());
+	let job_queue = Arc::new(job_queue);
+	let rate_limiter = Arc::new(RateLimiter::new(config.server.rate_limits.clone()));
+
+	// Admin changes to suffix rules and custom passwords are written back to this file
+	let config_path = Config::config_file_path(args.config.as_deref());
+	if args.verbose >= 2 {
+		eprintln!(
+			"[DEBUG] Runtime configuration changes persist to {:?}",
+			config_path
+		);
+	}
+	let config_store = ConfigStore::new(config, config_path);
 	let state = Arc::new(AppState {
 		job_queue: job_queue.clone(),
 		oauth_provider: Arc::new(oauth),
+		working_copy_mgr: Arc::new(working_copy_mgr),
+		config: Arc::new(config_store),
+		database_path,
+		max_upload_bytes,
+		uploads: Arc::new(uploads),
+		rate_limiter,
+		verbose: args.verbose as u32,
 	});
 
+	// Deliver signed callbacks for jobs submitted with a callback URL
+	let webhooks = state.config.current().server.webhooks.clone();
+	if webhooks.signing_configured() {
+		let dispatcher = WebhookDispatcher::new(&webhooks)
+			.map_err(|e| format!("Failed to initialize webhooks: {}", e))?;
+		Arc::new(dispatcher).spawn(job_queue.clone());
+		if args.verbose >= 1 {
+			eprintln!("[INFO] Job completion callbacks enabled");
+		}
+	}
+
 	// Create router with all endpoints
 	let app = create_app(state.clone());
 
@@ -971,40 +2018,67 @@ pub async fn server(args: ServerArgs) -> Result<(), String> {
 	}
 
 	for worker_id in 0..worker_count {
-		let queue = job_queue.clone();
-		let verbose = args.verbose as u32;
+		let worker_state = state.clone();
 		let mut shutdown_rx = shutdown_workers_tx.subscribe();
 
 		tokio::spawn(async move {
-			process_jobs(worker_id, queue, verbose, &mut shutdown_rx).await;
+			metrics::metrics().workers.inc();
+			process_jobs(worker_id, worker_state, &mut shutdown_rx).await;
+			metrics::metrics().workers.dec();
 		});
 	}
 
-	// Create socket address
-	let addr = std::net::SocketAddr::from(([127, 0, 0, 1], args.port));
-	if args.verbose >= 1 {
-		eprintln!("[INFO] Listening on {}", addr);
+	// Abandoned resumable uploads are removed on startup and then periodically
+	let upload_expiry_secs = state.config.current().server.upload_expiry_secs;
+	if upload_expiry_secs > 0 {
+		spawn_upload_sweeper(
+			state.uploads.clone(),
+			std::time::Duration::from_secs(upload_expiry_secs),
+			args.verbose as u32,
+		);
 	}
 
-	// Load TLS certificates
-	let cert_path = args.cert.unwrap_or_else(|| "/etc/tls/tls.crt".to_string());
-	let key_path = args.key.unwrap_or_else(|| "/etc/tls/tls.key".to_string());
-
 	if args.verbose >= 2 {
-		eprintln!("[DEBUG] Loading TLS certificates from {}", cert_path);
-		eprintln!("[DEBUG] Loading TLS key from {}", key_path);
+		eprintln!(
+			"[DEBUG] Loading TLS certificates from {}",
+			tls_settings.cert_path
+		);
+		eprintln!("[DEBUG] Loading TLS key from {}", tls_settings.key_path);
 	}
 
-	// Create TLS config using axum-server
-	let tls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&cert_path, &key_path)
-		.await
+	// Build the TLS config; it is shared by all listeners and reloaded in place
+	let tls_config = build_server_config(&tls_settings)
 		.map_err(|e| format!("Failed to load TLS config: {}", e))?;
+	let tls_config = axum_server::tls_rustls::RustlsConfig::from_config(Arc::new(tls_config));
 
+	if args.verbose >= 1
+		&& let Some(ca_path) = &tls_settings.client_ca_path
+	{
+		eprintln!(
+			"[INFO] Mutual TLS enabled: client certificates {} (CA: {})",
+			if tls_settings.client_auth_optional {
+				"optional"
+			} else {
+				"required"
+			},
+			ca_path
+		);
+	}
 	if args.verbose >= 2 {
 		eprintln!("[DEBUG] TLS configuration loaded successfully");
 	}
 
+	spawn_tls_reloader(
+		tls_config.clone(),
+		tls_settings,
+		tls_reload_interval_secs,
+		args.verbose as u32,
+	);
+
 	if args.verbose >= 1 {
+		for addr in &addrs {
+			eprintln!("[INFO] Listening on {}", addr);
+		}
 		eprintln!("[INFO] Server started successfully, waiting for connections...");
 		eprintln!("[INFO] Press Ctrl+C to shutdown gracefully");
 	}
@@ -1013,8 +2087,10 @@ pub async fn server(args: ServerArgs) -> Result<(), String> {
 	let shutdown_tx = setup_signal_handler(args.verbose as u32)?;
 	let mut shutdown_rx = shutdown_tx.subscribe();
 
-	// Start server with TLS using axum-server
-	let server = axum_server::bind_rustls(addr, tls_config).serve(app.into_make_service());
+	// Start one TLS listener per address using axum-server
+	let server = futures_util::future::try_join_all(addrs.iter().map(|addr| {
+		axum_server::bind_rustls(*addr, tls_config.clone()).serve(app.clone().into_make_service())
+	}));
 
 	// Race between server and shutdown signal
 	tokio::select! {
@@ -1050,6 +2126,111 @@ pub async fn server(args: ServerArgs) -> Result<(), String> {
 	Ok(())
 }
 
+/// Remove resumable uploads idle for `max_idle` now and every [`UPLOAD_SWEEP_INTERVAL`]
+fn spawn_upload_sweeper(
+	uploads: Arc<ResumableUploads>,
+	max_idle: std::time::Duration,
+	verbose: u32,
+) {
+	tokio::spawn(async move {
+		// The first tick completes immediately, so uploads left before a restart go first
+		let mut ticker = tokio::time::interval(UPLOAD_SWEEP_INTERVAL);
+		loop {
+			ticker.tick().await;
+			match uploads.remove_expired(max_idle).await {
+				Ok(removed) => {
+					if verbose >= 1 {
+						for id in removed {
+							eprintln!("[INFO] Removed expired upload {}", id);
+						}
+					}
+				}
+				Err(e) => {
+					if verbose >= 1 {
+						eprintln!("[WARN] Failed to remove expired uploads: {}", e);
+					}
+				}
+			}
+		}
+	});
+}
+
+/// Reload the server's TLS configuration on SIGHUP or when its files change
+///
+/// New connections use the reloaded certificate; established connections are
+/// unaffected. A configuration that fails to load is reported and the current
+/// one kept; file changes are retried until they load.
+fn spawn_tls_reloader(
+	tls_config: axum_server::tls_rustls::RustlsConfig,
+	settings: crate::network::tls::TlsSettings,
+	interval_secs: u64,
+	verbose: u32,
+) {
+	tokio::spawn(async move {
+		#[cfg(unix)]
+		let mut sighup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+			Ok(sig) => Some(sig),
+			Err(e) => {
+				if verbose >= 1 {
+					eprintln!("[WARN] Failed to setup SIGHUP handler: {}", e);
+				}
+				None
+			}
+		};
+		let mut ticker = (interval_secs > 0)
+			.then(|| tokio::time::interval(std::time::Duration::from_secs(interval_secs)));
+		let mut stamp = settings.file_stamp();
+
+		loop {
+			let hangup = async {
+				#[cfg(unix)]
+				if let Some(sighup) = sighup.as_mut() {
+					sighup.recv().await;
+					return;
+				}
+				std::future::pending::<()>().await
+			};
+			let tick = async {
+				match ticker.as_mut() {
+					Some(ticker) => {
+						ticker.tick().await;
+					}
+					None => std::future::pending().await,
+				}
+			};
+
+			let reason = tokio::select! {
+				_ = hangup => "SIGHUP received",
+				_ = tick => {
+					if settings.file_stamp() == stamp {
+						continue;
+					}
+					"certificate files changed"
+				}
+			};
+
+			let current = settings.file_stamp();
+			match crate::network::tls::build_server_config(&settings) {
+				Ok(config) => {
+					tls_config.reload_from_config(Arc::new(config));
+					stamp = current;
+					if verbose >= 1 {
+						eprintln!("[INFO] TLS configuration reloaded ({})", reason);
+					}
+				}
+				Err(e) => {
+					if verbose >= 1 {
+						eprintln!(
+							"[WARN] Failed to reload TLS configuration ({}); keeping current: {}",
+							reason, e
+						);
+					}
+				}
+			}
+		}
+	});
+}
+
 /// Handle the stats command
 pub async fn stats(args: crate::cli::StatsArgs) -> Result<(), String> {
 	if args.verbose >= 1 {
@@ -1311,4 +2492,517 @@ mod tests {
 		let path = Path::new("test");
 		assert_eq!(detect_format_from_path(path), "csv");
 	}
+
+	#[test]
+	fn test_detect_format_sql() {
+		let path = Path::new("backup/Users.SQL");
+		assert_eq!(detect_format_from_path(path), "sql");
+	}
+
+	/// Queue `input` as a job, run it in a server whose state lives next to it,
+	/// and open the database the job stored its rows in
+	async fn run_job_on(input: &Path) -> (Job, rusqlite::Connection) {
+		let dir = input.parent().expect("input directory");
+		let state = crate::api::server::tests::test_state(dir);
+		let job_id = state
+			.job_queue
+			.enqueue(input.to_string_lossy().to_string(), 64)
+			.await
+			.expect("enqueue");
+		state
+			.job_queue
+			.update_job(&job_id, |j| j.start_processing())
+			.await
+			.expect("start");
+
+		process_single_job(&state, &job_id, &input.to_string_lossy(), None, 0).await;
+
+		let job = state.job_queue.get_job(&job_id).await.expect("job");
+		let conn = rusqlite::Connection::open(&state.database_path).expect("open db");
+		(job, conn)
+	}
+
+	fn address_count(conn: &rusqlite::Connection) -> i64 {
+		conn.query_row("SELECT COUNT(*) FROM canonical_addresses", [], |r| r.get(0))
+			.expect("count")
+	}
+
+	#[tokio::test]
+	async fn test_process_single_job_runs_pipeline() {
+		use crate::storage::job_queue::JobStatus;
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let input = dir.path().join("dump.csv");
+		std::fs::write(
+			&input,
+			"email,password\nalice@example.com,hunter2\nbob@example.com,letmein\n",
+		)
+		.expect("write input");
+
+		let (job, conn) = run_job_on(&input).await;
+		assert_eq!(job.status, JobStatus::Completed);
+		assert_eq!(job.rows_processed, 2);
+		assert_eq!(job.progress_percentage, 100);
+		let result = job.result.expect("result");
+		assert_eq!(result.rows_processed, 2);
+		assert!(result.unique_addresses >= 2);
+		assert!(!result.detailed_findings.is_empty());
+
+		assert_eq!(address_count(&conn), 2);
+		let files: i64 = conn
+			.query_row("SELECT COUNT(*) FROM file_metadata", [], |r| r.get(0))
+			.expect("count");
+		assert_eq!(files, 1);
+	}
+
+	#[tokio::test]
+	async fn test_process_single_job_ingests_sql_dump_tables() {
+		use crate::storage::job_queue::JobStatus;
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let input = dir.path().join("dump.sql");
+		std::fs::write(
+			&input,
+			"CREATE TABLE `users` (`id` int, `email` varchar(255), `password` varchar(64));\n\
+			 INSERT INTO `users` VALUES (1,'alice@example.com','hunter2'),(2,'bob@example.com',\
+			 'letmein');\n\
+			 COPY public.admins (email) FROM stdin;\n\
+			 carol@example.com\n\
+			 \\.\n",
+		)
+		.expect("write input");
+
+		let (job, conn) = run_job_on(&input).await;
+		assert_eq!(job.status, JobStatus::Completed);
+		let result = job.result.expect("result");
+		// Column names are not rows of the dump
+		assert_eq!(result.rows_processed, 3);
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+		assert_eq!(address_count(&conn), 3);
+	}
+
+	#[tokio::test]
+	async fn test_process_single_job_reads_sqlite_database_by_header() {
+		use crate::storage::job_queue::JobStatus;
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		// Detected by its header, not its extension
+		let input = dir.path().join("customers.dat");
+		rusqlite::Connection::open(&input)
+			.expect("create db")
+			.execute_batch(
+				"CREATE TABLE customers (id INTEGER PRIMARY KEY, email TEXT, password TEXT);
+				 INSERT INTO customers (email, password) VALUES
+				   ('alice@example.com', 'hunter2'), ('bob@example.com', 'letmein');",
+			)
+			.expect("populate db");
+
+		let (job, conn) = run_job_on(&input).await;
+		assert_eq!(job.status, JobStatus::Completed);
+		let result = job.result.expect("result");
+		assert_eq!(result.rows_processed, 2);
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+		assert_eq!(address_count(&conn), 2);
+	}
+
+	#[tokio::test]
+	async fn test_sqlite_tables_count_only_their_data_rows() {
+		use crate::storage::job_queue::JobStatus;
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let input = dir.path().join("shop.db");
+		rusqlite::Connection::open(&input)
+			.expect("create db")
+			.execute_batch(
+				"CREATE TABLE customers (email TEXT, password TEXT);
+				 INSERT INTO customers VALUES
+				   ('alice@example.com', 'hunter2'), ('bob@example.com', 'letmein'),
+				   ('carol@example.com', 'qwerty');
+				 CREATE TABLE staff (email TEXT);
+				 INSERT INTO staff VALUES ('dave@example.com');
+				 CREATE TABLE empty (email TEXT);",
+			)
+			.expect("populate db");
+
+		let (job, _) = run_job_on(&input).await;
+		assert_eq!(job.status, JobStatus::Completed);
+		assert_eq!(job.rows_processed, 4);
+		let result = job.result.expect("result");
+		assert_eq!(result.rows_processed, 4);
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		for (rows, table) in [(3, "customers"), (1, "staff")] {
+			let prefix = format!(
+				"Processed {} rows from {}#{} |",
+				rows,
+				input.display(),
+				table
+			);
+			assert!(
+				result.metadata.iter().any(|m| m.starts_with(&prefix)),
+				"{:?}",
+				result.metadata
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_process_single_job_extracts_gzip_input() {
+		use std::io::Write;
+
+		use crate::storage::job_queue::JobStatus;
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let input = dir.path().join("dump.csv.gz");
+		let mut encoder = flate2::write::GzEncoder::new(
+			std::fs::File::create(&input).expect("create input"),
+			flate2::Compression::default(),
+		);
+		encoder
+			.write_all(b"email,password\nalice@example.com,hunter2\nbob@example.com,letmein\n")
+			.expect("write input");
+		encoder.finish().expect("finish gzip");
+
+		let (job, conn) = run_job_on(&input).await;
+		assert_eq!(job.status, JobStatus::Completed);
+		assert_eq!(job.rows_processed, 2);
+
+		assert_eq!(address_count(&conn), 2);
+		let filename: String = conn
+			.query_row("SELECT original_filename FROM file_metadata", [], |r| {
+				r.get(0)
+			})
+			.expect("file metadata");
+		assert_eq!(filename, "dump.csv.gz!dump.csv");
+	}
+
+	#[tokio::test]
+	async fn test_process_single_job_missing_file_fails() {
+		use crate::storage::job_queue::JobStatus;
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let (job, _) = run_job_on(&dir.path().join("missing.csv")).await;
+		assert_eq!(job.status, JobStatus::Failed);
+		assert!(job.error_message.is_some());
+		assert!(job.result.is_none());
+	}
+
+	#[tokio::test]
+	async fn test_cancelled_job_keeps_status_and_removes_working_copy() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = crate::api::server::tests::test_state(dir.path());
+		let input = state.working_copy_mgr.working_dir().join("upload.csv");
+		std::fs::write(&input, "email,password\nalice@example.com,hunter2\n").expect("write");
+		let job = Job::for_stored_file(
+			"upload.csv".to_string(),
+			input.to_string_lossy().to_string(),
+			40,
+			"abc".to_string(),
+		);
+		let job_id = state.job_queue.enqueue_job(job).await.expect("enqueue");
+		state
+			.job_queue
+			.update_job(&job_id, |j| j.start_processing())
+			.await
+			.expect("start");
+		state.job_queue.cancel_job(&job_id).await.expect("cancel");
+
+		process_single_job(&state, &job_id, &input.to_string_lossy(), None, 0).await;
+
+		let job = state.job_queue.get_job(&job_id).await.expect("job");
+		assert_eq!(job.status, JobStatus::Cancelled);
+		assert!(job.result.is_none());
+		assert!(job.error_message.is_none());
+		assert!(!input.exists());
+	}
+
+	#[tokio::test]
+	async fn test_cancelled_job_leaves_files_outside_working_dir_intact() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = crate::api::server::tests::test_state(dir.path());
+		let csv = "email,password\nalice@example.com,hunter2\n";
+		let outside = dir.path().join("precious.csv");
+		std::fs::write(&outside, csv).expect("write");
+
+		// Given directly and through the working directory
+		let working_dir = state.working_copy_mgr.working_dir();
+		for path in [outside.clone(), working_dir.join("..").join("precious.csv")] {
+			let job_id = state
+				.job_queue
+				.enqueue(path.to_string_lossy().to_string(), 40)
+				.await
+				.expect("enqueue");
+			state
+				.job_queue
+				.update_job(&job_id, |j| j.start_processing())
+				.await
+				.expect("start");
+			state.job_queue.cancel_job(&job_id).await.expect("cancel");
+
+			process_single_job(&state, &job_id, &path.to_string_lossy(), None, 0).await;
+
+			let job = state.job_queue.get_job(&job_id).await.expect("job");
+			assert_eq!(job.status, JobStatus::Cancelled);
+			assert_eq!(std::fs::read_to_string(&outside).expect("read"), csv);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_finished_job_removes_only_its_own_upload() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = crate::api::server::tests::test_state(dir.path());
+		let working_dir = state.working_copy_mgr.working_dir().to_path_buf();
+		let csv = "email,password\nalice@example.com,hunter2\n";
+
+		// An upload the job owns is ingested in place and removed once the job completes
+		let upload = working_dir.join("upload_1_dump.csv");
+		std::fs::write(&upload, csv).expect("write");
+		let job = Job::for_stored_file(
+			"dump.csv".to_string(),
+			upload.to_string_lossy().to_string(),
+			40,
+			"abc".to_string(),
+		);
+		let job_id = state.job_queue.enqueue_job(job).await.expect("enqueue");
+		state
+			.job_queue
+			.update_job(&job_id, |j| j.start_processing())
+			.await
+			.expect("start");
+		process_single_job(&state, &job_id, &upload.to_string_lossy(), None, 0).await;
+		let job = state.job_queue.get_job(&job_id).await.expect("job");
+		assert_eq!(job.status, JobStatus::Completed);
+		assert!(!upload.exists());
+
+		// A path that merely resolves into the working directory is copied, never owned
+		let other = working_dir.join("other.csv");
+		std::fs::write(&other, csv).expect("write");
+		let sneaky = working_dir.join("..").join("work").join("other.csv");
+		let job_id = state
+			.job_queue
+			.enqueue(sneaky.to_string_lossy().to_string(), 40)
+			.await
+			.expect("enqueue");
+		state
+			.job_queue
+			.update_job(&job_id, |j| j.start_processing())
+			.await
+			.expect("start");
+		process_single_job(&state, &job_id, &sneaky.to_string_lossy(), None, 0).await;
+		let job = state.job_queue.get_job(&job_id).await.expect("job");
+		assert_eq!(job.status, JobStatus::Completed);
+		assert_eq!(std::fs::read_to_string(&other).expect("read"), csv);
+	}
+
+	#[tokio::test]
+	async fn test_streamed_rows_match_in_memory_result() {
+		use crate::ingest::adapters::{CsvAdapter, FormatAdapter};
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = crate::api::server::tests::test_state(dir.path());
+		let ctx = IngestContext {
+			working_copy_mgr: state.working_copy_mgr.clone(),
+			config: state.config.current(),
+			// Only the reported results are compared; keep the stored rows in memory
+			storage: StorageTarget::Database(":memory:".to_string()),
+			format: None,
+			verbose: 0,
+			progress: None,
+			file_id: None,
+			cancel: None,
+			owns_input: false,
+		};
+		// Every row has an email; a few quoted notes span lines
+		let mut csv = String::from("contact,note\n");
+		for i in 0..PROGRESS_BATCH_ROWS + 5 {
+			if i % 100 == 0 {
+				csv.push_str(&format!("user{}@example.com,\"see,\nbelow\"\n", i));
+			} else {
+				csv.push_str(&format!("user{}@example.com,x\n", i));
+			}
+		}
+		let path = dir.path().join("dump.csv");
+		std::fs::write(&path, &csv).expect("write");
+		let storage = ctx.storage.open().expect("storage");
+
+		let mut streamed = IngestStats::default();
+		let stream = StreamingCsvParser::new(&path).await.expect("open");
+		let file = FileRun::new(&path, "file-1", csv.len());
+		process_stream(
+			&mut RowStream::Csv(stream),
+			file,
+			&ctx,
+			&storage,
+			&mut streamed,
+		)
+		.await;
+
+		let mut buffered = IngestStats::default();
+		let rows = CsvAdapter::new().parse(&csv);
+		let headers = extract_headers(&rows, &ctx);
+		process_rows(
+			&rows,
+			&headers,
+			&path,
+			"file-2",
+			&ctx,
+			&storage,
+			&mut buffered,
+		)
+		.await;
+
+		assert_eq!(streamed.total_rows, PROGRESS_BATCH_ROWS + 5);
+		assert_eq!(streamed.detailed_findings.len(), PROGRESS_BATCH_ROWS + 5);
+		assert_eq!(
+			serde_json::to_value(streamed.to_result()).expect("json"),
+			serde_json::to_value(buffered.to_result()).expect("json")
+		);
+	}
+
+	#[test]
+	fn test_detailed_findings_are_capped_and_counted() {
+		let headers = vec!["email".to_string()];
+		let mut stats = IngestStats::default();
+		for idx in 1..=MAX_DETAILED_FINDINGS + 5 {
+			let row = vec![format!("user{}@example.com", idx)];
+			let detection = detection::analyzer::detect_row(&row, Some(&headers), idx);
+			record_findings(idx, &detection, &mut stats);
+		}
+
+		let result = stats.to_result();
+		assert_eq!(result.detailed_findings.len(), MAX_DETAILED_FINDINGS);
+		assert_eq!(result.rows_with_findings, MAX_DETAILED_FINDINGS + 5);
+		assert_eq!(
+			result.pii_summary.expect("summary").emails,
+			MAX_DETAILED_FINDINGS + 5
+		);
+		assert!(
+			result
+				.metadata
+				.iter()
+				.any(|m| m.starts_with("Detailed findings limited"))
+		);
+	}
+
+	#[tokio::test]
+	async fn test_row_batches_do_not_block_the_runtime() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = crate::api::server::tests::test_state(dir.path());
+		let ctx = IngestContext {
+			working_copy_mgr: state.working_copy_mgr.clone(),
+			config: state.config.current(),
+			storage: StorageTarget::Database(state.database_path.clone()),
+			format: None,
+			verbose: 0,
+			progress: None,
+			file_id: None,
+			cancel: None,
+			owns_input: false,
+		};
+		let rows = vec![
+			vec!["email".to_string()],
+			vec!["alice@example.com".to_string()],
+		];
+		let headers = Some(rows[0].clone());
+		let storage = ctx.storage.open().expect("storage");
+		let mut stats = IngestStats::default();
+
+		// Another process holds the write lock, so storing the batch waits for it
+		let blocker = rusqlite::Connection::open(&state.database_path).expect("open db");
+		blocker.execute_batch("BEGIN IMMEDIATE").expect("lock");
+		let mut ingest = Box::pin(process_rows(
+			&rows,
+			&headers,
+			Path::new("dump.csv"),
+			"file-1",
+			&ctx,
+			&storage,
+			&mut stats,
+		));
+
+		// The single-threaded runtime keeps running other tasks meanwhile
+		let started = std::time::Instant::now();
+		tokio::select! {
+			_ = &mut ingest => panic!("batch finished while the database was locked"),
+			_ = tokio::time::sleep(std::time::Duration::from_millis(50)) => {}
+		}
+		assert!(started.elapsed() < std::time::Duration::from_secs(1));
+
+		blocker.execute_batch("COMMIT").expect("unlock");
+		ingest.await;
+		assert!(stats.errors.is_empty(), "{:?}", stats.errors);
+		assert_eq!(stats.rows_with_findings, 1);
+	}
+
+	#[tokio::test]
+	async fn test_cancelled_rows_are_marked_incomplete() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = crate::api::server::tests::test_state(dir.path());
+		let cancel = CancellationToken::new();
+		let ctx = IngestContext {
+			working_copy_mgr: state.working_copy_mgr.clone(),
+			config: state.config.current(),
+			storage: StorageTarget::Database(state.database_path.clone()),
+			format: None,
+			verbose: 0,
+			progress: None,
+			file_id: None,
+			cancel: Some(cancel.clone()),
+			owns_input: false,
+		};
+		let rows: Vec<Vec<String>> = (0..PROGRESS_BATCH_ROWS + 10)
+			.map(|i| vec![format!("user{}@example.com", i), "secret".to_string()])
+			.collect();
+		let storage = ctx.storage.open().expect("storage");
+		let mut stats = IngestStats::default();
+
+		cancel.cancel();
+		process_rows(
+			&rows,
+			&None,
+			Path::new("dump.csv"),
+			"file-1",
+			&ctx,
+			&storage,
+			&mut stats,
+		)
+		.await;
+
+		assert!(stats.cancelled);
+		assert_eq!(stats.total_rows, 0);
+		let conn = rusqlite::Connection::open(&state.database_path).expect("open db");
+		let markers: i64 = conn
+			.query_row(
+				"SELECT COUNT(*) FROM normalized_rows WHERE file_id = 'file-1' AND event_type = \
+				 '__ingest_cancelled__'",
+				[],
+				|r| r.get(0),
+			)
+			.expect("count");
+		assert_eq!(markers, 1);
+		assert_eq!(address_count(&conn), 0);
+	}
+
+	#[test]
+	fn test_transient_storage_errors() {
+		let busy = std::io::Error::other(rusqlite::Error::SqliteFailure(
+			rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+			None,
+		));
+		assert!(is_transient_storage_error(&busy));
+
+		let constraint = std::io::Error::other(rusqlite::Error::SqliteFailure(
+			rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT),
+			None,
+		));
+		assert!(!is_transient_storage_error(&constraint));
+
+		assert!(is_transient_storage_error(&std::io::Error::from(
+			std::io::ErrorKind::TimedOut
+		)));
+		assert!(!is_transient_storage_error(&std::io::Error::from(
+			std::io::ErrorKind::InvalidData
+		)));
+	}
 }
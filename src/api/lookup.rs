@@ -0,0 +1,355 @@
+//! Synchronous lookups against the stored corpus.
+//!
+//! Addresses and credentials can be queried as plaintext or as SHA-256 hashes
+//! of their normalized form. Plaintext values go through the same normalization
+//! as ingest, and address hashes are resolved to their canonical address via the
+//! alternate-form mapping. Responses only ever contain hashes, never the stored
+//! plaintext of other addresses.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::{
+	core::{config::Config, hash_utils},
+	normalization::engine::{normalize_email_with_config, normalize_field},
+	storage::{StorageAdapter, StoredBreach},
+};
+
+/// Lookup errors
+#[derive(Debug, Error)]
+pub enum LookupError {
+	#[error("Invalid lookup: {0}")]
+	InvalidQuery(String),
+
+	#[error("Storage error: {0}")]
+	Storage(#[from] std::io::Error),
+}
+
+/// Address lookup request: exactly one of `address` or `address_hash`
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct AddressLookupRequest {
+	/// Plaintext address
+	pub address: Option<String>,
+	/// SHA-256 of the normalized address (or of its canonical form)
+	pub address_hash: Option<String>,
+}
+
+/// Credential lookup request: exactly one of `credential` or `credential_hash`,
+/// optionally paired with an address to check whether they were seen together
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CredentialLookupRequest {
+	/// Plaintext credential
+	pub credential: Option<String>,
+	/// SHA-256 of the normalized credential
+	pub credential_hash: Option<String>,
+	#[serde(flatten)]
+	pub address: AddressLookupRequest,
+}
+
+/// A co-occurring address
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Neighbor {
+	pub canonical_hash: String,
+	/// Number of rows the two addresses appeared in together
+	pub cooccurrence_count: i32,
+}
+
+/// What the corpus knows about an address
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AddressLookupResponse {
+	pub found: bool,
+	pub canonical_hash: Option<String>,
+	/// Hashes of known alternate forms of the address
+	pub alternates: Vec<String>,
+	pub credential_count: usize,
+	pub first_seen: Option<String>,
+	pub last_seen: Option<String>,
+	pub breaches: Vec<StoredBreach>,
+	pub neighbors: Vec<Neighbor>,
+}
+
+/// What the corpus knows about a credential
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CredentialLookupResponse {
+	pub found: bool,
+	pub credential_hash: String,
+	/// Number of distinct canonical addresses the credential was seen with
+	pub address_count: usize,
+	pub first_seen: Option<String>,
+	pub last_seen: Option<String>,
+	/// Canonical hash of the queried address, when one was given and is known
+	pub canonical_hash: Option<String>,
+	/// Whether the credential was seen with the queried address
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub seen_with_address: Option<bool>,
+}
+
+/// Look up an address and everything recorded about it
+pub fn lookup_address<S: StorageAdapter + ?Sized>(
+	storage: &mut S,
+	request: &AddressLookupRequest,
+	config: &Config,
+) -> Result<AddressLookupResponse, LookupError> {
+	let Some(canonical_hash) = resolve_address(storage, request, config)? else {
+		return Ok(AddressLookupResponse {
+			found: false,
+			canonical_hash: None,
+			alternates: Vec::new(),
+			credential_count: 0,
+			first_seen: None,
+			last_seen: None,
+			breaches: Vec::new(),
+			neighbors: Vec::new(),
+		});
+	};
+
+	let (first_seen, last_seen) = storage.get_address_seen_range(&canonical_hash)?.unzip();
+	let neighbors = storage
+		.get_address_neighbors(&canonical_hash)?
+		.into_iter()
+		.map(|(canonical_hash, cooccurrence_count)| Neighbor {
+			canonical_hash,
+			cooccurrence_count,
+		})
+		.collect();
+
+	Ok(AddressLookupResponse {
+		found: true,
+		alternates: storage.get_address_alternates(&canonical_hash)?,
+		credential_count: storage.get_credentials_for_address(&canonical_hash)?.len(),
+		first_seen,
+		last_seen,
+		breaches: storage.get_address_breaches(&canonical_hash)?,
+		neighbors,
+		canonical_hash: Some(canonical_hash),
+	})
+}
+
+/// Look up a credential, optionally checking it against an address
+pub fn lookup_credential<S: StorageAdapter + ?Sized>(
+	storage: &mut S,
+	request: &CredentialLookupRequest,
+	config: &Config,
+) -> Result<CredentialLookupResponse, LookupError> {
+	let credential_hash = match (&request.credential, &request.credential_hash) {
+		(Some(credential), None) => hash_utils::sha256_hex(&normalize_field(credential)),
+		(None, Some(hash)) => parse_hash("credential_hash", hash)?,
+		_ => {
+			return Err(LookupError::InvalidQuery(
+				"Provide exactly one of credential or credential_hash".to_string(),
+			));
+		}
+	};
+
+	let with_address = request.address.address.is_some() || request.address.address_hash.is_some();
+	let canonical_hash = if with_address {
+		resolve_address(storage, &request.address, config)?
+	} else {
+		None
+	};
+
+	let sightings = storage.get_credential_sightings(&credential_hash)?;
+	let first_seen = sightings.iter().map(|s| s.first_seen.clone()).min();
+	let last_seen = sightings.iter().map(|s| s.last_seen.clone()).max();
+	let seen_with_address = with_address.then(|| {
+		canonical_hash
+			.as_ref()
+			.is_some_and(|hash| sightings.iter().any(|s| &s.canonical_hash == hash))
+	});
+
+	Ok(CredentialLookupResponse {
+		found: !sightings.is_empty(),
+		credential_hash,
+		address_count: sightings.len(),
+		first_seen,
+		last_seen,
+		canonical_hash,
+		seen_with_address,
+	})
+}
+
+/// Resolve an address query to a known canonical hash
+///
+/// The query is first treated as an alternate form; a plaintext address is
+/// then canonicalized with the configured suffix rules, and a hash is tried as
+/// a canonical hash directly.
+fn resolve_address<S: StorageAdapter + ?Sized>(
+	storage: &mut S,
+	request: &AddressLookupRequest,
+	config: &Config,
+) -> Result<Option<String>, LookupError> {
+	let (alternate_hash, canonical_hash) = match (&request.address, &request.address_hash) {
+		(Some(address), None) => (
+			hash_utils::sha256_hex(&normalize_field(address)),
+			hash_utils::sha256_hex(&normalize_email_with_config(address, config)),
+		),
+		(None, Some(hash)) => {
+			let hash = parse_hash("address_hash", hash)?;
+			(hash.clone(), hash)
+		}
+		_ => {
+			return Err(LookupError::InvalidQuery(
+				"Provide exactly one of address or address_hash".to_string(),
+			));
+		}
+	};
+
+	if let Some(canonical) = storage.lookup_canonical_by_alternate(&alternate_hash)? {
+		return Ok(Some(canonical));
+	}
+	if storage.canonical_address_exists(&canonical_hash)? {
+		return Ok(Some(canonical_hash));
+	}
+	Ok(None)
+}
+
+/// Validate a hex-encoded SHA-256 hash, returning it lowercased
+fn parse_hash(field: &str, hash: &str) -> Result<String, LookupError> {
+	let hash = hash.trim();
+	if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+		return Err(LookupError::InvalidQuery(format!(
+			"{} must be a hex-encoded SHA-256 hash",
+			field
+		)));
+	}
+	Ok(hash.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::deploy::pipeline::store_normalized_row;
+	use crate::storage::{BreachRecord, SqliteStorage};
+
+	fn row(values: &[&str]) -> Vec<String> {
+		values.iter().map(|v| v.to_string()).collect()
+	}
+
+	/// Storage with two rows: alice+bob sharing a password, and alice again
+	fn seeded_storage() -> SqliteStorage {
+		let mut storage = SqliteStorage::new(":memory:", None).expect("storage");
+		let config = Config::default();
+		let header = row(&["email", "other_email", "password"]);
+		store_normalized_row(
+			&mut storage,
+			&row(&["Alice@Example.com", "bob@example.com", "hunter2"]),
+			1,
+			Some(&header),
+			"file-1",
+			&config,
+		)
+		.expect("store");
+		store_normalized_row(
+			&mut storage,
+			&row(&["alice@example.com", "", "letmein"]),
+			2,
+			Some(&header),
+			"file-1",
+			&config,
+		)
+		.expect("store");
+		storage
+	}
+
+	#[test]
+	fn test_lookup_address_by_plaintext_and_hash() {
+		let mut storage = seeded_storage();
+		let config = Config::default();
+		let canonical = hash_utils::sha256_hex("alice@example.com");
+		storage
+			.insert_address_breach(&BreachRecord {
+				canonical_hash: &canonical,
+				breach_name: "Example",
+				breach_title: None,
+				breach_domain: Some("example.com"),
+				breach_date: Some("2020-01-01"),
+				pwn_count: Some(10),
+				description: None,
+				is_verified: true,
+				is_fabricated: false,
+				is_sensitive: false,
+				is_retired: false,
+			})
+			.expect("breach");
+
+		let request = AddressLookupRequest {
+			address: Some("ALICE@example.com ".to_string()),
+			address_hash: None,
+		};
+		let response = lookup_address(&mut storage, &request, &config).expect("lookup");
+		assert!(response.found);
+		assert_eq!(response.canonical_hash.as_deref(), Some(canonical.as_str()));
+		assert_eq!(response.credential_count, 2);
+		assert_eq!(response.breaches.len(), 1);
+		assert_eq!(response.neighbors.len(), 1);
+		assert!(response.first_seen.is_some());
+
+		let request = AddressLookupRequest {
+			address: None,
+			address_hash: Some(canonical.to_uppercase()),
+		};
+		let response = lookup_address(&mut storage, &request, &config).expect("lookup");
+		assert!(response.found);
+
+		let request = AddressLookupRequest {
+			address: Some("nobody@example.com".to_string()),
+			address_hash: None,
+		};
+		let response = lookup_address(&mut storage, &request, &config).expect("lookup");
+		assert!(!response.found);
+		assert!(response.canonical_hash.is_none());
+	}
+
+	#[test]
+	fn test_lookup_credential_with_address() {
+		let mut storage = seeded_storage();
+		let config = Config::default();
+
+		let request = CredentialLookupRequest {
+			credential: Some("hunter2".to_string()),
+			address: AddressLookupRequest {
+				address: Some("bob@example.com".to_string()),
+				address_hash: None,
+			},
+			..Default::default()
+		};
+		let response = lookup_credential(&mut storage, &request, &config).expect("lookup");
+		assert!(response.found);
+		assert_eq!(response.address_count, 2);
+		assert_eq!(response.seen_with_address, Some(true));
+
+		let request = CredentialLookupRequest {
+			credential: Some("letmein".to_string()),
+			address: AddressLookupRequest {
+				address: Some("bob@example.com".to_string()),
+				address_hash: None,
+			},
+			..Default::default()
+		};
+		let response = lookup_credential(&mut storage, &request, &config).expect("lookup");
+		assert_eq!(response.address_count, 1);
+		assert_eq!(response.seen_with_address, Some(false));
+	}
+
+	#[test]
+	fn test_lookup_rejects_ambiguous_or_malformed_queries() {
+		let mut storage = seeded_storage();
+		let config = Config::default();
+
+		let request = AddressLookupRequest::default();
+		assert!(matches!(
+			lookup_address(&mut storage, &request, &config),
+			Err(LookupError::InvalidQuery(_))
+		));
+
+		let request = CredentialLookupRequest {
+			credential_hash: Some("not-a-hash".to_string()),
+			..Default::default()
+		};
+		assert!(matches!(
+			lookup_credential(&mut storage, &request, &config),
+			Err(LookupError::InvalidQuery(_))
+		));
+	}
+}
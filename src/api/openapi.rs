@@ -0,0 +1,64 @@
+//! OpenAPI 3 description of the HTTP API.
+//!
+//! Paths and schemas are collected from the `#[utoipa::path]` annotations on
+//! the handlers in [`crate::api::server`] as the router is built, so the
+//! document served at `/api/v1/openapi.json` always lists exactly the routes
+//! the server answers.
+
+use utoipa::{
+	Modify, OpenApi,
+	openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+/// Name of the bearer token security scheme referenced by protected operations
+pub const BEARER_SCHEME: &str = "bearer";
+
+/// Tag of the job submission and upload operations
+pub const TAG_INGEST: &str = "ingest";
+/// Tag of the job status, result and event operations
+pub const TAG_JOBS: &str = "jobs";
+/// Tag of the corpus lookup operations
+pub const TAG_LOOKUP: &str = "lookup";
+/// Tag of the runtime configuration operations
+pub const TAG_ADMIN: &str = "admin";
+/// Tag of the health, readiness, metrics and specification operations
+pub const TAG_SYSTEM: &str = "system";
+
+/// Base document that the server routes are added to
+#[derive(OpenApi)]
+#[openapi(
+	info(
+		title = "Dumptruck API",
+		description = "Bulk breach data ingest, job tracking and corpus lookups"
+	),
+	modifiers(&BearerAuth),
+	tags(
+		(name = TAG_INGEST, description = "Submit files for analysis"),
+		(name = TAG_JOBS, description = "Track jobs and fetch their results"),
+		(name = TAG_LOOKUP, description = "Query addresses and credentials in the corpus"),
+		(name = TAG_ADMIN, description = "View and change the server configuration"),
+		(name = TAG_SYSTEM, description = "Health, readiness and metrics")
+	)
+)]
+pub struct ApiDoc;
+
+/// Registers the OAuth 2.0 bearer token scheme
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+	fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+		let components = openapi.components.get_or_insert_with(Default::default);
+		components.add_security_scheme(
+			BEARER_SCHEME,
+			SecurityScheme::Http(
+				HttpBuilder::new()
+					.scheme(HttpAuthScheme::Bearer)
+					.bearer_format("JWT")
+					.description(Some(
+						"OAuth 2.0 access token; operations list the scopes they require",
+					))
+					.build(),
+			),
+		);
+	}
+}
@@ -7,9 +7,10 @@ use std::{
 };
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Result of a data ingestion operation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct IngestResult {
 	/// Total rows processed
 	pub rows_processed: usize,
@@ -24,9 +25,12 @@ pub struct IngestResult {
 	/// PII/NPI detection summary
 	#[serde(default)]
 	pub pii_summary: Option<PiiDetectionSummary>,
-	/// Detailed per-row detection findings
+	/// Detailed per-row detection findings, limited to the first rows with detections
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub detailed_findings: Vec<DetailedRowFinding>,
+	/// Rows with detections, including those beyond the detailed findings
+	#[serde(default)]
+	pub rows_with_findings: usize,
 	/// Summary metadata events
 	pub metadata: Vec<String>,
 	/// Processing errors encountered
@@ -34,7 +38,7 @@ pub struct IngestResult {
 }
 
 /// Detailed finding for a single row
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DetailedRowFinding {
 	/// Row index (1-based for user-friendly display)
 	pub row_number: usize,
@@ -43,7 +47,7 @@ pub struct DetailedRowFinding {
 }
 
 /// A single detected PII/NPI value
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Detection {
 	/// Column name (if available)
 	pub column: Option<String>,
@@ -54,7 +58,7 @@ pub struct Detection {
 }
 
 /// Summary of PII/NPI detections found
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct PiiDetectionSummary {
 	/// Count of rows with email addresses detected
 	pub emails: usize,
@@ -241,6 +245,16 @@ impl OutputFormatter for JsonlFormatter {
 		output.push_str(&summary.to_string());
 		output.push('\n');
 
+		for finding in &result.detailed_findings {
+			let line = serde_json::json!({
+				"event": "finding",
+				"row_number": finding.row_number,
+				"detections": finding.detections,
+			});
+			output.push_str(&line.to_string());
+			output.push('\n');
+		}
+
 		for event in &result.metadata {
 			let meta = serde_json::json!({
 				"event": "metadata",
@@ -295,6 +309,7 @@ mod tests {
 			breached_addresses: 15,
 			pii_summary: None,
 			detailed_findings: vec![],
+			rows_with_findings: 0,
 			metadata: vec!["test".to_string()],
 			errors: vec![],
 		};
@@ -315,6 +330,7 @@ mod tests {
 			breached_addresses: 15,
 			pii_summary: None,
 			detailed_findings: vec![],
+			rows_with_findings: 0,
 			metadata: vec![],
 			errors: vec!["test error".to_string()],
 		};
@@ -324,4 +340,33 @@ mod tests {
 		assert!(output.contains("Dumptruck Analysis Results"));
 		assert!(output.contains("Rows Processed: 100"));
 	}
+
+	#[test]
+	fn test_jsonl_formatter_emits_findings() {
+		let result = IngestResult {
+			rows_processed: 1,
+			unique_addresses: 1,
+			hashed_credentials_detected: 0,
+			weak_passwords_found: 0,
+			breached_addresses: 0,
+			pii_summary: None,
+			detailed_findings: vec![DetailedRowFinding {
+				row_number: 1,
+				detections: vec![Detection {
+					column: Some("email".to_string()),
+					value: "alice@example.com".to_string(),
+					detection_type: "email".to_string(),
+				}],
+			}],
+			rows_with_findings: 1,
+			metadata: vec![],
+			errors: vec![],
+		};
+
+		let output = JsonlFormatter.format(&result).expect("formatting failed");
+		let lines: Vec<&str> = output.lines().collect();
+		assert_eq!(lines.len(), 2);
+		assert!(lines[1].contains("\"event\":\"finding\""));
+		assert!(lines[1].contains("alice@example.com"));
+	}
 }
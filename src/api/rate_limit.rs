@@ -0,0 +1,411 @@
+//! Per-principal request rate limits and daily byte quotas for the HTTP API.
+//!
+//! Each authenticated subject gets a token bucket and a daily byte allowance per
+//! route class, so a client flooding one class of routes cannot starve others.
+//! Quotas reset at midnight UTC. A request whose `Content-Length` exceeds what
+//! is left of the day's quota is refused up front; otherwise its bytes are
+//! charged as its body streams in and the body is cut off once the quota is
+//! crossed. Usage of earlier days is dropped once the subject's bucket has
+//! refilled, so idle subjects are not kept forever.
+
+use std::{
+	collections::HashMap,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+use chrono::{NaiveDate, Utc};
+
+use crate::core::config::{RateLimitConfig, RateLimitsConfig};
+
+/// Groups of routes that are limited separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+	/// Job submission and uploads
+	Ingest,
+	/// Corpus lookups and range queries
+	Lookup,
+}
+
+/// Why a request was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+	/// Too many requests; a token is available after the given time
+	Rate(Duration),
+	/// Daily byte quota used up; it resets after the given time
+	Quota(Duration),
+}
+
+impl LimitExceeded {
+	/// Seconds the client should wait before retrying (at least one)
+	pub fn retry_after_secs(&self) -> u64 {
+		let (LimitExceeded::Rate(wait) | LimitExceeded::Quota(wait)) = self;
+		wait.as_secs_f64().ceil().max(1.0) as u64
+	}
+}
+
+/// Usage of one subject in one route class
+struct Usage {
+	tokens: f64,
+	refilled_at: Instant,
+	day: NaiveDate,
+	bytes: u64,
+}
+
+impl Usage {
+	fn new(limits: &RateLimitConfig, now: Instant, today: NaiveDate) -> Self {
+		Self {
+			tokens: f64::from(limits.burst.max(1)),
+			refilled_at: now,
+			day: today,
+			bytes: 0,
+		}
+	}
+
+	/// Start a new day's byte count
+	fn roll_over(&mut self, today: NaiveDate) {
+		if self.day != today {
+			self.day = today;
+			self.bytes = 0;
+		}
+	}
+
+	/// Whether the bucket has refilled by `now`, so forgetting it changes nothing
+	fn is_idle(&self, limits: &RateLimitConfig, now: Instant) -> bool {
+		if limits.requests_per_minute == 0 {
+			return true;
+		}
+		let refill_secs =
+			f64::from(limits.burst.max(1)) * 60.0 / f64::from(limits.requests_per_minute);
+		now.saturating_duration_since(self.refilled_at)
+			.as_secs_f64()
+			>= refill_secs
+	}
+}
+
+/// Usage per route class and subject
+#[derive(Default)]
+struct UsageTable {
+	entries: HashMap<(RouteClass, String), Usage>,
+	/// Day entries of earlier days were last dropped
+	pruned_on: Option<NaiveDate>,
+}
+
+/// Token buckets and byte quotas keyed by route class and subject
+pub struct RateLimiter {
+	limits: RateLimitsConfig,
+	usage: Mutex<UsageTable>,
+}
+
+impl RateLimiter {
+	pub fn new(limits: RateLimitsConfig) -> Self {
+		Self {
+			limits,
+			usage: Mutex::new(UsageTable::default()),
+		}
+	}
+
+	fn limits(&self, class: RouteClass) -> &RateLimitConfig {
+		match class {
+			RouteClass::Ingest => &self.limits.ingest,
+			RouteClass::Lookup => &self.limits.lookup,
+		}
+	}
+
+	/// Admit one request from `subject`, taking a token from its bucket
+	///
+	/// `expected_bytes` is the request's announced body size (0 if unknown); a
+	/// request that would exceed the rest of the day's quota is refused.
+	pub fn check(
+		&self,
+		class: RouteClass,
+		subject: &str,
+		expected_bytes: u64,
+	) -> Result<(), LimitExceeded> {
+		self.check_at(
+			class,
+			subject,
+			expected_bytes,
+			Instant::now(),
+			Utc::now().date_naive(),
+		)
+	}
+
+	fn check_at(
+		&self,
+		class: RouteClass,
+		subject: &str,
+		expected_bytes: u64,
+		now: Instant,
+		today: NaiveDate,
+	) -> Result<(), LimitExceeded> {
+		self.with_usage(class, subject, now, today, |entry, limits| {
+			if limits.daily_bytes > 0
+				&& (entry.bytes >= limits.daily_bytes
+					|| expected_bytes > limits.daily_bytes - entry.bytes)
+			{
+				return Err(LimitExceeded::Quota(until_midnight_utc()));
+			}
+
+			if limits.requests_per_minute == 0 {
+				return Ok(());
+			}
+			let per_sec = f64::from(limits.requests_per_minute) / 60.0;
+			let elapsed = now
+				.saturating_duration_since(entry.refilled_at)
+				.as_secs_f64();
+			entry.tokens = (entry.tokens + elapsed * per_sec).min(f64::from(limits.burst.max(1)));
+			entry.refilled_at = now;
+
+			if entry.tokens < 1.0 {
+				let wait = (1.0 - entry.tokens) / per_sec;
+				return Err(LimitExceeded::Rate(Duration::from_secs_f64(wait)));
+			}
+			entry.tokens -= 1.0;
+			Ok(())
+		})
+	}
+
+	/// Charge `bytes` of a request body against the subject's daily quota
+	///
+	/// Returns an error once the quota has been crossed; the caller should stop
+	/// reading the body.
+	pub fn record_bytes(
+		&self,
+		class: RouteClass,
+		subject: &str,
+		bytes: u64,
+	) -> Result<(), LimitExceeded> {
+		let today = Utc::now().date_naive();
+		self.with_usage(class, subject, Instant::now(), today, |entry, limits| {
+			entry.bytes = entry.bytes.saturating_add(bytes);
+			if limits.daily_bytes > 0 && entry.bytes > limits.daily_bytes {
+				return Err(LimitExceeded::Quota(until_midnight_utc()));
+			}
+			Ok(())
+		})
+	}
+
+	/// Charge `bytes` the server reads on the subject's behalf, such as a file
+	/// queued from the import directory, unless they exceed the rest of the quota
+	pub fn try_charge(
+		&self,
+		class: RouteClass,
+		subject: &str,
+		bytes: u64,
+	) -> Result<(), LimitExceeded> {
+		let today = Utc::now().date_naive();
+		self.with_usage(class, subject, Instant::now(), today, |entry, limits| {
+			if limits.daily_bytes > 0 && bytes > limits.daily_bytes.saturating_sub(entry.bytes) {
+				return Err(LimitExceeded::Quota(until_midnight_utc()));
+			}
+			entry.bytes = entry.bytes.saturating_add(bytes);
+			Ok(())
+		})
+	}
+
+	/// Run `f` on the subject's usage for `today`, creating it if needed
+	fn with_usage<T>(
+		&self,
+		class: RouteClass,
+		subject: &str,
+		now: Instant,
+		today: NaiveDate,
+		f: impl FnOnce(&mut Usage, &RateLimitConfig) -> T,
+	) -> T {
+		let limits = self.limits(class);
+		let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+		self.prune(&mut usage, now, today);
+		let entry = usage
+			.entries
+			.entry((class, subject.to_string()))
+			.or_insert_with(|| Usage::new(limits, now, today));
+		entry.roll_over(today);
+		f(entry, limits)
+	}
+
+	/// Once a day, drop the usage of earlier days whose buckets have refilled
+	fn prune(&self, usage: &mut UsageTable, now: Instant, today: NaiveDate) {
+		if usage.pruned_on == Some(today) {
+			return;
+		}
+		usage.pruned_on = Some(today);
+		usage.entries.retain(|(class, _), entry| {
+			entry.day == today || !entry.is_idle(self.limits(*class), now)
+		});
+	}
+
+	/// Number of subjects and route classes with tracked usage
+	#[cfg(test)]
+	fn tracked(&self) -> usize {
+		self.usage
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.entries
+			.len()
+	}
+}
+
+/// Time left until the daily quotas reset
+fn until_midnight_utc() -> Duration {
+	let now = Utc::now();
+	let midnight = (now.date_naive() + chrono::Days::new(1))
+		.and_hms_opt(0, 0, 0)
+		.map(|t| t.and_utc())
+		.unwrap_or(now);
+	(midnight - now).to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn limiter(requests_per_minute: u32, burst: u32, daily_bytes: u64) -> RateLimiter {
+		let limit = RateLimitConfig {
+			requests_per_minute,
+			burst,
+			daily_bytes,
+		};
+		RateLimiter::new(RateLimitsConfig {
+			ingest: limit.clone(),
+			lookup: limit,
+		})
+	}
+
+	#[test]
+	fn test_token_bucket_refills_over_time() {
+		let limiter = limiter(60, 2, 0);
+		let start = Instant::now();
+		let today = Utc::now().date_naive();
+
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "a", 0, start, today)
+				.is_ok()
+		);
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "a", 0, start, today)
+				.is_ok()
+		);
+		let err = limiter
+			.check_at(RouteClass::Ingest, "a", 0, start, today)
+			.expect_err("bucket empty");
+		assert!(matches!(err, LimitExceeded::Rate(_)));
+		assert_eq!(err.retry_after_secs(), 1);
+
+		// Other subjects and route classes have their own buckets
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "b", 0, start, today)
+				.is_ok()
+		);
+		assert!(
+			limiter
+				.check_at(RouteClass::Lookup, "a", 0, start, today)
+				.is_ok()
+		);
+
+		let later = start + Duration::from_secs(1);
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "a", 0, later, today)
+				.is_ok()
+		);
+	}
+
+	#[test]
+	fn test_daily_quota_resets_each_day() {
+		let limiter = limiter(0, 1, 100);
+		let now = Instant::now();
+		let today = Utc::now().date_naive();
+
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "a", 0, now, today)
+				.is_ok()
+		);
+		assert!(limiter.record_bytes(RouteClass::Ingest, "a", 60).is_ok());
+		assert!(matches!(
+			limiter.record_bytes(RouteClass::Ingest, "a", 90),
+			Err(LimitExceeded::Quota(_))
+		));
+		assert!(matches!(
+			limiter.check_at(RouteClass::Ingest, "a", 0, now, today),
+			Err(LimitExceeded::Quota(_))
+		));
+
+		let tomorrow = today + chrono::Days::new(1);
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "a", 0, now, tomorrow)
+				.is_ok()
+		);
+	}
+
+	#[test]
+	fn test_announced_body_size_is_checked_against_remaining_quota() {
+		let limiter = limiter(0, 1, 100);
+		let now = Instant::now();
+		let today = Utc::now().date_naive();
+
+		assert!(matches!(
+			limiter.check_at(RouteClass::Ingest, "a", 101, now, today),
+			Err(LimitExceeded::Quota(_))
+		));
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "a", 100, now, today)
+				.is_ok()
+		);
+		assert!(limiter.record_bytes(RouteClass::Ingest, "a", 40).is_ok());
+		assert!(matches!(
+			limiter.check_at(RouteClass::Ingest, "a", 61, now, today),
+			Err(LimitExceeded::Quota(_))
+		));
+
+		// Server-side reads are charged only when they fit
+		assert!(matches!(
+			limiter.try_charge(RouteClass::Ingest, "a", 61),
+			Err(LimitExceeded::Quota(_))
+		));
+		assert!(limiter.try_charge(RouteClass::Ingest, "a", 60).is_ok());
+		assert!(matches!(
+			limiter.check_at(RouteClass::Ingest, "a", 0, now, today),
+			Err(LimitExceeded::Quota(_))
+		));
+	}
+
+	#[test]
+	fn test_usage_of_idle_subjects_is_pruned() {
+		let limiter = limiter(60, 2, 100);
+		let start = Instant::now();
+		let today = Utc::now().date_naive();
+		for subject in ["a", "b"] {
+			assert!(
+				limiter
+					.check_at(RouteClass::Ingest, subject, 0, start, today)
+					.is_ok()
+			);
+		}
+		assert_eq!(limiter.tracked(), 2);
+
+		// The next day, only subjects whose bucket has not refilled are kept
+		let tomorrow = today + chrono::Days::new(1);
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "b", 0, start, tomorrow)
+				.is_ok()
+		);
+		assert_eq!(limiter.tracked(), 2);
+
+		let day_after = tomorrow + chrono::Days::new(1);
+		let later = start + Duration::from_secs(5);
+		assert!(
+			limiter
+				.check_at(RouteClass::Ingest, "c", 0, later, day_after)
+				.is_ok()
+		);
+		assert_eq!(limiter.tracked(), 1);
+	}
+}
@@ -0,0 +1,344 @@
+//! Readiness checks shared by `/api/v1/ready` and the `status` command.
+//!
+//! Liveness (`/api/v1/health`) only shows the process is serving requests.
+//! Readiness checks each dependency and reports it as ok, degraded (the server
+//! works with reduced function, e.g. an enrichment service is unreachable) or
+//! failed (ingest cannot work, e.g. the database is read-only).
+
+use std::{path::Path, sync::Arc, time::Duration};
+
+use rusqlite::{Connection, OpenFlags, TransactionBehavior};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+	core::config::Config,
+	detection::rainbow_table,
+	storage::{SCHEMA_VERSION, WorkingCopyManager, schema_version},
+};
+
+/// How long to wait for an external service to answer
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Health of one component, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentStatus {
+	Ok,
+	Degraded,
+	Failed,
+}
+
+/// Result of checking one component
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ComponentCheck {
+	#[schema(value_type = String)]
+	pub name: &'static str,
+	pub status: ComponentStatus,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub detail: Option<String>,
+}
+
+impl ComponentCheck {
+	fn ok(name: &'static str, detail: Option<String>) -> Self {
+		Self {
+			name,
+			status: ComponentStatus::Ok,
+			detail,
+		}
+	}
+
+	fn with_status(name: &'static str, status: ComponentStatus, detail: String) -> Self {
+		Self {
+			name,
+			status,
+			detail: Some(detail),
+		}
+	}
+
+	/// Ok, or the given status with the error as detail
+	fn from_result(
+		name: &'static str,
+		result: Result<(), String>,
+		on_error: ComponentStatus,
+	) -> Self {
+		match result {
+			Ok(()) => Self::ok(name, None),
+			Err(e) => Self::with_status(name, on_error, e),
+		}
+	}
+}
+
+/// Per-component readiness report
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessReport {
+	/// Worst status of any component
+	pub status: ComponentStatus,
+	pub components: Vec<ComponentCheck>,
+	pub timestamp: String,
+}
+
+/// Check every component the server depends on
+///
+/// Local components (database, working directory) fail readiness; the rainbow
+/// table and configured enrichment services only degrade it.
+pub async fn check_readiness(
+	config: &Config,
+	database_path: &str,
+	working_copy_mgr: Arc<WorkingCopyManager>,
+) -> ReadinessReport {
+	let db_path = database_path.to_string();
+	let min_free_bytes = config.working_directory.min_free_bytes;
+	let local = tokio::task::spawn_blocking(move || {
+		vec![
+			ComponentCheck::from_result(
+				"database",
+				check_database(&db_path),
+				ComponentStatus::Failed,
+			),
+			check_free_space(&working_copy_mgr, min_free_bytes),
+			check_noexec(&working_copy_mgr),
+		]
+	});
+
+	let ollama = config.services.ollama.enabled.then(|| {
+		let ollama = &config.services.ollama;
+		format!("http://{}:{}", ollama.host, ollama.port)
+	});
+	let hibp = config
+		.api_keys
+		.hibp
+		.enabled
+		.then(|| config.api_keys.hibp.api_key.clone());
+	let (ollama, hibp) = tokio::join!(
+		async {
+			match ollama {
+				Some(url) => Some(check_ollama(&url, PROBE_TIMEOUT).await),
+				None => None,
+			}
+		},
+		async {
+			match hibp {
+				Some(key) => Some(check_hibp(Some(&key), PROBE_TIMEOUT).await),
+				None => None,
+			}
+		}
+	);
+
+	let mut components = local.await.unwrap_or_else(|e| {
+		vec![ComponentCheck::with_status(
+			"database",
+			ComponentStatus::Failed,
+			format!("Check did not complete: {}", e),
+		)]
+	});
+	components.push(check_rainbow_table());
+	if let Some(result) = ollama {
+		components.push(ComponentCheck::from_result(
+			"ollama",
+			result,
+			ComponentStatus::Degraded,
+		));
+	}
+	if let Some(result) = hibp {
+		components.push(ComponentCheck::from_result(
+			"hibp",
+			result,
+			ComponentStatus::Degraded,
+		));
+	}
+
+	ReadinessReport {
+		status: components
+			.iter()
+			.map(|c| c.status)
+			.max()
+			.unwrap_or(ComponentStatus::Ok),
+		components,
+		timestamp: chrono::Utc::now().to_rfc3339(),
+	}
+}
+
+/// Check Ollama connectivity
+pub async fn check_ollama(url: &str, timeout: Duration) -> Result<(), String> {
+	// Simple HTTP health check to Ollama's API endpoint
+	let client = reqwest::Client::new();
+	match client
+		.get(format!("{}/api/tags", url))
+		.timeout(timeout)
+		.send()
+		.await
+	{
+		Ok(response) => {
+			if response.status().is_success() {
+				Ok(())
+			} else {
+				Err(format!("HTTP {}", response.status()))
+			}
+		}
+		Err(e) => Err(format!("Connection failed: {}", e)),
+	}
+}
+
+/// Check HIBP API connectivity
+pub async fn check_hibp(_api_key: Option<&str>, timeout: Duration) -> Result<(), String> {
+	// Simple connectivity check to HIBP API
+	let client = reqwest::Client::new();
+	match client
+		.get("https://haveibeenpwned.com/api/v3/breaches")
+		.timeout(timeout)
+		.send()
+		.await
+	{
+		Ok(response) => {
+			// HIBP returns 401 without API key, which is still a valid response
+			if response.status().is_client_error() || response.status().is_success() {
+				Ok(())
+			} else {
+				Err(format!("HTTP {}", response.status()))
+			}
+		}
+		Err(e) => Err(format!("Connection failed: {}", e)),
+	}
+}
+
+/// Check that the database is writable and has the current schema
+///
+/// A missing file is accepted: SQLite creates it, with the schema, on first use.
+pub fn check_database(db_path: &str) -> Result<(), String> {
+	let path = Path::new(db_path);
+	if !path.exists() {
+		return Ok(());
+	}
+	match std::fs::metadata(path) {
+		Ok(metadata) if metadata.is_file() => {}
+		Ok(_) => return Err(format!("Database path is not a file: {}", db_path)),
+		Err(e) => return Err(format!("Cannot access database file: {}", e)),
+	}
+
+	let mut conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+		.map_err(|e| format!("Cannot open database: {}", e))?;
+	conn.busy_timeout(Duration::from_secs(2))
+		.map_err(|e| format!("Cannot configure database: {}", e))?;
+
+	let version = schema_version(&conn).map_err(|e| format!("Cannot read schema: {}", e))?;
+	if version != SCHEMA_VERSION {
+		return Err(format!(
+			"Schema version {} does not match expected version {}",
+			version, SCHEMA_VERSION
+		));
+	}
+
+	// Take the write lock and write inside a transaction that is rolled back
+	let tx = conn
+		.transaction_with_behavior(TransactionBehavior::Immediate)
+		.map_err(|e| format!("Database is not writable: {}", e))?;
+	tx.execute("CREATE TABLE readiness_probe (id INTEGER)", [])
+		.map_err(|e| format!("Database is not writable: {}", e))?;
+	tx.rollback()
+		.map_err(|e| format!("Database is not writable: {}", e))
+}
+
+/// Check the working directory has room for uploads and working copies
+fn check_free_space(working_copy_mgr: &WorkingCopyManager, min_free_bytes: u64) -> ComponentCheck {
+	const NAME: &str = "working_directory";
+	match working_copy_mgr.available_space() {
+		Ok(free) if free < min_free_bytes => ComponentCheck::with_status(
+			NAME,
+			ComponentStatus::Failed,
+			format!(
+				"{} bytes free, below the minimum of {}",
+				free, min_free_bytes
+			),
+		),
+		Ok(free) => ComponentCheck::ok(NAME, Some(format!("{} bytes free", free))),
+		Err(e) => ComponentCheck::with_status(
+			NAME,
+			ComponentStatus::Degraded,
+			format!("Cannot determine free space: {}", e),
+		),
+	}
+}
+
+/// Check the working directory is still mounted noexec, when that is required
+fn check_noexec(working_copy_mgr: &WorkingCopyManager) -> ComponentCheck {
+	const NAME: &str = "noexec";
+	if !working_copy_mgr.verifies_noexec() {
+		return ComponentCheck::ok(NAME, Some("Verification disabled".to_string()));
+	}
+	ComponentCheck::from_result(
+		NAME,
+		working_copy_mgr
+			.verify_noexec_mount()
+			.map_err(|e| e.to_string()),
+		ComponentStatus::Failed,
+	)
+}
+
+/// Check weak password hashes are loaded for detection
+fn check_rainbow_table() -> ComponentCheck {
+	const NAME: &str = "rainbow_table";
+	match rainbow_table::loaded_entries() {
+		Some(0) => ComponentCheck::with_status(
+			NAME,
+			ComponentStatus::Degraded,
+			"Rainbow table is empty; weak password detection is limited".to_string(),
+		),
+		Some(entries) => ComponentCheck::ok(NAME, Some(format!("{} entries", entries))),
+		None => ComponentCheck::with_status(
+			NAME,
+			ComponentStatus::Degraded,
+			"Rainbow table is not loaded".to_string(),
+		),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::storage::SqliteStorage;
+
+	#[test]
+	fn test_check_database_requires_current_schema() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let db_path = dir.path().join("dumptruck.db");
+		let db_path = db_path.to_str().expect("path");
+
+		assert!(
+			check_database(db_path).is_ok(),
+			"missing file is created on use"
+		);
+
+		Connection::open(db_path)
+			.and_then(|conn| conn.execute("CREATE TABLE other (id INTEGER)", []))
+			.expect("plain database");
+		let err = check_database(db_path).expect_err("no schema version");
+		assert!(err.contains("Schema version 0"));
+
+		SqliteStorage::new(db_path, None).expect("create schema");
+		assert!(check_database(db_path).is_ok());
+		// The probe leaves nothing behind
+		let conn = Connection::open(db_path).expect("open");
+		let probes: i64 = conn
+			.query_row(
+				"SELECT COUNT(*) FROM sqlite_master WHERE name = 'readiness_probe'",
+				[],
+				|row| row.get(0),
+			)
+			.expect("query");
+		assert_eq!(probes, 0);
+	}
+
+	#[test]
+	fn test_free_space_below_minimum_fails() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let mgr = WorkingCopyManager::new(dir.path(), false, 0).expect("working dir");
+
+		assert_eq!(check_free_space(&mgr, 0).status, ComponentStatus::Ok);
+		assert_eq!(
+			check_free_space(&mgr, u64::MAX).status,
+			ComponentStatus::Failed
+		);
+		assert_eq!(check_noexec(&mgr).status, ComponentStatus::Ok);
+	}
+}
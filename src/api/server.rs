@@ -3,20 +3,51 @@
 //! Provides REST API for bulk data analysis with secure async job processing.
 //! Supports arbitrarily large file uploads via streaming (raw binary or chunked transfer).
 
-use crate::network::oauth::OAuthProvider;
-use crate::storage::job_queue::{Job, JobQueue, JobStatus};
+use crate::api::handlers;
+use crate::api::lookup::{
+	self, AddressLookupRequest, AddressLookupResponse, CredentialLookupRequest,
+	CredentialLookupResponse, LookupError,
+};
+use crate::api::openapi::{ApiDoc, TAG_ADMIN, TAG_INGEST, TAG_JOBS, TAG_LOOKUP, TAG_SYSTEM};
+use crate::api::output::{
+	CsvFormatter, IngestResult, JsonFormatter, JsonlFormatter, OutputFormatter,
+	PiiDetectionSummary, TextFormatter,
+};
+use crate::api::rate_limit::{RateLimiter, RouteClass};
+use crate::api::readiness::{self, ComponentStatus, ReadinessReport};
+use crate::api::upload::{self, ResumableUploads, UploadError, UploadSession};
+use crate::api::webhook::{self, WebhookError};
+use crate::core::config::{Config, ConfigError, ConfigStore};
+use crate::core::secure_deletion::{SecureDeletionConfig, secure_delete_file};
+use crate::core::{hash_utils, metrics};
+use crate::network::oauth::{OAuthError, OAuthProvider, Principal};
+use crate::normalization::evidence::FileEvidence;
+use crate::storage::job_queue::{CallbackDelivery, Job, JobQueue, JobStatus};
+use crate::storage::working_copy::WorkingCopyManager;
+use crate::storage::{DigestAlgorithm, SqliteStorage, StorageAdapter};
 use axum::{
-	Json, Router,
-	extract::{Path, Query, State},
-	http::{HeaderMap, StatusCode},
-	response::{IntoResponse, Response},
-	routing::{get, post},
+	Extension, Json, Router,
+	body::Body,
+	extract::{MatchedPath, Path, Query, Request, State},
+	http::{HeaderMap, Method, StatusCode},
+	middleware::{self, Next},
+	response::{
+		IntoResponse, Response,
+		sse::{Event, KeepAlive, Sse},
+	},
 };
+use futures_util::StreamExt;
+use futures_util::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use thiserror::Error;
+use tokio::sync::broadcast;
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{info, warn};
+use utoipa::openapi::{ObjectBuilder, RefOr, Schema, Type};
+use utoipa::{IntoParams, OpenApi, PartialSchema, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
 
 /// Server errors
 #[derive(Debug, Error)]
@@ -24,23 +55,147 @@ pub enum ServerError {
 	#[error("Unauthorized")]
 	Unauthorized,
 
+	#[error("Forbidden: {0}")]
+	Forbidden(String),
+
 	#[error("Job not found")]
 	NotFound,
 
+	#[error("Not found: {0}")]
+	EntryNotFound(String),
+
 	#[error("Bad request: {0}")]
 	BadRequest(String),
 
+	#[error("Payload too large: {0}")]
+	PayloadTooLarge(String),
+
+	#[error("Upload not found: {0}")]
+	UploadNotFound(String),
+
+	#[error("Conflict: {0}")]
+	Conflict(String),
+
+	#[error("Not acceptable: {0}")]
+	NotAcceptable(String),
+
 	#[error("Internal server error: {0}")]
 	InternalError(String),
+
+	#[error("Service unavailable: {0}")]
+	Unavailable(String),
+
+	#[error("Too many requests: retry after {0} seconds")]
+	TooManyRequests(u64),
+}
+
+impl From<OAuthError> for ServerError {
+	fn from(err: OAuthError) -> Self {
+		match err {
+			OAuthError::InvalidScope(scope) => {
+				ServerError::Forbidden(format!("Missing required scope: {}", scope))
+			}
+			OAuthError::HttpError(_)
+			| OAuthError::TokenRequestFailed(_)
+			| OAuthError::InvalidTokenResponse(_) => {
+				warn!("Token validation failed: {}", err);
+				ServerError::Unavailable("Token validation unavailable".to_string())
+			}
+			OAuthError::ValidationNotConfigured => {
+				warn!("Rejecting request: {}", err);
+				ServerError::Unauthorized
+			}
+			OAuthError::TokenExpired | OAuthError::InvalidToken(_) | OAuthError::MissingToken => {
+				info!("Rejecting token: {}", err);
+				ServerError::Unauthorized
+			}
+		}
+	}
+}
+
+impl From<UploadError> for ServerError {
+	fn from(err: UploadError) -> Self {
+		match err {
+			UploadError::TooLarge(_) => ServerError::PayloadTooLarge(err.to_string()),
+			UploadError::Empty
+			| UploadError::InvalidFilename(_)
+			| UploadError::InvalidImportPath(_)
+			| UploadError::Body(_) => ServerError::BadRequest(err.to_string()),
+			UploadError::NotFound(id) => ServerError::UploadNotFound(id),
+			UploadError::OffsetMismatch { .. }
+			| UploadError::Busy(_)
+			| UploadError::Incomplete { .. } => ServerError::Conflict(err.to_string()),
+			UploadError::Io(_) => ServerError::InternalError(err.to_string()),
+		}
+	}
+}
+
+impl From<WebhookError> for ServerError {
+	fn from(err: WebhookError) -> Self {
+		ServerError::BadRequest(err.to_string())
+	}
+}
+
+impl From<ConfigError> for ServerError {
+	fn from(err: ConfigError) -> Self {
+		match err {
+			ConfigError::ValidationError(msg) => ServerError::BadRequest(msg),
+			_ => {
+				warn!("Configuration update failed: {}", err);
+				ServerError::InternalError(err.to_string())
+			}
+		}
+	}
+}
+
+impl From<LookupError> for ServerError {
+	fn from(err: LookupError) -> Self {
+		match err {
+			LookupError::InvalidQuery(msg) => ServerError::BadRequest(msg),
+			LookupError::Storage(_) => ServerError::InternalError(err.to_string()),
+		}
+	}
 }
 
 impl IntoResponse for ServerError {
 	fn into_response(self) -> Response {
 		let (status, message) = match self {
-			ServerError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
+			ServerError::Unauthorized => {
+				let body = Json(serde_json::json!({
+					"error": "Unauthorized",
+					"status": StatusCode::UNAUTHORIZED.as_u16(),
+				}));
+				return (
+					StatusCode::UNAUTHORIZED,
+					[(axum::http::header::WWW_AUTHENTICATE, "Bearer")],
+					body,
+				)
+					.into_response();
+			}
+			ServerError::TooManyRequests(retry_after) => {
+				let body = Json(serde_json::json!({
+					"error": "Too many requests",
+					"status": StatusCode::TOO_MANY_REQUESTS.as_u16(),
+				}));
+				return (
+					StatusCode::TOO_MANY_REQUESTS,
+					[(axum::http::header::RETRY_AFTER, retry_after.to_string())],
+					body,
+				)
+					.into_response();
+			}
+			ServerError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
 			ServerError::NotFound => (StatusCode::NOT_FOUND, "Job not found".to_string()),
+			ServerError::EntryNotFound(msg) => (StatusCode::NOT_FOUND, msg),
 			ServerError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+			ServerError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg),
+			ServerError::UploadNotFound(id) => {
+				(StatusCode::NOT_FOUND, format!("Upload not found: {}", id))
+			}
+			ServerError::Conflict(msg) => (StatusCode::CONFLICT, msg),
+			ServerError::NotAcceptable(msg) => (StatusCode::NOT_ACCEPTABLE, msg),
 			ServerError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+			ServerError::Unavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
 		};
 
 		let body = Json(serde_json::json!({
@@ -52,29 +207,91 @@ impl IntoResponse for ServerError {
 	}
 }
 
+/// Every error is returned as `{"error": ..., "status": ...}`
+impl PartialSchema for ServerError {
+	fn schema() -> RefOr<Schema> {
+		ObjectBuilder::new()
+			.property(
+				"error",
+				ObjectBuilder::new()
+					.schema_type(Type::String)
+					.description(Some("Description of the error")),
+			)
+			.property(
+				"status",
+				ObjectBuilder::new()
+					.schema_type(Type::Integer)
+					.description(Some("HTTP status code")),
+			)
+			.required("error")
+			.required("status")
+			.into()
+	}
+}
+
+impl ToSchema for ServerError {}
+
+/// Scope required to submit jobs and manage one's own jobs and uploads
+pub const SCOPE_INGEST: &str = "dumptruck:ingest";
+/// Scope required to read job status
+pub const SCOPE_READ: &str = "dumptruck:read";
+/// Scope granting access to every user's jobs and uploads
+pub const SCOPE_ADMIN: &str = "dumptruck:admin";
+
 /// Application state
 pub struct AppState {
 	pub job_queue: Arc<JobQueue>,
 	pub oauth_provider: Arc<OAuthProvider>,
+	/// Isolated working directory that job input files are copied into
+	pub working_copy_mgr: Arc<WorkingCopyManager>,
+	/// Live configuration (suffix rules, custom passwords, services)
+	pub config: Arc<ConfigStore>,
+	/// SQLite database that job workers store normalized rows into
+	pub database_path: String,
+	/// Maximum accepted upload size in bytes
+	pub max_upload_bytes: u64,
+	/// Resumable upload sessions
+	pub uploads: Arc<ResumableUploads>,
+	/// Per-principal rate limits and byte quotas for ingest and lookup routes
+	pub rate_limiter: Arc<RateLimiter>,
+	/// Verbosity level for server and worker logging
+	pub verbose: u32,
 }
 
 /// Ingest request
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct IngestRequest {
+	/// Path of the file relative to the server's configured import directory
 	pub filename: String,
-	pub file_size_bytes: u64,
+	/// Expected size of the file; when given, the request is refused unless the
+	/// file has exactly this size
+	#[serde(default)]
+	pub file_size_bytes: Option<u64>,
+	/// Jobs with higher priority are processed first (default 0; above 0 requires
+	/// `dumptruck:admin`)
+	#[serde(default)]
+	pub priority: i32,
+	/// URL to POST a signed summary to when the job finishes
+	#[serde(default)]
+	pub callback_url: Option<String>,
 }
 
 /// Ingest response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct IngestResponse {
 	pub job_id: String,
 	pub status: String,
 	pub created_at: String,
+	/// Size of the stored upload as counted by the server
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub file_size_bytes: Option<u64>,
+	/// SHA-256 of the stored upload as computed by the server
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sha256: Option<String>,
 }
 
 /// Job status response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct JobStatusResponse {
 	pub job_id: String,
 	pub status: String,
@@ -85,6 +302,24 @@ pub struct JobStatusResponse {
 	pub rows_processed: usize,
 	pub progress_percentage: u32,
 	pub error_message: Option<String>,
+	pub priority: i32,
+	pub attempts: u32,
+	pub max_attempts: u32,
+	/// When a job waiting to be retried becomes eligible again
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub next_retry_at: Option<String>,
+	/// SHA-256 of the uploaded file, for jobs created from an upload
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sha256: Option<String>,
+	/// Subject of the principal that submitted the job
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub owner: Option<String>,
+	/// URL notified when the job finishes
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub callback_url: Option<String>,
+	/// Attempts to deliver the completion callback, oldest first
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub callback_deliveries: Vec<CallbackDelivery>,
 }
 
 impl From<Job> for JobStatusResponse {
@@ -99,12 +334,87 @@ impl From<Job> for JobStatusResponse {
 			rows_processed: job.rows_processed,
 			progress_percentage: job.progress_percentage,
 			error_message: job.error_message,
+			priority: job.priority,
+			attempts: job.attempts,
+			max_attempts: job.max_attempts,
+			next_retry_at: job.next_retry_at.map(|t| t.to_rfc3339()),
+			sha256: job.sha256,
+			owner: job.owner,
+			callback_url: job.callback_url,
+			callback_deliveries: job.callback_deliveries,
+		}
+	}
+}
+
+/// Headline numbers of a job's result, sent when a job stream finishes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResultSummary {
+	pub rows_processed: usize,
+	pub unique_addresses: usize,
+	pub hashed_credentials_detected: usize,
+	pub weak_passwords_found: usize,
+	pub breached_addresses: usize,
+	pub pii_summary: Option<PiiDetectionSummary>,
+	/// Number of rows with detailed findings
+	pub findings: usize,
+	/// Number of processing errors
+	pub errors: usize,
+}
+
+impl From<&IngestResult> for ResultSummary {
+	fn from(result: &IngestResult) -> Self {
+		Self {
+			rows_processed: result.rows_processed,
+			unique_addresses: result.unique_addresses,
+			hashed_credentials_detected: result.hashed_credentials_detected,
+			weak_passwords_found: result.weak_passwords_found,
+			breached_addresses: result.breached_addresses,
+			pii_summary: result.pii_summary.clone(),
+			findings: result
+				.rows_with_findings
+				.max(result.detailed_findings.len()),
+			errors: result.errors.len(),
 		}
 	}
 }
 
+/// Final event of a job stream
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobFinishedEvent {
+	#[serde(flatten)]
+	pub job: JobStatusResponse,
+	/// Present for completed jobs
+	pub result: Option<ResultSummary>,
+}
+
+/// Create resumable upload request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateUploadRequest {
+	pub filename: String,
+	/// Total size of the file in bytes
+	pub upload_length: u64,
+	/// Priority of the job queued when the upload is finalized (default 0; above 0
+	/// requires `dumptruck:admin`)
+	#[serde(default)]
+	pub priority: i32,
+	/// URL to POST a signed summary to when the job finishes
+	#[serde(default)]
+	pub callback_url: Option<String>,
+}
+
+/// Resumable upload status response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadResponse {
+	pub upload_id: String,
+	pub filename: String,
+	pub upload_length: u64,
+	/// Number of bytes received so far
+	pub offset: u64,
+	pub created_at: String,
+}
+
 /// List jobs response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ListJobsResponse {
 	pub jobs: Vec<JobStatusResponse>,
 	pub total: usize,
@@ -112,14 +422,70 @@ pub struct ListJobsResponse {
 	pub limit: usize,
 }
 
+/// Email suffix substitution rules
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuffixRulesResponse {
+	/// Alternate suffixes keyed by canonical suffix
+	pub rules: BTreeMap<String, Vec<String>>,
+}
+
+/// Set the alternates of a canonical email suffix
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SuffixRuleRequest {
+	/// Alternate suffixes that addresses are also recorded under, e.g. `googlemail.com`
+	pub alternates: Vec<String>,
+}
+
+/// Configured custom passwords, identified by SHA-256 so the plaintext is never returned
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CustomPasswordsResponse {
+	/// Hex SHA-256 of each custom password, in configuration order
+	pub sha256: Vec<String>,
+}
+
+/// Add a custom password
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CustomPasswordRequest {
+	/// Plaintext password to detect
+	pub password: String,
+}
+
+impl From<&Config> for SuffixRulesResponse {
+	fn from(config: &Config) -> Self {
+		Self {
+			rules: config
+				.all_suffix_rules()
+				.iter()
+				.map(|(canonical, alternates)| (canonical.clone(), alternates.clone()))
+				.collect(),
+		}
+	}
+}
+
+impl From<&Config> for CustomPasswordsResponse {
+	fn from(config: &Config) -> Self {
+		Self {
+			sha256: config
+				.custom_passwords
+				.passwords
+				.iter()
+				.map(|password| hash_utils::sha256_hex(password))
+				.collect(),
+		}
+	}
+}
+
 /// Pagination query params
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PaginationParams {
+	/// Number of items to skip
 	pub offset: Option<usize>,
+	/// Maximum number of items to return
 	pub limit: Option<usize>,
 }
 
-/// Extract and validate OAuth bearer token
+/// Extract the OAuth bearer token from the Authorization header
 async fn extract_bearer_token(headers: &HeaderMap) -> Result<String, ServerError> {
 	let auth_header = headers
 		.get("authorization")
@@ -134,17 +500,161 @@ async fn extract_bearer_token(headers: &HeaderMap) -> Result<String, ServerError
 	Ok(parts[1].to_string())
 }
 
+/// Validate the request's bearer token and return the authenticated caller
+///
+/// The token must carry every scope in `required_scopes`.
+async fn authenticate(
+	state: &AppState,
+	headers: &HeaderMap,
+	required_scopes: &[&str],
+) -> Result<Principal, ServerError> {
+	let token = extract_bearer_token(headers).await?;
+	Ok(state
+		.oauth_provider
+		.validate_token(&token, required_scopes)
+		.await?)
+}
+
+/// Authenticate the caller and apply the rate limits of a route class
+///
+/// Rejects over-limit requests with 429 and `Retry-After`, charges the request
+/// body against the caller's daily byte quota as it streams, and hands the
+/// validated principal to the handler as an extension.
+async fn enforce_limits(
+	State((state, class)): State<(Arc<AppState>, RouteClass)>,
+	request: Request,
+	next: Next,
+) -> Result<Response, ServerError> {
+	let principal = authenticate(&state, request.headers(), &[]).await?;
+
+	let expected_bytes = request
+		.headers()
+		.get(axum::http::header::CONTENT_LENGTH)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.parse().ok())
+		.unwrap_or(0);
+	if let Err(exceeded) = state
+		.rate_limiter
+		.check(class, &principal.subject, expected_bytes)
+	{
+		if state.verbose >= 1 {
+			eprintln!(
+				"[WARN] Rate limit for {} exceeded: {:?}",
+				principal.subject, exceeded
+			);
+		}
+		return Err(ServerError::TooManyRequests(exceeded.retry_after_secs()));
+	}
+
+	// Cut the body off as soon as it crosses the daily quota
+	let (mut parts, body) = request.into_parts();
+	let limiter = state.rate_limiter.clone();
+	let subject = principal.subject.clone();
+	let crossed = Arc::new(std::sync::OnceLock::new());
+	let crossed_in_body = crossed.clone();
+	let body = Body::from_stream(body.into_data_stream().map(move |chunk| {
+		let bytes = chunk?;
+		if let Err(exceeded) = limiter.record_bytes(class, &subject, bytes.len() as u64) {
+			crossed_in_body.get_or_init(|| exceeded);
+			return Err(axum::Error::new("daily byte quota exceeded"));
+		}
+		Ok(bytes)
+	}));
+	parts.extensions.insert(principal.clone());
+
+	let response = next.run(Request::from_parts(parts, body)).await;
+	if let Some(exceeded) = crossed.get() {
+		if state.verbose >= 1 {
+			eprintln!(
+				"[WARN] Daily byte quota for {} exceeded mid-request",
+				principal.subject
+			);
+		}
+		return Err(ServerError::TooManyRequests(exceeded.retry_after_secs()));
+	}
+	Ok(response)
+}
+
+/// Authenticate an administrator and hand the principal to the handler as an extension
+///
+/// Runs before the handler's extractors, so unauthenticated callers get 401
+/// rather than a complaint about the request body.
+async fn require_admin(
+	State(state): State<Arc<AppState>>,
+	mut request: Request,
+	next: Next,
+) -> Result<Response, ServerError> {
+	let principal = authenticate(&state, request.headers(), &[SCOPE_ADMIN]).await?;
+	request.extensions_mut().insert(principal);
+	Ok(next.run(request).await)
+}
+
+/// Whether `principal` may access a job or upload owned by `owner`
+fn can_access(principal: &Principal, owner: Option<&str>) -> bool {
+	principal.has_scope(SCOPE_ADMIN) || owner == Some(principal.subject.as_str())
+}
+
+/// Check that `principal` may queue a job at `priority`
+///
+/// Anyone who can ingest may lower their own jobs' priority, but only
+/// administrators may move a job ahead of the default.
+fn authorize_priority(principal: &Principal, priority: i32) -> Result<i32, ServerError> {
+	if priority > 0 && !principal.has_scope(SCOPE_ADMIN) {
+		return Err(ServerError::Forbidden(format!(
+			"Priorities above 0 require the {} scope",
+			SCOPE_ADMIN
+		)));
+	}
+	Ok(priority)
+}
+
+/// Fetch a job the caller may access; other users' jobs are reported as not found
+async fn accessible_job(
+	state: &AppState,
+	principal: &Principal,
+	job_id: &str,
+) -> Result<Job, ServerError> {
+	match state.job_queue.get_job(job_id).await {
+		Ok(job) if can_access(principal, job.owner.as_deref()) => Ok(job),
+		_ => Err(ServerError::NotFound),
+	}
+}
+
+/// Look up an upload the caller may access; other users' uploads are reported as not found
+async fn accessible_upload(
+	state: &AppState,
+	principal: &Principal,
+	upload_id: &str,
+) -> Result<(UploadSession, u64), ServerError> {
+	let (session, offset) = state.uploads.status(upload_id).await?;
+	if !can_access(principal, session.owner.as_deref()) {
+		return Err(ServerError::UploadNotFound(upload_id.to_string()));
+	}
+	Ok((session, offset))
+}
+
 /// POST /api/v1/ingest - Upload and queue a file for analysis
+#[utoipa::path(
+	post,
+	path = "/api/v1/ingest",
+	tag = TAG_INGEST,
+	request_body = IngestRequest,
+	responses(
+		(status = 202, description = "Job queued", body = IngestResponse),
+		(status = 400, description = "Invalid request", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope, a priority above 0 needs `dumptruck:admin`, or no import directory is configured", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]))
+)]
 async fn ingest_file(
 	State(state): State<Arc<AppState>>,
-	headers: HeaderMap,
+	Extension(principal): Extension<Principal>,
 	Json(req): Json<IngestRequest>,
 ) -> Result<(StatusCode, Json<IngestResponse>), ServerError> {
-	// Validate OAuth token
-	let _token = extract_bearer_token(&headers).await?;
-
-	// TODO: Validate token with OAuth provider
-	// state.oauth_provider.validate_token(&token).await?;
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_INGEST])?;
 
 	// Validate request
 	if req.filename.is_empty() {
@@ -153,16 +663,54 @@ async fn ingest_file(
 		));
 	}
 
-	if req.file_size_bytes == 0 {
-		return Err(ServerError::BadRequest(
-			"file_size_bytes must be > 0".to_string(),
-		));
+	let priority = authorize_priority(&principal, req.priority)?;
+
+	let config = state.config.current();
+	let callback_url = req
+		.callback_url
+		.as_deref()
+		.map(|url| webhook::validate_callback_url(url, &config.server.webhooks))
+		.transpose()?;
+
+	// Only files inside the configured import directory may be queued by path
+	let import_dir = config.server.import_dir.as_deref().ok_or_else(|| {
+		ServerError::Forbidden(
+			"Ingest by path is disabled; upload the file or configure server.import_dir"
+				.to_string(),
+		)
+	})?;
+	let import_path = upload::resolve_import_path(std::path::Path::new(import_dir), &req.filename)?;
+	let file_size_bytes = std::fs::metadata(&import_path)
+		.map_err(|e| ServerError::InternalError(e.to_string()))?
+		.len();
+	if let Some(expected) = req.file_size_bytes
+		&& expected != file_size_bytes
+	{
+		return Err(ServerError::BadRequest(format!(
+			"file_size_bytes is {} but {} has {} bytes",
+			expected, req.filename, file_size_bytes
+		)));
 	}
 
+	// The server reads the file on the caller's behalf, so it counts toward their quota
+	state
+		.rate_limiter
+		.try_charge(RouteClass::Ingest, &principal.subject, file_size_bytes)
+		.map_err(|exceeded| ServerError::TooManyRequests(exceeded.retry_after_secs()))?;
+
 	// Enqueue job
 	let job_id = state
 		.job_queue
-		.enqueue(req.filename, req.file_size_bytes)
+		.enqueue_job(
+			Job::for_import(
+				req.filename,
+				import_path.to_string_lossy().into_owned(),
+				file_size_bytes,
+			)
+			.owned_by(&principal.subject)
+			.with_priority(priority)
+			.with_callback(callback_url),
+		)
 		.await
 		.map_err(|e| ServerError::InternalError(e.to_string()))?;
 
@@ -172,6 +720,8 @@ async fn ingest_file(
 		job_id: job_id.clone(),
 		status: JobStatus::Queued.to_string(),
 		created_at: chrono::Utc::now().to_rfc3339(),
+		file_size_bytes: None,
+		sha256: None,
 	};
 
 	Ok((StatusCode::ACCEPTED, Json(response)))
@@ -179,14 +729,18 @@ async fn ingest_file(
 
 /// POST /api/v1/ingest/upload - Upload a file via raw binary stream (supports arbitrarily large files)
 ///
-/// This endpoint handles arbitrarily large file uploads using HTTP/2 streaming.
-/// Files are written to disk as received without loading into memory.
+/// The request body is streamed to the working directory in chunks as it arrives,
+/// so the file is never held in memory. The server counts the bytes and computes the
+/// SHA-256 while writing, and the queued job processes the stored file.
 ///
 /// Query parameters:
 ///   - `filename`: Name of the file (required)
+///   - `callback_url`: URL notified with a signed summary when the job finishes (optional)
 ///
 /// Request body: Raw binary file data (application/octet-stream)
 ///
+/// Uploads larger than the configured `server.max_upload_bytes` are rejected with 413.
+///
 /// Example curl:
 /// ```bash
 /// curl -X POST "https://localhost:8443/api/v1/ingest/upload?filename=large_file.csv" \
@@ -194,16 +748,35 @@ async fn ingest_file(
 ///   -H "Content-Type: application/octet-stream" \
 ///   --data-binary @large_file.csv
 /// ```
+#[utoipa::path(
+	post,
+	path = "/api/v1/ingest/upload",
+	tag = TAG_INGEST,
+	params(
+		("filename" = String, Query, description = "Name of the uploaded file"),
+		("priority" = Option<i32>, Query, description = "Job priority (default 0; above 0 requires `dumptruck:admin`)"),
+		("callback_url" = Option<String>, Query, description = "URL to POST a signed summary to when the job finishes"),
+	),
+	request_body(content = String, content_type = "application/octet-stream", description = "Raw file bytes"),
+	responses(
+		(status = 202, description = "File stored and job queued", body = IngestResponse),
+		(status = 400, description = "Invalid request", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope, or a priority above 0 needs `dumptruck:admin`", body = ServerError),
+		(status = 413, description = "Upload exceeds `server.max_upload_bytes`", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]))
+)]
 async fn ingest_file_upload(
 	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
 	headers: HeaderMap,
 	Query(params): Query<std::collections::HashMap<String, String>>,
+	body: Body,
 ) -> Result<(StatusCode, Json<IngestResponse>), ServerError> {
-	// Validate OAuth token
-	let _token = extract_bearer_token(&headers).await?;
-
-	// TODO: Validate token with OAuth provider
-	// state.oauth_provider.validate_token(&token).await?;
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_INGEST])?;
 
 	// Get filename from query parameter
 	let filename = params
@@ -218,83 +791,573 @@ async fn ingest_file_upload(
 		));
 	}
 
-	// Create temporary file path for streaming (unused - streaming upload not implemented)
-	let _temp_filename = format!("/tmp/dumptruck_{}_{}", uuid::Uuid::new_v4(), filename);
+	let priority = match params.get("priority") {
+		Some(priority) => priority
+			.parse::<i32>()
+			.map_err(|_| ServerError::BadRequest("priority must be an integer".to_string()))?,
+		None => 0,
+	};
+	let priority = authorize_priority(&principal, priority)?;
 
-	// In axum 0.8, streaming bodies require using RawBody or similar approach
-	// For now, we enqueue the job metadata. The actual file transfer would be
-	// handled by the client uploading to a separate endpoint or using a different
-	// mechanism (e.g., S3 presigned URL)
+	let callback_url = params
+		.get("callback_url")
+		.map(|url| webhook::validate_callback_url(url, &state.config.current().server.webhooks))
+		.transpose()?;
 
-	// Assume file size is provided via header for demo purposes
-	let file_size = headers
-		.get("x-file-size")
+	// Reject early when the client announces an oversized body
+	let declared_size = headers
+		.get(axum::http::header::CONTENT_LENGTH)
 		.and_then(|v| v.to_str().ok())
-		.and_then(|s| s.parse::<u64>().ok())
-		.ok_or_else(|| ServerError::BadRequest("x-file-size header required".to_string()))?;
-
-	if file_size == 0 {
-		return Err(ServerError::BadRequest("File size must be > 0".to_string()));
+		.and_then(|s| s.parse::<u64>().ok());
+	if declared_size.is_some_and(|size| size > state.max_upload_bytes) {
+		return Err(UploadError::TooLarge(state.max_upload_bytes).into());
 	}
 
-	// Enqueue job with file metadata
+	let dest = upload::upload_path(state.working_copy_mgr.working_dir(), &filename)?;
+	let stored = upload::stream_body_to_file(body, &dest, state.max_upload_bytes).await?;
+
+	// Enqueue job for the stored file
 	let job_id = state
 		.job_queue
-		.enqueue(filename.clone(), file_size)
+		.enqueue_job(
+			Job::for_stored_file(
+				filename.clone(),
+				stored.path.to_string_lossy().to_string(),
+				stored.size_bytes,
+				stored.sha256.clone(),
+			)
+			.owned_by(&principal.subject)
+			.with_priority(priority)
+			.with_callback(callback_url),
+		)
 		.await
 		.map_err(|e| ServerError::InternalError(e.to_string()))?;
 
 	info!(
-		"Upload job {} queued: {} ({} bytes)",
-		job_id, filename, file_size
+		"Upload job {} queued: {} ({} bytes, sha256 {})",
+		job_id, filename, stored.size_bytes, stored.sha256
 	);
 
 	let response = IngestResponse {
 		job_id: job_id.clone(),
 		status: JobStatus::Queued.to_string(),
 		created_at: chrono::Utc::now().to_rfc3339(),
+		file_size_bytes: Some(stored.size_bytes),
+		sha256: Some(stored.sha256),
 	};
 
 	Ok((StatusCode::ACCEPTED, Json(response)))
 }
 
+/// Header carrying the byte offset of a resumable upload
+const UPLOAD_OFFSET: &str = "upload-offset";
+/// Header carrying the total length of a resumable upload
+const UPLOAD_LENGTH: &str = "upload-length";
+
+/// POST /api/v1/uploads - Create a resumable upload
+///
+/// Returns the upload ID and a `Location` header for subsequent PATCH/HEAD requests.
+#[utoipa::path(
+	post,
+	path = "/api/v1/uploads",
+	tag = TAG_INGEST,
+	request_body = CreateUploadRequest,
+	responses(
+		(status = 201, description = "Upload created", body = UploadResponse, headers(
+			("location" = String, description = "URL of the upload"),
+			("upload-offset" = u64, description = "Bytes received so far"),
+		)),
+		(status = 400, description = "Invalid request", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope, or a priority above 0 needs `dumptruck:admin`", body = ServerError),
+		(status = 413, description = "Upload exceeds `server.max_upload_bytes`", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]))
+)]
+async fn create_upload(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Json(req): Json<CreateUploadRequest>,
+) -> Result<Response, ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_INGEST])?;
+	let priority = authorize_priority(&principal, req.priority)?;
+
+	let callback_url = req
+		.callback_url
+		.as_deref()
+		.map(|url| webhook::validate_callback_url(url, &state.config.current().server.webhooks))
+		.transpose()?;
+
+	let session = state
+		.uploads
+		.create(
+			&req.filename,
+			req.upload_length,
+			&principal.subject,
+			priority,
+			callback_url,
+		)
+		.await?;
+
+	info!(
+		"Upload {} created: {} ({} bytes)",
+		session.id, session.filename, session.upload_length
+	);
+
+	let location = format!("/api/v1/uploads/{}", session.id);
+	let response = UploadResponse {
+		upload_id: session.id,
+		filename: session.filename,
+		upload_length: session.upload_length,
+		offset: 0,
+		created_at: session.created_at.to_rfc3339(),
+	};
+
+	Ok((
+		StatusCode::CREATED,
+		[
+			(axum::http::header::LOCATION, location),
+			(
+				UPLOAD_OFFSET.parse().expect("valid header name"),
+				"0".to_string(),
+			),
+		],
+		Json(response),
+	)
+		.into_response())
+}
+
+/// HEAD /api/v1/uploads/{upload_id} - Query how many bytes have been received
+#[utoipa::path(
+	head,
+	path = "/api/v1/uploads/{upload_id}",
+	tag = TAG_INGEST,
+	params(("upload_id" = String, Path, description = "Upload ID")),
+	responses(
+		(status = 200, description = "Upload progress", headers(
+			("upload-offset" = u64, description = "Bytes received so far"),
+			("upload-length" = u64, description = "Total size of the upload"),
+		)),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such upload visible to the caller", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]))
+)]
+async fn upload_offset(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Path(upload_id): Path<String>,
+) -> Result<Response, ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_INGEST])?;
+
+	let (session, offset) = accessible_upload(&state, &principal, &upload_id).await?;
+
+	Ok((
+		StatusCode::OK,
+		[
+			(UPLOAD_OFFSET, offset.to_string()),
+			(UPLOAD_LENGTH, session.upload_length.to_string()),
+			("cache-control", "no-store".to_string()),
+		],
+	)
+		.into_response())
+}
+
+/// PATCH /api/v1/uploads/{upload_id} - Append a chunk at the `Upload-Offset` header
+///
+/// The offset must equal the bytes already received (see HEAD). Responds with the
+/// new offset in the `Upload-Offset` header.
+#[utoipa::path(
+	patch,
+	path = "/api/v1/uploads/{upload_id}",
+	tag = TAG_INGEST,
+	params(
+		("upload_id" = String, Path, description = "Upload ID"),
+		("upload-offset" = u64, Header, description = "Offset the chunk starts at"),
+	),
+	request_body(content = String, content_type = "application/offset+octet-stream", description = "Chunk bytes"),
+	responses(
+		(status = 204, description = "Chunk appended", headers(
+			("upload-offset" = u64, description = "Bytes received so far"),
+		)),
+		(status = 400, description = "Invalid request", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such upload visible to the caller", body = ServerError),
+		(status = 409, description = "Offset mismatch or upload busy", body = ServerError),
+		(status = 413, description = "Chunk exceeds the declared upload length", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]))
+)]
+async fn upload_chunk(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	headers: HeaderMap,
+	Path(upload_id): Path<String>,
+	body: Body,
+) -> Result<Response, ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_INGEST])?;
+	accessible_upload(&state, &principal, &upload_id).await?;
+
+	let offset = headers
+		.get(UPLOAD_OFFSET)
+		.and_then(|v| v.to_str().ok())
+		.and_then(|s| s.parse::<u64>().ok())
+		.ok_or_else(|| ServerError::BadRequest("upload-offset header required".to_string()))?;
+
+	let new_offset = state.uploads.append(&upload_id, offset, body).await?;
+
+	Ok((
+		StatusCode::NO_CONTENT,
+		[(UPLOAD_OFFSET, new_offset.to_string())],
+	)
+		.into_response())
+}
+
+/// DELETE /api/v1/uploads/{upload_id} - Abandon an upload
+///
+/// Securely deletes the bytes received so far; the upload ID is no longer valid afterwards.
+#[utoipa::path(
+	delete,
+	path = "/api/v1/uploads/{upload_id}",
+	tag = TAG_INGEST,
+	params(("upload_id" = String, Path, description = "Upload ID")),
+	responses(
+		(status = 204, description = "Upload removed"),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such upload visible to the caller", body = ServerError),
+		(status = 409, description = "Upload busy", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]))
+)]
+async fn terminate_upload(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Path(upload_id): Path<String>,
+) -> Result<StatusCode, ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_INGEST])?;
+	accessible_upload(&state, &principal, &upload_id).await?;
+
+	state.uploads.terminate(&upload_id).await?;
+	info!("Upload {} terminated", upload_id);
+
+	Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v1/uploads/{upload_id}/finalize - Complete an upload and queue it for analysis
+///
+/// Records the file evidence (ID, SHA-256, size, names) and enqueues a job for the
+/// assembled file. If either step fails, the assembled file is securely deleted.
+#[utoipa::path(
+	post,
+	path = "/api/v1/uploads/{upload_id}/finalize",
+	tag = TAG_INGEST,
+	params(("upload_id" = String, Path, description = "Upload ID")),
+	responses(
+		(status = 202, description = "Upload complete and job queued", body = IngestResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such upload visible to the caller", body = ServerError),
+		(status = 409, description = "Upload is incomplete", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]))
+)]
+async fn finalize_upload(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Path(upload_id): Path<String>,
+) -> Result<(StatusCode, Json<IngestResponse>), ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_INGEST])?;
+	accessible_upload(&state, &principal, &upload_id).await?;
+
+	let (session, path) = state
+		.uploads
+		.finalize(&upload_id, state.working_copy_mgr.working_dir())
+		.await?;
+
+	match queue_finalized_upload(&state, &upload_id, &session, &path).await {
+		Ok(response) => Ok((StatusCode::ACCEPTED, Json(response))),
+		Err(e) => {
+			// Nothing refers to the assembled file without a job
+			let orphan = path.clone();
+			let config = SecureDeletionConfig {
+				log_deletions: state.verbose >= 2,
+				..SecureDeletionConfig::default()
+			};
+			let removed = tokio::task::spawn_blocking(move || secure_delete_file(&orphan, config))
+				.await
+				.map_err(|e| e.to_string())
+				.and_then(|result| result.map_err(|e| e.to_string()));
+			if let Err(err) = removed {
+				warn!("Failed to remove finalized upload {:?}: {}", path, err);
+			}
+			Err(e)
+		}
+	}
+}
+
+/// Record the evidence of a finalized upload and queue its job
+async fn queue_finalized_upload(
+	state: &AppState,
+	upload_id: &str,
+	session: &UploadSession,
+	path: &std::path::Path,
+) -> Result<IngestResponse, ServerError> {
+	// Hash the assembled file and record its evidence off the async runtime
+	let evidence_path = path.to_path_buf();
+	let filename = session.filename.clone();
+	let database_path = state.database_path.clone();
+	let evidence = tokio::task::spawn_blocking(move || {
+		let evidence = FileEvidence::create(&evidence_path, Some(vec![filename.clone()]))
+			.map_err(|e| format!("Failed to create file evidence: {}", e))?;
+		let mut storage = SqliteStorage::new(&database_path, None)
+			.map_err(|e| format!("Failed to open database: {}", e))?;
+		storage
+			.insert_file_metadata(
+				&evidence.file_id,
+				&filename,
+				&evidence.sha256_hash,
+				evidence.file_size as i64,
+			)
+			.map_err(|e| format!("Failed to record file evidence: {}", e))?;
+		Ok::<_, String>(evidence)
+	})
+	.await
+	.map_err(|e| ServerError::InternalError(e.to_string()))?
+	.map_err(ServerError::InternalError)?;
+
+	let mut job = Job::for_stored_file(
+		session.filename.clone(),
+		path.to_string_lossy().to_string(),
+		evidence.file_size,
+		evidence.sha256_hash.clone(),
+	);
+	job.file_id = Some(evidence.file_id.clone());
+	job.owner = session.owner.clone();
+	job.priority = session.priority;
+	job.callback_url = session.callback_url.clone();
+
+	let job_id = state
+		.job_queue
+		.enqueue_job(job)
+		.await
+		.map_err(|e| ServerError::InternalError(e.to_string()))?;
+
+	info!(
+		"Upload {} finalized as job {} (file {}, sha256 {})",
+		upload_id, job_id, evidence.file_id, evidence.sha256_hash
+	);
+
+	Ok(IngestResponse {
+		job_id,
+		status: JobStatus::Queued.to_string(),
+		created_at: chrono::Utc::now().to_rfc3339(),
+		file_size_bytes: Some(evidence.file_size),
+		sha256: Some(evidence.sha256_hash),
+	})
+}
+
 /// GET /api/v1/status/{job_id} - Get job status
+#[utoipa::path(
+	get,
+	path = "/api/v1/status/{job_id}",
+	tag = TAG_JOBS,
+	params(("job_id" = String, Path, description = "Job ID")),
+	responses(
+		(status = 200, description = "Job status", body = JobStatusResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such job visible to the caller", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
 async fn get_job_status(
 	State(state): State<Arc<AppState>>,
 	headers: HeaderMap,
 	Path(job_id): Path<String>,
 ) -> Result<Json<JobStatusResponse>, ServerError> {
 	// Validate OAuth token
-	let _token = extract_bearer_token(&headers).await?;
+	let principal = authenticate(&state, &headers, &[SCOPE_READ]).await?;
 
-	// TODO: Validate token
-	// state.oauth_provider.validate_token(&token).await?;
-
-	let job = state
-		.job_queue
-		.get_job(&job_id)
-		.await
-		.map_err(|_| ServerError::NotFound)?;
+	let job = accessible_job(&state, &principal, &job_id).await?;
 
 	Ok(Json(job.into()))
 }
 
-/// GET /api/v1/jobs - List all jobs with pagination
+/// Default number of detailed findings per result page
+const RESULT_PAGE_SIZE: usize = 100;
+
+/// Maximum number of detailed findings per result page
+const RESULT_MAX_PAGE_SIZE: usize = 1000;
+
+/// Pick a result formatter from the Accept header, honouring q-values
+///
+/// Returns the formatter and its content type, or `None` if no supported
+/// format is acceptable. A missing header means JSON.
+fn negotiate_result_format(
+	accept: Option<&str>,
+) -> Option<(Box<dyn OutputFormatter>, &'static str)> {
+	let mut ranges: Vec<(String, f32)> = accept
+		.unwrap_or("application/json")
+		.split(',')
+		.filter_map(|range| {
+			let mut parts = range.split(';');
+			let media_type = parts.next()?.trim().to_ascii_lowercase();
+			let quality = parts
+				.filter_map(|param| param.trim().strip_prefix("q="))
+				.find_map(|q| q.trim().parse::<f32>().ok())
+				.unwrap_or(1.0);
+			(!media_type.is_empty() && quality > 0.0).then_some((media_type, quality))
+		})
+		.collect();
+	// Stable sort keeps the client's order among equal q-values
+	ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+	ranges
+		.iter()
+		.find_map(|(media_type, _)| match media_type.as_str() {
+			"application/json" | "application/*" | "*/*" => Some((
+				Box::new(JsonFormatter) as Box<dyn OutputFormatter>,
+				"application/json",
+			)),
+			"application/x-ndjson" | "application/jsonl" => {
+				Some((Box::new(JsonlFormatter) as _, "application/x-ndjson"))
+			}
+			"text/csv" => Some((Box::new(CsvFormatter) as _, "text/csv; charset=utf-8")),
+			"text/plain" | "text/*" => {
+				Some((Box::new(TextFormatter) as _, "text/plain; charset=utf-8"))
+			}
+			_ => None,
+		})
+}
+
+/// GET /api/v1/jobs/{job_id}/result - Get the result of a completed job
+///
+/// The format (JSON, JSONL, CSV or text) is chosen from the Accept header.
+/// `offset` and `limit` page through the detailed findings; the total number
+/// of findings is returned in `X-Total-Count`.
+#[utoipa::path(
+	get,
+	path = "/api/v1/jobs/{job_id}/result",
+	tag = TAG_JOBS,
+	params(("job_id" = String, Path, description = "Job ID"), PaginationParams),
+	responses(
+		(status = 200, description = "Job result with a page of detailed findings",
+			headers(("x-total-count" = usize, description = "Total number of detailed findings")),
+			content(
+				(IngestResult = "application/json"),
+				(String = "application/x-ndjson"),
+				(String = "text/csv"),
+				(String = "text/plain"),
+			)
+		),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such job visible to the caller", body = ServerError),
+		(status = 406, description = "No supported format is acceptable", body = ServerError),
+		(status = 409, description = "Job has no result yet", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
+async fn get_job_result(
+	State(state): State<Arc<AppState>>,
+	headers: HeaderMap,
+	Path(job_id): Path<String>,
+	Query(params): Query<PaginationParams>,
+) -> Result<Response, ServerError> {
+	// Validate OAuth token
+	let principal = authenticate(&state, &headers, &[SCOPE_READ]).await?;
+
+	let accept = headers
+		.get(axum::http::header::ACCEPT)
+		.and_then(|h| h.to_str().ok());
+	let (formatter, content_type) = negotiate_result_format(accept).ok_or_else(|| {
+		ServerError::NotAcceptable(
+			"Supported formats: application/json, application/x-ndjson, text/csv, text/plain"
+				.to_string(),
+		)
+	})?;
+
+	let job = accessible_job(&state, &principal, &job_id).await?;
+	let Some(mut result) = job.result else {
+		return Err(ServerError::Conflict(format!(
+			"Job {} has no result (status: {})",
+			job_id, job.status
+		)));
+	};
+
+	let offset = params.offset.unwrap_or(0);
+	let limit = params
+		.limit
+		.unwrap_or(RESULT_PAGE_SIZE)
+		.min(RESULT_MAX_PAGE_SIZE);
+	let total = result.detailed_findings.len();
+	result.detailed_findings = result
+		.detailed_findings
+		.into_iter()
+		.skip(offset)
+		.take(limit)
+		.collect();
+
+	let body = formatter
+		.format(&result)
+		.map_err(|e| ServerError::InternalError(e.to_string()))?;
+
+	Ok((
+		[
+			(axum::http::header::CONTENT_TYPE, content_type.to_string()),
+			(
+				axum::http::header::HeaderName::from_static("x-total-count"),
+				total.to_string(),
+			),
+		],
+		body,
+	)
+		.into_response())
+}
+
+/// GET /api/v1/jobs - List the caller's jobs (all jobs for admins) with pagination
+#[utoipa::path(
+	get,
+	path = "/api/v1/jobs",
+	tag = TAG_JOBS,
+	params(PaginationParams),
+	responses(
+		(status = 200, description = "Page of jobs", body = ListJobsResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
 async fn list_jobs(
 	State(state): State<Arc<AppState>>,
 	headers: HeaderMap,
 	Query(params): Query<PaginationParams>,
 ) -> Result<Json<ListJobsResponse>, ServerError> {
 	// Validate OAuth token
-	let _token = extract_bearer_token(&headers).await?;
-
-	// TODO: Validate token
-	// state.oauth_provider.validate_token(&token).await?;
+	let principal = authenticate(&state, &headers, &[SCOPE_READ]).await?;
 
 	let offset = params.offset.unwrap_or(0);
 	let limit = params.limit.unwrap_or(50).min(200); // Max 200 per page
 
-	let (jobs, total) = state.job_queue.list_jobs(offset, limit).await;
+	let owner = (!principal.has_scope(SCOPE_ADMIN)).then_some(principal.subject.as_str());
+	let (jobs, total) = state
+		.job_queue
+		.list_jobs(owner, offset, limit)
+		.await
+		.map_err(|e| ServerError::InternalError(e.to_string()))?;
 
 	let response = ListJobsResponse {
 		jobs: jobs.into_iter().map(Into::into).collect(),
@@ -307,16 +1370,33 @@ async fn list_jobs(
 }
 
 /// DELETE /api/v1/jobs/{job_id} - Cancel a job
+///
+/// A running job stops at its worker's next row batch.
+/// Owners need the ingest scope; cancelling other users' jobs requires the admin scope.
+#[utoipa::path(
+	delete,
+	path = "/api/v1/jobs/{job_id}",
+	tag = TAG_JOBS,
+	params(("job_id" = String, Path, description = "Job ID")),
+	responses(
+		(status = 200, description = "Job cancelled", body = JobStatusResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such job visible to the caller", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:ingest"]), ("bearer" = ["dumptruck:admin"]))
+)]
 async fn cancel_job(
 	State(state): State<Arc<AppState>>,
 	headers: HeaderMap,
 	Path(job_id): Path<String>,
 ) -> Result<Json<JobStatusResponse>, ServerError> {
 	// Validate OAuth token
-	let _token = extract_bearer_token(&headers).await?;
-
-	// TODO: Validate token
-	// state.oauth_provider.validate_token(&token).await?;
+	let principal = authenticate(&state, &headers, &[]).await?;
+	if !principal.has_scope(SCOPE_ADMIN) {
+		principal.require_scopes(&[SCOPE_INGEST])?;
+	}
+	accessible_job(&state, &principal, &job_id).await?;
 
 	let job = state
 		.job_queue
@@ -324,71 +1404,2558 @@ async fn cancel_job(
 		.await
 		.map_err(|_| ServerError::NotFound)?;
 
+	// A running job's worker removes its upload when it stops
+	if job.started_at.is_none() {
+		handlers::remove_job_upload(&job, state.verbose);
+	}
+
 	info!("Job {} cancelled", job_id);
 
 	Ok(Json(job.into()))
 }
 
-/// Health check endpoint
-async fn health() -> Json<serde_json::Value> {
-	Json(serde_json::json!({
-		"status": "healthy",
-		"timestamp": chrono::Utc::now().to_rfc3339(),
-	}))
+/// Run a lookup against the corpus database off the async runtime
+async fn run_lookup<T, F>(state: &AppState, lookup: F) -> Result<T, ServerError>
+where
+	T: Send + 'static,
+	F: FnOnce(&mut SqliteStorage, &Config) -> Result<T, LookupError> + Send + 'static,
+{
+	let database_path = state.database_path.clone();
+	let config = state.config.current();
+	tokio::task::spawn_blocking(move || {
+		let mut storage = SqliteStorage::new(&database_path, None)
+			.map_err(|e| ServerError::InternalError(format!("Failed to open database: {}", e)))?;
+		lookup(&mut storage, &config).map_err(ServerError::from)
+	})
+	.await
+	.map_err(|e| ServerError::InternalError(e.to_string()))?
 }
 
-/// Create the API router
-///
-/// Routes:
-/// - GET /api/v1/health - Health check
-/// - POST /api/v1/ingest - JSON metadata-based ingest (for metadata-only submissions)
-/// - POST /api/v1/ingest/upload - Raw binary stream upload (supports arbitrarily large files)
-/// - GET /api/v1/status/:job_id - Get job status
-/// - GET /api/v1/jobs - List all jobs
-/// - DELETE /api/v1/jobs/:job_id - Cancel a job
-///
-/// The upload endpoint (/api/v1/ingest/upload) supports files of any size
-/// limited only by the underlying OS/filesystem via streaming HTTP/2 transfer.
-pub fn create_api_router(state: Arc<AppState>) -> Router {
-	Router::new()
-		.route("/api/v1/health", get(health))
-		.route("/api/v1/ingest", post(ingest_file))
-		.route("/api/v1/ingest/upload", post(ingest_file_upload))
-		.route("/api/v1/status/{job_id}", get(get_job_status))
-		.route("/api/v1/jobs", get(list_jobs))
-		.route("/api/v1/jobs/{job_id}", axum::routing::delete(cancel_job))
-		.layer(TraceLayer::new_for_http())
-		.with_state(state)
+/// POST /api/v1/lookup/address - Look up an address (plaintext or hashed) in the corpus
+#[utoipa::path(
+	post,
+	path = "/api/v1/lookup/address",
+	tag = TAG_LOOKUP,
+	request_body = AddressLookupRequest,
+	responses(
+		(status = 200, description = "What the corpus knows about the address", body = AddressLookupResponse),
+		(status = 400, description = "Invalid request", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
+async fn lookup_address(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Json(request): Json<AddressLookupRequest>,
+) -> Result<Json<AddressLookupResponse>, ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_READ])?;
+
+	let response = run_lookup(&state, move |storage, config| {
+		lookup::lookup_address(storage, &request, config)
+	})
+	.await?;
+
+	Ok(Json(response))
 }
 
-/// Create the application (alias for create_api_router)
-pub fn create_app(state: Arc<AppState>) -> Router {
-	create_api_router(state)
+/// POST /api/v1/lookup/credential - Look up a credential (plaintext or hashed) in the corpus
+#[utoipa::path(
+	post,
+	path = "/api/v1/lookup/credential",
+	tag = TAG_LOOKUP,
+	request_body = CredentialLookupRequest,
+	responses(
+		(status = 200, description = "What the corpus knows about the credential", body = CredentialLookupResponse),
+		(status = 400, description = "Invalid request", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
+async fn lookup_credential(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Json(request): Json<CredentialLookupRequest>,
+) -> Result<Json<CredentialLookupResponse>, ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_READ])?;
+
+	let response = run_lookup(&state, move |storage, config| {
+		lookup::lookup_credential(storage, &request, config)
+	})
+	.await?;
+
+	Ok(Json(response))
 }
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+/// Number of hex characters a range query prefix must have
+const RANGE_PREFIX_LEN: usize = 5;
 
-	#[test]
-	fn test_ingest_response_serialization() {
-		let response = IngestResponse {
-			job_id: "job-123".to_string(),
-			status: "queued".to_string(),
-			created_at: chrono::Utc::now().to_rfc3339(),
-		};
+/// Range query parameters
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RangeParams {
+	/// Hash type of the prefix: `sha1` (default) or `ntlm`
+	#[serde(default)]
+	pub mode: RangeMode,
+}
 
-		let json = serde_json::to_string(&response).expect("serialization failed");
-		assert!(json.contains("job-123"));
-		assert!(json.contains("queued"));
+/// Hash type of a range query
+#[derive(Debug, Default, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RangeMode {
+	#[default]
+	Sha1,
+	Ntlm,
+}
+
+impl From<RangeMode> for DigestAlgorithm {
+	fn from(mode: RangeMode) -> Self {
+		match mode {
+			RangeMode::Sha1 => DigestAlgorithm::Sha1,
+			RangeMode::Ntlm => DigestAlgorithm::Ntlm,
+		}
 	}
+}
 
-	#[test]
-	fn test_job_status_response_from_job() {
+/// GET /api/v1/range/{prefix} - k-anonymity search of credential hashes
+///
+/// Takes the first five hex characters of a SHA-1 (or, with `?mode=ntlm`, NTLM)
+/// hash of a password and returns every known hash suffix with that prefix as
+/// `SUFFIX:COUNT` lines, in the style of the Pwned Passwords range API. The count
+/// is how often that exact password was ingested.
+///
+/// Only credentials ingested since digests were first recorded are searchable.
+/// Older rows keep just a hash of the case-folded credential, from which the
+/// exact password cannot be recovered, so their source files have to be
+/// ingested again to appear here.
+#[utoipa::path(
+	get,
+	path = "/api/v1/range/{prefix}",
+	tag = TAG_LOOKUP,
+	params(
+		("prefix" = String, Path, description = "First five hex characters of the hash"),
+		RangeParams,
+	),
+	responses(
+		(status = 200, description = "`SUFFIX:COUNT` lines", body = String, content_type = "text/plain"),
+		(status = 400, description = "Invalid request", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 429, description = "Rate limit or daily byte quota exceeded", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
+async fn credential_range(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Path(prefix): Path<String>,
+	Query(params): Query<RangeParams>,
+) -> Result<Response, ServerError> {
+	// Token validated by the rate-limit layer
+	principal.require_scopes(&[SCOPE_READ])?;
+
+	if prefix.len() != RANGE_PREFIX_LEN || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+		return Err(ServerError::BadRequest(format!(
+			"Prefix must be {} hex characters",
+			RANGE_PREFIX_LEN
+		)));
+	}
+	let prefix = prefix.to_ascii_uppercase();
+
+	let range = run_lookup(&state, move |storage, _| {
+		Ok(storage.get_credential_range(&prefix, params.mode.into())?)
+	})
+	.await?;
+
+	let body: String = range
+		.iter()
+		.map(|(suffix, count)| format!("{}:{}\r\n", suffix, count))
+		.collect();
+
+	Ok((
+		[(
+			axum::http::header::CONTENT_TYPE,
+			"text/plain; charset=utf-8",
+		)],
+		body,
+	)
+		.into_response())
+}
+
+/// SSE event for a job snapshot: `job` while it runs, `finished` once it reaches a terminal state
+fn job_event(job: Job) -> Result<Event, axum::Error> {
+	if job.status.is_terminal() {
+		let result = job.result.as_ref().map(ResultSummary::from);
+		Event::default()
+			.event("finished")
+			.id(job.id.clone())
+			.json_data(JobFinishedEvent {
+				job: job.into(),
+				result,
+			})
+	} else {
+		Event::default()
+			.event("job")
+			.id(job.id.clone())
+			.json_data(JobStatusResponse::from(job))
+	}
+}
+
+/// Progress of a single-job event stream
+enum JobStreamState {
+	/// Send the job as it was when the stream opened
+	Snapshot(Box<Job>, broadcast::Receiver<Job>),
+	/// Send updates until the job finishes
+	Following(broadcast::Receiver<Job>),
+	Done,
+}
+
+/// GET /api/v1/jobs/{job_id}/events - Stream a job's status and progress (Server-Sent Events)
+///
+/// Sends the current state, a `job` event for every change, and ends with a
+/// `finished` event carrying the result summary.
+#[utoipa::path(
+	get,
+	path = "/api/v1/jobs/{job_id}/events",
+	tag = TAG_JOBS,
+	params(("job_id" = String, Path, description = "Job ID")),
+	responses(
+		(status = 200, description = "`job` events carry a `JobStatusResponse`, the final `finished` event a `JobFinishedEvent`",
+			body = JobFinishedEvent, content_type = "text/event-stream"),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No such job visible to the caller", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
+async fn job_events(
+	State(state): State<Arc<AppState>>,
+	headers: HeaderMap,
+	Path(job_id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ServerError> {
+	// Validate OAuth token
+	let principal = authenticate(&state, &headers, &[SCOPE_READ]).await?;
+
+	// Subscribe before reading the job so no update in between is missed
+	let updates = state.job_queue.subscribe();
+	let job = accessible_job(&state, &principal, &job_id).await?;
+	let queue = state.job_queue.clone();
+
+	let events = stream::unfold(
+		JobStreamState::Snapshot(Box::new(job), updates),
+		move |stream_state| {
+			let queue = queue.clone();
+			let job_id = job_id.clone();
+			async move {
+				let (job, updates) = match stream_state {
+					JobStreamState::Snapshot(job, updates) => (*job, updates),
+					JobStreamState::Following(mut updates) => loop {
+						match updates.recv().await {
+							Ok(job) if job.id == job_id => break (job, updates),
+							Ok(_) => continue,
+							// Missed some updates; the current state supersedes them
+							Err(broadcast::error::RecvError::Lagged(_)) => {
+								match queue.get_job(&job_id).await {
+									Ok(job) => break (job, updates),
+									Err(_) => return None,
+								}
+							}
+							Err(broadcast::error::RecvError::Closed) => return None,
+						}
+					},
+					JobStreamState::Done => return None,
+				};
+
+				let next = if job.status.is_terminal() {
+					JobStreamState::Done
+				} else {
+					JobStreamState::Following(updates)
+				};
+				Some((job_event(job), next))
+			}
+		},
+	);
+
+	Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// GET /api/v1/events - Stream updates for every job the caller can see (Server-Sent Events)
+#[utoipa::path(
+	get,
+	path = "/api/v1/events",
+	tag = TAG_JOBS,
+	responses(
+		(status = 200, description = "`job` and `finished` events for every visible job",
+			body = JobFinishedEvent, content_type = "text/event-stream"),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:read"]))
+)]
+async fn all_job_events(
+	State(state): State<Arc<AppState>>,
+	headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ServerError> {
+	// Validate OAuth token
+	let principal = authenticate(&state, &headers, &[SCOPE_READ]).await?;
+
+	let updates = state.job_queue.subscribe();
+	let events = stream::unfold(updates, move |mut updates| {
+		let principal = principal.clone();
+		async move {
+			loop {
+				match updates.recv().await {
+					Ok(job) if can_access(&principal, job.owner.as_deref()) => {
+						return Some((job_event(job), updates));
+					}
+					Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+					Err(broadcast::error::RecvError::Closed) => return None,
+				}
+			}
+		}
+	});
+
+	Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// GET /metrics - Prometheus metrics
+///
+/// Unauthenticated like the health check, for scrapers; no label carries request data.
+#[utoipa::path(
+	get,
+	path = "/metrics",
+	tag = TAG_SYSTEM,
+	responses(
+		(status = 200, description = "Prometheus text exposition", body = String, content_type = "text/plain"),
+	)
+)]
+async fn metrics_endpoint(State(state): State<Arc<AppState>>) -> Response {
+	match state.job_queue.count_by_status().await {
+		Ok(counts) => {
+			for (status, count) in counts {
+				metrics::metrics()
+					.jobs
+					.with_label_values(&[status.to_string()])
+					.set(count as i64);
+			}
+		}
+		Err(e) => warn!("Failed to count jobs for metrics: {}", e),
+	}
+
+	(
+		[(axum::http::header::CONTENT_TYPE, metrics::CONTENT_TYPE)],
+		metrics::metrics().render(),
+	)
+		.into_response()
+}
+
+/// Record request latency by method, route template and status
+async fn record_request_metrics(request: Request, next: Next) -> Response {
+	let start = std::time::Instant::now();
+	let method = match *request.method() {
+		Method::GET
+		| Method::HEAD
+		| Method::POST
+		| Method::PUT
+		| Method::PATCH
+		| Method::DELETE
+		| Method::OPTIONS => request.method().as_str().to_string(),
+		_ => "OTHER".to_string(),
+	};
+	let route = request
+		.extensions()
+		.get::<MatchedPath>()
+		.map_or("unmatched", |path| path.as_str())
+		.to_string();
+
+	let response = next.run(request).await;
+	metrics::metrics()
+		.http_request_duration
+		.with_label_values(&[method.as_str(), route.as_str(), response.status().as_str()])
+		.observe(start.elapsed().as_secs_f64());
+	response
+}
+
+/// GET /api/v1/ready - Readiness probe with a per-component report
+///
+/// Returns 503 when any component has failed; degraded components still
+/// report ready.
+#[utoipa::path(
+	get,
+	path = "/api/v1/ready",
+	tag = TAG_SYSTEM,
+	responses(
+		(status = 200, description = "Ready, possibly degraded", body = ReadinessReport),
+		(status = 503, description = "A component has failed", body = ReadinessReport),
+	)
+)]
+async fn ready(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ReadinessReport>) {
+	let report = readiness::check_readiness(
+		&state.config.current(),
+		&state.database_path,
+		state.working_copy_mgr.clone(),
+	)
+	.await;
+
+	let status = if report.status == ComponentStatus::Failed {
+		StatusCode::SERVICE_UNAVAILABLE
+	} else {
+		StatusCode::OK
+	};
+	(status, Json(report))
+}
+
+/// Health check endpoint
+#[utoipa::path(
+	get,
+	path = "/api/v1/health",
+	tag = TAG_SYSTEM,
+	responses(
+		(status = 200, description = "Server is live", body = Object),
+	)
+)]
+async fn health() -> Json<serde_json::Value> {
+	Json(serde_json::json!({
+		"status": "healthy",
+		"timestamp": chrono::Utc::now().to_rfc3339(),
+	}))
+}
+
+/// GET /api/v1/openapi.json - OpenAPI 3 description of this API
+#[utoipa::path(
+	get,
+	path = "/api/v1/openapi.json",
+	tag = TAG_SYSTEM,
+	responses(
+		(status = 200, description = "OpenAPI document", body = Object),
+	)
+)]
+async fn openapi_json() -> Response {
+	static SPEC: once_cell::sync::Lazy<String> = once_cell::sync::Lazy::new(|| {
+		openapi()
+			.to_pretty_json()
+			.expect("OpenAPI document serializes")
+	});
+
+	(
+		[(axum::http::header::CONTENT_TYPE, "application/json")],
+		SPEC.as_str(),
+	)
+		.into_response()
+}
+
+/// Apply a configuration change off the async runtime, persisting it to the config file
+async fn update_config<T, F>(state: &AppState, change: F) -> Result<(T, Arc<Config>), ServerError>
+where
+	T: Send + 'static,
+	F: FnOnce(&mut Config) -> Result<T, ConfigError> + Send + 'static,
+{
+	let store = state.config.clone();
+	tokio::task::spawn_blocking(move || store.update(change))
+		.await
+		.map_err(|e| ServerError::InternalError(e.to_string()))?
+		.map_err(ServerError::from)
+}
+
+/// Normalize an email suffix for comparison and storage
+fn normalize_suffix(suffix: &str) -> String {
+	suffix.trim().trim_start_matches('@').to_lowercase()
+}
+
+/// GET /api/v1/admin/config - Effective configuration with secrets redacted
+#[utoipa::path(
+	get,
+	path = "/api/v1/admin/config",
+	tag = TAG_ADMIN,
+	responses(
+		(status = 200, description = "Configuration; the OAuth client secret, HIBP key, webhook secret and custom passwords are redacted", body = Object),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:admin"]))
+)]
+async fn get_config(State(state): State<Arc<AppState>>) -> Json<Config> {
+	Json(state.config.current().redacted())
+}
+
+/// GET /api/v1/admin/suffix-rules - List email suffix substitution rules
+#[utoipa::path(
+	get,
+	path = "/api/v1/admin/suffix-rules",
+	tag = TAG_ADMIN,
+	responses(
+		(status = 200, description = "Suffix rules", body = SuffixRulesResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:admin"]))
+)]
+async fn list_suffix_rules(State(state): State<Arc<AppState>>) -> Json<SuffixRulesResponse> {
+	Json(state.config.current().as_ref().into())
+}
+
+/// PUT /api/v1/admin/suffix-rules/{suffix} - Add or replace a suffix rule
+///
+/// Applies to jobs and lookups started afterwards and is written to the config file.
+#[utoipa::path(
+	put,
+	path = "/api/v1/admin/suffix-rules/{suffix}",
+	tag = TAG_ADMIN,
+	params(("suffix" = String, Path, description = "Canonical email suffix, e.g. `gmail.com`")),
+	request_body = SuffixRuleRequest,
+	responses(
+		(status = 200, description = "Rule saved", body = SuffixRulesResponse),
+		(status = 400, description = "Invalid domain or no alternates", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:admin"]))
+)]
+async fn put_suffix_rule(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Path(suffix): Path<String>,
+	Json(req): Json<SuffixRuleRequest>,
+) -> Result<Json<SuffixRulesResponse>, ServerError> {
+	let canonical = normalize_suffix(&suffix);
+	let mut alternates: Vec<String> = Vec::new();
+	for alternate in req.alternates.iter().map(|a| normalize_suffix(a)) {
+		if alternate != canonical && !alternates.contains(&alternate) {
+			alternates.push(alternate);
+		}
+	}
+	if alternates.is_empty() {
+		return Err(ServerError::BadRequest(
+			"alternates must list at least one other suffix".to_string(),
+		));
+	}
+
+	let rule = (canonical.clone(), alternates.clone());
+	let (_, config) = update_config(&state, move |config| {
+		config.add_suffix_rule(rule.0, rule.1);
+		Ok(())
+	})
+	.await?;
+
+	info!(
+		"{} set suffix rule {} -> {}",
+		principal.subject,
+		canonical,
+		alternates.join(", ")
+	);
+
+	Ok(Json(config.as_ref().into()))
+}
+
+/// DELETE /api/v1/admin/suffix-rules/{suffix} - Remove a suffix rule
+#[utoipa::path(
+	delete,
+	path = "/api/v1/admin/suffix-rules/{suffix}",
+	tag = TAG_ADMIN,
+	params(("suffix" = String, Path, description = "Canonical email suffix")),
+	responses(
+		(status = 200, description = "Rule removed", body = SuffixRulesResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No rule for the suffix", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:admin"]))
+)]
+async fn delete_suffix_rule(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Path(suffix): Path<String>,
+) -> Result<Json<SuffixRulesResponse>, ServerError> {
+	let canonical = normalize_suffix(&suffix);
+	if !state.config.current().has_suffix_alternates(&canonical) {
+		return Err(ServerError::EntryNotFound(format!(
+			"No suffix rule for {}",
+			canonical
+		)));
+	}
+
+	let key = canonical.clone();
+	let (_, config) = update_config(&state, move |config| {
+		config.remove_suffix_rule(&key);
+		Ok(())
+	})
+	.await?;
+
+	info!("{} removed suffix rule {}", principal.subject, canonical);
+
+	Ok(Json(config.as_ref().into()))
+}
+
+/// GET /api/v1/admin/custom-passwords - List custom passwords by SHA-256
+#[utoipa::path(
+	get,
+	path = "/api/v1/admin/custom-passwords",
+	tag = TAG_ADMIN,
+	responses(
+		(status = 200, description = "Custom password hashes", body = CustomPasswordsResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:admin"]))
+)]
+async fn list_custom_passwords(
+	State(state): State<Arc<AppState>>,
+) -> Json<CustomPasswordsResponse> {
+	Json(state.config.current().as_ref().into())
+}
+
+/// POST /api/v1/admin/custom-passwords - Add a custom password
+///
+/// Returns 201 when the password was added and 200 when it was already configured.
+#[utoipa::path(
+	post,
+	path = "/api/v1/admin/custom-passwords",
+	tag = TAG_ADMIN,
+	request_body = CustomPasswordRequest,
+	responses(
+		(status = 201, description = "Password added", body = CustomPasswordsResponse),
+		(status = 200, description = "Password already configured", body = CustomPasswordsResponse),
+		(status = 400, description = "Empty password", body = ServerError),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:admin"]))
+)]
+async fn add_custom_password(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Json(req): Json<CustomPasswordRequest>,
+) -> Result<(StatusCode, Json<CustomPasswordsResponse>), ServerError> {
+	if req.password.is_empty() {
+		return Err(ServerError::BadRequest(
+			"password must not be empty".to_string(),
+		));
+	}
+
+	let (added, config) = update_config(&state, move |config| {
+		if config.custom_passwords.passwords.contains(&req.password) {
+			return Ok(false);
+		}
+		config.add_custom_password(req.password);
+		Ok(true)
+	})
+	.await?;
+
+	if !added {
+		return Ok((StatusCode::OK, Json(config.as_ref().into())));
+	}
+
+	info!("{} added a custom password", principal.subject);
+
+	Ok((StatusCode::CREATED, Json(config.as_ref().into())))
+}
+
+/// DELETE /api/v1/admin/custom-passwords/{sha256} - Remove a custom password
+#[utoipa::path(
+	delete,
+	path = "/api/v1/admin/custom-passwords/{sha256}",
+	tag = TAG_ADMIN,
+	params(("sha256" = String, Path, description = "Hex SHA-256 of the password")),
+	responses(
+		(status = 200, description = "Password removed", body = CustomPasswordsResponse),
+		(status = 401, description = "Missing or invalid bearer token", body = ServerError),
+		(status = 403, description = "Token lacks the required scope", body = ServerError),
+		(status = 404, description = "No custom password with this hash", body = ServerError),
+	),
+	security(("bearer" = ["dumptruck:admin"]))
+)]
+async fn delete_custom_password(
+	State(state): State<Arc<AppState>>,
+	Extension(principal): Extension<Principal>,
+	Path(sha256): Path<String>,
+) -> Result<Json<CustomPasswordsResponse>, ServerError> {
+	let sha256 = sha256.to_lowercase();
+	let password = state
+		.config
+		.current()
+		.custom_passwords
+		.passwords
+		.iter()
+		.find(|password| hash_utils::sha256_hex(password) == sha256)
+		.cloned()
+		.ok_or_else(|| {
+			ServerError::EntryNotFound(format!("No custom password with SHA-256 {}", sha256))
+		})?;
+
+	let (_, config) = update_config(&state, move |config| {
+		Ok(config.remove_custom_password(&password))
+	})
+	.await?;
+
+	info!("{} removed custom password {}", principal.subject, sha256);
+
+	Ok(Json(config.as_ref().into()))
+}
+
+/// Routes that submit jobs and uploads, rate limited as [`RouteClass::Ingest`]
+fn ingest_routes() -> OpenApiRouter<Arc<AppState>> {
+	OpenApiRouter::new()
+		.routes(routes!(ingest_file))
+		.routes(routes!(ingest_file_upload))
+		.routes(routes!(create_upload))
+		.routes(routes!(upload_offset, upload_chunk, terminate_upload))
+		.routes(routes!(finalize_upload))
+}
+
+/// Routes that query the corpus, rate limited as [`RouteClass::Lookup`]
+fn lookup_routes() -> OpenApiRouter<Arc<AppState>> {
+	OpenApiRouter::new()
+		.routes(routes!(lookup_address))
+		.routes(routes!(lookup_credential))
+		.routes(routes!(credential_range))
+}
+
+/// Routes that view and change the running configuration, restricted to administrators
+fn admin_routes() -> OpenApiRouter<Arc<AppState>> {
+	OpenApiRouter::new()
+		.routes(routes!(get_config))
+		.routes(routes!(list_suffix_rules))
+		.routes(routes!(put_suffix_rule, delete_suffix_rule))
+		.routes(routes!(list_custom_passwords, add_custom_password))
+		.routes(routes!(delete_custom_password))
+}
+
+/// Routes without rate limits: probes, metrics and job tracking
+fn base_routes() -> OpenApiRouter<Arc<AppState>> {
+	OpenApiRouter::with_openapi(ApiDoc::openapi())
+		.routes(routes!(metrics_endpoint))
+		.routes(routes!(health))
+		.routes(routes!(ready))
+		.routes(routes!(openapi_json))
+		.routes(routes!(get_job_status))
+		.routes(routes!(list_jobs))
+		.routes(routes!(cancel_job))
+		.routes(routes!(get_job_result))
+		.routes(routes!(job_events))
+		.routes(routes!(all_job_events))
+}
+
+/// OpenAPI document of every route served by [`create_api_router`]
+pub fn openapi() -> utoipa::openapi::OpenApi {
+	base_routes()
+		.merge(ingest_routes())
+		.merge(lookup_routes())
+		.merge(admin_routes())
+		.into_openapi()
+}
+
+/// Create the API router
+///
+/// Routes:
+/// - GET /metrics - Prometheus metrics
+/// - GET /api/v1/health - Health check (liveness)
+/// - GET /api/v1/ready - Readiness of the database, working directory and services
+/// - GET /api/v1/openapi.json - OpenAPI 3 description of these routes
+/// - POST /api/v1/ingest - JSON metadata-based ingest (for metadata-only submissions)
+/// - POST /api/v1/ingest/upload - Raw binary stream upload (supports arbitrarily large files)
+/// - POST /api/v1/uploads - Create a resumable upload
+/// - HEAD /api/v1/uploads/:upload_id - Get the current offset of a resumable upload
+/// - PATCH /api/v1/uploads/:upload_id - Append a chunk to a resumable upload
+/// - DELETE /api/v1/uploads/:upload_id - Abandon a resumable upload
+/// - POST /api/v1/uploads/:upload_id/finalize - Complete a resumable upload and queue a job
+/// - GET /api/v1/status/:job_id - Get job status
+/// - GET /api/v1/jobs - List all jobs
+/// - DELETE /api/v1/jobs/:job_id - Cancel a job
+/// - GET /api/v1/jobs/:job_id/result - Get a completed job's result and findings
+/// - GET /api/v1/jobs/:job_id/events - Stream a job's progress (Server-Sent Events)
+/// - GET /api/v1/events - Stream progress of all visible jobs (Server-Sent Events)
+/// - GET /api/v1/admin/config - Effective configuration with secrets redacted
+/// - GET /api/v1/admin/suffix-rules - List email suffix substitution rules
+/// - PUT/DELETE /api/v1/admin/suffix-rules/:suffix - Set or remove a suffix rule
+/// - GET/POST /api/v1/admin/custom-passwords - List or add custom passwords
+/// - DELETE /api/v1/admin/custom-passwords/:sha256 - Remove a custom password
+/// - POST /api/v1/lookup/address - Look up an address in the corpus
+/// - POST /api/v1/lookup/credential - Look up a credential in the corpus
+/// - GET /api/v1/range/:prefix - k-anonymity range search of credential hashes
+///
+/// The upload endpoint (/api/v1/ingest/upload) streams the body to the working
+/// directory, so file size is bounded only by `server.max_upload_bytes` and disk space.
+///
+/// The ingest, upload and lookup routes are rate limited per principal according
+/// to `server.rate_limits`.
+pub fn create_api_router(state: Arc<AppState>) -> Router {
+	let ingest_routes = ingest_routes().route_layer(middleware::from_fn_with_state(
+		(state.clone(), RouteClass::Ingest),
+		enforce_limits,
+	));
+
+	let lookup_routes = lookup_routes().route_layer(middleware::from_fn_with_state(
+		(state.clone(), RouteClass::Lookup),
+		enforce_limits,
+	));
+
+	let admin_routes =
+		admin_routes().route_layer(middleware::from_fn_with_state(state.clone(), require_admin));
+
+	let (router, _) = base_routes()
+		.merge(ingest_routes)
+		.merge(lookup_routes)
+		.merge(admin_routes)
+		.split_for_parts();
+
+	router
+		.layer(middleware::from_fn(record_request_metrics))
+		.layer(TraceLayer::new_for_http())
+		.with_state(state)
+}
+
+/// Create the application (alias for create_api_router)
+pub fn create_app(state: Arc<AppState>) -> Router {
+	create_api_router(state)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::core::config::{REDACTED, RateLimitConfig, RateLimitsConfig};
+	use axum::http::Request;
+	use tower::ServiceExt;
+
+	const TEST_SIGNING_SECRET: &[u8] = b"dumptruck-test-signing-secret";
+
+	/// OAuth provider that trusts HS256 tokens signed with the test secret
+	fn test_oauth_provider() -> OAuthProvider {
+		let secret = openssl::base64::encode_block(TEST_SIGNING_SECRET)
+			.trim_end_matches('=')
+			.replace('+', "-")
+			.replace('/', "_");
+		let jwks = serde_json::from_value(serde_json::json!({
+			"keys": [{ "kty": "oct", "kid": "test", "alg": "HS256", "k": secret }]
+		}))
+		.expect("jwks");
+
+		OAuthProvider::new(
+			"client".to_string(),
+			"secret".to_string(),
+			"https://localhost/token".to_string(),
+			"read".to_string(),
+		)
+		.with_validation(crate::network::oauth::TokenValidation {
+			issuer: Some("https://idp.test".to_string()),
+			audience: Some("dumptruck".to_string()),
+			..Default::default()
+		})
+		.with_jwks(jwks)
+	}
+
+	/// Sign a test access token for `subject` with the given space-separated scopes
+	pub(crate) fn test_token(subject: &str, scope: &str) -> String {
+		let mut header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::HS256);
+		header.kid = Some("test".to_string());
+		let claims = serde_json::json!({
+			"sub": subject,
+			"iss": "https://idp.test",
+			"aud": "dumptruck",
+			"exp": chrono::Utc::now().timestamp() + 300,
+			"scope": scope,
+		});
+		jsonwebtoken::encode(
+			&header,
+			&claims,
+			&jsonwebtoken::EncodingKey::from_secret(TEST_SIGNING_SECRET),
+		)
+		.expect("token")
+	}
+
+	fn bearer() -> String {
+		format!(
+			"Bearer {}",
+			test_token("analyst", "dumptruck:ingest dumptruck:read")
+		)
+	}
+
+	fn get_as(uri: &str, subject: &str, scope: &str) -> Request<Body> {
+		Request::builder()
+			.uri(uri)
+			.header(
+				"authorization",
+				format!("Bearer {}", test_token(subject, scope)),
+			)
+			.body(Body::empty())
+			.expect("request")
+	}
+
+	async fn json_body(response: Response) -> serde_json::Value {
+		let bytes = http_body_util::BodyExt::collect(response.into_body())
+			.await
+			.expect("body")
+			.to_bytes();
+		serde_json::from_slice(&bytes).expect("json")
+	}
+
+	/// Build application state backed by a temporary directory
+	pub(crate) fn test_state(dir: &std::path::Path) -> Arc<AppState> {
+		let working_copy_mgr =
+			WorkingCopyManager::new(&dir.join("work"), false, 0).expect("working dir");
+		let uploads =
+			ResumableUploads::new(working_copy_mgr.working_dir(), 1024 * 1024).expect("uploads");
+		Arc::new(AppState {
+			job_queue: Arc::new(JobQueue::new()),
+			oauth_provider: Arc::new(test_oauth_provider()),
+			working_copy_mgr: Arc::new(working_copy_mgr),
+			config: Arc::new(ConfigStore::new(Config::default(), None)),
+			database_path: dir.join("jobs.db").to_string_lossy().to_string(),
+			max_upload_bytes: 1024 * 1024,
+			uploads: Arc::new(uploads),
+			rate_limiter: Arc::new(RateLimiter::new(Default::default())),
+			verbose: 0,
+		})
+	}
+
+	fn upload_request(filename: &str, body: Vec<u8>) -> Request<Body> {
+		Request::builder()
+			.method("POST")
+			.uri(format!("/api/v1/ingest/upload?filename={}", filename))
+			.header("authorization", bearer())
+			.header("content-type", "application/octet-stream")
+			.body(Body::from(body))
+			.expect("request")
+	}
+
+	#[test]
+	fn test_ingest_response_serialization() {
+		let response = IngestResponse {
+			job_id: "job-123".to_string(),
+			status: "queued".to_string(),
+			created_at: chrono::Utc::now().to_rfc3339(),
+			file_size_bytes: None,
+			sha256: None,
+		};
+
+		let json = serde_json::to_string(&response).expect("serialization failed");
+		assert!(json.contains("job-123"));
+		assert!(json.contains("queued"));
+	}
+
+	#[test]
+	fn test_job_status_response_from_job() {
 		let job = Job::new("test.csv".to_string(), 1024);
 		let response: JobStatusResponse = job.into();
 
 		assert_eq!(response.filename, "test.csv");
 		assert_eq!(response.status, "queued");
 	}
+
+	#[tokio::test]
+	async fn test_upload_streams_body_to_working_dir() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		let body = b"email,password\nalice@example.com,hunter2\n".to_vec();
+
+		let response = create_app(state.clone())
+			.oneshot(upload_request("dump.csv", body.clone()))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+		let bytes = http_body_util::BodyExt::collect(response.into_body())
+			.await
+			.expect("body")
+			.to_bytes();
+		let json: serde_json::Value = serde_json::from_slice(&bytes).expect("json");
+		assert_eq!(json["file_size_bytes"], body.len() as u64);
+		assert_eq!(
+			json["sha256"],
+			crate::core::hash_utils::sha256_hex_bytes(&body)
+		);
+
+		let job = state
+			.job_queue
+			.get_job(json["job_id"].as_str().expect("job id"))
+			.await
+			.expect("job");
+		let stored = std::path::PathBuf::from(job.file_path.expect("file path"));
+		assert!(stored.starts_with(state.working_copy_mgr.working_dir()));
+		assert!(stored.to_string_lossy().ends_with("dump.csv"));
+		assert_eq!(std::fs::read(&stored).expect("stored file"), body);
+
+		// Cancelling the queued job removes the upload it owns
+		let response = create_app(state)
+			.oneshot(
+				Request::builder()
+					.method("DELETE")
+					.uri(format!("/api/v1/jobs/{}", job.id))
+					.header("authorization", bearer())
+					.body(Body::empty())
+					.expect("request"),
+			)
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert!(!stored.exists());
+	}
+
+	#[tokio::test]
+	async fn test_requests_without_valid_token_are_rejected() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let app = create_app(test_state(dir.path()));
+
+		for authorization in [None, Some("Bearer not-a-token".to_string())] {
+			let mut builder = Request::builder().uri("/api/v1/jobs");
+			if let Some(authorization) = authorization {
+				builder = builder.header("authorization", authorization);
+			}
+			let response = app
+				.clone()
+				.oneshot(builder.body(Body::empty()).expect("request"))
+				.await
+				.expect("response");
+
+			assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+			assert_eq!(response.headers()["www-authenticate"], "Bearer");
+		}
+
+		let response = app
+			.oneshot(
+				Request::builder()
+					.uri("/api/v1/jobs")
+					.header("authorization", bearer())
+					.body(Body::empty())
+					.expect("request"),
+			)
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+	}
+
+	#[tokio::test]
+	async fn test_routes_require_scopes() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let app = create_app(test_state(dir.path()));
+
+		let response = app
+			.clone()
+			.oneshot(get_as("/api/v1/jobs", "alice", "dumptruck:ingest"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+		let mut request = upload_request("dump.csv", b"email\n".to_vec());
+		request.headers_mut().insert(
+			"authorization",
+			format!("Bearer {}", test_token("alice", "dumptruck:read"))
+				.parse()
+				.expect("header"),
+		);
+		let response = app.oneshot(request).await.expect("response");
+		assert_eq!(response.status(), StatusCode::FORBIDDEN);
+	}
+
+	#[tokio::test]
+	async fn test_jobs_are_isolated_by_owner() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		let app = create_app(state.clone());
+		let job_id = state
+			.job_queue
+			.enqueue_job(Job::new("dump.csv".to_string(), 10).owned_by("alice"))
+			.await
+			.expect("enqueue");
+		let status_uri = format!("/api/v1/status/{}", job_id);
+
+		let response = app
+			.clone()
+			.oneshot(get_as(&status_uri, "alice", "dumptruck:read"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(json_body(response).await["owner"], "alice");
+
+		let response = app
+			.clone()
+			.oneshot(get_as(&status_uri, "bob", "dumptruck:read"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+		let response = app
+			.clone()
+			.oneshot(get_as("/api/v1/jobs", "bob", "dumptruck:read"))
+			.await
+			.expect("response");
+		assert_eq!(json_body(response).await["total"], 0);
+
+		let response = app
+			.clone()
+			.oneshot(get_as(
+				"/api/v1/jobs",
+				"root",
+				"dumptruck:read dumptruck:admin",
+			))
+			.await
+			.expect("response");
+		assert_eq!(json_body(response).await["total"], 1);
+
+		// Only the owner (with ingest scope) or an admin may cancel
+		let mut request = get_as(
+			&format!("/api/v1/jobs/{}", job_id),
+			"bob",
+			"dumptruck:ingest",
+		);
+		*request.method_mut() = axum::http::Method::DELETE;
+		let response = app.clone().oneshot(request).await.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+		let mut request = get_as(
+			&format!("/api/v1/jobs/{}", job_id),
+			"root",
+			"dumptruck:admin",
+		);
+		*request.method_mut() = axum::http::Method::DELETE;
+		let response = app.oneshot(request).await.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(json_body(response).await["status"], "cancelled");
+	}
+
+	#[tokio::test]
+	async fn test_ingest_callback_url_requires_signing() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let ingest = |state: Arc<AppState>, callback_url: &str| {
+			create_app(state).oneshot(
+				Request::builder()
+					.method("POST")
+					.uri("/api/v1/ingest")
+					.header("authorization", bearer())
+					.header("content-type", "application/json")
+					.body(Body::from(
+						serde_json::json!({
+							"filename": "dump.csv",
+							"callback_url": callback_url,
+						})
+						.to_string(),
+					))
+					.expect("request"),
+			)
+		};
+
+		let response = ingest(test_state(dir.path()), "https://soar.example/hook")
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+		let Ok(mut state) = Arc::try_unwrap(test_state(dir.path())) else {
+			unreachable!("state is not shared yet");
+		};
+		let imports = dir.path().join("imports");
+		std::fs::create_dir_all(&imports).expect("import dir");
+		std::fs::write(imports.join("dump.csv"), "email\nalice@example.com\n").expect("write");
+		let mut config = Config::default();
+		config.server.webhooks.hmac_secret = "topsecret".to_string();
+		config.server.import_dir = Some(imports.to_string_lossy().into_owned());
+		state.config = Arc::new(ConfigStore::new(config, None));
+		let state = Arc::new(state);
+
+		let response = ingest(state.clone(), "http://soar.example/hook")
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+		let response = ingest(state.clone(), "https://soar.example/hook")
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+		let job_id = json_body(response).await["job_id"]
+			.as_str()
+			.expect("job id")
+			.to_string();
+
+		let response = create_app(state)
+			.oneshot(get_as(
+				&format!("/api/v1/status/{}", job_id),
+				"analyst",
+				"dumptruck:read",
+			))
+			.await
+			.expect("response");
+		let status = json_body(response).await;
+		assert_eq!(status["callback_url"], "https://soar.example/hook");
+		assert!(status.get("callback_deliveries").is_none());
+	}
+
+	#[tokio::test]
+	async fn test_only_admins_may_raise_priority() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		let upload = |scope: &str, priority: i32| {
+			Request::builder()
+				.method("POST")
+				.uri(format!(
+					"/api/v1/ingest/upload?filename=dump.csv&priority={}",
+					priority
+				))
+				.header(
+					"authorization",
+					format!("Bearer {}", test_token("analyst", scope)),
+				)
+				.header("content-type", "application/octet-stream")
+				.body(Body::from("email\nalice@example.com\n"))
+				.expect("request")
+		};
+		let create = |scope: &str, priority: i32| {
+			Request::builder()
+				.method("POST")
+				.uri("/api/v1/uploads")
+				.header(
+					"authorization",
+					format!("Bearer {}", test_token("analyst", scope)),
+				)
+				.header("content-type", "application/json")
+				.body(Body::from(
+					serde_json::json!({
+						"filename": "dump.csv",
+						"upload_length": 10,
+						"priority": priority,
+					})
+					.to_string(),
+				))
+				.expect("request")
+		};
+
+		let app = create_app(state.clone());
+		for request in [
+			upload("dumptruck:ingest", i32::MAX),
+			create("dumptruck:ingest", i32::MAX),
+		] {
+			let response = app.clone().oneshot(request).await.expect("response");
+			assert_eq!(response.status(), StatusCode::FORBIDDEN);
+		}
+		assert_eq!(
+			state
+				.job_queue
+				.list_jobs(None, 0, 10)
+				.await
+				.expect("jobs")
+				.1,
+			0
+		);
+
+		// Lowering a job's priority needs no extra scope
+		let response = app
+			.clone()
+			.oneshot(upload("dumptruck:ingest", -5))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+		let response = app
+			.clone()
+			.oneshot(create("dumptruck:ingest", -5))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::CREATED);
+
+		let response = app
+			.clone()
+			.oneshot(upload("dumptruck:ingest dumptruck:admin", i32::MAX))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+		let job_id = json_body(response).await["job_id"]
+			.as_str()
+			.expect("job id")
+			.to_string();
+		let job = state.job_queue.get_job(&job_id).await.expect("job");
+		assert_eq!(job.priority, i32::MAX);
+	}
+
+	#[tokio::test]
+	async fn test_ingest_by_path_is_confined_to_import_dir() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let ingest = |state: Arc<AppState>, filename: &str| {
+			create_app(state).oneshot(
+				Request::builder()
+					.method("POST")
+					.uri("/api/v1/ingest")
+					.header("authorization", bearer())
+					.header("content-type", "application/json")
+					.body(Body::from(
+						serde_json::json!({"filename": filename}).to_string(),
+					))
+					.expect("request"),
+			)
+		};
+
+		let outside = dir.path().join("secret.csv");
+		std::fs::write(&outside, "email\nalice@example.com\n").expect("write");
+
+		// Without an import directory, nothing may be queued by path
+		let response = ingest(test_state(dir.path()), "secret.csv")
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+		let imports = dir.path().join("imports");
+		std::fs::create_dir_all(&imports).expect("import dir");
+		std::fs::write(imports.join("dump.csv"), "email\nbob@example.com\n").expect("write");
+		let Ok(mut state) = Arc::try_unwrap(test_state(dir.path())) else {
+			unreachable!("state is not shared yet");
+		};
+		let mut config = Config::default();
+		config.server.import_dir = Some(imports.to_string_lossy().into_owned());
+		state.config = Arc::new(ConfigStore::new(config, None));
+		let state = Arc::new(state);
+
+		for filename in [
+			"../secret.csv",
+			"./../secret.csv",
+			outside.to_str().expect("utf-8 path"),
+			"missing.csv",
+		] {
+			let response = ingest(state.clone(), filename).await.expect("response");
+			assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", filename);
+		}
+		#[cfg(unix)]
+		{
+			std::os::unix::fs::symlink(&outside, imports.join("link.csv")).expect("symlink");
+			let response = ingest(state.clone(), "link.csv").await.expect("response");
+			assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		}
+		assert_eq!(
+			state
+				.job_queue
+				.list_jobs(None, 0, 10)
+				.await
+				.expect("jobs")
+				.1,
+			0
+		);
+
+		// A size the client gives must match the file
+		let sized = |size: u64| {
+			Request::builder()
+				.method("POST")
+				.uri("/api/v1/ingest")
+				.header("authorization", bearer())
+				.header("content-type", "application/json")
+				.body(Body::from(
+					serde_json::json!({"filename": "dump.csv", "file_size_bytes": size})
+						.to_string(),
+				))
+				.expect("request")
+		};
+		let response = create_app(state.clone())
+			.oneshot(sized(10))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		let response = create_app(state.clone())
+			.oneshot(sized(22))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+		let response = ingest(state.clone(), "dump.csv").await.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+		let job_id = json_body(response).await["job_id"]
+			.as_str()
+			.expect("job id")
+			.to_string();
+		let job = state.job_queue.get_job(&job_id).await.expect("job");
+		assert_eq!(
+			job.source_path().map(std::path::PathBuf::from),
+			Some(imports.join("dump.csv").canonicalize().expect("canonical"))
+		);
+		assert_eq!(job.file_size_bytes, 22);
+	}
+
+	#[tokio::test]
+	async fn test_upload_rejects_oversized_body() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+
+		let response = create_app(state.clone())
+			.oneshot(upload_request("big.csv", vec![b'a'; 2 * 1024 * 1024]))
+			.await
+			.expect("response");
+
+		assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+		let (jobs, total) = state
+			.job_queue
+			.list_jobs(None, 0, 10)
+			.await
+			.expect("list jobs");
+		assert!(jobs.is_empty());
+		assert_eq!(total, 0);
+	}
+
+	fn resumable_request(
+		method: &str,
+		uri: &str,
+		offset: Option<u64>,
+		body: Body,
+	) -> Request<Body> {
+		let mut builder = Request::builder()
+			.method(method)
+			.uri(uri)
+			.header("authorization", bearer());
+		if let Some(offset) = offset {
+			builder = builder
+				.header(UPLOAD_OFFSET, offset.to_string())
+				.header("content-type", "application/offset+octet-stream");
+		}
+		builder.body(body).expect("request")
+	}
+
+	#[tokio::test]
+	async fn test_resumable_upload_flow() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		let app = create_app(state.clone());
+		let data = b"email,password\nalice@example.com,hunter2\n".to_vec();
+
+		let response = app
+			.clone()
+			.oneshot(
+				Request::builder()
+					.method("POST")
+					.uri("/api/v1/uploads")
+					.header("authorization", bearer())
+					.header("content-type", "application/json")
+					.body(Body::from(
+						serde_json::json!({ "filename": "dump.csv", "upload_length": data.len() })
+							.to_string(),
+					))
+					.expect("request"),
+			)
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::CREATED);
+		let location = response.headers()["location"]
+			.to_str()
+			.expect("location")
+			.to_string();
+
+		let (first, rest) = data.split_at(10);
+		let response = app
+			.clone()
+			.oneshot(resumable_request(
+				"PATCH",
+				&location,
+				Some(0),
+				Body::from(first.to_vec()),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NO_CONTENT);
+		assert_eq!(response.headers()[UPLOAD_OFFSET], "10");
+
+		// Finalizing early is rejected and a stale offset conflicts
+		let finalize_uri = format!("{}/finalize", location);
+		let response = app
+			.clone()
+			.oneshot(resumable_request(
+				"POST",
+				&finalize_uri,
+				None,
+				Body::empty(),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::CONFLICT);
+		let response = app
+			.clone()
+			.oneshot(resumable_request(
+				"PATCH",
+				&location,
+				Some(0),
+				Body::from(rest.to_vec()),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::CONFLICT);
+
+		let response = app
+			.clone()
+			.oneshot(resumable_request("HEAD", &location, None, Body::empty()))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(response.headers()[UPLOAD_OFFSET], "10");
+
+		let response = app
+			.clone()
+			.oneshot(resumable_request(
+				"PATCH",
+				&location,
+				Some(10),
+				Body::from(rest.to_vec()),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+		let response = app
+			.clone()
+			.oneshot(resumable_request(
+				"POST",
+				&finalize_uri,
+				None,
+				Body::empty(),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+		let bytes = http_body_util::BodyExt::collect(response.into_body())
+			.await
+			.expect("body")
+			.to_bytes();
+		let json: serde_json::Value = serde_json::from_slice(&bytes).expect("json");
+		assert_eq!(
+			json["sha256"],
+			crate::core::hash_utils::sha256_hex_bytes(&data)
+		);
+
+		let job = state
+			.job_queue
+			.get_job(json["job_id"].as_str().expect("job id"))
+			.await
+			.expect("job");
+		let file_id = job.file_id.expect("file id");
+		assert_eq!(
+			std::fs::read(job.file_path.expect("file path")).expect("stored file"),
+			data
+		);
+
+		let conn = rusqlite::Connection::open(&state.database_path).expect("open db");
+		let recorded: String = conn
+			.query_row(
+				"SELECT sha256_hash FROM file_metadata WHERE file_id = ?1",
+				[&file_id],
+				|r| r.get(0),
+			)
+			.expect("file metadata");
+		assert_eq!(recorded, crate::core::hash_utils::sha256_hex_bytes(&data));
+
+		let response = app
+			.oneshot(resumable_request("HEAD", &location, None, Body::empty()))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	}
+
+	/// Create a resumable upload as the test analyst and send it all of `data`
+	async fn uploaded(app: &Router, data: &[u8]) -> String {
+		let response = app
+			.clone()
+			.oneshot(
+				Request::builder()
+					.method("POST")
+					.uri("/api/v1/uploads")
+					.header("authorization", bearer())
+					.header("content-type", "application/json")
+					.body(Body::from(
+						serde_json::json!({ "filename": "dump.csv", "upload_length": data.len() })
+							.to_string(),
+					))
+					.expect("request"),
+			)
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::CREATED);
+		let location = response.headers()["location"]
+			.to_str()
+			.expect("location")
+			.to_string();
+		let response = app
+			.clone()
+			.oneshot(resumable_request(
+				"PATCH",
+				&location,
+				Some(0),
+				Body::from(data.to_vec()),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NO_CONTENT);
+		location
+	}
+
+	#[tokio::test]
+	async fn test_resumable_upload_can_be_terminated() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let app = create_app(test_state(dir.path()));
+		let location = uploaded(&app, b"email\nalice@example.com\n").await;
+
+		// Only the owner (or an admin) may terminate the upload
+		let response = app
+			.clone()
+			.oneshot(
+				Request::builder()
+					.method("DELETE")
+					.uri(&location)
+					.header(
+						"authorization",
+						format!("Bearer {}", test_token("mallory", "dumptruck:ingest")),
+					)
+					.body(Body::empty())
+					.expect("request"),
+			)
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+		let response = app
+			.clone()
+			.oneshot(resumable_request("DELETE", &location, None, Body::empty()))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NO_CONTENT);
+		let response = app
+			.clone()
+			.oneshot(resumable_request("HEAD", &location, None, Body::empty()))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+		let leftovers = std::fs::read_dir(dir.path().join("work").join("uploads"))
+			.expect("upload dir")
+			.count();
+		assert_eq!(leftovers, 0);
+	}
+
+	#[tokio::test]
+	async fn test_failed_finalize_removes_assembled_file() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let Ok(mut state) = Arc::try_unwrap(test_state(dir.path())) else {
+			unreachable!("state is not shared yet");
+		};
+		// A directory cannot be opened as the database, so recording evidence fails
+		state.database_path = dir.path().to_string_lossy().into_owned();
+		let state = Arc::new(state);
+		let app = create_app(state.clone());
+		let location = uploaded(&app, b"email\nalice@example.com\n").await;
+
+		let response = app
+			.oneshot(resumable_request(
+				"POST",
+				&format!("{}/finalize", location),
+				None,
+				Body::empty(),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+		let work_dir = state.working_copy_mgr.working_dir();
+		let assembled = std::fs::read_dir(work_dir)
+			.expect("working dir")
+			.filter_map(Result::ok)
+			.filter(|entry| entry.file_name().to_string_lossy().starts_with("upload_"))
+			.count();
+		assert_eq!(assembled, 0);
+		assert_eq!(
+			state
+				.job_queue
+				.list_jobs(None, 0, 10)
+				.await
+				.expect("jobs")
+				.1,
+			0
+		);
+	}
+
+	#[tokio::test]
+	async fn test_job_events_stream_until_finished() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		let job_id = state
+			.job_queue
+			.enqueue_job(Job::new("dump.csv".to_string(), 10).owned_by("analyst"))
+			.await
+			.expect("enqueue");
+
+		let response = create_app(state.clone())
+			.oneshot(get_as(
+				&format!("/api/v1/jobs/{}/events", job_id),
+				"analyst",
+				"dumptruck:read",
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+		let queue = state.job_queue.clone();
+		queue
+			.update_job(&job_id, |j| j.start_processing())
+			.await
+			.expect("start");
+		queue
+			.update_job(&job_id, |j| {
+				j.update_progress(1, 2);
+				Ok(())
+			})
+			.await
+			.expect("progress");
+		queue
+			.update_job(&job_id, |j| {
+				j.complete_with_result(IngestResult {
+					rows_processed: 2,
+					unique_addresses: 2,
+					hashed_credentials_detected: 0,
+					weak_passwords_found: 1,
+					breached_addresses: 0,
+					pii_summary: None,
+					detailed_findings: Vec::new(),
+					rows_with_findings: 0,
+					metadata: Vec::new(),
+					errors: Vec::new(),
+				})
+			})
+			.await
+			.expect("complete");
+
+		// The stream ends on its own after the final event
+		let bytes = tokio::time::timeout(
+			std::time::Duration::from_secs(5),
+			http_body_util::BodyExt::collect(response.into_body()),
+		)
+		.await
+		.expect("stream ended")
+		.expect("body")
+		.to_bytes();
+		let body = String::from_utf8(bytes.to_vec()).expect("utf8");
+
+		assert_eq!(body.matches("event: job").count(), 3);
+		assert!(body.contains("\"progress_percentage\":50"));
+		assert!(body.contains("event: finished"));
+		assert!(body.contains("\"weak_passwords_found\":1"));
+	}
+
+	#[tokio::test]
+	async fn test_all_job_events_only_include_visible_jobs() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+
+		let response = create_app(state.clone())
+			.oneshot(get_as("/api/v1/events", "bob", "dumptruck:read"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+
+		state
+			.job_queue
+			.enqueue_job(Job::new("alice.csv".to_string(), 10).owned_by("alice"))
+			.await
+			.expect("enqueue");
+		let bob_job = state
+			.job_queue
+			.enqueue_job(Job::new("bob.csv".to_string(), 10).owned_by("bob"))
+			.await
+			.expect("enqueue");
+
+		let mut body = response.into_body();
+		let frame = tokio::time::timeout(
+			std::time::Duration::from_secs(5),
+			http_body_util::BodyExt::frame(&mut body),
+		)
+		.await
+		.expect("event")
+		.expect("frame")
+		.expect("body");
+		let event = String::from_utf8(frame.into_data().expect("data").to_vec()).expect("utf8");
+
+		assert!(event.contains(&bob_job));
+		assert!(!event.contains("alice.csv"));
+	}
+
+	#[test]
+	fn test_negotiate_result_format() {
+		let content_type = |accept| negotiate_result_format(accept).map(|(_, ct)| ct);
+
+		assert_eq!(content_type(None), Some("application/json"));
+		assert_eq!(content_type(Some("*/*")), Some("application/json"));
+		assert_eq!(
+			content_type(Some("text/csv")),
+			Some("text/csv; charset=utf-8")
+		);
+		assert_eq!(
+			content_type(Some("application/json;q=0.5, application/x-ndjson")),
+			Some("application/x-ndjson")
+		);
+		assert_eq!(
+			content_type(Some("text/plain;q=0, text/*")),
+			Some("text/plain; charset=utf-8")
+		);
+		assert_eq!(content_type(Some("image/png")), None);
+	}
+
+	#[tokio::test]
+	async fn test_job_result_is_paginated_and_negotiated() {
+		use crate::api::output::{DetailedRowFinding, Detection};
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		let app = create_app(state.clone());
+		let job_id = state
+			.job_queue
+			.enqueue_job(Job::new("dump.csv".to_string(), 10).owned_by("analyst"))
+			.await
+			.expect("enqueue");
+		let result_uri = format!("/api/v1/jobs/{}/result", job_id);
+
+		// No result until the job completes
+		let response = app
+			.clone()
+			.oneshot(get_as(&result_uri, "analyst", "dumptruck:read"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::CONFLICT);
+
+		let findings = (1..=3)
+			.map(|row_number| DetailedRowFinding {
+				row_number,
+				detections: vec![Detection {
+					column: Some("email".to_string()),
+					value: format!("user{}@example.com", row_number),
+					detection_type: "email".to_string(),
+				}],
+			})
+			.collect();
+		state
+			.job_queue
+			.update_job(&job_id, |j| {
+				j.start_processing()?;
+				j.complete_with_result(IngestResult {
+					rows_processed: 3,
+					unique_addresses: 3,
+					hashed_credentials_detected: 0,
+					weak_passwords_found: 0,
+					breached_addresses: 0,
+					pii_summary: None,
+					detailed_findings: findings,
+					rows_with_findings: 3,
+					metadata: Vec::new(),
+					errors: Vec::new(),
+				})
+			})
+			.await
+			.expect("complete");
+
+		let response = app
+			.clone()
+			.oneshot(get_as(
+				&format!("{}?offset=1&limit=1", result_uri),
+				"analyst",
+				"dumptruck:read",
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(response.headers()["x-total-count"], "3");
+		let body = json_body(response).await;
+		assert_eq!(body["rows_processed"], 3);
+		assert_eq!(
+			body["detailed_findings"]
+				.as_array()
+				.expect("findings")
+				.len(),
+			1
+		);
+		assert_eq!(body["detailed_findings"][0]["row_number"], 2);
+
+		let mut request = get_as(&result_uri, "analyst", "dumptruck:read");
+		request
+			.headers_mut()
+			.insert("accept", "text/plain".parse().expect("header"));
+		let response = app.clone().oneshot(request).await.expect("response");
+		assert_eq!(
+			response.headers()["content-type"],
+			"text/plain; charset=utf-8"
+		);
+		let bytes = http_body_util::BodyExt::collect(response.into_body())
+			.await
+			.expect("body")
+			.to_bytes();
+		assert!(String::from_utf8_lossy(&bytes).contains("user3@example.com"));
+
+		let mut request = get_as(&result_uri, "analyst", "dumptruck:read");
+		request
+			.headers_mut()
+			.insert("accept", "application/xml".parse().expect("header"));
+		let response = app.clone().oneshot(request).await.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+		let response = app
+			.oneshot(get_as(&result_uri, "bob", "dumptruck:read"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn test_lookup_address_route() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let app = create_app(test_state(dir.path()));
+		let lookup = |body: &'static str| {
+			let mut request = get_as("/api/v1/lookup/address", "analyst", "dumptruck:read");
+			*request.method_mut() = axum::http::Method::POST;
+			request
+				.headers_mut()
+				.insert("content-type", "application/json".parse().expect("header"));
+			*request.body_mut() = Body::from(body);
+			request
+		};
+
+		let response = app
+			.clone()
+			.oneshot(lookup(r#"{"address": "nobody@example.com"}"#))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(json_body(response).await["found"], false);
+
+		let response = app
+			.oneshot(lookup(r#"{"address_hash": "abc"}"#))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+	}
+
+	#[tokio::test]
+	async fn test_credential_range_returns_matching_suffixes() {
+		use crate::core::hash_utils;
+
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		{
+			let mut storage = SqliteStorage::new(&state.database_path, None).expect("storage");
+			let header: Vec<String> = vec!["email".to_string(), "password".to_string()];
+			let rows = [
+				("alice@example.com", "Hunter2"),
+				("bob@example.com", "Hunter2"),
+				("carol@example.com", "hunter2"),
+			];
+			for (idx, (email, password)) in rows.iter().enumerate() {
+				crate::deploy::pipeline::store_normalized_row(
+					&mut storage,
+					&[email.to_string(), password.to_string()],
+					idx + 1,
+					Some(&header),
+					"file-1",
+					&state.config.current(),
+				)
+				.expect("store");
+			}
+		}
+		let app = create_app(state);
+
+		let range = |uri: String| {
+			let app = app.clone();
+			async move {
+				let response = app
+					.oneshot(get_as(&uri, "analyst", "dumptruck:read"))
+					.await
+					.expect("response");
+				let status = response.status();
+				let bytes = http_body_util::BodyExt::collect(response.into_body())
+					.await
+					.expect("body")
+					.to_bytes();
+				(status, String::from_utf8(bytes.to_vec()).expect("utf8"))
+			}
+		};
+
+		let sha1 = hash_utils::sha1_hex("Hunter2").to_uppercase();
+		let (status, body) = range(format!("/api/v1/range/{}", &sha1[..5].to_lowercase())).await;
+		assert_eq!(status, StatusCode::OK);
+		assert!(body.contains(&format!("{}:2\r\n", &sha1[5..])));
+
+		let ntlm = hash_utils::ntlm_hex("Hunter2").to_uppercase();
+		let (_, body) = range(format!("/api/v1/range/{}?mode=ntlm", &ntlm[..5])).await;
+		assert!(body.contains(&ntlm[5..]));
+
+		// Credentials that only differ in case are counted apart
+		let lower = hash_utils::sha1_hex("hunter2").to_uppercase();
+		let (_, body) = range(format!("/api/v1/range/{}", &lower[..5])).await;
+		assert!(body.contains(&format!("{}:1\r\n", &lower[5..])));
+
+		let unseen = hash_utils::sha1_hex("HUNTER2").to_uppercase();
+		let (_, body) = range(format!("/api/v1/range/{}", &unseen[..5])).await;
+		assert!(!body.contains(&unseen[5..]));
+
+		let (status, _) = range("/api/v1/range/12345678".to_string()).await;
+		assert_eq!(status, StatusCode::BAD_REQUEST);
+	}
+
+	/// Test state with the given limits for both route classes
+	fn limited_state(dir: &std::path::Path, limit: RateLimitConfig) -> Arc<AppState> {
+		let Ok(mut state) = Arc::try_unwrap(test_state(dir)) else {
+			unreachable!("state is not shared yet");
+		};
+		state.rate_limiter = Arc::new(RateLimiter::new(RateLimitsConfig {
+			ingest: limit.clone(),
+			lookup: limit,
+		}));
+		Arc::new(state)
+	}
+
+	#[tokio::test]
+	async fn test_rate_limit_returns_retry_after_per_principal() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let app = create_app(limited_state(
+			dir.path(),
+			RateLimitConfig {
+				requests_per_minute: 1,
+				burst: 2,
+				daily_bytes: 0,
+			},
+		));
+		let range = |subject: &str| get_as("/api/v1/range/ABCDE", subject, "dumptruck:read");
+
+		for _ in 0..2 {
+			let response = app.clone().oneshot(range("alice")).await.expect("response");
+			assert_eq!(response.status(), StatusCode::OK);
+		}
+		let response = app.clone().oneshot(range("alice")).await.expect("response");
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+		let retry_after: u64 = response.headers()[axum::http::header::RETRY_AFTER]
+			.to_str()
+			.expect("header")
+			.parse()
+			.expect("seconds");
+		assert!((1..=60).contains(&retry_after));
+
+		// Other principals have their own bucket; unlimited routes are unaffected
+		let response = app.clone().oneshot(range("bob")).await.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		let response = app
+			.oneshot(get_as("/api/v1/jobs", "alice", "dumptruck:read"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+	}
+
+	#[tokio::test]
+	async fn test_daily_byte_quota_rejects_once_exhausted() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = limited_state(
+			dir.path(),
+			RateLimitConfig {
+				requests_per_minute: 0,
+				burst: 0,
+				daily_bytes: 64,
+			},
+		);
+		let app = create_app(state.clone());
+		let body = b"email,password\nalice@example.com,hunter2\n".to_vec();
+
+		let response = app
+			.clone()
+			.oneshot(upload_request("dump.csv", body.clone()))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+		// An announced size beyond the rest of the quota is refused before the body is read
+		let mut request = upload_request("dump.csv", body.clone());
+		request.headers_mut().insert(
+			axum::http::header::CONTENT_LENGTH,
+			body.len().to_string().parse().expect("header"),
+		);
+		let response = app.clone().oneshot(request).await.expect("response");
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+		assert!(
+			response
+				.headers()
+				.contains_key(axum::http::header::RETRY_AFTER)
+		);
+
+		// Without a length, the body is cut off once it crosses the quota
+		let response = app
+			.clone()
+			.oneshot(upload_request("dump.csv", body.clone()))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+		let (jobs, total) = state.job_queue.list_jobs(None, 0, 10).await.expect("jobs");
+		assert_eq!(total, 1);
+		assert!(jobs[0].file_path.is_some());
+
+		let response = app
+			.oneshot(upload_request("dump.csv", body))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+	}
+
+	#[tokio::test]
+	async fn test_ingest_by_path_is_charged_to_byte_quota() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let imports = dir.path().join("imports");
+		std::fs::create_dir_all(&imports).expect("import dir");
+		std::fs::write(imports.join("big.csv"), vec![b'a'; 400]).expect("write");
+		std::fs::write(imports.join("small.csv"), vec![b'a'; 10]).expect("write");
+
+		let Ok(mut state) = Arc::try_unwrap(limited_state(
+			dir.path(),
+			RateLimitConfig {
+				requests_per_minute: 0,
+				burst: 0,
+				daily_bytes: 256,
+			},
+		)) else {
+			unreachable!("state is not shared yet");
+		};
+		let mut config = Config::default();
+		config.server.import_dir = Some(imports.to_string_lossy().into_owned());
+		state.config = Arc::new(ConfigStore::new(config, None));
+		let app = create_app(Arc::new(state));
+
+		let ingest = |filename: &str| {
+			Request::builder()
+				.method("POST")
+				.uri("/api/v1/ingest")
+				.header("authorization", bearer())
+				.header("content-type", "application/json")
+				.body(Body::from(
+					serde_json::json!({"filename": filename}).to_string(),
+				))
+				.expect("request")
+		};
+
+		// The file is charged at its size on disk
+		let response = app
+			.clone()
+			.oneshot(ingest("big.csv"))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+		let response = app.oneshot(ingest("small.csv")).await.expect("response");
+		assert_eq!(response.status(), StatusCode::ACCEPTED);
+	}
+
+	#[tokio::test]
+	async fn test_metrics_report_routes_and_job_counts() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let state = test_state(dir.path());
+		state
+			.job_queue
+			.enqueue("queued.csv".to_string(), 1)
+			.await
+			.expect("enqueue");
+		let app = create_app(state);
+
+		let response = app
+			.clone()
+			.oneshot(get_as(
+				"/api/v1/status/secret-job-id",
+				"analyst",
+				"dumptruck:read",
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+		let response = app
+			.oneshot(
+				Request::builder()
+					.uri("/metrics")
+					.body(Body::empty())
+					.expect("request"),
+			)
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert!(
+			response.headers()[axum::http::header::CONTENT_TYPE]
+				.to_str()
+				.expect("content type")
+				.starts_with("text/plain")
+		);
+		let bytes = http_body_util::BodyExt::collect(response.into_body())
+			.await
+			.expect("body")
+			.to_bytes();
+		let text = String::from_utf8(bytes.to_vec()).expect("utf-8");
+
+		assert!(text.contains("route=\"/api/v1/status/{job_id}\",status=\"404\""));
+		assert!(!text.contains("secret-job-id"));
+		assert!(text.contains("dumptruck_jobs{status=\"dead_lettered\"}"));
+		assert!(text.contains("dumptruck_workers_busy"));
+	}
+
+	#[tokio::test]
+	async fn test_ready_reports_components() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let ready = |min_free_bytes: u64| {
+			let Ok(mut state) = Arc::try_unwrap(test_state(dir.path())) else {
+				unreachable!("state is not shared yet");
+			};
+			let mut config = Config::default();
+			config.working_directory.min_free_bytes = min_free_bytes;
+			state.config = Arc::new(ConfigStore::new(config, None));
+			create_app(Arc::new(state)).oneshot(
+				Request::builder()
+					.uri("/api/v1/ready")
+					.body(Body::empty())
+					.expect("request"),
+			)
+		};
+
+		let response = ready(0).await.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		let report = json_body(response).await;
+		assert_ne!(report["status"], "failed");
+		let components = report["components"].as_array().expect("components");
+		for name in ["database", "working_directory", "noexec"] {
+			let check = components
+				.iter()
+				.find(|c| c["name"] == name)
+				.expect("component listed");
+			assert_eq!(check["status"], "ok", "{} should be ok", name);
+		}
+		assert!(components.iter().any(|c| c["name"] == "rainbow_table"));
+		assert!(!components.iter().any(|c| c["name"] == "hibp"));
+
+		let response = ready(u64::MAX).await.expect("response");
+		assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+		let report = json_body(response).await;
+		assert_eq!(report["status"], "failed");
+	}
+
+	#[tokio::test]
+	async fn test_openapi_document_is_served() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let response = create_app(test_state(dir.path()))
+			.oneshot(
+				Request::builder()
+					.uri("/api/v1/openapi.json")
+					.body(Body::empty())
+					.expect("request"),
+			)
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		let spec = json_body(response).await;
+
+		assert!(spec["openapi"].as_str().expect("version").starts_with("3."));
+		for path in [
+			"/api/v1/ingest",
+			"/api/v1/ingest/upload",
+			"/api/v1/uploads/{upload_id}",
+			"/api/v1/status/{job_id}",
+			"/api/v1/jobs",
+			"/api/v1/lookup/credential",
+			"/api/v1/range/{prefix}",
+			"/api/v1/openapi.json",
+		] {
+			assert!(spec["paths"][path].is_object(), "{} documented", path);
+		}
+		assert!(spec["paths"]["/api/v1/uploads/{upload_id}"]["head"].is_object());
+		assert!(spec["paths"]["/api/v1/uploads/{upload_id}"]["patch"].is_object());
+		assert!(spec["components"]["securitySchemes"]["bearer"].is_object());
+		for schema in [
+			"IngestRequest",
+			"IngestResponse",
+			"JobStatusResponse",
+			"ListJobsResponse",
+			"ServerError",
+			"IngestResult",
+			"ReadinessReport",
+		] {
+			assert!(
+				spec["components"]["schemas"][schema].is_object(),
+				"{} schema present",
+				schema
+			);
+		}
+	}
+
+	fn admin_request(method: &str, uri: &str, body: Option<serde_json::Value>) -> Request<Body> {
+		let builder = Request::builder().method(method).uri(uri).header(
+			"authorization",
+			format!("Bearer {}", test_token("root", "dumptruck:admin")),
+		);
+		match body {
+			Some(body) => builder
+				.header("content-type", "application/json")
+				.body(Body::from(body.to_string())),
+			None => builder.body(Body::empty()),
+		}
+		.expect("request")
+	}
+
+	#[tokio::test]
+	async fn test_admin_config_is_redacted_and_restricted() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let Ok(mut state) = Arc::try_unwrap(test_state(dir.path())) else {
+			unreachable!("state is not shared yet");
+		};
+		let mut config = Config::default();
+		config.oauth.client_secret = "oauth-secret".to_string();
+		config.server.webhooks.hmac_secret = "hook-secret".to_string();
+		config.add_custom_password("Autumn2024!".to_string());
+		state.config = Arc::new(ConfigStore::new(config, None));
+		let app = create_app(Arc::new(state));
+
+		let response = app
+			.clone()
+			.oneshot(get_as(
+				"/api/v1/admin/config",
+				"analyst",
+				"dumptruck:ingest dumptruck:read",
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+		let response = app
+			.oneshot(admin_request("GET", "/api/v1/admin/config", None))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		let body = json_body(response).await;
+		assert_eq!(body["oauth"]["client_secret"], REDACTED);
+		assert_eq!(body["server"]["webhooks"]["hmac_secret"], REDACTED);
+		assert!(!body.to_string().contains("Autumn2024!"));
+	}
+
+	#[tokio::test]
+	async fn test_admin_changes_apply_and_persist() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let config_path = dir.path().join("config.json");
+		let Ok(mut state) = Arc::try_unwrap(test_state(dir.path())) else {
+			unreachable!("state is not shared yet");
+		};
+		state.config = Arc::new(ConfigStore::new(
+			Config::default(),
+			Some(config_path.clone()),
+		));
+		let state = Arc::new(state);
+		let app = create_app(state.clone());
+
+		let response = app
+			.clone()
+			.oneshot(admin_request(
+				"PUT",
+				"/api/v1/admin/suffix-rules/Gmail.com",
+				Some(serde_json::json!({"alternates": ["googlemail.com", "gmail.com"]})),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(
+			json_body(response).await["rules"]["gmail.com"],
+			serde_json::json!(["googlemail.com"])
+		);
+		assert!(state.config.current().has_suffix_alternates("gmail.com"));
+
+		let response = app
+			.clone()
+			.oneshot(admin_request(
+				"PUT",
+				"/api/v1/admin/suffix-rules/example.com",
+				Some(serde_json::json!({"alternates": ["-bad.com"]})),
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+		let add = || {
+			admin_request(
+				"POST",
+				"/api/v1/admin/custom-passwords",
+				Some(serde_json::json!({"password": "Spring2024!"})),
+			)
+		};
+		let response = app.clone().oneshot(add()).await.expect("response");
+		assert_eq!(response.status(), StatusCode::CREATED);
+		let hash = hash_utils::sha256_hex("Spring2024!");
+		assert_eq!(
+			json_body(response).await["sha256"],
+			serde_json::json!([hash.clone()])
+		);
+		let response = app.clone().oneshot(add()).await.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let persisted = Config::from_file(&config_path).expect("persisted config");
+		assert_eq!(
+			persisted.get_suffix_alternates("gmail.com"),
+			vec!["googlemail.com"]
+		);
+		assert_eq!(persisted.custom_passwords.passwords, vec!["Spring2024!"]);
+
+		let uri = format!("/api/v1/admin/custom-passwords/{}", hash);
+		let response = app
+			.clone()
+			.oneshot(admin_request("DELETE", &uri, None))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		let response = app
+			.clone()
+			.oneshot(admin_request("DELETE", &uri, None))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+		let response = app
+			.clone()
+			.oneshot(admin_request(
+				"DELETE",
+				"/api/v1/admin/suffix-rules/gmail.com",
+				None,
+			))
+			.await
+			.expect("response");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert!(!state.config.current().has_suffix_alternates("gmail.com"));
+
+		let persisted = Config::from_file(&config_path).expect("persisted config");
+		assert!(persisted.all_suffix_rules().is_empty());
+		assert!(persisted.custom_passwords.passwords.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_openapi_operations_are_routed() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let app = create_app(test_state(dir.path()));
+		let spec = serde_json::to_value(openapi()).expect("spec");
+
+		let param = regex::Regex::new(r"\{[^}]+\}").expect("regex");
+		let mut operations = 0;
+		for (path, item) in spec["paths"].as_object().expect("paths") {
+			let uri = param.replace_all(path, "00000");
+			for (method, operation) in item.as_object().expect("path item") {
+				operations += 1;
+				let response = app
+					.clone()
+					.oneshot(
+						Request::builder()
+							.method(method.to_uppercase().as_str())
+							.uri(uri.as_ref())
+							.body(Body::empty())
+							.expect("request"),
+					)
+					.await
+					.expect("response");
+
+				let secured = operation["security"]
+					.as_array()
+					.is_some_and(|s| !s.is_empty());
+				if secured {
+					assert_eq!(
+						response.status(),
+						StatusCode::UNAUTHORIZED,
+						"{} {} requires a token",
+						method,
+						path
+					);
+				} else {
+					assert!(
+						response.status() != StatusCode::NOT_FOUND
+							&& response.status() != StatusCode::METHOD_NOT_ALLOWED,
+						"{} {} is routed",
+						method,
+						path
+					);
+				}
+			}
+		}
+		assert_eq!(operations, 27);
+	}
+
+	/// Check that a serialized value only has documented properties and has every required one
+	fn assert_matches_schema(spec: &serde_json::Value, name: &str, value: &serde_json::Value) {
+		let schema = &spec["components"]["schemas"][name];
+		let properties = schema["properties"].as_object().expect("properties");
+		let object = value.as_object().expect("object");
+		for key in object.keys() {
+			assert!(properties.contains_key(key), "{}.{} documented", name, key);
+		}
+		for required in schema["required"].as_array().into_iter().flatten() {
+			let required = required.as_str().expect("property name");
+			assert!(
+				object.contains_key(required),
+				"{}.{} present",
+				name,
+				required
+			);
+		}
+	}
+
+	#[test]
+	fn test_openapi_schemas_match_serialized_types() {
+		let spec = serde_json::to_value(openapi()).expect("spec");
+
+		let mut job = Job::for_stored_file(
+			"dump.csv".to_string(),
+			"/tmp/dump.csv".to_string(),
+			10,
+			"ab".repeat(32),
+		)
+		.owned_by("analyst");
+		job.next_retry_at = Some(chrono::Utc::now());
+		job.callback_url = Some("https://soar.example/hook".to_string());
+		job.callback_deliveries.push(CallbackDelivery {
+			attempt: 1,
+			attempted_at: chrono::Utc::now(),
+			status_code: Some(503),
+			error: Some("Callback endpoint returned 503".to_string()),
+			delivered: false,
+		});
+		let status = JobStatusResponse::from(job);
+		assert_matches_schema(
+			&spec,
+			"CallbackDelivery",
+			&serde_json::json!(status.callback_deliveries[0]),
+		);
+		assert_matches_schema(&spec, "JobStatusResponse", &serde_json::json!(status));
+
+		let list = ListJobsResponse {
+			jobs: vec![status],
+			total: 1,
+			offset: 0,
+			limit: 50,
+		};
+		assert_matches_schema(&spec, "ListJobsResponse", &serde_json::json!(list));
+
+		let response = IngestResponse {
+			job_id: "job".to_string(),
+			status: JobStatus::Queued.to_string(),
+			created_at: chrono::Utc::now().to_rfc3339(),
+			file_size_bytes: Some(10),
+			sha256: Some("ab".repeat(32)),
+		};
+		assert_matches_schema(&spec, "IngestResponse", &serde_json::json!(response));
+
+		let mut config = Config::default();
+		config.add_suffix_rule("gmail.com".to_string(), vec!["googlemail.com".to_string()]);
+		config.add_custom_password("hunter2".to_string());
+		let rules = SuffixRulesResponse::from(&config);
+		assert_matches_schema(&spec, "SuffixRulesResponse", &serde_json::json!(rules));
+		let passwords = CustomPasswordsResponse::from(&config);
+		assert_matches_schema(
+			&spec,
+			"CustomPasswordsResponse",
+			&serde_json::json!(passwords),
+		);
+
+		let error = ServerError::Conflict("busy".to_string()).into_response();
+		assert_eq!(error.status(), StatusCode::CONFLICT);
+		let body = tokio::runtime::Builder::new_current_thread()
+			.build()
+			.expect("runtime")
+			.block_on(json_body(error));
+		assert_matches_schema(&spec, "ServerError", &body);
+
+		// Request bodies: the documented required fields are the ones serde requires
+		let required = &spec["components"]["schemas"]["IngestRequest"]["required"];
+		assert_eq!(required, &serde_json::json!(["filename"]));
+		let request: IngestRequest =
+			serde_json::from_value(serde_json::json!({"filename": "a.csv"}))
+				.expect("minimal request parses");
+		assert_eq!(request.priority, 0);
+		assert_eq!(request.file_size_bytes, None);
+	}
 }
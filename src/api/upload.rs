@@ -0,0 +1,680 @@
+//! Streaming upload storage for the HTTP API.
+//!
+//! Request bodies are written to the working directory chunk by chunk as they
+//! arrive. The size and SHA-256 digest are computed on the server while
+//! streaming, so multi-GB uploads never have to fit in memory.
+//!
+//! Resumable uploads follow a tus-style protocol: an upload is created with its
+//! total length, chunks are appended at the current offset, and the upload is
+//! finalized once every byte has arrived. Partial data and session state live in
+//! an `uploads/` directory under the working directory so they survive restarts,
+//! until the upload is finalized, terminated or left idle long enough to expire.
+
+use std::{
+	collections::HashSet,
+	path::{Component, Path, PathBuf},
+	sync::Mutex,
+	time::{Duration, SystemTime},
+};
+
+use axum::body::Body;
+use chrono::{DateTime, Utc};
+use http_body_util::BodyExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+
+use crate::core::secure_deletion::{SecureDeletionConfig, secure_delete_file};
+
+/// Upload errors
+#[derive(Debug, Error)]
+pub enum UploadError {
+	#[error("Upload exceeds maximum size of {0} bytes")]
+	TooLarge(u64),
+
+	#[error("Upload body is empty")]
+	Empty,
+
+	#[error("Invalid upload filename: {0}")]
+	InvalidFilename(String),
+
+	#[error("Not a file in the import directory: {0}")]
+	InvalidImportPath(String),
+
+	#[error("Failed to read upload body: {0}")]
+	Body(String),
+
+	#[error("Upload not found: {0}")]
+	NotFound(String),
+
+	#[error("Upload offset mismatch: expected {expected}, got {actual}")]
+	OffsetMismatch { expected: u64, actual: u64 },
+
+	#[error("Upload {0} is being written by another request")]
+	Busy(String),
+
+	#[error("Upload incomplete: {offset} of {length} bytes received")]
+	Incomplete { offset: u64, length: u64 },
+
+	#[error("Failed to write upload: {0}")]
+	Io(#[from] std::io::Error),
+}
+
+/// A file that was streamed to disk
+#[derive(Debug, Clone)]
+pub struct StoredUpload {
+	/// Location of the stored file
+	pub path: PathBuf,
+	/// Number of bytes received
+	pub size_bytes: u64,
+	/// Hex-encoded SHA-256 digest of the received bytes
+	pub sha256: String,
+}
+
+/// Reduce a client-supplied filename to a safe, single path component.
+pub fn sanitize_filename(filename: &str) -> Result<String, UploadError> {
+	let name = Path::new(filename.trim())
+		.file_name()
+		.and_then(|n| n.to_str())
+		.unwrap_or("");
+
+	if name.is_empty() || name == "." || name == ".." {
+		return Err(UploadError::InvalidFilename(filename.to_string()));
+	}
+
+	Ok(name
+		.chars()
+		.map(|c| if c.is_control() || c == '\\' { '_' } else { c })
+		.collect())
+}
+
+/// Resolve a client-supplied path to a regular file inside `import_dir`.
+///
+/// Only relative paths without `..` components are accepted, and the canonical
+/// result must still lie inside the canonical import directory, so symbolic
+/// links cannot point the server at other files.
+pub fn resolve_import_path(import_dir: &Path, requested: &str) -> Result<PathBuf, UploadError> {
+	let invalid = || UploadError::InvalidImportPath(requested.to_string());
+	let relative = Path::new(requested);
+	if requested.is_empty()
+		|| !relative
+			.components()
+			.all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+	{
+		return Err(invalid());
+	}
+
+	let import_dir = import_dir.canonicalize()?;
+	let path = import_dir
+		.join(relative)
+		.canonicalize()
+		.map_err(|_| invalid())?;
+	if !path.starts_with(&import_dir) || !path.is_file() {
+		return Err(invalid());
+	}
+	Ok(path)
+}
+
+/// Build a unique destination path for an upload inside the working directory.
+///
+/// The original filename is kept as the suffix so the extension still drives
+/// format detection.
+pub fn upload_path(working_dir: &Path, filename: &str) -> Result<PathBuf, UploadError> {
+	let name = sanitize_filename(filename)?;
+	Ok(working_dir.join(format!("upload_{}_{}", uuid::Uuid::new_v4(), name)))
+}
+
+/// Stream a request body to `dest`, enforcing `max_bytes`.
+///
+/// The partially written file is removed if the body fails, is empty, or
+/// exceeds the limit.
+pub async fn stream_body_to_file(
+	body: Body,
+	dest: &Path,
+	max_bytes: u64,
+) -> Result<StoredUpload, UploadError> {
+	let result = write_body(body, dest, max_bytes).await;
+	if result.is_err() {
+		let _ = tokio::fs::remove_file(dest).await;
+	}
+	result
+}
+
+async fn write_body(body: Body, dest: &Path, max_bytes: u64) -> Result<StoredUpload, UploadError> {
+	let mut file = tokio::fs::OpenOptions::new()
+		.write(true)
+		.create_new(true)
+		.open(dest)
+		.await?;
+	let mut hasher = Sha256::new();
+
+	let size_bytes = write_frames(body, &mut file, max_bytes, Some(&mut hasher)).await?;
+
+	if size_bytes == 0 {
+		return Err(UploadError::Empty);
+	}
+
+	file.sync_all().await?;
+
+	Ok(StoredUpload {
+		path: dest.to_path_buf(),
+		size_bytes,
+		sha256: hex::encode(hasher.finalize()),
+	})
+}
+
+/// Copy body frames into `file`, failing once more than `limit` bytes arrive.
+///
+/// Data received before a failure stays written. Returns the number of bytes written.
+async fn write_frames(
+	mut body: Body,
+	file: &mut tokio::fs::File,
+	limit: u64,
+	mut hasher: Option<&mut Sha256>,
+) -> Result<u64, UploadError> {
+	let mut written: u64 = 0;
+
+	let result = async {
+		while let Some(frame) = body.frame().await {
+			let frame = frame.map_err(|e| UploadError::Body(e.to_string()))?;
+			let Ok(chunk) = frame.into_data() else {
+				continue;
+			};
+
+			if written + chunk.len() as u64 > limit {
+				return Err(UploadError::TooLarge(limit));
+			}
+
+			if let Some(hasher) = hasher.as_deref_mut() {
+				hasher.update(&chunk);
+			}
+			file.write_all(&chunk).await?;
+			written += chunk.len() as u64;
+		}
+		Ok(())
+	}
+	.await;
+
+	file.flush().await?;
+	result.map(|_| written)
+}
+
+/// Persistent state of a resumable upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSession {
+	/// Upload identifier (UUID)
+	pub id: String,
+	/// Client-supplied filename, sanitized
+	pub filename: String,
+	/// Total number of bytes the client will send
+	pub upload_length: u64,
+	/// Subject of the principal that created the upload
+	#[serde(default)]
+	pub owner: Option<String>,
+	/// Priority of the job queued when the upload is finalized
+	#[serde(default)]
+	pub priority: i32,
+	/// URL notified when the job queued for the upload finishes
+	#[serde(default)]
+	pub callback_url: Option<String>,
+	pub created_at: DateTime<Utc>,
+}
+
+/// Resumable upload sessions stored under the working directory
+pub struct ResumableUploads {
+	dir: PathBuf,
+	max_bytes: u64,
+	/// Uploads currently being written or finalized
+	active: Mutex<HashSet<String>>,
+}
+
+/// Marks an upload as in use until dropped
+struct ActiveGuard<'a> {
+	uploads: &'a ResumableUploads,
+	id: String,
+}
+
+impl Drop for ActiveGuard<'_> {
+	fn drop(&mut self) {
+		if let Ok(mut active) = self.uploads.active.lock() {
+			active.remove(&self.id);
+		}
+	}
+}
+
+impl ResumableUploads {
+	/// Open (or create) the upload store in `working_dir/uploads`
+	pub fn new(working_dir: &Path, max_bytes: u64) -> std::io::Result<Self> {
+		let dir = working_dir.join("uploads");
+		std::fs::create_dir_all(&dir)?;
+
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))?;
+		}
+
+		Ok(Self {
+			dir,
+			max_bytes,
+			active: Mutex::new(HashSet::new()),
+		})
+	}
+
+	/// Start a new upload of `upload_length` bytes owned by `owner`
+	pub async fn create(
+		&self,
+		filename: &str,
+		upload_length: u64,
+		owner: &str,
+		priority: i32,
+		callback_url: Option<String>,
+	) -> Result<UploadSession, UploadError> {
+		if upload_length == 0 {
+			return Err(UploadError::Empty);
+		}
+		if upload_length > self.max_bytes {
+			return Err(UploadError::TooLarge(self.max_bytes));
+		}
+
+		let session = UploadSession {
+			id: uuid::Uuid::new_v4().to_string(),
+			filename: sanitize_filename(filename)?,
+			upload_length,
+			owner: Some(owner.to_string()),
+			priority,
+			callback_url,
+			created_at: Utc::now(),
+		};
+
+		tokio::fs::File::create(self.data_path(&session.id)).await?;
+		let state = serde_json::to_vec(&session).map_err(std::io::Error::other)?;
+		tokio::fs::write(self.state_path(&session.id), state).await?;
+
+		Ok(session)
+	}
+
+	/// Look up an upload and the number of bytes received so far
+	pub async fn status(&self, id: &str) -> Result<(UploadSession, u64), UploadError> {
+		let session = self.load(id).await?;
+		let offset = tokio::fs::metadata(self.data_path(id)).await?.len();
+		Ok((session, offset))
+	}
+
+	/// Append a chunk at `offset`, returning the new offset
+	///
+	/// The offset must match the bytes already stored. Bytes received before a
+	/// dropped connection are kept so the client can resume from the new offset.
+	pub async fn append(&self, id: &str, offset: u64, body: Body) -> Result<u64, UploadError> {
+		let _guard = self.acquire(id)?;
+		let (session, current) = self.status(id).await?;
+
+		if offset != current {
+			return Err(UploadError::OffsetMismatch {
+				expected: current,
+				actual: offset,
+			});
+		}
+
+		let mut file = tokio::fs::OpenOptions::new()
+			.append(true)
+			.open(self.data_path(id))
+			.await?;
+		let result = write_frames(body, &mut file, session.upload_length - current, None).await;
+		file.sync_all().await?;
+
+		let written = result.map_err(|e| match e {
+			UploadError::TooLarge(_) => UploadError::TooLarge(session.upload_length),
+			other => other,
+		})?;
+
+		Ok(current + written)
+	}
+
+	/// Complete an upload, moving the data to `dest_dir`
+	///
+	/// Returns the session and the location of the assembled file.
+	pub async fn finalize(
+		&self,
+		id: &str,
+		dest_dir: &Path,
+	) -> Result<(UploadSession, PathBuf), UploadError> {
+		let _guard = self.acquire(id)?;
+		let (session, offset) = self.status(id).await?;
+
+		if offset != session.upload_length {
+			return Err(UploadError::Incomplete {
+				offset,
+				length: session.upload_length,
+			});
+		}
+
+		let dest = dest_dir.join(format!("upload_{}_{}", session.id, session.filename));
+		tokio::fs::rename(self.data_path(id), &dest).await?;
+		let _ = tokio::fs::remove_file(self.state_path(id)).await;
+
+		Ok((session, dest))
+	}
+
+	/// Abandon an upload, securely deleting the bytes received so far
+	pub async fn terminate(&self, id: &str) -> Result<(), UploadError> {
+		let _guard = self.acquire(id)?;
+		self.load(id).await?;
+		self.discard(id).await?;
+		Ok(())
+	}
+
+	/// Remove uploads that received nothing for `max_idle`, returning their IDs
+	///
+	/// Uploads being written or finalized are left alone, as are files in the
+	/// upload directory that are not named after an upload.
+	pub async fn remove_expired(&self, max_idle: Duration) -> std::io::Result<Vec<String>> {
+		let mut ids = HashSet::new();
+		let mut entries = tokio::fs::read_dir(&self.dir).await?;
+		while let Some(entry) = entries.next_entry().await? {
+			let path = entry.path();
+			let is_upload_file = path
+				.extension()
+				.is_some_and(|ext| ext == "part" || ext == "json");
+			if let Some(id) = path.file_stem().and_then(|stem| stem.to_str())
+				&& is_upload_file
+				&& check_upload_id(id).is_ok()
+			{
+				ids.insert(id.to_string());
+			}
+		}
+
+		let mut removed = Vec::new();
+		for id in ids {
+			let Ok(_guard) = self.acquire(&id) else {
+				continue;
+			};
+			// The last chunk or the creation of the upload, whichever came later
+			let mut last_activity = None;
+			for path in [self.data_path(&id), self.state_path(&id)] {
+				if let Ok(modified) = tokio::fs::metadata(&path).await.and_then(|m| m.modified()) {
+					last_activity = last_activity.max(Some(modified));
+				}
+			}
+			let idle = last_activity
+				.and_then(|at| SystemTime::now().duration_since(at).ok())
+				.unwrap_or_default();
+			if last_activity.is_some() && idle >= max_idle {
+				self.discard(&id).await?;
+				removed.push(id);
+			}
+		}
+		Ok(removed)
+	}
+
+	/// Delete an upload's data and state; the caller holds its guard
+	async fn discard(&self, id: &str) -> std::io::Result<()> {
+		let data_path = self.data_path(id);
+		if tokio::fs::try_exists(&data_path).await? {
+			let config = SecureDeletionConfig {
+				log_deletions: false,
+				..SecureDeletionConfig::default()
+			};
+			tokio::task::spawn_blocking(move || secure_delete_file(&data_path, config))
+				.await
+				.map_err(std::io::Error::other)?
+				.map_err(std::io::Error::other)?;
+		}
+		match tokio::fs::remove_file(self.state_path(id)).await {
+			Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+			_ => Ok(()),
+		}
+	}
+
+	async fn load(&self, id: &str) -> Result<UploadSession, UploadError> {
+		check_upload_id(id)?;
+		let state = match tokio::fs::read(self.state_path(id)).await {
+			Ok(state) => state,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+				return Err(UploadError::NotFound(id.to_string()));
+			}
+			Err(e) => return Err(e.into()),
+		};
+		serde_json::from_slice(&state).map_err(|e| std::io::Error::other(e).into())
+	}
+
+	fn acquire(&self, id: &str) -> Result<ActiveGuard<'_>, UploadError> {
+		check_upload_id(id)?;
+
+		let mut active = self
+			.active
+			.lock()
+			.map_err(|e| std::io::Error::other(e.to_string()))?;
+		if !active.insert(id.to_string()) {
+			return Err(UploadError::Busy(id.to_string()));
+		}
+
+		Ok(ActiveGuard {
+			uploads: self,
+			id: id.to_string(),
+		})
+	}
+
+	fn data_path(&self, id: &str) -> PathBuf {
+		self.dir.join(format!("{}.part", id))
+	}
+
+	fn state_path(&self, id: &str) -> PathBuf {
+		self.dir.join(format!("{}.json", id))
+	}
+}
+
+/// Only canonical UUIDs are valid upload IDs, which keeps them safe to use as file names
+fn check_upload_id(id: &str) -> Result<(), UploadError> {
+	match uuid::Uuid::parse_str(id) {
+		Ok(uuid) if uuid.to_string() == id => Ok(()),
+		_ => Err(UploadError::NotFound(id.to_string())),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sanitize_filename_strips_directories() {
+		assert_eq!(
+			sanitize_filename("../../etc/passwd").expect("valid"),
+			"passwd"
+		);
+		assert_eq!(sanitize_filename("dump.csv").expect("valid"), "dump.csv");
+		assert!(sanitize_filename("..").is_err());
+		assert!(sanitize_filename("  ").is_err());
+	}
+
+	#[tokio::test]
+	async fn test_stream_body_to_file_hashes_and_counts() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let dest = dir.path().join("upload.csv");
+
+		let stored = stream_body_to_file(Body::from("email\na@example.com\n"), &dest, 1024)
+			.await
+			.expect("stored");
+
+		assert_eq!(stored.size_bytes, 20);
+		assert_eq!(
+			stored.sha256,
+			crate::core::hash_utils::sha256_hex_bytes(b"email\na@example.com\n")
+		);
+		assert_eq!(
+			std::fs::read_to_string(&dest).expect("read"),
+			"email\na@example.com\n"
+		);
+	}
+
+	#[tokio::test]
+	async fn test_stream_body_to_file_enforces_limit() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let dest = dir.path().join("upload.csv");
+
+		let err = stream_body_to_file(Body::from(vec![b'a'; 64]), &dest, 16)
+			.await
+			.expect_err("too large");
+
+		assert!(matches!(err, UploadError::TooLarge(16)));
+		assert!(!dest.exists());
+	}
+
+	#[tokio::test]
+	async fn test_resumable_upload_append_and_finalize() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let uploads = ResumableUploads::new(dir.path(), 1024).expect("store");
+
+		let session = uploads
+			.create("dump.csv", 10, "alice", 0, None)
+			.await
+			.expect("create");
+		assert_eq!(uploads.status(&session.id).await.expect("status").1, 0);
+
+		let offset = uploads
+			.append(&session.id, 0, Body::from("hello"))
+			.await
+			.expect("append");
+		assert_eq!(offset, 5);
+
+		let err = uploads
+			.append(&session.id, 0, Body::from("again"))
+			.await
+			.expect_err("stale offset");
+		assert!(matches!(
+			err,
+			UploadError::OffsetMismatch {
+				expected: 5,
+				actual: 0
+			}
+		));
+
+		let err = uploads
+			.finalize(&session.id, dir.path())
+			.await
+			.expect_err("incomplete");
+		assert!(matches!(err, UploadError::Incomplete { offset: 5, .. }));
+
+		uploads
+			.append(&session.id, 5, Body::from("world"))
+			.await
+			.expect("append");
+		let (session, path) = uploads
+			.finalize(&session.id, dir.path())
+			.await
+			.expect("finalize");
+
+		assert_eq!(std::fs::read_to_string(&path).expect("read"), "helloworld");
+		assert!(path.to_string_lossy().ends_with("dump.csv"));
+		assert!(matches!(
+			uploads.status(&session.id).await,
+			Err(UploadError::NotFound(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_resumable_upload_state_survives_restart() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let id = {
+			let uploads = ResumableUploads::new(dir.path(), 1024).expect("store");
+			let session = uploads
+				.create("dump.csv", 8, "alice", 0, None)
+				.await
+				.expect("create");
+			uploads
+				.append(&session.id, 0, Body::from("abcd"))
+				.await
+				.expect("append");
+			session.id
+		};
+
+		let uploads = ResumableUploads::new(dir.path(), 1024).expect("store");
+		let (session, offset) = uploads.status(&id).await.expect("status");
+		assert_eq!(session.upload_length, 8);
+		assert_eq!(offset, 4);
+	}
+
+	#[tokio::test]
+	async fn test_resumable_upload_rejects_bytes_past_length() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let uploads = ResumableUploads::new(dir.path(), 1024).expect("store");
+		let session = uploads
+			.create("dump.csv", 4, "alice", 0, None)
+			.await
+			.expect("create");
+
+		let err = uploads
+			.append(&session.id, 0, Body::from("too long"))
+			.await
+			.expect_err("past length");
+		assert!(matches!(err, UploadError::TooLarge(4)));
+		assert!(matches!(
+			uploads.status("../etc/passwd").await,
+			Err(UploadError::NotFound(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_terminate_removes_upload() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let uploads = ResumableUploads::new(dir.path(), 1024).expect("store");
+		let session = uploads
+			.create("dump.csv", 8, "alice", 0, None)
+			.await
+			.expect("create");
+		uploads
+			.append(&session.id, 0, Body::from("half"))
+			.await
+			.expect("append");
+
+		uploads.terminate(&session.id).await.expect("terminate");
+		assert!(matches!(
+			uploads.status(&session.id).await,
+			Err(UploadError::NotFound(_))
+		));
+		assert!(!uploads.data_path(&session.id).exists());
+		assert!(matches!(
+			uploads.terminate(&session.id).await,
+			Err(UploadError::NotFound(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_remove_expired_skips_recent_and_active_uploads() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let uploads = ResumableUploads::new(dir.path(), 1024).expect("store");
+		let busy = uploads
+			.create("busy.csv", 8, "alice", 0, None)
+			.await
+			.expect("create");
+		let idle = uploads
+			.create("idle.csv", 8, "alice", 0, None)
+			.await
+			.expect("create");
+		// Data left behind by an upload whose state was never written
+		let orphan = uploads.data_path(&uuid::Uuid::new_v4().to_string());
+		std::fs::write(&orphan, "partial").expect("write");
+		let unrelated = uploads.dir.join("notes.txt");
+		std::fs::write(&unrelated, "keep").expect("write");
+
+		let removed = uploads
+			.remove_expired(Duration::from_secs(3600))
+			.await
+			.expect("sweep");
+		assert!(removed.is_empty());
+
+		let guard = uploads.acquire(&busy.id).expect("acquire");
+		let removed = uploads.remove_expired(Duration::ZERO).await.expect("sweep");
+		drop(guard);
+		assert_eq!(removed.len(), 2);
+		assert!(removed.contains(&idle.id));
+		assert!(!orphan.exists());
+		assert!(matches!(
+			uploads.status(&idle.id).await,
+			Err(UploadError::NotFound(_))
+		));
+		assert!(uploads.status(&busy.id).await.is_ok());
+		assert!(unrelated.exists());
+	}
+}
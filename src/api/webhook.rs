@@ -0,0 +1,897 @@
+//! Signed callbacks sent when server jobs finish.
+//!
+//! A job submitted with a `callback_url` gets a POST of its final status and
+//! result summary (the same body as the `finished` server-sent event) once it
+//! is completed, failed, dead-lettered or cancelled. The body is signed over
+//! `"{timestamp}.{body}"` with the configured HMAC secret and/or the
+//! instance's ed25519 key. Failed deliveries are retried with exponential
+//! backoff and every attempt is recorded on the job. Callbacks of jobs that
+//! finished while no dispatcher was running are sent at startup.
+//!
+//! Callbacks only go to public addresses unless the target is on the
+//! configured allow-list. Host names are checked as they are resolved for
+//! each delivery, and the connection uses the checked addresses, so a name
+//! that later resolves to an internal address is refused too.
+
+use std::{
+	collections::HashSet,
+	io,
+	net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+use chrono::Utc;
+use ed25519_dalek::{Signer, SigningKey};
+use hmac::{Hmac, Mac};
+use ipnet::IpNet;
+use reqwest::{
+	StatusCode, Url,
+	dns::{Addrs, Name, Resolve, Resolving},
+};
+use sha2::Sha256;
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::{
+	api::server::{JobFinishedEvent, ResultSummary},
+	core::config::WebhookConfig,
+	storage::{
+		CustodyKeyPair,
+		job_queue::{CallbackDelivery, Job, JobQueue},
+	},
+};
+
+/// Unix time (seconds) the callback was signed at
+pub const TIMESTAMP_HEADER: &str = "x-dumptruck-timestamp";
+/// `sha256=` followed by the hex HMAC-SHA256 of the signed message
+pub const HMAC_SIGNATURE_HEADER: &str = "x-dumptruck-signature";
+/// Hex ed25519 signature of the signed message
+pub const ED25519_SIGNATURE_HEADER: &str = "x-dumptruck-signature-ed25519";
+/// ID of the job the callback reports on
+pub const JOB_ID_HEADER: &str = "x-dumptruck-job-id";
+
+/// Upper bound on the delay between delivery attempts
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Webhook errors
+#[derive(Debug, Error)]
+pub enum WebhookError {
+	#[error("Invalid callback URL: {0}")]
+	InvalidUrl(String),
+
+	#[error("Callbacks are disabled: no webhook signing method is configured")]
+	NotConfigured,
+
+	#[error("Invalid webhook signing key: {0}")]
+	InvalidKey(String),
+
+	#[error("Failed to build webhook client: {0}")]
+	Client(String),
+
+	#[error("Invalid entry in webhook allowed_internal_networks: {0}")]
+	InvalidAllowList(String),
+
+	#[error("Callback target {0} is not a public address")]
+	ForbiddenTarget(String),
+
+	#[error("Callback request failed: {0}")]
+	Request(#[from] reqwest::Error),
+}
+
+/// Which addresses callbacks may be sent to
+#[derive(Debug, Clone, Default)]
+pub struct TargetPolicy {
+	/// Non-public networks allowed by configuration
+	allowed_internal: Vec<IpNet>,
+}
+
+impl TargetPolicy {
+	/// Parse the configured allow-list of addresses and CIDR blocks
+	pub fn from_config(config: &WebhookConfig) -> Result<Self, WebhookError> {
+		let allowed_internal = config
+			.allowed_internal_networks
+			.iter()
+			.map(|entry| {
+				let entry = entry.trim();
+				entry
+					.parse::<IpNet>()
+					.or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+					.map_err(|_| WebhookError::InvalidAllowList(entry.to_string()))
+			})
+			.collect::<Result<_, _>>()?;
+		Ok(Self { allowed_internal })
+	}
+
+	/// Whether a callback may be sent to `ip`
+	pub fn permits(&self, ip: IpAddr) -> bool {
+		is_public(ip) || self.allowed_internal.iter().any(|net| net.contains(&ip))
+	}
+
+	/// Check a URL whose host is an IP address; host names are checked when resolved
+	fn check_url(&self, url: &Url) -> Result<(), WebhookError> {
+		// IPv6 hosts keep their brackets in the URL
+		let host = url.host_str().unwrap_or_default();
+		let Ok(ip) = host
+			.trim_start_matches('[')
+			.trim_end_matches(']')
+			.parse::<IpAddr>()
+		else {
+			return Ok(());
+		};
+		if self.permits(ip) {
+			Ok(())
+		} else {
+			Err(WebhookError::ForbiddenTarget(ip.to_string()))
+		}
+	}
+}
+
+/// Whether `ip` is a globally routable unicast address
+fn is_public(ip: IpAddr) -> bool {
+	match ip {
+		IpAddr::V4(ip) => is_public_v4(ip),
+		IpAddr::V6(ip) => is_public_v6(ip),
+	}
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+	let [a, b, c, _] = ip.octets();
+	!(ip.is_unspecified()
+		|| ip.is_loopback()
+		|| ip.is_private()
+		|| ip.is_link_local()
+		|| ip.is_broadcast()
+		|| ip.is_documentation()
+		|| ip.is_multicast()
+		// "This network", shared address space (RFC 6598), IETF protocol
+		// assignments, benchmarking (RFC 2544) and reserved
+		|| a == 0
+		|| (a == 100 && (64..128).contains(&b))
+		|| (a == 192 && b == 0 && c == 0)
+		|| (a == 198 && (18..20).contains(&b))
+		|| a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+	// IPv4-mapped and NAT64 addresses reach the embedded IPv4 address
+	if let Some(v4) = ip.to_ipv4_mapped() {
+		return is_public_v4(v4);
+	}
+	let segments = ip.segments();
+	if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+		let [_, _, _, _, _, _, high, low] = segments;
+		return is_public_v4(Ipv4Addr::from((u32::from(high) << 16) | u32::from(low)));
+	}
+	!(ip.is_unspecified()
+		|| ip.is_loopback()
+		|| ip.is_multicast()
+		// Unique local, link-local and site-local
+		|| (segments[0] & 0xfe00) == 0xfc00
+		|| (segments[0] & 0xffc0) == 0xfe80
+		|| (segments[0] & 0xffc0) == 0xfec0
+		// Documentation and IPv4-compatible
+		|| (segments[0] == 0x2001 && segments[1] == 0x0db8)
+		|| segments[..6] == [0; 6])
+}
+
+/// Resolves callback hosts, refusing names with addresses the policy does not permit
+///
+/// The client connects to the addresses returned here, so the check cannot be
+/// bypassed by a name that resolves differently on a second lookup.
+struct GuardedResolver {
+	policy: Arc<TargetPolicy>,
+}
+
+impl Resolve for GuardedResolver {
+	fn resolve(&self, name: Name) -> Resolving {
+		let policy = self.policy.clone();
+		Box::pin(async move {
+			let host = name.as_str();
+			let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0)).await?.collect();
+			if let Some(addr) = addrs.iter().find(|addr| !policy.permits(addr.ip())) {
+				return Err(io::Error::new(
+					io::ErrorKind::PermissionDenied,
+					WebhookError::ForbiddenTarget(format!("{} ({})", host, addr.ip())),
+				)
+				.into());
+			}
+			let addrs: Addrs = Box::new(addrs.into_iter());
+			Ok(addrs)
+		})
+	}
+}
+
+/// Check a client-supplied callback URL against the webhook configuration
+///
+/// The URL must be absolute with a host and use HTTPS (or HTTP when
+/// `allow_http` is set), and callbacks must be signable. An IP address host
+/// must be public or allow-listed; host names are checked at delivery.
+pub fn validate_callback_url(url: &str, config: &WebhookConfig) -> Result<String, WebhookError> {
+	if !config.signing_configured() {
+		return Err(WebhookError::NotConfigured);
+	}
+
+	let parsed = Url::parse(url.trim()).map_err(|e| WebhookError::InvalidUrl(e.to_string()))?;
+	match parsed.scheme() {
+		"https" => {}
+		"http" if config.allow_http => {}
+		scheme => {
+			return Err(WebhookError::InvalidUrl(format!(
+				"scheme {} is not allowed",
+				scheme
+			)));
+		}
+	}
+	if parsed.host_str().is_none_or(str::is_empty) {
+		return Err(WebhookError::InvalidUrl("missing host".to_string()));
+	}
+	if !parsed.username().is_empty() || parsed.password().is_some() {
+		return Err(WebhookError::InvalidUrl(
+			"credentials in the URL are not allowed".to_string(),
+		));
+	}
+	TargetPolicy::from_config(config)?
+		.check_url(&parsed)
+		.map_err(|e| WebhookError::InvalidUrl(e.to_string()))?;
+
+	Ok(parsed.to_string())
+}
+
+/// The bytes a callback signature covers: `"{timestamp}.{body}"`
+pub fn signed_message(timestamp: i64, body: &[u8]) -> Vec<u8> {
+	let mut message = format!("{}.", timestamp).into_bytes();
+	message.extend_from_slice(body);
+	message
+}
+
+/// Signs callback bodies with the configured secret and key
+pub struct WebhookSigner {
+	hmac_secret: Option<Vec<u8>>,
+	ed25519_key: Option<SigningKey>,
+}
+
+impl WebhookSigner {
+	/// Load the HMAC secret and the ed25519 key pair file named in the configuration
+	pub fn from_config(config: &WebhookConfig) -> Result<Self, WebhookError> {
+		let ed25519_key = match &config.signing_key_path {
+			Some(path) => {
+				let data = std::fs::read_to_string(path)
+					.map_err(|e| WebhookError::InvalidKey(format!("{}: {}", path, e)))?;
+				let key_pair: CustodyKeyPair = serde_json::from_str(&data)
+					.map_err(|e| WebhookError::InvalidKey(format!("{}: {}", path, e)))?;
+				let secret = key_pair
+					.private_key_bytes()
+					.map_err(|e| WebhookError::InvalidKey(e.to_string()))?;
+				Some(SigningKey::from_bytes(&secret))
+			}
+			None => None,
+		};
+
+		Ok(Self {
+			hmac_secret: (!config.hmac_secret.is_empty())
+				.then(|| config.hmac_secret.as_bytes().to_vec()),
+			ed25519_key,
+		})
+	}
+
+	/// Signature headers for `body` signed at `timestamp`
+	pub fn sign(&self, timestamp: i64, body: &[u8]) -> Vec<(&'static str, String)> {
+		let message = signed_message(timestamp, body);
+		let mut headers = vec![(TIMESTAMP_HEADER, timestamp.to_string())];
+
+		if let Some(secret) = &self.hmac_secret {
+			let mut mac =
+				Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+			mac.update(&message);
+			headers.push((
+				HMAC_SIGNATURE_HEADER,
+				format!("sha256={}", hex::encode(mac.finalize().into_bytes())),
+			));
+		}
+		if let Some(key) = &self.ed25519_key {
+			headers.push((
+				ED25519_SIGNATURE_HEADER,
+				hex::encode(key.sign(&message).to_bytes()),
+			));
+		}
+
+		headers
+	}
+}
+
+/// Delivers completion callbacks for jobs as they finish
+pub struct WebhookDispatcher {
+	client: reqwest::Client,
+	policy: Arc<TargetPolicy>,
+	signer: WebhookSigner,
+	max_attempts: u32,
+	retry_base_delay: Duration,
+	/// Jobs whose callback is being delivered
+	in_flight: Mutex<HashSet<String>>,
+}
+
+impl WebhookDispatcher {
+	/// Create a dispatcher from the webhook configuration, loading its signing key
+	pub fn new(config: &WebhookConfig) -> Result<Self, WebhookError> {
+		let policy = Arc::new(TargetPolicy::from_config(config)?);
+		let client = reqwest::Client::builder()
+			.timeout(Duration::from_secs(config.timeout_secs.max(1)))
+			// A redirect could point the signed body somewhere the client did not ask for
+			.redirect(reqwest::redirect::Policy::none())
+			// Connect to the checked addresses ourselves rather than through a proxy
+			.no_proxy()
+			.dns_resolver(Arc::new(GuardedResolver {
+				policy: policy.clone(),
+			}))
+			.build()
+			.map_err(|e| WebhookError::Client(e.to_string()))?;
+
+		Ok(Self {
+			client,
+			policy,
+			signer: WebhookSigner::from_config(config)?,
+			max_attempts: config.max_attempts.max(1),
+			retry_base_delay: Duration::from_secs(config.retry_base_delay_secs),
+			in_flight: Mutex::new(HashSet::new()),
+		})
+	}
+
+	/// Watch `queue` and deliver the callback of every job that finishes
+	///
+	/// Jobs that finished while no dispatcher was watching, before a restart
+	/// or while updates were missed, are found by scanning the queue.
+	pub fn spawn(self: Arc<Self>, queue: Arc<JobQueue>) -> tokio::task::JoinHandle<()> {
+		// Subscribe before returning so no job finishing afterwards is missed
+		let mut updates = queue.subscribe();
+		tokio::spawn(async move {
+			self.deliver_pending(&queue).await;
+			loop {
+				match updates.recv().await {
+					Ok(job) if job.callback_pending() => self.dispatch(&queue, job),
+					Ok(_) => continue,
+					Err(broadcast::error::RecvError::Lagged(missed)) => {
+						warn!(
+							"Webhook dispatcher missed {} job updates; scanning for pending callbacks",
+							missed
+						);
+						self.deliver_pending(&queue).await;
+					}
+					Err(broadcast::error::RecvError::Closed) => return,
+				}
+			}
+		})
+	}
+
+	/// Deliver the callback of every finished job that has not been attempted yet
+	async fn deliver_pending(self: &Arc<Self>, queue: &Arc<JobQueue>) {
+		match queue.pending_callbacks().await {
+			Ok(jobs) => {
+				for job in jobs {
+					self.dispatch(queue, job);
+				}
+			}
+			Err(e) => warn!("Failed to scan for pending callbacks: {}", e),
+		}
+	}
+
+	/// Start delivering a job's callback unless it is already being delivered
+	fn dispatch(self: &Arc<Self>, queue: &Arc<JobQueue>, job: Job) {
+		if !self.claim(&job.id) {
+			return;
+		}
+		let dispatcher = self.clone();
+		let queue = queue.clone();
+		tokio::spawn(async move {
+			// The snapshot may predate an attempt that finished since
+			match queue.get_job(&job.id).await {
+				Ok(job) if job.callback_pending() => dispatcher.deliver(&queue, job).await,
+				_ => dispatcher.release(&job.id),
+			}
+		});
+	}
+
+	/// Mark a job's callback as being delivered; false if it already is
+	fn claim(&self, job_id: &str) -> bool {
+		self.in_flight
+			.lock()
+			.map(|mut jobs| jobs.insert(job_id.to_string()))
+			.unwrap_or(false)
+	}
+
+	/// Send a finished job's callback, retrying until delivered or out of attempts
+	pub async fn deliver(&self, queue: &JobQueue, job: Job) {
+		let job_id = job.id.clone();
+		let Some(url) = job.callback_url.clone() else {
+			return;
+		};
+
+		let result = job.result.as_ref().map(ResultSummary::from);
+		let body = match serde_json::to_vec(&JobFinishedEvent {
+			job: job.into(),
+			result,
+		}) {
+			Ok(body) => body,
+			Err(e) => {
+				warn!("Failed to serialize callback of job {}: {}", job_id, e);
+				return;
+			}
+		};
+
+		for attempt in 1..=self.max_attempts {
+			let attempted_at = Utc::now();
+			let (status_code, error) = match self.send(&url, &job_id, &body).await {
+				Ok(status) if status.is_success() => (Some(status), None),
+				Ok(status) => (
+					Some(status),
+					Some(format!("Callback endpoint returned {}", status)),
+				),
+				Err(e) => (None, Some(error_chain(&e))),
+			};
+			let delivered = error.is_none();
+
+			let delivery = CallbackDelivery {
+				attempt,
+				attempted_at,
+				status_code: status_code.map(|s| s.as_u16()),
+				error,
+				delivered,
+			};
+			if let Err(e) = queue
+				.update_job(&job_id, |j| {
+					j.callback_deliveries.push(delivery);
+					Ok(())
+				})
+				.await
+			{
+				warn!("Failed to record callback attempt of job {}: {}", job_id, e);
+			}
+
+			if delivered {
+				info!(
+					"Callback of job {} delivered on attempt {}",
+					job_id, attempt
+				);
+				break;
+			}
+			if status_code.is_some_and(|s| !is_retryable(s)) {
+				warn!(
+					"Callback of job {} rejected with {}; not retrying",
+					job_id,
+					status_code.unwrap_or_default()
+				);
+				break;
+			}
+			if attempt < self.max_attempts {
+				tokio::time::sleep(self.retry_delay(attempt)).await;
+			} else {
+				warn!(
+					"Callback of job {} not delivered after {} attempts",
+					job_id, attempt
+				);
+			}
+		}
+
+		self.release(&job_id);
+	}
+
+	/// Allow a job's callback to be claimed again
+	fn release(&self, job_id: &str) {
+		if let Ok(mut jobs) = self.in_flight.lock() {
+			jobs.remove(job_id);
+		}
+	}
+
+	/// POST a signed callback body
+	///
+	/// Host names are checked by the client's resolver; IP addresses are
+	/// never resolved, so they are checked here.
+	async fn send(&self, url: &str, job_id: &str, body: &[u8]) -> Result<StatusCode, WebhookError> {
+		let parsed = Url::parse(url).map_err(|e| WebhookError::InvalidUrl(e.to_string()))?;
+		self.policy.check_url(&parsed)?;
+
+		let mut request = self
+			.client
+			.post(url)
+			.header(reqwest::header::CONTENT_TYPE, "application/json")
+			.header(JOB_ID_HEADER, job_id);
+		for (name, value) in self.signer.sign(Utc::now().timestamp(), body) {
+			request = request.header(name, value);
+		}
+
+		Ok(request.body(body.to_vec()).send().await?.status())
+	}
+
+	/// Delay after a failed `attempt`: the base delay doubled per attempt, capped at five minutes
+	fn retry_delay(&self, attempt: u32) -> Duration {
+		self.retry_base_delay
+			.saturating_mul(1 << attempt.saturating_sub(1).min(16))
+			.min(MAX_RETRY_DELAY)
+	}
+}
+
+/// An error and its sources, so a refused target shows through the client's connect error
+fn error_chain(error: &dyn std::error::Error) -> String {
+	let mut message = error.to_string();
+	let mut source = error.source();
+	while let Some(cause) = source {
+		message.push_str(": ");
+		message.push_str(&cause.to_string());
+		source = cause.source();
+	}
+	message
+}
+
+/// Whether a failed delivery is worth retrying: server errors, timeouts and rate limits
+fn is_retryable(status: StatusCode) -> bool {
+	status.is_server_error()
+		|| status == StatusCode::REQUEST_TIMEOUT
+		|| status == StatusCode::TOO_MANY_REQUESTS
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use axum::{Router, extract::State, http::HeaderMap, routing::post};
+	use ed25519_dalek::{Signature, VerifyingKey};
+
+	/// Signs with a secret and may reach the test receivers on loopback
+	fn hmac_config() -> WebhookConfig {
+		WebhookConfig {
+			hmac_secret: "topsecret".to_string(),
+			retry_base_delay_secs: 0,
+			allow_http: true,
+			allowed_internal_networks: vec!["127.0.0.0/8".to_string(), "::1".to_string()],
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_validate_callback_url() {
+		let config = WebhookConfig {
+			hmac_secret: "topsecret".to_string(),
+			..Default::default()
+		};
+		assert_eq!(
+			validate_callback_url("https://soar.example/hook", &config).expect("valid"),
+			"https://soar.example/hook"
+		);
+		assert!(validate_callback_url("http://soar.example/hook", &config).is_err());
+		assert!(validate_callback_url("ftp://soar.example/hook", &config).is_err());
+		assert!(validate_callback_url("https://user:pw@soar.example/", &config).is_err());
+		assert!(validate_callback_url("not a url", &config).is_err());
+		assert!(matches!(
+			validate_callback_url("https://soar.example/hook", &WebhookConfig::default()),
+			Err(WebhookError::NotConfigured)
+		));
+
+		let config = WebhookConfig {
+			allow_http: true,
+			..config
+		};
+		for internal in [
+			"http://127.0.0.1:8080/hook",
+			"http://169.254.169.254/latest/meta-data/",
+			"https://10.0.0.5/hook",
+			"https://[::1]/hook",
+			"https://[::ffff:192.168.1.1]/hook",
+		] {
+			assert!(
+				matches!(
+					validate_callback_url(internal, &config),
+					Err(WebhookError::InvalidUrl(_))
+				),
+				"{} was accepted",
+				internal
+			);
+		}
+		assert!(validate_callback_url("https://93.184.216.34/hook", &config).is_ok());
+
+		let config = WebhookConfig {
+			allowed_internal_networks: vec!["127.0.0.1".to_string()],
+			..config
+		};
+		assert!(validate_callback_url("http://127.0.0.1:8080/hook", &config).is_ok());
+		assert!(validate_callback_url("https://10.0.0.5/hook", &config).is_err());
+	}
+
+	#[test]
+	fn test_target_policy_refuses_non_public_addresses() {
+		let policy = TargetPolicy::default();
+		for internal in [
+			"0.0.0.0",
+			"10.1.2.3",
+			"100.64.0.1",
+			"127.0.0.1",
+			"169.254.169.254",
+			"172.16.0.1",
+			"192.0.0.8",
+			"192.168.0.1",
+			"198.18.0.1",
+			"224.0.0.1",
+			"255.255.255.255",
+			"::",
+			"::1",
+			"::ffff:127.0.0.1",
+			"64:ff9b::a00:1",
+			"fc00::1",
+			"fd12:3456::1",
+			"fe80::1",
+			"2001:db8::1",
+		] {
+			let ip: IpAddr = internal.parse().expect("ip");
+			assert!(!policy.permits(ip), "{} was permitted", internal);
+		}
+		for public in [
+			"93.184.216.34",
+			"8.8.8.8",
+			"2606:4700::1111",
+			"64:ff9b::808:808",
+		] {
+			let ip: IpAddr = public.parse().expect("ip");
+			assert!(policy.permits(ip), "{} was refused", public);
+		}
+
+		let policy = TargetPolicy::from_config(&WebhookConfig {
+			allowed_internal_networks: vec!["10.1.2.0/24".to_string(), "fd00::1".to_string()],
+			..Default::default()
+		})
+		.expect("policy");
+		assert!(policy.permits("10.1.2.200".parse().expect("ip")));
+		assert!(!policy.permits("10.1.3.1".parse().expect("ip")));
+		assert!(policy.permits("fd00::1".parse().expect("ip")));
+
+		assert!(matches!(
+			TargetPolicy::from_config(&WebhookConfig {
+				allowed_internal_networks: vec!["intranet".to_string()],
+				..Default::default()
+			}),
+			Err(WebhookError::InvalidAllowList(_))
+		));
+	}
+
+	#[test]
+	fn test_signer_signs_with_hmac_and_ed25519() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let key_pair = CustodyKeyPair::generate().expect("key pair");
+		let key_path = dir.path().join("instance-key.json");
+		std::fs::write(&key_path, serde_json::to_vec(&key_pair).expect("json")).expect("write");
+
+		let signer = WebhookSigner::from_config(&WebhookConfig {
+			hmac_secret: "topsecret".to_string(),
+			signing_key_path: Some(key_path.to_string_lossy().to_string()),
+			..Default::default()
+		})
+		.expect("signer");
+		let body = br#"{"job_id":"1"}"#;
+		let headers = signer.sign(1_700_000_000, body);
+		let header = |name: &str| {
+			headers
+				.iter()
+				.find(|(n, _)| *n == name)
+				.map(|(_, v)| v.clone())
+				.expect("header present")
+		};
+		assert_eq!(header(TIMESTAMP_HEADER), "1700000000");
+
+		let message = signed_message(1_700_000_000, body);
+		let mut mac = Hmac::<Sha256>::new_from_slice(b"topsecret").expect("hmac");
+		mac.update(&message);
+		assert_eq!(
+			header(HMAC_SIGNATURE_HEADER),
+			format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+		);
+
+		let public_key: [u8; 32] = hex::decode(&key_pair.public_key)
+			.expect("hex")
+			.try_into()
+			.expect("32 bytes");
+		let signature: [u8; 64] = hex::decode(header(ED25519_SIGNATURE_HEADER))
+			.expect("hex")
+			.try_into()
+			.expect("64 bytes");
+		VerifyingKey::from_bytes(&public_key)
+			.expect("public key")
+			.verify_strict(&message, &Signature::from_bytes(&signature))
+			.expect("signature verifies");
+	}
+
+	/// Callback receiver that fails the first `failures` requests
+	#[derive(Default)]
+	struct Receiver {
+		failures: usize,
+		requests: Mutex<Vec<(HeaderMap, Vec<u8>)>>,
+	}
+
+	async fn receive(
+		State(receiver): State<Arc<Receiver>>,
+		headers: HeaderMap,
+		body: axum::body::Bytes,
+	) -> StatusCode {
+		let mut requests = receiver.requests.lock().expect("lock");
+		requests.push((headers, body.to_vec()));
+		if requests.len() <= receiver.failures {
+			StatusCode::SERVICE_UNAVAILABLE
+		} else {
+			StatusCode::NO_CONTENT
+		}
+	}
+
+	async fn start_receiver(failures: usize) -> (Arc<Receiver>, String) {
+		let receiver = Arc::new(Receiver {
+			failures,
+			..Default::default()
+		});
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+			.await
+			.expect("bind");
+		let url = format!("http://{}/hook", listener.local_addr().expect("addr"));
+		let app = Router::new()
+			.route("/hook", post(receive))
+			.with_state(receiver.clone());
+		tokio::spawn(async move { axum::serve(listener, app).await });
+		(receiver, url)
+	}
+
+	/// Wait until the job has recorded `count` callback deliveries
+	async fn wait_for_deliveries(queue: &JobQueue, job_id: &str, count: usize) -> Job {
+		for _ in 0..200 {
+			let job = queue.get_job(job_id).await.expect("job");
+			if job.callback_deliveries.len() >= count {
+				return job;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+		panic!("callback deliveries not recorded");
+	}
+
+	#[tokio::test]
+	async fn test_finished_job_callback_is_retried_and_recorded() {
+		let (receiver, url) = start_receiver(2).await;
+		let queue = Arc::new(JobQueue::new());
+		let dispatcher = Arc::new(WebhookDispatcher::new(&hmac_config()).expect("dispatcher"));
+		dispatcher.clone().spawn(queue.clone());
+
+		let job_id = queue
+			.enqueue_job(Job::new("dump.csv".to_string(), 10).with_callback(Some(url)))
+			.await
+			.expect("enqueue");
+		queue.claim_next(0).await.expect("claim").expect("job");
+		queue
+			.update_job(&job_id, |j| j.fail("bad input".to_string()))
+			.await
+			.expect("fail");
+
+		let job = wait_for_deliveries(&queue, &job_id, 3).await;
+		let attempts: Vec<_> = job
+			.callback_deliveries
+			.iter()
+			.map(|d| (d.attempt, d.status_code, d.delivered))
+			.collect();
+		assert_eq!(
+			attempts,
+			vec![
+				(1, Some(503), false),
+				(2, Some(503), false),
+				(3, Some(204), true)
+			]
+		);
+
+		let requests = receiver.requests.lock().expect("lock");
+		assert_eq!(requests.len(), 3);
+		let (headers, body) = &requests[2];
+		let payload: serde_json::Value = serde_json::from_slice(body).expect("json");
+		assert_eq!(payload["job_id"], job_id.as_str());
+		assert_eq!(payload["status"], "failed");
+		assert_eq!(payload["error_message"], "bad input");
+		assert_eq!(headers[JOB_ID_HEADER], job_id.as_str());
+
+		let timestamp: i64 = headers[TIMESTAMP_HEADER]
+			.to_str()
+			.expect("ascii")
+			.parse()
+			.expect("timestamp");
+		let mut mac = Hmac::<Sha256>::new_from_slice(b"topsecret").expect("hmac");
+		mac.update(&signed_message(timestamp, body));
+		assert_eq!(
+			headers[HMAC_SIGNATURE_HEADER].to_str().expect("ascii"),
+			format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+		);
+	}
+
+	#[tokio::test]
+	async fn test_callbacks_to_internal_targets_are_refused_at_send_time() {
+		let (receiver, url) = start_receiver(0).await;
+		let config = WebhookConfig {
+			max_attempts: 1,
+			allowed_internal_networks: Vec::new(),
+			..hmac_config()
+		};
+		let queue = Arc::new(JobQueue::new());
+		let dispatcher = Arc::new(WebhookDispatcher::new(&config).expect("dispatcher"));
+
+		// By address, and by a name that resolves to loopback
+		let by_name = url.replace("127.0.0.1", "localhost");
+		for url in [url, by_name] {
+			let job_id = queue
+				.enqueue_job(Job::new("dump.csv".to_string(), 10).with_callback(Some(url)))
+				.await
+				.expect("enqueue");
+			let job = queue.cancel_job(&job_id).await.expect("cancel");
+			dispatcher.deliver(&queue, job).await;
+
+			let job = queue.get_job(&job_id).await.expect("job");
+			let delivery = &job.callback_deliveries[0];
+			assert!(!delivery.delivered);
+			assert!(delivery.status_code.is_none());
+			assert!(
+				delivery
+					.error
+					.as_deref()
+					.is_some_and(|e| e.contains("not a public address")),
+				"{:?}",
+				delivery.error
+			);
+		}
+		assert!(receiver.requests.lock().expect("lock").is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_jobs_without_callback_or_unfinished_are_not_sent() {
+		let (receiver, url) = start_receiver(0).await;
+		let queue = Arc::new(JobQueue::new());
+		let dispatcher = Arc::new(WebhookDispatcher::new(&hmac_config()).expect("dispatcher"));
+		dispatcher.clone().spawn(queue.clone());
+
+		let plain = queue
+			.enqueue_job(Job::new("plain.csv".to_string(), 10))
+			.await
+			.expect("enqueue");
+		queue.cancel_job(&plain).await.expect("cancel");
+		let hooked = queue
+			.enqueue_job(Job::new("hooked.csv".to_string(), 10).with_callback(Some(url)))
+			.await
+			.expect("enqueue");
+		queue.claim_next(0).await.expect("claim");
+		queue.claim_next(1).await.expect("claim");
+		assert!(receiver.requests.lock().expect("lock").is_empty());
+
+		queue.cancel_job(&hooked).await.expect("cancel");
+		let job = wait_for_deliveries(&queue, &hooked, 1).await;
+		assert!(job.callback_deliveries[0].delivered);
+		assert_eq!(receiver.requests.lock().expect("lock").len(), 1);
+	}
+
+	#[tokio::test]
+	async fn test_callbacks_of_jobs_finished_before_startup_are_sent() {
+		let (receiver, url) = start_receiver(0).await;
+		let queue = Arc::new(JobQueue::new());
+		let dispatcher = Arc::new(WebhookDispatcher::new(&hmac_config()).expect("dispatcher"));
+
+		// Finished while no dispatcher was watching, e.g. before a restart
+		let missed = queue
+			.enqueue_job(Job::new("missed.csv".to_string(), 10).with_callback(Some(url.clone())))
+			.await
+			.expect("enqueue");
+		queue.cancel_job(&missed).await.expect("cancel");
+		let sent = queue
+			.enqueue_job(Job::new("sent.csv".to_string(), 10).with_callback(Some(url)))
+			.await
+			.expect("enqueue");
+		let job = queue.cancel_job(&sent).await.expect("cancel");
+		dispatcher.deliver(&queue, job).await;
+		assert_eq!(receiver.requests.lock().expect("lock").len(), 1);
+
+		dispatcher.clone().spawn(queue.clone());
+		let job = wait_for_deliveries(&queue, &missed, 1).await;
+		assert!(job.callback_deliveries[0].delivered);
+
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		let job = queue.get_job(&sent).await.expect("job");
+		assert_eq!(job.callback_deliveries.len(), 1);
+		let requests = receiver.requests.lock().expect("lock");
+		assert_eq!(requests.len(), 2);
+		assert_eq!(requests[1].0[JOB_ID_HEADER], missed.as_str());
+	}
+}
@@ -319,6 +319,12 @@ pub enum InputFormat {
 	/// Protocol Buffers (binary format)
 	#[value(name = "protobuf")]
 	Protobuf,
+	/// Combo lists and stealer logs (`email:password`, `url:user:pass`, `email|hash|salt`)
+	#[value(name = "combo")]
+	Combo,
+	/// MySQL or PostgreSQL SQL dump (`CREATE TABLE`, `INSERT`, `COPY ... FROM stdin`)
+	#[value(name = "sql")]
+	Sql,
 }
 
 impl std::fmt::Display for InputFormat {
@@ -329,6 +335,8 @@ impl std::fmt::Display for InputFormat {
 			InputFormat::Json => write!(f, "json"),
 			InputFormat::Yaml => write!(f, "yaml"),
 			InputFormat::Protobuf => write!(f, "protobuf"),
+			InputFormat::Combo => write!(f, "combo"),
+			InputFormat::Sql => write!(f, "sql"),
 		}
 	}
 }
@@ -372,6 +380,11 @@ pub struct ServerArgs {
 	#[arg(short, long, value_name = "PATH")]
 	pub config: Option<String>,
 
+	/// Address to listen on: IPv4/IPv6, optionally with :port ([v6]:port); repeat for
+	/// multiple listeners - overrides config (default: 127.0.0.1)
+	#[arg(long = "bind", value_name = "ADDR")]
+	pub bind: Vec<String>,
+
 	/// Path to TLS certificate file (PEM format) - overrides config
 	#[arg(long, value_name = "PATH")]
 	pub cert: Option<String>,
@@ -380,6 +393,10 @@ pub struct ServerArgs {
 	#[arg(long, value_name = "PATH")]
 	pub key: Option<String>,
 
+	/// CA bundle (PEM format) for verifying client certificates; enables mutual TLS - overrides config
+	#[arg(long, value_name = "PATH")]
+	pub client_ca: Option<String>,
+
 	/// OAuth 2.0 Client ID - overrides config
 	#[arg(long, value_name = "ID")]
 	pub oauth_client_id: Option<String>,
@@ -400,6 +417,10 @@ pub struct ServerArgs {
 	#[arg(long, value_name = "CONN_STRING")]
 	pub database: Option<String>,
 
+	/// Maximum accepted upload size in bytes - overrides config
+	#[arg(long, value_name = "BYTES")]
+	pub max_upload_bytes: Option<u64>,
+
 	/// Ollama server URL
 	#[arg(long, value_name = "URL")]
 	pub ollama_url: Option<String>,
@@ -4,11 +4,13 @@
 //! - Configuration management and loading
 //! - Hash utilities (SHA-256, BLAKE3, fingerprinting)
 //! - File locking mechanisms
+//! - Prometheus metrics
 //! - Secure deletion procedures
 
 pub mod config;
 pub mod file_lock;
 pub mod hash_utils;
+pub mod metrics;
 pub mod secure_deletion;
 
 pub use config::Config;
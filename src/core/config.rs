@@ -10,8 +10,16 @@
 //! - `email_suffix_substitutions`: Map of canonical domains to alternate domain forms
 //!
 //! See `config.schema.json` for complete schema definition and validation rules.
+//!
+//! The server keeps its configuration in a [`ConfigStore`] so the suffix rules
+//! and custom passwords can be changed through the admin API without a restart.
 
-use std::{collections::HashMap, fs, path::Path};
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, RwLock},
+};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -30,8 +38,14 @@ pub enum ConfigError {
 
 	#[error("Configuration validation error: {0}")]
 	ValidationError(String),
+
+	#[error("Failed to write config file: {0}")]
+	WriteError(String),
 }
 
+/// Placeholder shown instead of secrets when the configuration is displayed
+pub const REDACTED: &str = "[REDACTED]";
+
 /// HIBP API configuration.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HibpConfig {
@@ -52,7 +66,7 @@ pub struct ApiKeys {
 }
 
 /// OAuth 2.0/OIDC configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OAuth {
 	#[serde(default)]
 	pub client_id: String,
@@ -62,6 +76,54 @@ pub struct OAuth {
 
 	#[serde(default)]
 	pub discovery_url: String,
+
+	/// Expected issuer (`iss`) of access tokens
+	#[serde(default)]
+	pub issuer: String,
+
+	/// Expected audience (`aud`) of access tokens
+	#[serde(default)]
+	pub audience: String,
+
+	/// JWKS endpoint for verifying JWT access tokens
+	#[serde(default)]
+	pub jwks_uri: String,
+
+	/// RFC 7662 token introspection endpoint for opaque access tokens
+	#[serde(default)]
+	pub introspection_endpoint: String,
+
+	/// Seconds a fetched JWKS is cached
+	#[serde(default = "default_jwks_cache_ttl_secs")]
+	pub jwks_cache_ttl_secs: u64,
+
+	/// Allowed clock skew in seconds when checking token expiry
+	#[serde(default = "default_token_leeway_secs")]
+	pub leeway_secs: u64,
+}
+
+fn default_jwks_cache_ttl_secs() -> u64 {
+	3600
+}
+
+fn default_token_leeway_secs() -> u64 {
+	60
+}
+
+impl Default for OAuth {
+	fn default() -> Self {
+		Self {
+			client_id: String::new(),
+			client_secret: String::new(),
+			discovery_url: String::new(),
+			issuer: String::new(),
+			audience: String::new(),
+			jwks_uri: String::new(),
+			introspection_endpoint: String::new(),
+			jwks_cache_ttl_secs: default_jwks_cache_ttl_secs(),
+			leeway_secs: default_token_leeway_secs(),
+		}
+	}
 }
 
 /// Email suffix substitution rules.
@@ -92,17 +154,44 @@ pub struct WorkingDirectoryConfig {
 	/// Whether to verify working directory is mounted with noexec
 	#[serde(default = "default_verify_noexec")]
 	pub verify_noexec: bool,
+
+	/// Free space below which the server reports itself not ready, in bytes
+	#[serde(default = "default_min_free_bytes")]
+	pub min_free_bytes: u64,
+
+	/// Bytes that may be extracted from one compressed input, across all members
+	#[serde(default = "default_max_extracted_bytes")]
+	pub max_extracted_bytes: u64,
+
+	/// Largest expanded-to-compressed size ratio accepted for an archive member
+	#[serde(default = "default_max_compression_ratio")]
+	pub max_compression_ratio: u64,
 }
 
 fn default_verify_noexec() -> bool {
 	true
 }
 
+fn default_min_free_bytes() -> u64 {
+	1024 * 1024 * 1024
+}
+
+fn default_max_extracted_bytes() -> u64 {
+	4 * 1024 * 1024 * 1024
+}
+
+fn default_max_compression_ratio() -> u64 {
+	200
+}
+
 impl Default for WorkingDirectoryConfig {
 	fn default() -> Self {
 		Self {
 			path: None,
 			verify_noexec: true,
+			min_free_bytes: default_min_free_bytes(),
+			max_extracted_bytes: default_max_extracted_bytes(),
+			max_compression_ratio: default_max_compression_ratio(),
 		}
 	}
 }
@@ -141,6 +230,244 @@ impl Default for OllamaConfig {
 	}
 }
 
+/// HTTP API server configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerConfig {
+	/// Maximum accepted upload size in bytes
+	#[serde(default = "default_max_upload_bytes")]
+	pub max_upload_bytes: u64,
+
+	/// Resumable uploads that receive no chunk for this many seconds are
+	/// removed (0 keeps them until finalized or terminated)
+	#[serde(default = "default_upload_expiry_secs")]
+	pub upload_expiry_secs: u64,
+
+	/// Addresses to listen on: an IPv4/IPv6 address (using the `--port` port)
+	/// or `address:port` (`[v6]:port` for IPv6); one listener per entry
+	#[serde(default = "default_bind_addresses")]
+	pub bind_addresses: Vec<String>,
+
+	/// TLS certificate, client authentication and reload settings
+	#[serde(default)]
+	pub tls: ServerTlsConfig,
+
+	/// Per-client rate limits and daily byte quotas
+	#[serde(default)]
+	pub rate_limits: RateLimitsConfig,
+
+	/// Signed job completion callbacks
+	#[serde(default)]
+	pub webhooks: WebhookConfig,
+
+	/// Directory whose files `POST /api/v1/ingest` may queue by relative path;
+	/// the endpoint is refused when unset
+	#[serde(default)]
+	pub import_dir: Option<String>,
+}
+
+fn default_max_upload_bytes() -> u64 {
+	64 * 1024 * 1024 * 1024
+}
+
+fn default_upload_expiry_secs() -> u64 {
+	7 * 24 * 60 * 60
+}
+
+fn default_bind_addresses() -> Vec<String> {
+	vec!["127.0.0.1".to_string()]
+}
+
+impl Default for ServerConfig {
+	fn default() -> Self {
+		Self {
+			max_upload_bytes: default_max_upload_bytes(),
+			upload_expiry_secs: default_upload_expiry_secs(),
+			bind_addresses: default_bind_addresses(),
+			tls: ServerTlsConfig::default(),
+			rate_limits: RateLimitsConfig::default(),
+			webhooks: WebhookConfig::default(),
+			import_dir: None,
+		}
+	}
+}
+
+/// Server TLS configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerTlsConfig {
+	/// Certificate chain (PEM); `--cert` overrides
+	#[serde(default)]
+	pub cert_path: Option<String>,
+
+	/// Private key (PEM); `--key` overrides
+	#[serde(default)]
+	pub key_path: Option<String>,
+
+	/// CA bundle (PEM) for verifying client certificates; enables mutual TLS
+	#[serde(default)]
+	pub client_ca_path: Option<String>,
+
+	/// Accept clients without a certificate when mutual TLS is enabled
+	#[serde(default)]
+	pub client_auth_optional: bool,
+
+	/// How often to check the certificate files for changes, in seconds (0 disables;
+	/// SIGHUP always reloads)
+	#[serde(default = "default_tls_reload_interval_secs")]
+	pub reload_interval_secs: u64,
+}
+
+fn default_tls_reload_interval_secs() -> u64 {
+	30
+}
+
+impl Default for ServerTlsConfig {
+	fn default() -> Self {
+		Self {
+			cert_path: None,
+			key_path: None,
+			client_ca_path: None,
+			client_auth_optional: false,
+			reload_interval_secs: default_tls_reload_interval_secs(),
+		}
+	}
+}
+
+/// Rate limits per route class, applied to each authenticated principal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitsConfig {
+	/// Job submission and upload routes
+	#[serde(default = "default_ingest_rate_limit")]
+	pub ingest: RateLimitConfig,
+
+	/// Address, credential and range lookup routes
+	#[serde(default = "default_lookup_rate_limit")]
+	pub lookup: RateLimitConfig,
+}
+
+/// Token-bucket rate and daily byte quota; 0 disables a limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+	/// Sustained requests per minute
+	#[serde(default)]
+	pub requests_per_minute: u32,
+
+	/// Requests allowed in a burst above the sustained rate
+	#[serde(default)]
+	pub burst: u32,
+
+	/// Request body bytes accepted per UTC day
+	#[serde(default)]
+	pub daily_bytes: u64,
+}
+
+fn default_ingest_rate_limit() -> RateLimitConfig {
+	RateLimitConfig {
+		requests_per_minute: 60,
+		burst: 20,
+		daily_bytes: 256 * 1024 * 1024 * 1024,
+	}
+}
+
+fn default_lookup_rate_limit() -> RateLimitConfig {
+	RateLimitConfig {
+		requests_per_minute: 600,
+		burst: 100,
+		daily_bytes: 0,
+	}
+}
+
+impl Default for RateLimitsConfig {
+	fn default() -> Self {
+		Self {
+			ingest: default_ingest_rate_limit(),
+			lookup: default_lookup_rate_limit(),
+		}
+	}
+}
+
+/// Job completion callback delivery and signing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+	/// Shared secret for the HMAC-SHA256 signature header (empty disables)
+	#[serde(default)]
+	pub hmac_secret: String,
+
+	/// JSON key pair file of the instance's ed25519 key for the ed25519 signature header
+	#[serde(default)]
+	pub signing_key_path: Option<String>,
+
+	/// Delivery attempts per callback
+	#[serde(default = "default_webhook_max_attempts")]
+	pub max_attempts: u32,
+
+	/// Delay before the first retry in seconds; doubles with every further attempt
+	#[serde(default = "default_webhook_retry_base_delay_secs")]
+	pub retry_base_delay_secs: u64,
+
+	/// Timeout of each delivery request in seconds
+	#[serde(default = "default_webhook_timeout_secs")]
+	pub timeout_secs: u64,
+
+	/// Accept plain `http://` callback URLs
+	#[serde(default)]
+	pub allow_http: bool,
+
+	/// Non-public addresses or CIDR blocks callbacks may still be sent to;
+	/// loopback, private, link-local and other internal targets are refused otherwise
+	#[serde(default)]
+	pub allowed_internal_networks: Vec<String>,
+}
+
+impl WebhookConfig {
+	/// Whether callbacks can be signed
+	pub fn signing_configured(&self) -> bool {
+		!self.hmac_secret.is_empty() || self.signing_key_path.is_some()
+	}
+}
+
+fn default_webhook_max_attempts() -> u32 {
+	5
+}
+
+fn default_webhook_retry_base_delay_secs() -> u64 {
+	5
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+	10
+}
+
+impl Default for WebhookConfig {
+	fn default() -> Self {
+		Self {
+			hmac_secret: String::new(),
+			signing_key_path: None,
+			max_attempts: default_webhook_max_attempts(),
+			retry_base_delay_secs: default_webhook_retry_base_delay_secs(),
+			timeout_secs: default_webhook_timeout_secs(),
+			allow_http: false,
+			allowed_internal_networks: Vec::new(),
+		}
+	}
+}
+
+/// Parse a bind address: an IP address (listening on `default_port`) or `address:port`.
+pub fn parse_bind_address(
+	entry: &str,
+	default_port: u16,
+) -> Result<std::net::SocketAddr, ConfigError> {
+	let entry = entry.trim();
+	if let Ok(addr) = entry.parse::<std::net::SocketAddr>() {
+		return Ok(addr);
+	}
+	entry
+		.trim_start_matches('[')
+		.trim_end_matches(']')
+		.parse::<std::net::IpAddr>()
+		.map(|ip| std::net::SocketAddr::new(ip, default_port))
+		.map_err(|_| ConfigError::ValidationError(format!("invalid bind address: {}", entry)))
+}
+
 /// Services configuration
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ServicesConfig {
@@ -170,6 +497,10 @@ pub struct Config {
 	/// Working directory configuration
 	#[serde(default)]
 	pub working_directory: WorkingDirectoryConfig,
+
+	/// HTTP API server configuration
+	#[serde(default)]
+	pub server: ServerConfig,
 }
 
 impl Config {
@@ -270,6 +601,20 @@ impl Config {
 		paths
 	}
 
+	/// File that [`Config::load_with_search`] reads, or that a new configuration
+	/// should be written to when none exists yet.
+	///
+	/// # Arguments
+	/// * `explicit_path` - Optional explicit path provided by user
+	pub fn config_file_path(explicit_path: Option<&str>) -> Option<PathBuf> {
+		let paths = Self::get_config_search_paths(explicit_path);
+		paths
+			.iter()
+			.find(|path| fs::metadata(path).is_ok())
+			.or_else(|| paths.first())
+			.map(PathBuf::from)
+	}
+
 	/// Load configuration with environment variable overrides.
 	///
 	/// Environment variables take precedence:
@@ -378,6 +723,49 @@ impl Config {
 		self.custom_passwords.passwords.push(password);
 	}
 
+	/// Remove a suffix substitution rule.
+	///
+	/// # Returns
+	/// The rule's alternates, or `None` if no rule existed for the suffix
+	pub fn remove_suffix_rule(&mut self, canonical_suffix: &str) -> Option<Vec<String>> {
+		self.email_suffix_substitutions
+			.rules
+			.remove(canonical_suffix)
+	}
+
+	/// Remove a custom password.
+	///
+	/// # Returns
+	/// Whether the password was configured
+	pub fn remove_custom_password(&mut self, password: &str) -> bool {
+		let before = self.custom_passwords.passwords.len();
+		self.custom_passwords.passwords.retain(|p| p != password);
+		self.custom_passwords.passwords.len() != before
+	}
+
+	/// Copy of the configuration that is safe to display.
+	///
+	/// The OAuth client secret, HIBP API key, webhook HMAC secret and custom
+	/// passwords are replaced by [`REDACTED`]; unset secrets stay empty.
+	pub fn redacted(&self) -> Config {
+		fn redact(secret: &mut String) {
+			if !secret.is_empty() {
+				*secret = REDACTED.to_string();
+			}
+		}
+
+		let mut config = self.clone();
+		redact(&mut config.oauth.client_secret);
+		redact(&mut config.api_keys.hibp.api_key);
+		redact(&mut config.server.webhooks.hmac_secret);
+		config
+			.custom_passwords
+			.passwords
+			.iter_mut()
+			.for_each(redact);
+		config
+	}
+
 	/// Validate configuration against schema constraints.
 	///
 	/// Validates:
@@ -402,6 +790,11 @@ impl Config {
 			));
 		}
 
+		// Validate bind addresses (the port is irrelevant here)
+		for entry in &self.server.bind_addresses {
+			parse_bind_address(entry, 0)?;
+		}
+
 		// Validate email suffix substitutions structure
 		for (canonical, alternates) in &self.email_suffix_substitutions.rules {
 			// Validate canonical domain format
@@ -449,6 +842,133 @@ impl Config {
 	}
 }
 
+/// Configuration shared by the server and changed at runtime by the admin API
+///
+/// Readers take a snapshot with [`ConfigStore::current`], so a running job keeps
+/// the configuration it started with. Updates are validated and written back to
+/// the configuration file before they are swapped in.
+pub struct ConfigStore {
+	current: RwLock<Arc<Config>>,
+	/// File that runtime changes are persisted to; `None` keeps them in memory
+	path: Option<PathBuf>,
+	/// Serializes updates so concurrent changes are not lost
+	update_lock: Mutex<()>,
+}
+
+impl ConfigStore {
+	/// Wrap a loaded configuration, persisting changes to `path` if given
+	pub fn new(config: Config, path: Option<PathBuf>) -> Self {
+		Self {
+			current: RwLock::new(Arc::new(config)),
+			path,
+			update_lock: Mutex::new(()),
+		}
+	}
+
+	/// Snapshot of the current configuration
+	pub fn current(&self) -> Arc<Config> {
+		self.current
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.clone()
+	}
+
+	/// File that runtime changes are persisted to
+	pub fn path(&self) -> Option<&Path> {
+		self.path.as_deref()
+	}
+
+	/// Apply a change, validate the result, persist it and make it current
+	///
+	/// Nothing changes if `change` or validation fails. Only the sections that
+	/// can be edited at runtime (`email_suffix_substitutions` and
+	/// `custom_passwords`) are written back; the rest of the file is kept as is.
+	pub fn update<T>(
+		&self,
+		change: impl FnOnce(&mut Config) -> Result<T, ConfigError>,
+	) -> Result<(T, Arc<Config>), ConfigError> {
+		let _guard = self
+			.update_lock
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		let mut config = (*self.current()).clone();
+		let outcome = change(&mut config)?;
+		config.validate()?;
+
+		if let Some(path) = &self.path {
+			persist_runtime_sections(path, &config)?;
+		}
+
+		let config = Arc::new(config);
+		*self
+			.current
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner()) = config.clone();
+		Ok((outcome, config))
+	}
+}
+
+/// Write the runtime-editable sections of `config` into the JSON file at `path`
+///
+/// The file is replaced atomically and keeps its permissions; a new file is
+/// only readable by its owner since it may later hold secrets.
+fn persist_runtime_sections(path: &Path, config: &Config) -> Result<(), ConfigError> {
+	let write_error =
+		|e: std::io::Error| ConfigError::WriteError(format!("{}: {}", path.display(), e));
+
+	let mut document = match fs::read_to_string(path) {
+		Ok(contents) => serde_json::from_str(&contents)?,
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+		Err(e) => {
+			return Err(ConfigError::ReadError(format!("{}: {}", path.display(), e)));
+		}
+	};
+	let object = document.as_object_mut().ok_or_else(|| {
+		ConfigError::ValidationError(format!("{} is not a JSON object", path.display()))
+	})?;
+	object.insert(
+		"email_suffix_substitutions".to_string(),
+		serde_json::to_value(&config.email_suffix_substitutions)?,
+	);
+	object.insert(
+		"custom_passwords".to_string(),
+		serde_json::to_value(&config.custom_passwords)?,
+	);
+
+	if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+		fs::create_dir_all(parent).map_err(write_error)?;
+	}
+
+	let mut tmp_name = path.as_os_str().to_owned();
+	tmp_name.push(".tmp");
+	let tmp_path = PathBuf::from(tmp_name);
+
+	let mut options = fs::OpenOptions::new();
+	options.write(true).create(true).truncate(true);
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::OpenOptionsExt;
+		options.mode(0o600);
+	}
+
+	let result = (|| {
+		let mut file = options.open(&tmp_path)?;
+		serde_json::to_writer_pretty(&mut file, &document)?;
+		std::io::Write::write_all(&mut file, b"\n")?;
+		file.sync_all()?;
+		if let Ok(metadata) = fs::metadata(path) {
+			fs::set_permissions(&tmp_path, metadata.permissions())?;
+		}
+		fs::rename(&tmp_path, path)
+	})();
+
+	if result.is_err() {
+		let _ = fs::remove_file(&tmp_path);
+	}
+	result.map_err(write_error)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -458,6 +978,25 @@ mod tests {
 		let config = Config::default();
 		assert!(config.hibp_api_key().is_empty());
 		assert!(config.all_suffix_rules().is_empty());
+		assert_eq!(config.server.max_upload_bytes, 64 * 1024 * 1024 * 1024);
+		assert_eq!(config.server.upload_expiry_secs, 7 * 24 * 60 * 60);
+		assert_eq!(config.server.bind_addresses, vec!["127.0.0.1"]);
+		assert_eq!(config.server.tls.reload_interval_secs, 30);
+		assert_eq!(config.server.rate_limits.ingest.requests_per_minute, 60);
+		assert_eq!(config.server.rate_limits.lookup.daily_bytes, 0);
+	}
+
+	#[test]
+	fn test_parse_bind_address() {
+		let addr = parse_bind_address("0.0.0.0", 8443).expect("ipv4");
+		assert_eq!(addr.to_string(), "0.0.0.0:8443");
+		let addr = parse_bind_address("::", 8443).expect("ipv6");
+		assert_eq!(addr.to_string(), "[::]:8443");
+		let addr = parse_bind_address("[::1]:9443", 8443).expect("ipv6 with port");
+		assert_eq!(addr.to_string(), "[::1]:9443");
+		let addr = parse_bind_address("10.0.0.5:443", 8443).expect("ipv4 with port");
+		assert_eq!(addr.port(), 443);
+		assert!(parse_bind_address("localhost", 8443).is_err());
 	}
 
 	#[test]
@@ -534,4 +1073,88 @@ mod tests {
 		assert!(!Config::is_valid_domain("invalid-.com"));
 		assert!(!Config::is_valid_domain("invalid..com"));
 	}
+
+	#[test]
+	fn test_redacted_hides_secrets() {
+		let mut config = Config::default();
+		config.oauth.client_secret = "client-secret".to_string();
+		config.api_keys.hibp.api_key = "abcdef0123456789abcdef0123456789".to_string();
+		config.server.webhooks.hmac_secret = "hook-secret".to_string();
+		config.add_custom_password("Winter2024!".to_string());
+
+		let redacted = config.redacted();
+		assert_eq!(redacted.oauth.client_secret, REDACTED);
+		assert_eq!(redacted.api_keys.hibp.api_key, REDACTED);
+		assert_eq!(redacted.server.webhooks.hmac_secret, REDACTED);
+		assert_eq!(redacted.custom_passwords.passwords, vec![REDACTED]);
+
+		let json = serde_json::to_string(&redacted).expect("serialize");
+		for secret in [
+			"client-secret",
+			"abcdef0123456789",
+			"hook-secret",
+			"Winter2024!",
+		] {
+			assert!(!json.contains(secret), "{} leaked", secret);
+		}
+
+		// Unset secrets are not reported as set
+		assert!(Config::default().redacted().oauth.client_secret.is_empty());
+	}
+
+	#[test]
+	fn test_config_store_persists_runtime_sections() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let path = dir.path().join("config.json");
+		fs::write(
+			&path,
+			r#"{"oauth": {"client_id": "dumptruck", "client_secret": "keep-me"}, "extra": 1}"#,
+		)
+		.expect("write config");
+		let store = ConfigStore::new(Config::from_file(&path).expect("load"), Some(path.clone()));
+
+		store
+			.update(|config| {
+				config.add_suffix_rule("gmail.com".to_string(), vec!["googlemail.com".to_string()]);
+				config.add_custom_password("Summer2024!".to_string());
+				Ok(())
+			})
+			.expect("update");
+		assert!(store.current().has_suffix_alternates("gmail.com"));
+
+		let reloaded = Config::from_file(&path).expect("reload");
+		assert_eq!(
+			reloaded.get_suffix_alternates("gmail.com"),
+			vec!["googlemail.com"]
+		);
+		assert_eq!(reloaded.custom_passwords.passwords, vec!["Summer2024!"]);
+		assert_eq!(reloaded.oauth.client_secret, "keep-me");
+		let raw: serde_json::Value =
+			serde_json::from_str(&fs::read_to_string(&path).expect("read")).expect("json");
+		assert_eq!(raw["extra"], 1);
+
+		// Invalid changes are neither applied nor written
+		let err = store.update(|config| {
+			config.add_suffix_rule("-bad.com".to_string(), vec![]);
+			Ok(())
+		});
+		assert!(matches!(err, Err(ConfigError::ValidationError(_))));
+		assert!(!store.current().has_suffix_alternates("-bad.com"));
+		assert!(
+			!Config::from_file(&path)
+				.expect("reload")
+				.has_suffix_alternates("-bad.com")
+		);
+
+		store
+			.update(|config| Ok(config.remove_custom_password("Summer2024!")))
+			.expect("remove");
+		assert!(
+			Config::from_file(&path)
+				.expect("reload")
+				.custom_passwords
+				.passwords
+				.is_empty()
+		);
+	}
 }
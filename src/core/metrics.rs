@@ -0,0 +1,199 @@
+//! Prometheus metrics for the server, job workers and ingest pipeline.
+//!
+//! Metrics live in one process-wide registry so that deeply nested code (the
+//! enrichment clients, SQLite storage) can record without threading a handle
+//! through every call. The server renders them at `GET /metrics`.
+//!
+//! Label values are always drawn from small fixed sets (route templates, job
+//! statuses, PII types, operation names) and never from request data.
+
+use std::{future::Future, io, time::Instant};
+
+use once_cell::sync::Lazy;
+use prometheus::{
+	Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+	Registry, TextEncoder,
+};
+
+/// Content type of the Prometheus text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Buckets for SQLite writes, which are usually well under a millisecond
+const SQLITE_BUCKETS: &[f64] = &[
+	0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0,
+];
+
+static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+/// Get the process-wide metrics
+pub fn metrics() -> &'static Metrics {
+	&METRICS
+}
+
+/// All metrics exported by dumptruck
+pub struct Metrics {
+	registry: Registry,
+	/// HTTP request latency by method, route template and status code
+	pub http_request_duration: HistogramVec,
+	/// Jobs by status, refreshed when metrics are rendered
+	pub jobs: IntGaugeVec,
+	/// Job workers running
+	pub workers: IntGauge,
+	/// Job workers currently processing a job
+	pub workers_busy: IntGauge,
+	/// Rows that have gone through the detection pipeline
+	pub pipeline_rows: IntCounter,
+	/// PII findings by PII type
+	pub pii_findings: IntCounterVec,
+	/// Enrichment call latency by service and operation
+	pub enrichment_duration: HistogramVec,
+	/// Failed enrichment calls by service and operation
+	pub enrichment_errors: IntCounterVec,
+	/// SQLite write latency by operation
+	pub sqlite_write_duration: HistogramVec,
+}
+
+impl Metrics {
+	fn new() -> Self {
+		let registry = Registry::new_custom(Some("dumptruck".to_string()), None)
+			.expect("valid registry prefix");
+
+		let metrics = Self {
+			http_request_duration: HistogramVec::new(
+				HistogramOpts::new(
+					"http_request_duration_seconds",
+					"HTTP request latency by route",
+				),
+				&["method", "route", "status"],
+			)
+			.expect("valid metric"),
+			jobs: IntGaugeVec::new(Opts::new("jobs", "Jobs by status"), &["status"])
+				.expect("valid metric"),
+			workers: IntGauge::new("workers", "Job workers running").expect("valid metric"),
+			workers_busy: IntGauge::new("workers_busy", "Job workers currently processing a job")
+				.expect("valid metric"),
+			pipeline_rows: IntCounter::new(
+				"pipeline_rows_total",
+				"Rows processed by the detection pipeline",
+			)
+			.expect("valid metric"),
+			pii_findings: IntCounterVec::new(
+				Opts::new("pii_findings_total", "PII findings by type"),
+				&["pii_type"],
+			)
+			.expect("valid metric"),
+			enrichment_duration: HistogramVec::new(
+				HistogramOpts::new(
+					"enrichment_request_duration_seconds",
+					"Enrichment service call latency",
+				),
+				&["service", "operation"],
+			)
+			.expect("valid metric"),
+			enrichment_errors: IntCounterVec::new(
+				Opts::new("enrichment_errors_total", "Failed enrichment service calls"),
+				&["service", "operation"],
+			)
+			.expect("valid metric"),
+			sqlite_write_duration: HistogramVec::new(
+				HistogramOpts::new("sqlite_write_duration_seconds", "SQLite write latency")
+					.buckets(SQLITE_BUCKETS.to_vec()),
+				&["operation"],
+			)
+			.expect("valid metric"),
+			registry,
+		};
+
+		let collectors: [Box<dyn prometheus::core::Collector>; 9] = [
+			Box::new(metrics.http_request_duration.clone()),
+			Box::new(metrics.jobs.clone()),
+			Box::new(metrics.workers.clone()),
+			Box::new(metrics.workers_busy.clone()),
+			Box::new(metrics.pipeline_rows.clone()),
+			Box::new(metrics.pii_findings.clone()),
+			Box::new(metrics.enrichment_duration.clone()),
+			Box::new(metrics.enrichment_errors.clone()),
+			Box::new(metrics.sqlite_write_duration.clone()),
+		];
+		for collector in collectors {
+			metrics
+				.registry
+				.register(collector)
+				.expect("metric registered once");
+		}
+
+		metrics
+	}
+
+	/// Render all metrics in the Prometheus text format
+	pub fn render(&self) -> String {
+		let mut buffer = Vec::new();
+		if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+			tracing::warn!("Failed to encode metrics: {}", e);
+		}
+		String::from_utf8(buffer).unwrap_or_default()
+	}
+}
+
+/// Time an enrichment call, counting it as an error if it fails
+pub async fn observe_enrichment<T, F>(service: &str, operation: &str, call: F) -> io::Result<T>
+where
+	F: Future<Output = io::Result<T>>,
+{
+	let start = Instant::now();
+	let result = call.await;
+	let labels = [service, operation];
+	metrics()
+		.enrichment_duration
+		.with_label_values(&labels)
+		.observe(start.elapsed().as_secs_f64());
+	if result.is_err() {
+		metrics().enrichment_errors.with_label_values(&labels).inc();
+	}
+	result
+}
+
+/// Time a SQLite write
+pub fn observe_sqlite_write<T>(operation: &str, write: impl FnOnce() -> T) -> T {
+	let start = Instant::now();
+	let result = write();
+	metrics()
+		.sqlite_write_duration
+		.with_label_values(&[operation])
+		.observe(start.elapsed().as_secs_f64());
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_enrichment_errors_are_counted() {
+		let errors = || {
+			metrics()
+				.enrichment_errors
+				.with_label_values(&["test", "fail"])
+				.get()
+		};
+		let before = errors();
+
+		let ok = observe_enrichment("test", "fail", async { Ok(1) }).await;
+		assert_eq!(ok.expect("ok"), 1);
+		assert_eq!(errors(), before);
+
+		let err: io::Result<()> =
+			observe_enrichment("test", "fail", async { Err(io::Error::other("down")) }).await;
+		assert!(err.is_err());
+		assert_eq!(errors(), before + 1);
+	}
+
+	#[test]
+	fn test_render_uses_prefixed_names() {
+		observe_sqlite_write("test", || ());
+		let text = metrics().render();
+		assert!(text.contains("dumptruck_sqlite_write_duration_seconds_bucket"));
+		assert!(text.contains("operation=\"test\""));
+		assert!(text.contains("# TYPE dumptruck_workers_busy gauge"));
+	}
+}
@@ -1,6 +1,11 @@
 //! Simple ingest -> normalize -> enrich -> store pipeline and integration tests.
 
-use crate::{ingest::adapters::FormatAdapter, normalization, storage::StorageAdapter};
+use crate::{
+	core::{config::Config, hash_utils},
+	ingest::adapters::FormatAdapter,
+	normalization,
+	storage::StorageAdapter,
+};
 
 /// Pipeline wires together adapter and storage.
 pub struct Pipeline<A: FormatAdapter, S: StorageAdapter> {
@@ -16,8 +21,6 @@ impl<A: FormatAdapter, S: StorageAdapter> Pipeline<A, S> {
 	/// Ingest input, normalize, enrich, and persist rows.
 	/// Consume the pipeline, ingest input, and return ownership of the storage.
 	pub fn ingest(self, input: &str) -> Result<S, std::io::Error> {
-		use crate::core::hash_utils;
-
 		let rows = self.adapter.parse(input);
 		let mut storage = self.storage;
 
@@ -32,170 +35,260 @@ impl<A: FormatAdapter, S: StorageAdapter> Pipeline<A, S> {
 			file_sha256.clone(),
 		];
 
-		let store_with_file = |s: &mut S, row: &Vec<String>| -> std::io::Result<()> {
-			let mut r = row.clone();
-			r.push(format!("file_id:{}", file_id));
-			s.store_row(&r)
-		};
-
-		let _ = store_with_file(&mut storage, &meta);
+		let _ = store_with_file(&mut storage, &meta, &file_id);
 
 		// Detect header row
-		let (header, expected_columns) = Self::detect_header(&rows);
+		let header = detect_header(&rows);
+		let config = Config::default();
 
 		for (idx, row) in rows.iter().enumerate() {
 			if idx == 0 && header.is_some() {
 				continue;
 			}
+			store_normalized_row(&mut storage, row, idx, header.as_deref(), &file_id, &config)?;
+		}
 
-			let normalized = normalization::engine::normalize_row(row);
+		Ok(storage)
+	}
+}
 
-			// Validate column count
-			if let Some(expected) = expected_columns
-				&& normalized.len() != expected
-			{
-				let raw = row.join(",");
-				let m = vec!["__malformed_row__".to_string(), idx.to_string(), raw];
-				let _ = store_with_file(&mut storage, &m);
-				continue;
-			}
+/// Store a row tagged with the identifier of the file it came from.
+pub fn store_with_file<S: StorageAdapter + ?Sized>(
+	storage: &mut S,
+	row: &[String],
+	file_id: &str,
+) -> std::io::Result<()> {
+	let mut r = row.to_vec();
+	r.push(format!("file_id:{}", file_id));
+	storage.store_row(&r)
+}
 
-			// Detect addresses and credentials
-			let (addr_hashes, cred_hashes, has_hashed_credentials) =
-				Self::extract_address_credentials(&normalized, &header);
-
-			// Skip hashed-only rows
-			if has_hashed_credentials && !cred_hashes.is_empty() && addr_hashes.is_empty() {
-				let ev = vec![
-					"__hashed_credentials_only__".to_string(),
-					"row_skipped".to_string(),
-					format!("cred_count:{}", cred_hashes.len()),
-				];
-				let _ = store_with_file(&mut storage, &ev);
-				continue;
-			}
+/// Normalize a single parsed row and persist it with its address/credential events.
+///
+/// `idx` is the row's position in the source file and `header` the detected header
+/// row, if any. Addresses are also recorded in the canonical address tables using
+/// the email suffix rules from `config`.
+pub fn store_normalized_row<S: StorageAdapter + ?Sized>(
+	storage: &mut S,
+	row: &[String],
+	idx: usize,
+	header: Option<&[String]>,
+	file_id: &str,
+	config: &Config,
+) -> std::io::Result<()> {
+	let normalized = normalization::engine::normalize_row(row);
+
+	// Validate column count
+	if let Some(expected) = header.map(|h| h.len())
+		&& normalized.len() != expected
+	{
+		let raw = row.join(",");
+		let m = vec!["__malformed_row__".to_string(), idx.to_string(), raw];
+		let _ = store_with_file(storage, &m, file_id);
+		return Ok(());
+	}
 
-			// Process addresses and credentials
-			for addr in addr_hashes.iter() {
-				let addr_seen = storage.address_exists(addr)?;
-				if !addr_seen {
-					let ev = vec!["__new_address__".to_string(), addr.clone()];
-					store_with_file(&mut storage, &ev)?;
-					let r = vec!["__address_hash__".to_string(), addr.clone()];
-					store_with_file(&mut storage, &r)?;
-				}
+	// Detect addresses and credentials
+	let (addr_values, cred_values, has_hashed_credentials) =
+		extract_address_credentials(&normalized, header);
+	let addr_hashes: Vec<String> = addr_values
+		.iter()
+		.map(|v| hash_utils::sha256_hex(v))
+		.collect();
+	let cred_hashes: Vec<String> = cred_values
+		.iter()
+		.map(|v| hash_utils::sha256_hex(v))
+		.collect();
+
+	// Skip hashed-only rows
+	if has_hashed_credentials && !cred_hashes.is_empty() && addr_hashes.is_empty() {
+		let ev = vec![
+			"__hashed_credentials_only__".to_string(),
+			"row_skipped".to_string(),
+			format!("cred_count:{}", cred_hashes.len()),
+		];
+		let _ = store_with_file(storage, &ev, file_id);
+		return Ok(());
+	}
 
-				for cred in cred_hashes.iter() {
-					if !storage.contains_hash(cred)? {
-						let r = vec!["__credential_hash__".to_string(), cred.clone()];
-						store_with_file(&mut storage, &r)?;
-					}
+	// Process addresses and credentials
+	for addr in addr_hashes.iter() {
+		let addr_seen = storage.address_exists(addr)?;
+		if !addr_seen {
+			let ev = vec!["__new_address__".to_string(), addr.clone()];
+			store_with_file(storage, &ev, file_id)?;
+			let r = vec!["__address_hash__".to_string(), addr.clone()];
+			store_with_file(storage, &r, file_id)?;
+		}
 
-					let assoc = storage.address_has_credential(addr, cred)?;
-					if !assoc {
-						if addr_seen {
-							let ev = vec![
-								"__known_address_new_credential__".to_string(),
-								addr.clone(),
-								cred.clone(),
-							];
-							store_with_file(&mut storage, &ev)?;
-						}
-						let mapping = vec!["__addr_cred__".to_string(), addr.clone(), cred.clone()];
-						store_with_file(&mut storage, &mapping)?;
-					}
+		for cred in cred_hashes.iter() {
+			if !storage.contains_hash(cred)? {
+				let r = vec!["__credential_hash__".to_string(), cred.clone()];
+				store_with_file(storage, &r, file_id)?;
+			}
+
+			let assoc = storage.address_has_credential(addr, cred)?;
+			if !assoc {
+				if addr_seen {
+					let ev = vec![
+						"__known_address_new_credential__".to_string(),
+						addr.clone(),
+						cred.clone(),
+					];
+					store_with_file(storage, &ev, file_id)?;
 				}
+				let mapping = vec!["__addr_cred__".to_string(), addr.clone(), cred.clone()];
+				store_with_file(storage, &mapping, file_id)?;
 			}
+		}
+	}
 
-			// Enrich and store
-			let mut enriched = normalized.to_vec();
-			let row_join = normalized.join("|");
-			let row_hash = hash_utils::sha256_hex(&row_join);
+	store_canonical_addresses(storage, &addr_values, &cred_hashes, config)?;
+	store_credential_digests(storage, row, header)?;
 
-			for h in addr_hashes.iter() {
-				enriched.push(format!("addr_sha256:{}", h));
-			}
-			for h in cred_hashes.iter() {
-				enriched.push(format!("cred_sha256:{}", h));
-			}
+	// Enrich and store
+	let mut enriched = normalized.to_vec();
+	let row_join = normalized.join("|");
+	let row_hash = hash_utils::sha256_hex(&row_join);
 
-			if storage.contains_hash(&row_hash)? {
-				let dup = vec!["__duplicate_row__".to_string(), row_hash.clone()];
-				let _ = store_with_file(&mut storage, &dup);
-			} else {
-				enriched.push(format!("row_hash:{}", row_hash));
-				store_with_file(&mut storage, &enriched)?;
-			}
+	for h in addr_hashes.iter() {
+		enriched.push(format!("addr_sha256:{}", h));
+	}
+	for h in cred_hashes.iter() {
+		enriched.push(format!("cred_sha256:{}", h));
+	}
+
+	if storage.contains_hash(&row_hash)? {
+		let dup = vec!["__duplicate_row__".to_string(), row_hash.clone()];
+		let _ = store_with_file(storage, &dup, file_id);
+	} else {
+		enriched.push(format!("row_hash:{}", row_hash));
+		store_with_file(storage, &enriched, file_id)?;
+	}
+
+	Ok(())
+}
+
+/// Record canonical addresses, their alternate forms, credentials and row co-occurrence.
+///
+/// No-op for storage backends without the canonical address tables.
+fn store_canonical_addresses<S: StorageAdapter + ?Sized>(
+	storage: &mut S,
+	addr_values: &[String],
+	cred_hashes: &[String],
+	config: &Config,
+) -> std::io::Result<()> {
+	let mut canonical_hashes = Vec::with_capacity(addr_values.len());
+
+	for addr in addr_values.iter().filter(|a| a.contains('@')) {
+		let canonical = normalization::engine::normalize_email_with_config(addr, config);
+		let canonical_hash = hash_utils::sha256_hex(&canonical);
+
+		storage.insert_canonical_address(&canonical_hash, addr, &canonical)?;
+		storage.insert_address_alternate(&canonical_hash, &hash_utils::sha256_hex(addr), addr)?;
+		for cred in cred_hashes {
+			storage.insert_address_credential_canonical(&canonical_hash, cred)?;
 		}
 
-		Ok(storage)
+		if !canonical_hashes.contains(&canonical_hash) {
+			canonical_hashes.push(canonical_hash);
+		}
 	}
 
-	/// Detect header row from the first row
-	fn detect_header(rows: &[Vec<String>]) -> (Option<Vec<String>>, Option<usize>) {
-		if !rows.is_empty() {
-			let first = &rows[0];
-			if first.iter().any(|c| c.chars().any(|ch| ch.is_alphabetic())) {
-				return (Some(first.clone()), Some(first.len()));
-			}
+	for (i, a) in canonical_hashes.iter().enumerate() {
+		for b in canonical_hashes.iter().skip(i + 1) {
+			storage.record_address_cooccurrence(a, b)?;
 		}
-		(None, None)
 	}
 
-	/// Extract address and credential hashes from normalized row
-	fn extract_address_credentials(
-		normalized: &[String],
-		header: &Option<Vec<String>>,
-	) -> (Vec<String>, Vec<String>, bool) {
-		use crate::core::hash_utils;
-
-		let mut addr_hashes = Vec::new();
-		let mut cred_hashes = Vec::new();
-		let mut has_hashed_credentials = false;
-
-		if let Some(h) = header {
-			for (i, col_name) in h.iter().enumerate() {
-				let lname = col_name.to_lowercase();
-				if i < normalized.len() {
-					let val = &normalized[i];
-					if lname.contains("mail")
-						|| lname.contains("email")
-						|| lname.contains("addr")
-						|| lname.contains("address")
-					{
-						let sha = hash_utils::sha256_hex(val);
-						addr_hashes.push(sha);
-					}
-					if lname.contains("pass")
-						|| lname.contains("pwd")
-						|| lname.contains("password")
-						|| lname.contains("credential")
-						|| lname.contains("secret")
-					{
-						if hash_utils::is_credential_hash(val) {
-							has_hashed_credentials = true;
-						}
-						let sha = hash_utils::sha256_hex(val);
-						cred_hashes.push(sha);
-					}
-				}
-			}
-		} else {
-			for val in normalized.iter() {
-				if val.contains('@') {
-					addr_hashes.push(hash_utils::sha256_hex(val));
+	Ok(())
+}
+
+/// Record SHA-1 and NTLM digests of the row's plaintext credentials for range queries.
+///
+/// Digests are taken over the raw field values, since normalization case-folds
+/// them; values that already look like hashes are skipped.
+fn store_credential_digests<S: StorageAdapter + ?Sized>(
+	storage: &mut S,
+	row: &[String],
+	header: Option<&[String]>,
+) -> std::io::Result<()> {
+	let (_, raw_credentials, _) = extract_address_credentials(row, header);
+
+	for raw in raw_credentials
+		.iter()
+		.filter(|c| !c.is_empty() && !hash_utils::is_credential_hash(c))
+	{
+		let credential_hash = hash_utils::sha256_hex(&normalization::engine::normalize_field(raw));
+		storage.insert_credential_digest(
+			&credential_hash,
+			&hash_utils::sha1_hex(raw).to_uppercase(),
+			&hash_utils::ntlm_hex(raw).to_uppercase(),
+		)?;
+	}
+
+	Ok(())
+}
+
+/// Detect header row from the first row
+pub fn detect_header(rows: &[Vec<String>]) -> Option<Vec<String>> {
+	let first = rows.first()?;
+	if first.iter().any(|c| c.chars().any(|ch| ch.is_alphabetic())) {
+		Some(first.clone())
+	} else {
+		None
+	}
+}
+
+/// Extract normalized address and credential values from a normalized row
+fn extract_address_credentials(
+	normalized: &[String],
+	header: Option<&[String]>,
+) -> (Vec<String>, Vec<String>, bool) {
+	let mut addr_values = Vec::new();
+	let mut cred_values = Vec::new();
+	let mut has_hashed_credentials = false;
+
+	if let Some(h) = header {
+		for (i, col_name) in h.iter().enumerate() {
+			let lname = col_name.to_lowercase();
+			if i < normalized.len() {
+				let val = &normalized[i];
+				if lname.contains("mail")
+					|| lname.contains("email")
+					|| lname.contains("addr")
+					|| lname.contains("address")
+				{
+					addr_values.push(val.clone());
 				}
-				if val.contains(':') || val.to_lowercase().contains("pass") {
+				if lname.contains("pass")
+					|| lname.contains("pwd")
+					|| lname.contains("password")
+					|| lname.contains("credential")
+					|| lname.contains("secret")
+				{
 					if hash_utils::is_credential_hash(val) {
 						has_hashed_credentials = true;
 					}
-					cred_hashes.push(hash_utils::sha256_hex(val));
+					cred_values.push(val.clone());
 				}
 			}
 		}
-
-		(addr_hashes, cred_hashes, has_hashed_credentials)
+	} else {
+		for val in normalized.iter() {
+			if val.contains('@') {
+				addr_values.push(val.clone());
+			}
+			if val.contains(':') || val.to_lowercase().contains("pass") {
+				if hash_utils::is_credential_hash(val) {
+					has_hashed_credentials = true;
+				}
+				cred_values.push(val.clone());
+			}
+		}
 	}
+
+	(addr_values, cred_values, has_hashed_credentials)
 }
 
 #[cfg(test)]
@@ -98,7 +98,7 @@ fn extract_emails(value: &str) -> Vec<String> {
 }
 
 /// Check if a string looks like an email address
-fn is_email_like(value: &str) -> bool {
+pub fn is_email_like(value: &str) -> bool {
 	let parts: Vec<&str> = value.split('@').collect();
 	if parts.len() != 2 {
 		return false;
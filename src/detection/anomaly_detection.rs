@@ -398,7 +398,7 @@ impl DatasetBaseline {
 		}
 
 		let mut common_domains: Vec<_> = domain_counts.into_iter().collect();
-		common_domains.sort_by(|a, b| b.1.cmp(&a.1));
+		common_domains.sort_by_key(|d| std::cmp::Reverse(d.1));
 		common_domains.truncate(10);
 
 		Ok(DatasetBaseline {
@@ -524,4 +524,19 @@ mod tests {
 		assert!(baseline.mean_length > 0.0);
 		assert_eq!(baseline.record_count, 5);
 	}
+
+	#[test]
+	fn test_dataset_baseline_ranks_common_domains() {
+		// Twelve domains, domainN.com appearing N times
+		let values: Vec<String> = (1..=12)
+			.flat_map(|n| (0..n).map(move |i| format!("user{}@domain{}.com", i, n)))
+			.collect();
+		let values: Vec<&str> = values.iter().map(String::as_str).collect();
+
+		let baseline = DatasetBaseline::from_sample(&values).expect("Should create baseline");
+
+		let counts: Vec<usize> = baseline.common_domains.iter().map(|d| d.1).collect();
+		assert_eq!(counts, (3..=12).rev().collect::<Vec<_>>());
+		assert_eq!(baseline.common_domains[0].0, "domain12.com");
+	}
 }
@@ -109,6 +109,11 @@ pub fn initialize() -> Result<(), String> {
 	Ok(())
 }
 
+/// Number of weak password hashes loaded, or `None` if `initialize` has not run.
+pub fn loaded_entries() -> Option<usize> {
+	RAINBOW_TABLE.get().map(Vec::len)
+}
+
 /// Load rainbow table from JSON file.
 fn load_from_json() -> Result<Vec<WeakPasswordHash>, String> {
 	let json_path = ".cache/rainbow_table.json";
@@ -6,6 +6,8 @@
 
 use std::io;
 
+use crate::core::metrics;
+
 /// Breach information returned by HIBP API.
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct Breach {
@@ -73,6 +75,10 @@ impl HibpClient {
 	/// Returns a vector of breaches that included this email address.
 	/// Empty vector means the address was not found in any known breach.
 	pub async fn get_breaches_for_address(&self, email: &str) -> io::Result<Vec<Breach>> {
+		metrics::observe_enrichment("hibp", "breached_account", self.fetch_breaches(email)).await
+	}
+
+	async fn fetch_breaches(&self, email: &str) -> io::Result<Vec<Breach>> {
 		let url = format!(
 			"{}/breachedaccount/{}?includeUnverified=true",
 			self.base_url,
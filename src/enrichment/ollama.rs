@@ -6,6 +6,8 @@
 
 use std::io;
 
+use crate::core::metrics;
+
 /// Ollama embedding request payload.
 #[derive(serde::Serialize)]
 pub struct EmbedRequest {
@@ -49,6 +51,10 @@ impl OllamaClient {
 
 	/// Generate a 768-dimensional embedding for the given text using Nomic.
 	pub async fn embed(&self, text: &str) -> io::Result<Vec<f32>> {
+		metrics::observe_enrichment("ollama", "embed", self.request_embedding(text)).await
+	}
+
+	async fn request_embedding(&self, text: &str) -> io::Result<Vec<f32>> {
 		let url = format!("{}/api/embed", self.base_url);
 		let req = EmbedRequest {
 			model: self.model.clone(),
@@ -92,6 +98,10 @@ impl OllamaClient {
 
 	/// Pull the Nomic embedding model if not already available.
 	pub async fn ensure_model(&self) -> io::Result<()> {
+		metrics::observe_enrichment("ollama", "pull", self.pull_model()).await
+	}
+
+	async fn pull_model(&self) -> io::Result<()> {
 		let url = format!("{}/api/pull", self.base_url);
 		let req = serde_json::json!({"name": self.model});
 
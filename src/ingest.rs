@@ -4,11 +4,19 @@
 //! - Safe ingestion with validation (binary detection, UTF-8 checking)
 //! - Multiple format support (CSV, TSV, JSON, YAML, XML, Protocol Buffers, BSON)
 //! - Compression detection (ZIP, gzip with nested level limits)
+//! - Safe extraction of gzip and ZIP archives into the working directory
+//! - Combo lists and stealer logs (`email:password`, `url:user:pass`, `email|hash|salt`)
+//! - MySQL and PostgreSQL SQL dumps (`CREATE TABLE`, `INSERT`, `COPY ... FROM stdin`)
+//! - SQLite database files, recognized by their header
 //! - Memory-efficient streaming pipelines
 
 pub mod adapters;
+pub mod archive;
+pub mod combo;
 pub mod compression;
 pub mod safe_ingest;
+pub mod sql_dump;
+pub mod sqlite;
 pub mod streaming;
 pub mod universal_parser;
 
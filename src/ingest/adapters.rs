@@ -20,61 +20,86 @@ impl CsvAdapter {
 
 impl FormatAdapter for CsvAdapter {
 	fn parse(&self, input: &str) -> Vec<Vec<String>> {
+		let mut builder = CsvRecordBuilder::new();
+		let mut rows: Vec<Vec<String>> = input
+			.split_inclusive('\n')
+			.filter_map(|line| builder.push_line(line))
+			.collect();
+		rows.extend(builder.finish());
+		rows
+	}
+}
+
+/// Incremental CSV record parser behind [`CsvAdapter`]
+///
+/// Fed one line at a time, so streaming readers split records exactly like
+/// `CsvAdapter::parse` does on the whole input.
+#[derive(Default)]
+pub struct CsvRecordBuilder {
+	row: Vec<String>,
+	field: String,
+	in_quotes: bool,
+}
+
+impl CsvRecordBuilder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Feed one line (including its trailing `\n`, if any), returning the
+	/// record it completes. Lines inside a quoted field continue the record.
+	pub fn push_line(&mut self, line: &str) -> Option<Vec<String>> {
 		// RFC4180-like parser: handles quoted fields with doubled quotes and
 		// allows newlines inside quoted fields.
-		let mut rows: Vec<Vec<String>> = Vec::new();
-		let mut row: Vec<String> = Vec::new();
-		let mut field = String::new();
-		let mut in_quotes = false;
-		let mut chars = input.chars().peekable();
+		let mut record = None;
+		let mut chars = line.chars().peekable();
 
 		while let Some(ch) = chars.next() {
 			match ch {
 				'"' => {
-					if in_quotes {
+					if self.in_quotes {
 						// possible escaped quote
 						if let Some('"') = chars.peek() {
 							// doubled quote -> literal quote
 							chars.next();
-							field.push('"');
+							self.field.push('"');
 						} else {
 							// closing quote
-							in_quotes = false;
+							self.in_quotes = false;
 						}
 					} else {
 						// opening quote
-						in_quotes = true;
+						self.in_quotes = true;
 					}
 				}
-				',' if !in_quotes => {
+				',' if !self.in_quotes => {
 					// Per RFC4180, spaces outside quotes are part of the field.
-					row.push(field.clone());
-					field.clear();
+					self.row.push(std::mem::take(&mut self.field));
 				}
-				'\n' if !in_quotes => {
-					row.push(field.clone());
-					field.clear();
-					rows.push(row);
-					row = Vec::new();
+				'\n' if !self.in_quotes => {
+					self.row.push(std::mem::take(&mut self.field));
+					record = Some(std::mem::take(&mut self.row));
 				}
 				'\r' => {
 					// ignore CR, handle CRLF by letting LF end the record
 				}
-				c => field.push(c),
+				c => self.field.push(c),
 			}
 		}
 
-		// push remaining data
-		if in_quotes {
+		record
+	}
+
+	/// Flush the last record once the input ends
+	pub fn finish(&mut self) -> Option<Vec<String>> {
+		if self.in_quotes {
 			// unterminated quoted field: treat as-is
-			row.push(field.clone());
-			rows.push(row);
-		} else if !field.is_empty() || !row.is_empty() {
-			row.push(field.clone());
-			rows.push(row);
+			self.in_quotes = false;
+		} else if self.field.is_empty() && self.row.is_empty() {
+			return None;
 		}
-
-		rows
+		self.row.push(std::mem::take(&mut self.field));
+		Some(std::mem::take(&mut self.row))
 	}
 }
 
@@ -0,0 +1,954 @@
+//! Safe Archive Extraction (Stage 2)
+//!
+//! Expands compressed dumps recognized by [`CompressionInfo::detect`] into the
+//! working directory so every contained file can be ingested on its own:
+//! - gzip streams and ZIP archives (stored or deflated members, including ZIP64)
+//! - Nested archives are expanded up to [`MAX_NESTING_LEVEL`](crate::ingest::compression::MAX_NESTING_LEVEL) levels deep
+//! - Each stream is capped by its compression ratio and the whole input by total
+//!   size and member count, so zip bombs are stopped while they are being written
+//! - Members with absolute or `..` paths, symbolic links and encrypted members are skipped
+//! - Every extracted file records the chain of archives it came from
+//!
+//! bzip2 and 7-Zip are detected but cannot be expanded and are rejected as unsupported.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+
+use crate::core::secure_deletion::{SecureDeletionConfig, secure_delete_file};
+use crate::ingest::compression::{CompressionError, CompressionFormat, CompressionInfo};
+
+/// Separator between an archive and one of its members in provenance paths,
+/// e.g. `dump.zip!users.csv.gz!users.csv`
+pub const MEMBER_SEPARATOR: char = '!';
+
+/// Streams may expand past the ratio cap up to this size; tiny files legitimately compress very well
+const RATIO_FLOOR_BYTES: u64 = 1024 * 1024;
+
+/// Buffer size for copying decompressed data
+const COPY_BUFFER_BYTES: usize = 64 * 1024;
+
+/// ZIP record signatures
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x0606_4b50;
+const ZIP64_EOCD_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+
+/// Fixed sizes of ZIP records
+const LOCAL_HEADER_LEN: u64 = 30;
+const CENTRAL_HEADER_LEN: usize = 46;
+const EOCD_LEN: usize = 22;
+const ZIP64_EOCD_LEN: usize = 56;
+const ZIP64_EOCD_LOCATOR_LEN: usize = 20;
+
+/// ZIP extra field carrying 64-bit sizes and offsets
+const ZIP64_EXTRA_FIELD: u16 = 0x0001;
+
+/// ZIP compression methods that can be expanded
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+/// Limits applied while expanding one input file
+#[derive(Debug, Clone, Copy)]
+pub struct ExtractionLimits {
+	/// Bytes that may be written across all extracted files
+	pub max_total_bytes: u64,
+	/// Largest allowed ratio of expanded to compressed size for a single stream
+	pub max_ratio: u64,
+	/// Files that may be written, counting nested archives
+	pub max_members: usize,
+}
+
+impl Default for ExtractionLimits {
+	fn default() -> Self {
+		Self {
+			max_total_bytes: 4 * 1024 * 1024 * 1024,
+			max_ratio: 200,
+			max_members: 10_000,
+		}
+	}
+}
+
+/// A file extracted from an archive
+#[derive(Debug, Clone)]
+pub struct ExtractedFile {
+	/// Extracted contents in the working directory
+	pub path: PathBuf,
+	/// Provenance of the archive it came from, e.g. `dump.zip!inner.gz`
+	pub archive: String,
+	/// Path of the member inside that archive
+	pub member: String,
+	/// Expanded size in bytes
+	pub size: u64,
+	/// Number of archives enclosing the file
+	pub nesting_level: u32,
+}
+
+impl ExtractedFile {
+	/// Full provenance: the archive chain followed by the member path
+	pub fn provenance(&self) -> String {
+		format!("{}{}{}", self.archive, MEMBER_SEPARATOR, self.member)
+	}
+}
+
+/// Result of expanding an input file
+#[derive(Debug)]
+pub struct Extraction {
+	/// Directory holding the extracted files; remove it with [`remove_extraction`]
+	pub dir: PathBuf,
+	/// Extracted files that are not archives themselves, in archive order
+	pub files: Vec<ExtractedFile>,
+	/// Members that were not extracted, with the reason
+	pub skipped: Vec<String>,
+}
+
+/// Expand the archive at `source` into a new directory under `dest_dir`
+///
+/// `name` is the input's original file name and starts every provenance path.
+/// Nothing is left behind in `dest_dir` when extraction fails.
+///
+/// # Errors
+/// Returns [`CompressionError::NestingLimitExceeded`] for archives nested too
+/// deeply, [`CompressionError::ExpansionLimitExceeded`] when a cap is hit,
+/// [`CompressionError::MalformedArchive`] for damaged archives and
+/// [`CompressionError::UnsupportedFormat`] for bzip2 and 7-Zip input.
+pub fn extract(
+	source: &Path,
+	name: &str,
+	dest_dir: &Path,
+	limits: &ExtractionLimits,
+) -> Result<Extraction, CompressionError> {
+	if fs::symlink_metadata(source)?.file_type().is_symlink() {
+		return Err(CompressionError::InvalidPath(format!(
+			"{} is a symbolic link",
+			source.display()
+		)));
+	}
+
+	let dir = dest_dir.join(format!("extract-{}", uuid::Uuid::new_v4()));
+	fs::create_dir(&dir)?;
+
+	let mut extractor = Extractor {
+		dir: dir.clone(),
+		limits,
+		written: 0,
+		members: 0,
+		files: Vec::new(),
+		skipped: Vec::new(),
+	};
+
+	match extractor.expand(source, name, 1) {
+		Ok(()) => Ok(Extraction {
+			dir,
+			files: extractor.files,
+			skipped: extractor.skipped,
+		}),
+		Err(e) => {
+			let _ = remove_extraction(&dir);
+			Err(e)
+		}
+	}
+}
+
+/// Shred every extracted file and remove the extraction directory
+pub fn remove_extraction(dir: &Path) -> io::Result<()> {
+	for entry in fs::read_dir(dir)? {
+		let entry = entry?;
+		if entry.file_type()?.is_file() {
+			shred(&entry.path())?;
+		}
+	}
+	fs::remove_dir_all(dir)
+}
+
+/// Securely delete an extracted file
+fn shred(path: &Path) -> io::Result<()> {
+	let config = SecureDeletionConfig {
+		log_deletions: false,
+		verify_deletion: false,
+		..SecureDeletionConfig::default()
+	};
+	secure_delete_file(path, config)
+		.map(|_| ())
+		.map_err(io::Error::other)
+}
+
+/// Expands one input file, tracking the caps across nested archives
+struct Extractor<'a> {
+	dir: PathBuf,
+	limits: &'a ExtractionLimits,
+	/// Bytes written so far across all streams
+	written: u64,
+	/// Files written so far, including nested archives
+	members: usize,
+	files: Vec<ExtractedFile>,
+	skipped: Vec<String>,
+}
+
+impl Extractor<'_> {
+	/// Expand the archive at `path`, whose provenance is `provenance`
+	fn expand(
+		&mut self,
+		path: &Path,
+		provenance: &str,
+		nesting_level: u32,
+	) -> Result<(), CompressionError> {
+		let mut info = CompressionInfo::detect(path)?;
+		info.nesting_level = nesting_level;
+		info.validate_nesting()?;
+
+		match info.format {
+			CompressionFormat::Gzip => self.expand_gzip(path, provenance, nesting_level),
+			CompressionFormat::Zip => self.expand_zip(path, provenance, nesting_level),
+			CompressionFormat::Bzip2
+			| CompressionFormat::SevenZip
+			| CompressionFormat::Uncompressed => Err(CompressionError::UnsupportedFormat),
+		}
+	}
+
+	/// Record an extracted file, expanding it further if it is an archive itself
+	fn add(&mut self, file: ExtractedFile) -> Result<(), CompressionError> {
+		match CompressionInfo::detect(&file.path)?.format {
+			CompressionFormat::Uncompressed => {
+				self.files.push(file);
+				Ok(())
+			}
+			CompressionFormat::Gzip | CompressionFormat::Zip => {
+				let result = self.expand(&file.path, &file.provenance(), file.nesting_level + 1);
+				shred(&file.path)?;
+				result
+			}
+			format @ (CompressionFormat::Bzip2 | CompressionFormat::SevenZip) => {
+				self.skipped.push(format!(
+					"{}: {:?} archives cannot be extracted",
+					file.provenance(),
+					format
+				));
+				Ok(shred(&file.path)?)
+			}
+		}
+	}
+
+	/// Expand a (possibly multi-member) gzip stream into a single file
+	fn expand_gzip(
+		&mut self,
+		path: &Path,
+		provenance: &str,
+		nesting_level: u32,
+	) -> Result<(), CompressionError> {
+		let file = File::open(path)?;
+		let compressed_size = file.metadata()?.len();
+		let member = gzip_member_name(provenance);
+
+		let mut decoder = MultiGzDecoder::new(BufReader::new(file));
+		let (out, size) = self.write_stream(&mut decoder, &member, compressed_size)?;
+
+		self.add(ExtractedFile {
+			path: out,
+			archive: provenance.to_string(),
+			member,
+			size,
+			nesting_level,
+		})
+	}
+
+	/// Expand every safe member of a ZIP archive
+	fn expand_zip(
+		&mut self,
+		path: &Path,
+		provenance: &str,
+		nesting_level: u32,
+	) -> Result<(), CompressionError> {
+		let mut file = File::open(path)?;
+		let entries = read_central_directory(&mut file)?;
+
+		for entry in entries {
+			if entry.name.ends_with('/') || entry.name.ends_with('\\') {
+				continue;
+			}
+
+			let Some(member) = sanitize_member_name(&entry.name) else {
+				self.skipped.push(format!(
+					"{}{}{}: unsafe path",
+					provenance, MEMBER_SEPARATOR, entry.name
+				));
+				continue;
+			};
+			let label = format!("{}{}{}", provenance, MEMBER_SEPARATOR, member);
+
+			let reason = if entry.symlink {
+				Some("symbolic link".to_string())
+			} else if entry.flags & 0x0001 != 0 {
+				Some("encrypted".to_string())
+			} else if entry.method != METHOD_STORED && entry.method != METHOD_DEFLATED {
+				Some(format!("compression method {} not supported", entry.method))
+			} else {
+				None
+			};
+			if let Some(reason) = reason {
+				self.skipped.push(format!("{}: {}", label, reason));
+				continue;
+			}
+
+			// Refuse declared bombs before writing anything
+			if entry.uncompressed_size > self.stream_cap(entry.compressed_size) {
+				return Err(CompressionError::ExpansionLimitExceeded(format!(
+					"{} declares {} bytes from {} compressed",
+					label, entry.uncompressed_size, entry.compressed_size
+				)));
+			}
+
+			let data_offset = local_data_offset(&mut file, entry.local_header_offset)?;
+			file.seek(SeekFrom::Start(data_offset))?;
+			let raw = (&mut file).take(entry.compressed_size);
+			let decoder: Box<dyn Read + '_> = if entry.method == METHOD_STORED {
+				Box::new(raw)
+			} else {
+				Box::new(DeflateDecoder::new(raw))
+			};
+			let mut reader = Crc32Reader {
+				inner: decoder,
+				hasher: crc32fast::Hasher::new(),
+			};
+
+			let (out, size) = self.write_stream(&mut reader, &member, entry.compressed_size)?;
+			if reader.hasher.finalize() != entry.crc32 || size != entry.uncompressed_size {
+				return Err(CompressionError::MalformedArchive(format!(
+					"{} failed its integrity check",
+					label
+				)));
+			}
+
+			self.add(ExtractedFile {
+				path: out,
+				archive: provenance.to_string(),
+				member,
+				size,
+				nesting_level,
+			})?;
+		}
+
+		Ok(())
+	}
+
+	/// Largest expanded size allowed for a stream of `compressed_size` bytes
+	fn stream_cap(&self, compressed_size: u64) -> u64 {
+		compressed_size
+			.saturating_mul(self.limits.max_ratio)
+			.max(RATIO_FLOOR_BYTES)
+	}
+
+	/// Write one decompressed stream to a new file, enforcing the expansion caps
+	fn write_stream(
+		&mut self,
+		reader: &mut dyn Read,
+		member: &str,
+		compressed_size: u64,
+	) -> Result<(PathBuf, u64), CompressionError> {
+		if self.members >= self.limits.max_members {
+			return Err(CompressionError::ExpansionLimitExceeded(format!(
+				"more than {} members",
+				self.limits.max_members
+			)));
+		}
+		self.members += 1;
+
+		let path = self
+			.dir
+			.join(format!("{:05}-{}", self.members, safe_file_name(member)));
+		// create_new never follows a planted symlink or overwrites an existing file
+		let mut out = OpenOptions::new()
+			.write(true)
+			.create_new(true)
+			.open(&path)?;
+
+		let stream_cap = self.stream_cap(compressed_size);
+		let mut size = 0u64;
+		let mut buf = vec![0u8; COPY_BUFFER_BYTES];
+		loop {
+			let n = match reader.read(&mut buf) {
+				Ok(0) => break,
+				Ok(n) => n,
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+				Err(e) => return Err(e.into()),
+			};
+			size += n as u64;
+			self.written += n as u64;
+
+			if size > stream_cap {
+				return Err(CompressionError::ExpansionLimitExceeded(format!(
+					"{} expands more than {}:1",
+					member, self.limits.max_ratio
+				)));
+			}
+			if self.written > self.limits.max_total_bytes {
+				return Err(CompressionError::ExpansionLimitExceeded(format!(
+					"more than {} bytes extracted",
+					self.limits.max_total_bytes
+				)));
+			}
+
+			out.write_all(&buf[..n])?;
+		}
+		out.flush()?;
+
+		Ok((path, size))
+	}
+}
+
+/// Computes the CRC-32 of everything read through it
+struct Crc32Reader<R> {
+	inner: R,
+	hasher: crc32fast::Hasher,
+}
+
+impl<R: Read> Read for Crc32Reader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let n = self.inner.read(buf)?;
+		self.hasher.update(&buf[..n]);
+		Ok(n)
+	}
+}
+
+/// Central directory entry of a ZIP archive
+struct ZipEntry {
+	name: String,
+	flags: u16,
+	method: u16,
+	crc32: u32,
+	compressed_size: u64,
+	uncompressed_size: u64,
+	local_header_offset: u64,
+	symlink: bool,
+}
+
+fn malformed(msg: &str) -> CompressionError {
+	CompressionError::MalformedArchive(msg.to_string())
+}
+
+fn le_u16(buf: &[u8], at: usize) -> u16 {
+	u16::from_le_bytes([buf[at], buf[at + 1]])
+}
+
+fn le_u32(buf: &[u8], at: usize) -> u32 {
+	u32::from_le_bytes([buf[at], buf[at + 1], buf[at + 2], buf[at + 3]])
+}
+
+fn le_u64(buf: &[u8], at: usize) -> u64 {
+	let mut bytes = [0u8; 8];
+	bytes.copy_from_slice(&buf[at..at + 8]);
+	u64::from_le_bytes(bytes)
+}
+
+/// Read the central directory of the ZIP archive in `file`
+fn read_central_directory(file: &mut File) -> Result<Vec<ZipEntry>, CompressionError> {
+	let len = file.metadata()?.len();
+	let tail_len = len.min((EOCD_LEN + u16::MAX as usize) as u64) as usize;
+	if tail_len < EOCD_LEN {
+		return Err(malformed("too short for a ZIP archive"));
+	}
+
+	let mut tail = vec![0u8; tail_len];
+	file.seek(SeekFrom::Start(len - tail_len as u64))?;
+	file.read_exact(&mut tail)?;
+
+	let eocd = (0..=tail_len - EOCD_LEN)
+		.rev()
+		.find(|&i| le_u32(&tail, i) == EOCD_SIGNATURE)
+		.ok_or_else(|| malformed("no end of central directory record"))?;
+
+	let mut entry_count = u64::from(le_u16(&tail, eocd + 10));
+	let mut cd_size = u64::from(le_u32(&tail, eocd + 12));
+	let mut cd_offset = u64::from(le_u32(&tail, eocd + 16));
+
+	if entry_count == u64::from(u16::MAX)
+		|| cd_size == u64::from(u32::MAX)
+		|| cd_offset == u64::from(u32::MAX)
+	{
+		// ZIP64: the locator sits right before the end of central directory record
+		let locator = eocd
+			.checked_sub(ZIP64_EOCD_LOCATOR_LEN)
+			.filter(|&i| le_u32(&tail, i) == ZIP64_EOCD_LOCATOR_SIGNATURE)
+			.ok_or_else(|| malformed("missing ZIP64 end of central directory locator"))?;
+
+		let mut record = [0u8; ZIP64_EOCD_LEN];
+		file.seek(SeekFrom::Start(le_u64(&tail, locator + 8)))?;
+		file.read_exact(&mut record)?;
+		if le_u32(&record, 0) != ZIP64_EOCD_SIGNATURE {
+			return Err(malformed("bad ZIP64 end of central directory record"));
+		}
+		entry_count = le_u64(&record, 32);
+		cd_size = le_u64(&record, 40);
+		cd_offset = le_u64(&record, 48);
+	}
+
+	if cd_offset.checked_add(cd_size).is_none_or(|end| end > len) {
+		return Err(malformed("central directory lies outside the file"));
+	}
+	if entry_count > cd_size / CENTRAL_HEADER_LEN as u64 {
+		return Err(malformed("entry count does not fit the central directory"));
+	}
+
+	let mut cd = vec![0u8; cd_size as usize];
+	file.seek(SeekFrom::Start(cd_offset))?;
+	file.read_exact(&mut cd)?;
+
+	let mut entries = Vec::with_capacity(entry_count as usize);
+	let mut pos = 0;
+	for _ in 0..entry_count {
+		if pos + CENTRAL_HEADER_LEN > cd.len() || le_u32(&cd, pos) != CENTRAL_HEADER_SIGNATURE {
+			return Err(malformed("truncated central directory"));
+		}
+
+		let made_by = le_u16(&cd, pos + 4);
+		let flags = le_u16(&cd, pos + 8);
+		let method = le_u16(&cd, pos + 10);
+		let crc32 = le_u32(&cd, pos + 16);
+		let mut compressed_size = u64::from(le_u32(&cd, pos + 20));
+		let mut uncompressed_size = u64::from(le_u32(&cd, pos + 24));
+		let name_len = le_u16(&cd, pos + 28) as usize;
+		let extra_len = le_u16(&cd, pos + 30) as usize;
+		let comment_len = le_u16(&cd, pos + 32) as usize;
+		let external_attributes = le_u32(&cd, pos + 38);
+		let mut local_header_offset = u64::from(le_u32(&cd, pos + 42));
+
+		let name_start = pos + CENTRAL_HEADER_LEN;
+		let extra_start = name_start + name_len;
+		let extra_end = extra_start + extra_len;
+		let end = extra_end + comment_len;
+		if end > cd.len() {
+			return Err(malformed("truncated central directory"));
+		}
+
+		// ZIP64 extended information replaces saturated fields, in this order
+		let mut field = extra_start;
+		while field + 4 <= extra_end {
+			let id = le_u16(&cd, field);
+			let data_end = field + 4 + le_u16(&cd, field + 2) as usize;
+			if data_end > extra_end {
+				return Err(malformed("truncated extra field"));
+			}
+			if id == ZIP64_EXTRA_FIELD {
+				let mut value = field + 4;
+				for size in [
+					&mut uncompressed_size,
+					&mut compressed_size,
+					&mut local_header_offset,
+				] {
+					if *size == u64::from(u32::MAX) {
+						if value + 8 > data_end {
+							return Err(malformed("truncated ZIP64 extra field"));
+						}
+						*size = le_u64(&cd, value);
+						value += 8;
+					}
+				}
+			}
+			field = data_end;
+		}
+
+		// Unix hosts store the file mode in the upper half of the external attributes
+		let symlink = made_by >> 8 == 3 && (external_attributes >> 16) & 0o170000 == 0o120000;
+
+		entries.push(ZipEntry {
+			name: String::from_utf8_lossy(&cd[name_start..extra_start]).into_owned(),
+			flags,
+			method,
+			crc32,
+			compressed_size,
+			uncompressed_size,
+			local_header_offset,
+			symlink,
+		});
+		pos = end;
+	}
+
+	Ok(entries)
+}
+
+/// Offset of a member's data, just past its local file header
+fn local_data_offset(file: &mut File, header_offset: u64) -> Result<u64, CompressionError> {
+	let mut header = [0u8; LOCAL_HEADER_LEN as usize];
+	file.seek(SeekFrom::Start(header_offset))?;
+	file.read_exact(&mut header)?;
+	if le_u32(&header, 0) != LOCAL_HEADER_SIGNATURE {
+		return Err(malformed("bad local file header"));
+	}
+
+	Ok(header_offset
+		+ LOCAL_HEADER_LEN
+		+ u64::from(le_u16(&header, 26))
+		+ u64::from(le_u16(&header, 28)))
+}
+
+/// Normalize a member path, refusing absolute paths, drive letters and `..` components
+fn sanitize_member_name(name: &str) -> Option<String> {
+	let name = name.replace('\\', "/");
+	if name.starts_with('/') {
+		return None;
+	}
+
+	let mut parts = Vec::new();
+	for part in name.split('/') {
+		match part {
+			"" | "." => {}
+			".." => return None,
+			_ if part.contains(':') || part.contains('\0') => return None,
+			_ => parts.push(part),
+		}
+	}
+
+	(!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Name of the file a gzip stream expands to: the archive's name without its extension
+fn gzip_member_name(provenance: &str) -> String {
+	let name = provenance
+		.rsplit([MEMBER_SEPARATOR, '/', '\\'])
+		.next()
+		.unwrap_or(provenance);
+	let lower = name.to_lowercase();
+
+	if let Some(stem) = lower.strip_suffix(".tgz").map(|s| &name[..s.len()]) {
+		format!("{}.tar", stem)
+	} else if let Some(stem) = lower.strip_suffix(".gz").map(|s| &name[..s.len()]) {
+		stem.to_string()
+	} else if let Some(stem) = lower.strip_suffix(".gzip").map(|s| &name[..s.len()]) {
+		stem.to_string()
+	} else {
+		name.to_string()
+	}
+}
+
+/// File name to write a member to: its last path component with unusual characters replaced
+fn safe_file_name(member: &str) -> String {
+	let name: String = member
+		.rsplit('/')
+		.next()
+		.unwrap_or(member)
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+				c
+			} else {
+				'_'
+			}
+		})
+		.take(100)
+		.collect();
+	let name = name.trim_start_matches('.');
+
+	if name.is_empty() {
+		"member".to_string()
+	} else {
+		name.to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ingest::compression::MAX_NESTING_LEVEL;
+	use flate2::{
+		Compression,
+		write::{DeflateEncoder, GzEncoder},
+	};
+
+	const USERS_CSV: &[u8] = b"email,password\nalice@example.com,hunter2\n";
+
+	fn gzip(data: &[u8]) -> Vec<u8> {
+		let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+		encoder.write_all(data).expect("compress");
+		encoder.finish().expect("finish")
+	}
+
+	/// ZIP member for [`zip_archive`]: name, contents, whether to deflate, unix mode
+	type Member<'a> = (&'a str, &'a [u8], bool, Option<u32>);
+
+	/// Build a ZIP archive in memory
+	fn zip_archive(members: &[Member<'_>]) -> Vec<u8> {
+		let mut out = Vec::new();
+		let mut central = Vec::new();
+
+		for (name, data, deflate, mode) in members {
+			let stored = if *deflate {
+				let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+				encoder.write_all(data).expect("compress");
+				encoder.finish().expect("finish")
+			} else {
+				data.to_vec()
+			};
+			let method: u16 = if *deflate {
+				METHOD_DEFLATED
+			} else {
+				METHOD_STORED
+			};
+			let crc = crc32fast::hash(data);
+			let offset = out.len() as u32;
+
+			out.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+			out.extend_from_slice(&20u16.to_le_bytes());
+			out.extend_from_slice(&0u16.to_le_bytes());
+			out.extend_from_slice(&method.to_le_bytes());
+			out.extend_from_slice(&[0; 4]);
+			out.extend_from_slice(&crc.to_le_bytes());
+			out.extend_from_slice(&(stored.len() as u32).to_le_bytes());
+			out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+			out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+			out.extend_from_slice(&0u16.to_le_bytes());
+			out.extend_from_slice(name.as_bytes());
+			out.extend_from_slice(&stored);
+
+			let made_by: u16 = if mode.is_some() { (3 << 8) | 20 } else { 20 };
+			central.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+			central.extend_from_slice(&made_by.to_le_bytes());
+			central.extend_from_slice(&20u16.to_le_bytes());
+			central.extend_from_slice(&0u16.to_le_bytes());
+			central.extend_from_slice(&method.to_le_bytes());
+			central.extend_from_slice(&[0; 4]);
+			central.extend_from_slice(&crc.to_le_bytes());
+			central.extend_from_slice(&(stored.len() as u32).to_le_bytes());
+			central.extend_from_slice(&(data.len() as u32).to_le_bytes());
+			central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+			central.extend_from_slice(&[0; 8]);
+			central.extend_from_slice(&(mode.unwrap_or(0) << 16).to_le_bytes());
+			central.extend_from_slice(&offset.to_le_bytes());
+			central.extend_from_slice(name.as_bytes());
+		}
+
+		let cd_offset = out.len() as u32;
+		out.extend_from_slice(&central);
+		out.extend_from_slice(&EOCD_SIGNATURE.to_le_bytes());
+		out.extend_from_slice(&[0; 4]);
+		out.extend_from_slice(&(members.len() as u16).to_le_bytes());
+		out.extend_from_slice(&(members.len() as u16).to_le_bytes());
+		out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+		out.extend_from_slice(&cd_offset.to_le_bytes());
+		out.extend_from_slice(&0u16.to_le_bytes());
+		out
+	}
+
+	fn write_input(dir: &Path, name: &str, data: &[u8]) -> PathBuf {
+		let path = dir.join(name);
+		fs::write(&path, data).expect("write input");
+		path
+	}
+
+	fn dir_is_empty(dir: &Path) -> bool {
+		fs::read_dir(dir).expect("read dir").next().is_none()
+	}
+
+	#[test]
+	fn test_extract_gzip() {
+		let input_dir = tempfile::tempdir().expect("tempdir");
+		let work = tempfile::tempdir().expect("tempdir");
+		let input = write_input(input_dir.path(), "users.csv.gz", &gzip(USERS_CSV));
+
+		let extraction = extract(
+			&input,
+			"users.csv.gz",
+			work.path(),
+			&ExtractionLimits::default(),
+		)
+		.expect("extract");
+		assert_eq!(extraction.files.len(), 1);
+		let file = &extraction.files[0];
+		assert_eq!(file.provenance(), "users.csv.gz!users.csv");
+		assert_eq!(file.size, USERS_CSV.len() as u64);
+		assert_eq!(file.nesting_level, 1);
+		assert_eq!(fs::read(&file.path).expect("read"), USERS_CSV);
+
+		remove_extraction(&extraction.dir).expect("remove");
+		assert!(dir_is_empty(work.path()));
+	}
+
+	#[test]
+	fn test_extract_zip_skips_unsafe_members() {
+		let input_dir = tempfile::tempdir().expect("tempdir");
+		let work = tempfile::tempdir().expect("tempdir");
+		let archive = zip_archive(&[
+			("data/", b"", false, None),
+			("data/users.csv", USERS_CSV, false, None),
+			("notes.txt", b"plain notes\n", true, None),
+			("../evil.csv", b"owned\n", false, None),
+			("/etc/cron.d/evil", b"owned\n", false, None),
+			("C:\\evil.csv", b"owned\n", false, None),
+			("link.csv", b"/etc/shadow", false, Some(0o120777)),
+		]);
+		let input = write_input(input_dir.path(), "dump.zip", &archive);
+
+		let extraction = extract(
+			&input,
+			"dump.zip",
+			work.path(),
+			&ExtractionLimits::default(),
+		)
+		.expect("extract");
+		let provenance: Vec<String> = extraction.files.iter().map(|f| f.provenance()).collect();
+		assert_eq!(
+			provenance,
+			vec!["dump.zip!data/users.csv", "dump.zip!notes.txt"]
+		);
+		assert_eq!(
+			fs::read(&extraction.files[0].path).expect("read"),
+			USERS_CSV
+		);
+		assert_eq!(
+			fs::read(&extraction.files[1].path).expect("read"),
+			b"plain notes\n"
+		);
+		assert_eq!(extraction.skipped.len(), 4);
+		assert!(
+			extraction
+				.skipped
+				.iter()
+				.any(|s| s.contains("symbolic link"))
+		);
+		for file in &extraction.files {
+			assert_eq!(file.path.parent(), Some(extraction.dir.as_path()));
+		}
+		assert!(!work.path().join("evil.csv").exists());
+		assert!(!input_dir.path().join("evil.csv").exists());
+	}
+
+	#[test]
+	fn test_nested_archives_respect_nesting_limit() {
+		let input_dir = tempfile::tempdir().expect("tempdir");
+		let work = tempfile::tempdir().expect("tempdir");
+
+		let inner = gzip(USERS_CSV);
+		let archive = zip_archive(&[("users.csv.gz", &inner, false, None)]);
+		let input = write_input(input_dir.path(), "dump.zip", &gzip(&archive));
+
+		let extraction = extract(
+			&input,
+			"dump.zip.gz",
+			work.path(),
+			&ExtractionLimits::default(),
+		)
+		.expect("extract");
+		assert_eq!(extraction.files.len(), 1);
+		let file = &extraction.files[0];
+		assert_eq!(
+			file.provenance(),
+			"dump.zip.gz!dump.zip!users.csv.gz!users.csv"
+		);
+		assert_eq!(file.nesting_level, 3);
+		assert_eq!(fs::read(&file.path).expect("read"), USERS_CSV);
+		// Intermediate archives are not kept
+		assert_eq!(fs::read_dir(&extraction.dir).expect("read dir").count(), 1);
+		remove_extraction(&extraction.dir).expect("remove");
+
+		let too_deep = write_input(input_dir.path(), "deep.gz", &gzip(&gzip(&archive)));
+		let result = extract(
+			&too_deep,
+			"deep.gz",
+			work.path(),
+			&ExtractionLimits::default(),
+		);
+		assert!(matches!(
+			result,
+			Err(CompressionError::NestingLimitExceeded {
+				actual: 4,
+				max_allowed: MAX_NESTING_LEVEL
+			})
+		));
+		assert!(dir_is_empty(work.path()));
+	}
+
+	#[test]
+	fn test_expansion_caps_stop_bombs() {
+		let input_dir = tempfile::tempdir().expect("tempdir");
+		let work = tempfile::tempdir().expect("tempdir");
+
+		// 16 MiB of zeros compresses far beyond 200:1
+		let bomb = write_input(
+			input_dir.path(),
+			"bomb.gz",
+			&gzip(&vec![0u8; 16 * 1024 * 1024]),
+		);
+		let result = extract(&bomb, "bomb.gz", work.path(), &ExtractionLimits::default());
+		assert!(matches!(
+			result,
+			Err(CompressionError::ExpansionLimitExceeded(_))
+		));
+		assert!(dir_is_empty(work.path()));
+
+		let archive = zip_archive(&[
+			("a.csv", USERS_CSV, false, None),
+			("b.csv", USERS_CSV, false, None),
+		]);
+		let input = write_input(input_dir.path(), "dump.zip", &archive);
+		let limits = ExtractionLimits {
+			max_total_bytes: USERS_CSV.len() as u64 + 1,
+			..ExtractionLimits::default()
+		};
+		let result = extract(&input, "dump.zip", work.path(), &limits);
+		assert!(matches!(
+			result,
+			Err(CompressionError::ExpansionLimitExceeded(_))
+		));
+
+		let limits = ExtractionLimits {
+			max_members: 1,
+			..ExtractionLimits::default()
+		};
+		let result = extract(&input, "dump.zip", work.path(), &limits);
+		assert!(matches!(
+			result,
+			Err(CompressionError::ExpansionLimitExceeded(_))
+		));
+		assert!(dir_is_empty(work.path()));
+	}
+
+	#[test]
+	fn test_corrupt_and_unsupported_archives_are_rejected() {
+		let input_dir = tempfile::tempdir().expect("tempdir");
+		let work = tempfile::tempdir().expect("tempdir");
+
+		let mut archive = zip_archive(&[("users.csv", USERS_CSV, false, None)]);
+		// Flip a byte of the stored data so the CRC no longer matches
+		archive[LOCAL_HEADER_LEN as usize + "users.csv".len()] ^= 0xFF;
+		let input = write_input(input_dir.path(), "corrupt.zip", &archive);
+		let result = extract(
+			&input,
+			"corrupt.zip",
+			work.path(),
+			&ExtractionLimits::default(),
+		);
+		assert!(matches!(result, Err(CompressionError::MalformedArchive(_))));
+
+		let input = write_input(input_dir.path(), "dump.bz2", b"BZh91AY&SY");
+		let result = extract(
+			&input,
+			"dump.bz2",
+			work.path(),
+			&ExtractionLimits::default(),
+		);
+		assert!(matches!(result, Err(CompressionError::UnsupportedFormat)));
+		assert!(dir_is_empty(work.path()));
+	}
+
+	#[test]
+	fn test_member_names() {
+		assert_eq!(
+			sanitize_member_name("a/./b.csv"),
+			Some("a/b.csv".to_string())
+		);
+		assert_eq!(
+			sanitize_member_name("a\\b.csv"),
+			Some("a/b.csv".to_string())
+		);
+		assert_eq!(sanitize_member_name("a/../../b.csv"), None);
+		assert_eq!(sanitize_member_name("/b.csv"), None);
+		assert_eq!(sanitize_member_name("c:/b.csv"), None);
+		assert_eq!(gzip_member_name("dump.zip!users.CSV.GZ"), "users.CSV");
+		assert_eq!(gzip_member_name("backup.tgz"), "backup.tar");
+		assert_eq!(gzip_member_name("raw"), "raw");
+		assert_eq!(safe_file_name("dir/..hidden name.csv"), "hidden_name.csv");
+		assert_eq!(safe_file_name("../"), "member");
+	}
+}
@@ -0,0 +1,444 @@
+//! Combo-list and stealer-log line format
+//!
+//! Most credential dumps are not CSV but one credential per line with loose,
+//! inconsistent delimiters:
+//! - `email:password` and `user;pass` combo lists
+//! - `url:user:pass` infostealer logs (the URL keeps its own `://` and port)
+//! - `email|hash|salt` hash dumps
+//!
+//! The delimiter and the role of each field are inferred from a sample of the
+//! file; lines that do not fit the file's layout are inferred on their own.
+//! Passwords and salts may contain the delimiter, since they take the rest of the line.
+//! Every line becomes a row under [`COMBO_HEADER`] so detection and storage see
+//! properly named columns.
+
+use std::collections::HashMap;
+
+use crate::core::hash_utils::{HashAlgorithmFingerprint, identify_hash_fingerprint};
+use crate::detection::analyzer::is_email_like;
+use crate::ingest::adapters::FormatAdapter;
+
+/// Synthetic header for combo-list rows
+pub const COMBO_HEADER: [&str; 6] = ["url", "username", "email", "password", "hash", "salt"];
+
+/// Delimiters tried when inferring a line's layout
+const DELIMITERS: [char; 5] = [':', ';', '|', '\t', ','];
+
+/// Number of lines sampled to infer a file's layout
+pub const SAMPLE_LINES: usize = 1000;
+
+/// Role of a field in a combo-list line, in [`COMBO_HEADER`] order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComboField {
+	Url,
+	Username,
+	Email,
+	Password,
+	Hash,
+	Salt,
+}
+
+impl ComboField {
+	/// Column of this field in [`COMBO_HEADER`]
+	fn column(self) -> usize {
+		self as usize
+	}
+
+	/// Whether a value can fill this role
+	fn accepts(self, value: &str) -> bool {
+		match self {
+			ComboField::Url => is_url(value),
+			ComboField::Email => is_email_like(value),
+			ComboField::Hash => is_hash(value),
+			ComboField::Username => !value.is_empty(),
+			ComboField::Password | ComboField::Salt => true,
+		}
+	}
+}
+
+/// Delimiter and field roles of a combo-list line
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ComboLayout {
+	pub delimiter: char,
+	pub fields: Vec<ComboField>,
+}
+
+impl ComboLayout {
+	/// Infer the most common layout among sample lines
+	pub fn infer<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+		let mut counts: HashMap<ComboLayout, usize> = HashMap::new();
+		let mut order = Vec::new();
+		for line in lines {
+			if let Some(layout) = Self::infer_line(trim_line_ending(line)) {
+				let count = counts.entry(layout.clone()).or_insert(0);
+				if *count == 0 {
+					order.push(layout);
+				}
+				*count += 1;
+			}
+		}
+
+		// Ties go to the layout seen first
+		let best = order.iter().map(|layout| counts[layout]).max()?;
+		order.into_iter().find(|layout| counts[layout] == best)
+	}
+
+	/// Infer the layout of a single line
+	///
+	/// The delimiter is the candidate that ends the first field earliest, since
+	/// URLs, emails and usernames rarely contain delimiters but passwords may.
+	pub fn infer_line(line: &str) -> Option<Self> {
+		DELIMITERS
+			.iter()
+			.filter_map(|&delimiter| {
+				let fields = split_fields(line, delimiter, usize::MAX);
+				let first_len = fields.first()?.len();
+				Self::from_fields(delimiter, &fields).map(|layout| (first_len, layout))
+			})
+			.min_by_key(|(first_len, _)| *first_len)
+			.map(|(_, layout)| layout)
+	}
+
+	/// Assign roles to the fields of a line split on `delimiter`
+	fn from_fields(delimiter: char, fields: &[&str]) -> Option<Self> {
+		let (first, second) = match fields {
+			[first, second, ..] if !first.is_empty() => (*first, *second),
+			_ => return None,
+		};
+
+		let account = |value: &str| {
+			if is_email_like(value) {
+				ComboField::Email
+			} else {
+				ComboField::Username
+			}
+		};
+
+		let roles = if is_url(first) {
+			// url:user:pass
+			if fields.len() < 3 || second.is_empty() {
+				return None;
+			}
+			vec![ComboField::Url, account(second), ComboField::Password]
+		} else if is_hash(second) {
+			// user|hash or user|hash|salt
+			let mut roles = vec![account(first), ComboField::Hash];
+			if fields.len() >= 3 {
+				roles.push(ComboField::Salt);
+			}
+			roles
+		} else {
+			// user:pass
+			vec![account(first), ComboField::Password]
+		};
+
+		Some(ComboLayout {
+			delimiter,
+			fields: roles,
+		})
+	}
+
+	/// Split a line into a [`COMBO_HEADER`] row, or None if it does not fit this layout
+	pub fn apply(&self, line: &str) -> Option<Vec<String>> {
+		let values = split_fields(line, self.delimiter, self.fields.len());
+		if values.len() != self.fields.len() {
+			return None;
+		}
+
+		let mut row = vec![String::new(); COMBO_HEADER.len()];
+		for (field, value) in self.fields.iter().zip(values) {
+			if !field.accepts(value) {
+				return None;
+			}
+			row[field.column()] = value.to_string();
+		}
+		Some(row)
+	}
+}
+
+/// Parses combo-list lines with a file-wide layout, falling back to per-line inference
+#[derive(Debug, Clone, Default)]
+pub struct ComboLineParser {
+	layout: Option<ComboLayout>,
+}
+
+impl ComboLineParser {
+	/// Create a parser for a file whose layout was inferred from a sample
+	pub fn new(layout: Option<ComboLayout>) -> Self {
+		ComboLineParser { layout }
+	}
+
+	/// Create a parser from sample lines of the file
+	pub fn from_sample<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+		Self::new(ComboLayout::infer(lines))
+	}
+
+	/// The file-wide layout, if one was inferred
+	pub fn layout(&self) -> Option<&ComboLayout> {
+		self.layout.as_ref()
+	}
+
+	/// Parse one line (with or without its line ending) into a [`COMBO_HEADER`] row
+	///
+	/// Returns None for blank lines and lines no layout fits.
+	pub fn parse_line(&self, line: &str) -> Option<Vec<String>> {
+		let line = trim_line_ending(line);
+		if line.trim().is_empty() {
+			return None;
+		}
+
+		self.layout
+			.as_ref()
+			.and_then(|layout| layout.apply(line))
+			.or_else(|| ComboLayout::infer_line(line)?.apply(line))
+	}
+}
+
+/// Combo-list adapter implementing `FormatAdapter`
+///
+/// Emits the [`COMBO_HEADER`] row followed by one row per recognized line;
+/// unrecognized lines are dropped.
+#[derive(Default)]
+pub struct ComboListAdapter;
+
+impl ComboListAdapter {
+	pub fn new() -> Self {
+		ComboListAdapter
+	}
+}
+
+impl FormatAdapter for ComboListAdapter {
+	fn parse(&self, input: &str) -> Vec<Vec<String>> {
+		let parser = ComboLineParser::from_sample(input.lines().take(SAMPLE_LINES));
+		let rows: Vec<Vec<String>> = input
+			.lines()
+			.filter_map(|line| parser.parse_line(line))
+			.collect();
+		if rows.is_empty() {
+			return rows;
+		}
+
+		std::iter::once(header_row()).chain(rows).collect()
+	}
+}
+
+/// The [`COMBO_HEADER`] as a row
+pub fn header_row() -> Vec<String> {
+	COMBO_HEADER.iter().map(|s| s.to_string()).collect()
+}
+
+/// Strip a trailing `\n` or `\r\n`
+fn trim_line_ending(line: &str) -> &str {
+	let line = line.strip_suffix('\n').unwrap_or(line);
+	line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Split a line on `delimiter` into at most `limit` fields, the last taking the rest
+///
+/// A leading URL stays whole: the `://` after its scheme and a `:port` are not delimiters.
+fn split_fields(line: &str, delimiter: char, limit: usize) -> Vec<&str> {
+	match url_end(line, delimiter) {
+		Some(end) if limit > 1 => {
+			let mut fields = vec![&line[..end]];
+			fields.extend(line[end + delimiter.len_utf8()..].splitn(limit - 1, delimiter));
+			fields
+		}
+		_ => line.splitn(limit, delimiter).collect(),
+	}
+}
+
+/// Byte offset of the delimiter ending a leading `scheme://` URL, if the line starts with one
+fn url_end(line: &str, delimiter: char) -> Option<usize> {
+	let scheme_end = line.find("://")?;
+	if !is_scheme(&line[..scheme_end]) {
+		return None;
+	}
+
+	let mut pos = scheme_end + 3;
+	loop {
+		let at = pos + line[pos..].find(delimiter)?;
+		if delimiter == ':'
+			&& let Some(port_len) = port_len(&line[at + 1..])
+		{
+			pos = at + 1 + port_len;
+			continue;
+		}
+		return Some(at);
+	}
+}
+
+/// Length of a port number at the start of `rest`, if it is one
+///
+/// `host:8080/path` is a port; in `host:8080:user:pass` the digits are a port
+/// only when a username and password still follow.
+fn port_len(rest: &str) -> Option<usize> {
+	let digits = rest.chars().take_while(char::is_ascii_digit).count();
+	if digits == 0 || digits > 5 {
+		return None;
+	}
+	match rest[digits..].chars().next() {
+		Some('/') => Some(digits),
+		Some(':') if rest[digits + 1..].contains(':') => Some(digits),
+		_ => None,
+	}
+}
+
+/// Whether a field is a URL (`scheme://...`) or a scheme-less `host/path` or `www.` host
+fn is_url(value: &str) -> bool {
+	if value.contains(char::is_whitespace) {
+		return false;
+	}
+	if let Some((scheme, rest)) = value.split_once("://") {
+		return is_scheme(scheme) && !rest.is_empty();
+	}
+	!value.contains('@')
+		&& (value.starts_with("www.")
+			|| value.split_once('/').is_some_and(|(host, _)| is_host(host)))
+}
+
+/// Whether a string is a URL scheme such as `https` or `android`
+fn is_scheme(value: &str) -> bool {
+	value.starts_with(|c: char| c.is_ascii_alphabetic())
+		&& value
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Whether a string is a dotted host name such as `example.com`
+fn is_host(value: &str) -> bool {
+	let labels: Vec<&str> = value.split('.').collect();
+	labels.len() >= 2
+		&& labels
+			.iter()
+			.all(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+		&& labels
+			.last()
+			.is_some_and(|tld| tld.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+/// Whether a field is a password hash with a recognizable fingerprint
+fn is_hash(value: &str) -> bool {
+	identify_hash_fingerprint(value).algorithm != HashAlgorithmFingerprint::Unknown
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn row(
+		url: &str,
+		username: &str,
+		email: &str,
+		password: &str,
+		hash: &str,
+		salt: &str,
+	) -> Vec<String> {
+		[url, username, email, password, hash, salt]
+			.iter()
+			.map(|s| s.to_string())
+			.collect()
+	}
+
+	#[test]
+	fn test_email_password_with_delimiter_in_password() {
+		let parser =
+			ComboLineParser::from_sample(["alice@example.com:hunter2", "bob@example.com:pa:ss"]);
+		assert_eq!(
+			parser.layout(),
+			Some(&ComboLayout {
+				delimiter: ':',
+				fields: vec![ComboField::Email, ComboField::Password],
+			})
+		);
+		assert_eq!(
+			parser.parse_line("bob@example.com:pa:ss\r\n"),
+			Some(row("", "", "bob@example.com", "pa:ss", "", ""))
+		);
+	}
+
+	#[test]
+	fn test_username_password_semicolon() {
+		let parser = ComboLineParser::from_sample(["jdoe;s3cret", "mallory;p;w"]);
+		assert_eq!(
+			parser.parse_line("mallory;p;w"),
+			Some(row("", "mallory", "", "p;w", "", ""))
+		);
+	}
+
+	#[test]
+	fn test_stealer_log_urls_keep_scheme_and_port() {
+		let parser = ComboLineParser::default();
+		assert_eq!(
+			parser.parse_line("https://login.example.com:8443/auth:alice@example.com:pw:1"),
+			Some(row(
+				"https://login.example.com:8443/auth",
+				"",
+				"alice@example.com",
+				"pw:1",
+				"",
+				""
+			))
+		);
+		assert_eq!(
+			parser.parse_line("http://example.com:8080:bob:letmein"),
+			Some(row("http://example.com:8080", "bob", "", "letmein", "", ""))
+		);
+		assert_eq!(
+			parser.parse_line("android://abc==@com.example.app/:carol:pw"),
+			Some(row(
+				"android://abc==@com.example.app/",
+				"carol",
+				"",
+				"pw",
+				"",
+				""
+			))
+		);
+		assert_eq!(
+			parser.parse_line("www.example.com|dave|pw"),
+			Some(row("www.example.com", "dave", "", "pw", "", ""))
+		);
+	}
+
+	#[test]
+	fn test_hash_and_salt() {
+		let md5 = "5f4dcc3b5aa765d61d8327deb882cf99";
+		let line = format!("alice@example.com|{}|x|y", md5);
+		let parser = ComboLineParser::from_sample([line.as_str()]);
+		assert_eq!(
+			parser.parse_line(&line),
+			Some(row("", "", "alice@example.com", "", md5, "x|y"))
+		);
+		assert_eq!(
+			parser.parse_line(&format!("bob|{}", md5)),
+			Some(row("", "bob", "", "", md5, ""))
+		);
+	}
+
+	#[test]
+	fn test_lines_recover_from_file_layout() {
+		let parser = ComboLineParser::from_sample([
+			"alice@example.com:one",
+			"bob@example.com:two",
+			"carol;three",
+		]);
+		assert_eq!(parser.layout().map(|l| l.delimiter), Some(':'));
+		// A plain username does not fit the file's email column
+		assert_eq!(
+			parser.parse_line("carol;three"),
+			Some(row("", "carol", "", "three", "", ""))
+		);
+		assert_eq!(parser.parse_line("   "), None);
+		assert_eq!(parser.parse_line("no delimiter here"), None);
+	}
+
+	#[test]
+	fn test_adapter_emits_synthetic_header() {
+		let rows =
+			ComboListAdapter::new().parse("alice@example.com:hunter2\n\nbob@example.com:letmein\n");
+		assert_eq!(rows.len(), 3);
+		assert_eq!(rows[0], header_row());
+		assert_eq!(rows[2], row("", "", "bob@example.com", "letmein", "", ""));
+		assert!(ComboListAdapter::new().parse("\n\n").is_empty());
+	}
+}
@@ -3,7 +3,7 @@
 //! Detects and handles compressed files with safety guardrails:
 //! - Magic byte detection for common compression formats
 //! - Nested compression tracking (max 3 levels)
-//! - Safe extraction to temporary directories
+//! - Safe extraction to temporary directories (see [`crate::ingest::archive`])
 //! - Audit logging of compression operations
 
 use std::fs::File;
@@ -53,10 +53,16 @@ pub enum CompressionError {
 	/// Unsupported compression format
 	#[error("Unsupported compression format")]
 	UnsupportedFormat,
+	/// Archive structure is damaged or uses features that cannot be read safely
+	#[error("Malformed archive: {0}")]
+	MalformedArchive(String),
+	/// Extraction would exceed the ratio, size or member-count caps (likely a zip bomb)
+	#[error("Archive expansion limit exceeded: {0}")]
+	ExpansionLimitExceeded(String),
 }
 
 /// Maximum allowed nesting level for compressed archives
-const MAX_NESTING_LEVEL: u32 = 3;
+pub const MAX_NESTING_LEVEL: u32 = 3;
 
 impl CompressionInfo {
 	/// Detect compression format of a file
@@ -107,8 +113,15 @@ impl CompressionInfo {
 		}
 
 		// Check magic bytes in order of specificity
-		// ZIP: 0x504B (PK)
-		if bytes_read >= 2 && magic[0] == 0x50 && magic[1] == 0x4B {
+		// ZIP: "PK" followed by a local file header, empty archive or spanning marker;
+		// plain "PK" alone is too common at the start of text files
+		if bytes_read >= 4
+			&& magic[0] == 0x50
+			&& magic[1] == 0x4B
+			&& matches!(
+				(magic[2], magic[3]),
+				(0x03, 0x04) | (0x05, 0x06) | (0x07, 0x08)
+			) {
 			return Ok(CompressionFormat::Zip);
 		}
 
@@ -117,8 +130,13 @@ impl CompressionInfo {
 			return Ok(CompressionFormat::Gzip);
 		}
 
-		// bzip2: 0x42 0x5A (BZ)
-		if bytes_read >= 2 && magic[0] == 0x42 && magic[1] == 0x5A {
+		// bzip2: "BZh" followed by the block size digit
+		if bytes_read >= 4
+			&& magic[0] == 0x42
+			&& magic[1] == 0x5A
+			&& magic[2] == b'h'
+			&& (b'1'..=b'9').contains(&magic[3])
+		{
 			return Ok(CompressionFormat::Bzip2);
 		}
 
@@ -204,6 +222,21 @@ mod tests {
 		assert!(info.is_compressed());
 	}
 
+	#[test]
+	fn test_text_with_magic_prefix_is_uncompressed() {
+		for contents in [&b"PKID,email\n1,a@example.com\n"[..], b"BZ-1234,code\n"] {
+			let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+			temp_file
+				.write_all(contents)
+				.expect("Failed to write to temp file");
+			temp_file.flush().expect("Failed to flush");
+
+			let info =
+				CompressionInfo::detect(temp_file.path()).expect("Failed to detect compression");
+			assert_eq!(info.format, CompressionFormat::Uncompressed);
+		}
+	}
+
 	#[test]
 	fn test_detect_bzip2() {
 		let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
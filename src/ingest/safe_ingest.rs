@@ -12,6 +12,9 @@ use std::io;
 /// Maximum size of file to attempt to process (100 MB)
 const MAX_FILE_SIZE: usize = 100 * 1024 * 1024;
 
+/// Size of the chunks read while scanning a file
+const SCAN_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Result of file safety analysis
 #[derive(Debug, Clone)]
 pub struct FileSafetyAnalysis {
@@ -34,117 +37,202 @@ pub struct FileSafetyAnalysis {
 /// This function never panics and returns detailed information about potential issues.
 /// Binary confidence is calculated as: null_bytes (95%) + non_printable_ratio (80%) + invalid_utf8 (40%)
 pub fn analyze_file_safety(data: &[u8]) -> FileSafetyAnalysis {
-	let file_size = data.len();
-	let mut warnings = Vec::new();
-	let mut is_binary = false;
-	let mut binary_confidence: f64 = 0.0;
-	let mut safe_to_process = true;
-
-	// Check file size
-	if file_size == 0 {
-		warnings.push("File is empty".to_string());
-		safe_to_process = false;
-	}
+	let mut scanner = SafetyScanner::default();
+	scanner.update(data);
+	scanner.finish()
+}
 
-	if file_size > MAX_FILE_SIZE {
-		warnings.push(format!(
-			"File is {} MB (max: {} MB), will attempt partial processing",
-			file_size / (1024 * 1024),
-			MAX_FILE_SIZE / (1024 * 1024)
-		));
-		// Still try to process up to MAX_FILE_SIZE
-	}
+/// Incremental form of [`analyze_file_safety`] for data read in chunks
+///
+/// Feeding a file chunk by chunk gives the same analysis as passing it whole.
+#[derive(Debug, Default)]
+pub struct SafetyScanner {
+	file_size: usize,
+	/// First bytes of the data, for magic header checks
+	head: Vec<u8>,
+	has_null: bool,
+	non_printable_count: usize,
+	utf8_invalid: bool,
+	/// Incomplete UTF-8 sequence at the end of the last chunk
+	utf8_carry: Vec<u8>,
+	has_newline: bool,
+	has_comma_or_tab: bool,
+	has_json_bracket: bool,
+	has_colon: bool,
+}
+
+impl SafetyScanner {
+	/// Scan the next chunk of data
+	pub fn update(&mut self, data: &[u8]) {
+		self.file_size += data.len();
+		if self.head.len() < 4 {
+			let take = (4 - self.head.len()).min(data.len());
+			self.head.extend_from_slice(&data[..take]);
+		}
+
+		for &b in data {
+			match b {
+				0 => self.has_null = true,
+				b'\n' => self.has_newline = true,
+				b',' | b'\t' => self.has_comma_or_tab = true,
+				b'{' | b'[' => self.has_json_bracket = true,
+				b':' => self.has_colon = true,
+				_ => {}
+			}
+			if !is_text_byte(b) {
+				self.non_printable_count += 1;
+			}
+		}
 
-	// Check for binary content (null bytes are strong indicator - 95% confidence)
-	if data.contains(&0) {
-		is_binary = true;
-		binary_confidence = 95.0;
-		warnings.push("File contains null bytes - binary format detected".to_string());
-		safe_to_process = false;
+		self.update_utf8(data);
 	}
 
-	// Check for high proportion of non-printable bytes (80% confidence when > 30%)
-	let non_printable_count = data.iter().filter(|&&b| !is_text_byte(b)).count();
+	/// Track UTF-8 validity, allowing multi-byte characters to span chunks
+	fn update_utf8(&mut self, mut data: &[u8]) {
+		if self.utf8_invalid {
+			return;
+		}
 
-	let non_printable_ratio = if file_size > 0 {
-		non_printable_count as f64 / file_size as f64
-	} else {
-		0.0
-	};
+		while !self.utf8_carry.is_empty() {
+			let Some((&b, rest)) = data.split_first() else {
+				return;
+			};
+			data = rest;
+			self.utf8_carry.push(b);
+			match std::str::from_utf8(&self.utf8_carry) {
+				Ok(_) => self.utf8_carry.clear(),
+				Err(e) if e.error_len().is_none() => {}
+				Err(_) => {
+					self.utf8_invalid = true;
+					return;
+				}
+			}
+		}
 
-	if non_printable_ratio > 0.30 {
-		is_binary = true;
-		// Scale confidence 0-80% based on non-printable ratio
-		binary_confidence = binary_confidence.max((non_printable_ratio * 100.0).min(80.0));
-		warnings.push(format!(
-			"File has {:.1}% non-text bytes - binary format likely",
-			non_printable_ratio * 100.0
-		));
-		safe_to_process = false;
-	} else if non_printable_ratio > 0.10 {
-		// Moderate binary likelihood (10-30% non-printable = 20-40% confidence)
-		binary_confidence = binary_confidence.max(10.0 + (non_printable_ratio * 200.0));
+		if let Err(e) = std::str::from_utf8(data) {
+			if e.error_len().is_none() {
+				self.utf8_carry = data[e.valid_up_to()..].to_vec();
+			} else {
+				self.utf8_invalid = true;
+			}
+		}
 	}
 
-	// Check UTF-8 validity (40% confidence if invalid)
-	let is_valid_utf8 = std::str::from_utf8(data).is_ok();
-	if !is_valid_utf8 {
-		warnings.push("File is not valid UTF-8 (contains invalid byte sequences)".to_string());
-		binary_confidence = binary_confidence.max(40.0);
-		// Can still try to process with lossy conversion
-	}
+	/// Finish the scan and report the analysis
+	pub fn finish(self) -> FileSafetyAnalysis {
+		let file_size = self.file_size;
+		let data = &self.head;
+		let mut warnings = Vec::new();
+		let mut is_binary = false;
+		let mut binary_confidence: f64 = 0.0;
+		let mut safe_to_process = true;
+
+		// Check file size
+		if file_size == 0 {
+			warnings.push("File is empty".to_string());
+			safe_to_process = false;
+		}
 
-	// Check for ELF magic header (0x7F 'E' 'L' 'F') - strong binary indicator
-	if data.len() >= 4 && data[0] == 0x7F && data[1] == b'E' && data[2] == b'L' && data[3] == b'F' {
-		is_binary = true;
-		binary_confidence = 99.0;
-		warnings.push("File appears to be ELF binary executable".to_string());
-		safe_to_process = false;
-	}
+		if file_size > MAX_FILE_SIZE {
+			warnings.push(format!(
+				"File is {} MB (max: {} MB), will attempt partial processing",
+				file_size / (1024 * 1024),
+				MAX_FILE_SIZE / (1024 * 1024)
+			));
+			// Still try to process up to MAX_FILE_SIZE
+		}
 
-	// Check for PE (Windows) magic header (0x4D 0x5A = 'MZ')
-	if data.len() >= 2 && data[0] == 0x4D && data[1] == 0x5A {
-		is_binary = true;
-		binary_confidence = 98.0;
-		warnings.push("File appears to be Windows PE binary".to_string());
-		safe_to_process = false;
-	}
+		// Check for binary content (null bytes are strong indicator - 95% confidence)
+		if self.has_null {
+			is_binary = true;
+			binary_confidence = 95.0;
+			warnings.push("File contains null bytes - binary format detected".to_string());
+			safe_to_process = false;
+		}
 
-	// Check for Mach-O magic header (0xFE 0xED 0xFA / 0xCE 0xFA = macOS binary)
-	if data.len() >= 2
-		&& ((data[0] == 0xFE && data[1] == 0xED) || (data[0] == 0xCE && data[1] == 0xFA))
-	{
-		is_binary = true;
-		binary_confidence = 98.0;
-		warnings.push("File appears to be Mach-O binary (macOS)".to_string());
-		safe_to_process = false;
-	}
+		// Check for high proportion of non-printable bytes (80% confidence when > 30%)
+		let non_printable_ratio = if file_size > 0 {
+			self.non_printable_count as f64 / file_size as f64
+		} else {
+			0.0
+		};
+
+		if non_printable_ratio > 0.30 {
+			is_binary = true;
+			// Scale confidence 0-80% based on non-printable ratio
+			binary_confidence = binary_confidence.max((non_printable_ratio * 100.0).min(80.0));
+			warnings.push(format!(
+				"File has {:.1}% non-text bytes - binary format likely",
+				non_printable_ratio * 100.0
+			));
+			safe_to_process = false;
+		} else if non_printable_ratio > 0.10 {
+			// Moderate binary likelihood (10-30% non-printable = 20-40% confidence)
+			binary_confidence = binary_confidence.max(10.0 + (non_printable_ratio * 200.0));
+		}
 
-	// Check for common text formats by looking at content patterns
-	if !is_binary
-		&& safe_to_process
-		&& !data.is_empty()
-		&& let Ok(text) = std::str::from_utf8(data)
-	{
-		// Check if it looks like structured data
-		let looks_like_csv = text.contains('\n') && (text.contains(',') || text.contains('\t'));
-		let looks_like_json = text.contains('{') || text.contains('[');
-		let looks_like_yaml = text.contains(':');
-
-		if !looks_like_csv && !looks_like_json && !looks_like_yaml {
-			warnings.push(
-				"File doesn't match common formats (CSV, JSON, YAML) - may be raw text".to_string(),
-			);
+		// Check UTF-8 validity (40% confidence if invalid)
+		let is_valid_utf8 = !self.utf8_invalid && self.utf8_carry.is_empty();
+		if !is_valid_utf8 {
+			warnings.push("File is not valid UTF-8 (contains invalid byte sequences)".to_string());
+			binary_confidence = binary_confidence.max(40.0);
+			// Can still try to process with lossy conversion
 		}
-	}
 
-	FileSafetyAnalysis {
-		is_binary,
-		binary_confidence: binary_confidence.min(100.0),
-		is_valid_utf8,
-		file_size,
-		warnings,
-		safe_to_process,
+		// Check for ELF magic header (0x7F 'E' 'L' 'F') - strong binary indicator
+		if data.len() >= 4
+			&& data[0] == 0x7F
+			&& data[1] == b'E'
+			&& data[2] == b'L'
+			&& data[3] == b'F'
+		{
+			is_binary = true;
+			binary_confidence = 99.0;
+			warnings.push("File appears to be ELF binary executable".to_string());
+			safe_to_process = false;
+		}
+
+		// Check for PE (Windows) magic header (0x4D 0x5A = 'MZ')
+		if data.len() >= 2 && data[0] == 0x4D && data[1] == 0x5A {
+			is_binary = true;
+			binary_confidence = 98.0;
+			warnings.push("File appears to be Windows PE binary".to_string());
+			safe_to_process = false;
+		}
+
+		// Check for Mach-O magic header (0xFE 0xED 0xFA / 0xCE 0xFA = macOS binary)
+		if data.len() >= 2
+			&& ((data[0] == 0xFE && data[1] == 0xED) || (data[0] == 0xCE && data[1] == 0xFA))
+		{
+			is_binary = true;
+			binary_confidence = 98.0;
+			warnings.push("File appears to be Mach-O binary (macOS)".to_string());
+			safe_to_process = false;
+		}
+
+		// Check for common text formats by looking at content patterns
+		if !is_binary && safe_to_process && file_size > 0 && is_valid_utf8 {
+			// Check if it looks like structured data
+			let looks_like_csv = self.has_newline && self.has_comma_or_tab;
+			let looks_like_json = self.has_json_bracket;
+			let looks_like_yaml = self.has_colon;
+
+			if !looks_like_csv && !looks_like_json && !looks_like_yaml {
+				warnings.push(
+					"File doesn't match common formats (CSV, JSON, YAML) - may be raw text"
+						.to_string(),
+				);
+			}
+		}
+
+		FileSafetyAnalysis {
+			is_binary,
+			binary_confidence: binary_confidence.min(100.0),
+			is_valid_utf8,
+			file_size,
+			warnings,
+			safe_to_process,
+		}
 	}
 }
 
@@ -231,6 +319,74 @@ pub async fn safe_read_file(
 	Ok((content, had_errors, safety))
 }
 
+/// Safety analysis and content hashes of a file, computed in one streaming pass
+#[derive(Debug, Clone)]
+pub struct FileScan {
+	pub safety: FileSafetyAnalysis,
+	/// SHA-256 of the raw file bytes (hex)
+	pub sha256: String,
+	/// MD5 of the raw file bytes (hex)
+	pub md5: String,
+}
+
+/// Safely scan a file from disk without loading it into memory
+///
+/// Reads the file in fixed-size chunks to analyze it like [`safe_read_file`]
+/// and hash its contents, so files of any size are checked in constant memory.
+pub async fn scan_file(path: &std::path::Path, verbose: u32) -> io::Result<FileScan> {
+	use sha2::Digest;
+	use tokio::io::AsyncReadExt;
+
+	let mut file = match tokio::fs::File::open(path).await {
+		Ok(f) => f,
+		Err(e) => {
+			if verbose >= 1 {
+				eprintln!("[ERROR] Failed to read file {}: {}", path.display(), e);
+			}
+			return Err(e);
+		}
+	};
+
+	let mut scanner = SafetyScanner::default();
+	let mut sha256 = sha2::Sha256::new();
+	let mut md5 = md5::Context::new();
+	let mut buffer = vec![0u8; SCAN_CHUNK_SIZE];
+	loop {
+		let bytes_read = file.read(&mut buffer).await?;
+		if bytes_read == 0 {
+			break;
+		}
+		let chunk = &buffer[..bytes_read];
+		scanner.update(chunk);
+		sha256.update(chunk);
+		md5.consume(chunk);
+	}
+
+	let safety = scanner.finish();
+
+	if verbose >= 2 {
+		eprintln!("[DEBUG] File safety analysis: {:?}", safety);
+	}
+
+	if !safety.safe_to_process {
+		for warning in &safety.warnings {
+			if verbose >= 1 {
+				eprintln!("[WARN] {}", warning);
+			}
+		}
+	}
+
+	if !safety.is_valid_utf8 && verbose >= 1 {
+		eprintln!("[WARN] File contains invalid UTF-8 sequences, using lossy conversion");
+	}
+
+	Ok(FileScan {
+		safety,
+		sha256: hex::encode(sha256.finalize()),
+		md5: hex::encode(md5.finalize().0),
+	})
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -274,6 +430,40 @@ mod tests {
 		// Still might be safe to process if not binary
 	}
 
+	#[test]
+	fn test_scanner_matches_whole_analysis_across_chunks() {
+		let data = "name,city\nJos\u{e9},Z\u{fc}rich\n".as_bytes();
+		let whole = analyze_file_safety(data);
+		for chunk_size in 1..data.len() {
+			let mut scanner = SafetyScanner::default();
+			for chunk in data.chunks(chunk_size) {
+				scanner.update(chunk);
+			}
+			let chunked = scanner.finish();
+			assert!(chunked.is_valid_utf8, "chunk size {}", chunk_size);
+			assert_eq!(chunked.warnings, whole.warnings);
+			assert_eq!(chunked.binary_confidence, whole.binary_confidence);
+		}
+
+		let mut truncated = SafetyScanner::default();
+		truncated.update(&"\u{e9}".as_bytes()[..1]);
+		assert!(!truncated.finish().is_valid_utf8);
+	}
+
+	#[tokio::test]
+	async fn test_scan_file_hashes_contents() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("dump.csv");
+		let data = b"email,password\nalice@example.com,hunter2\n";
+		std::fs::write(&path, data).unwrap();
+
+		let scan = scan_file(&path, 0).await.unwrap();
+		assert_eq!(scan.safety.file_size, data.len());
+		assert!(!scan.safety.is_binary);
+		assert_eq!(scan.sha256, crate::core::hash_utils::sha256_hex_bytes(data));
+		assert_eq!(scan.md5, crate::core::hash_utils::md5_hex_bytes(data));
+	}
+
 	#[test]
 	fn test_safe_string_conversion_valid() {
 		let data = b"Hello World";
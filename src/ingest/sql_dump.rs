@@ -0,0 +1,755 @@
+//! SQL dump parsing for `mysqldump` and `pg_dump` output
+//!
+//! Leaked databases usually arrive as SQL dumps rather than CSV exports.
+//! [`SqlDumpParser`] reads a dump line by line and turns its data statements
+//! into rows, one logical dataset per table:
+//!
+//! - `CREATE TABLE` gives a table's column names
+//! - `INSERT INTO ... VALUES (...), (...)` (and `REPLACE INTO`) gives a row per tuple
+//! - `COPY ... FROM stdin` gives a row per line of the data block that follows it
+//!
+//! String literals use MySQL backslash escapes unless the dump turns on
+//! `standard_conforming_strings`, as `pg_dump` does. Other statements and
+//! comments are skipped. Only the statement being read is held in memory.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	mem,
+};
+
+/// Words that start a table constraint rather than a column definition in `CREATE TABLE`
+const CONSTRAINT_KEYWORDS: [&str; 11] = [
+	"CONSTRAINT",
+	"PRIMARY",
+	"UNIQUE",
+	"KEY",
+	"INDEX",
+	"FOREIGN",
+	"CHECK",
+	"FULLTEXT",
+	"SPATIAL",
+	"EXCLUDE",
+	"LIKE",
+];
+
+/// Line that ends a `COPY ... FROM stdin` data block
+const COPY_END: &str = "\\.";
+
+/// Table whose rows follow in the dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlTable {
+	/// Table name, including its schema when the dump qualifies it (`public.users`)
+	pub name: String,
+	/// Column names from the statement's column list or the table's `CREATE TABLE`
+	///
+	/// Tables without a known schema get `column_1`, `column_2`, ...
+	pub columns: Vec<String>,
+}
+
+/// Output of [`SqlDumpParser`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlEvent {
+	/// The rows that follow belong to this table
+	Table(SqlTable),
+	/// Row of the current table, with `NULL` as an empty string
+	Row(Vec<String>),
+	/// A data statement that could not be parsed
+	Skipped(String),
+}
+
+/// Where the statement scanner is at the end of a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scan {
+	Code,
+	BlockComment,
+	/// Inside a string literal or quoted identifier
+	Quoted {
+		quote: char,
+		backslash_escapes: bool,
+	},
+}
+
+/// `COPY ... FROM stdin` block being read
+struct CopyBlock {
+	name: String,
+	columns: Option<Vec<String>>,
+	/// Resolved on the first data line, once the row width is known
+	table: Option<SqlTable>,
+}
+
+/// Incremental parser for SQL dumps
+///
+/// Feed the dump with [`push_line`](Self::push_line), call
+/// [`finish`](Self::finish) at the end, and take the parsed
+/// [`SqlEvent`]s with [`next_event`](Self::next_event) in between.
+pub struct SqlDumpParser {
+	/// Text of the statement being read, without comments
+	statement: String,
+	/// Line the statement being read starts on
+	statement_line: usize,
+	line_number: usize,
+	scan: Scan,
+	/// Backslashes are literal in plain strings (`standard_conforming_strings = on`)
+	standard_strings: bool,
+	/// Column names from `CREATE TABLE`, by table name
+	schemas: HashMap<String, Vec<String>>,
+	copy: Option<CopyBlock>,
+	/// Table of the last row emitted
+	current: Option<SqlTable>,
+	events: VecDeque<SqlEvent>,
+}
+
+impl Default for SqlDumpParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl SqlDumpParser {
+	/// Create a parser for a new dump
+	pub fn new() -> Self {
+		SqlDumpParser {
+			statement: String::new(),
+			statement_line: 0,
+			line_number: 0,
+			scan: Scan::Code,
+			standard_strings: false,
+			schemas: HashMap::new(),
+			copy: None,
+			current: None,
+			events: VecDeque::new(),
+		}
+	}
+
+	/// Parse every table's rows out of a complete dump
+	pub fn parse(input: &str) -> Vec<SqlEvent> {
+		let mut parser = Self::new();
+		for line in input.lines() {
+			parser.push_line(line);
+		}
+		parser.finish();
+		parser.events.into()
+	}
+
+	/// Take the next parsed event
+	pub fn next_event(&mut self) -> Option<SqlEvent> {
+		self.events.pop_front()
+	}
+
+	/// Feed the next line of the dump, with or without its line ending
+	pub fn push_line(&mut self, line: &str) {
+		self.line_number += 1;
+		let line = line.strip_suffix('\n').unwrap_or(line);
+		let line = line.strip_suffix('\r').unwrap_or(line);
+
+		if let Some(block) = self.copy.take() {
+			if line != COPY_END {
+				self.copy_line(block, line);
+			}
+			return;
+		}
+
+		let mut chars = line.chars().peekable();
+		while let Some(c) = chars.next() {
+			match self.scan {
+				Scan::BlockComment => {
+					if c == '*' && chars.next_if_eq(&'/').is_some() {
+						self.scan = Scan::Code;
+					}
+				}
+				Scan::Quoted {
+					quote,
+					backslash_escapes,
+				} => {
+					self.statement.push(c);
+					if backslash_escapes && c == '\\' {
+						if let Some(escaped) = chars.next() {
+							self.statement.push(escaped);
+						}
+					} else if c == quote {
+						match chars.next_if_eq(&quote) {
+							Some(doubled) => self.statement.push(doubled),
+							None => self.scan = Scan::Code,
+						}
+					}
+				}
+				Scan::Code => match c {
+					'-' if chars.peek() == Some(&'-') => break,
+					// MySQL comments only where a statement could start
+					'#' if self.statement.is_empty() => break,
+					'/' if chars.next_if_eq(&'*').is_some() => self.scan = Scan::BlockComment,
+					';' => {
+						self.end_statement();
+						// The data of a COPY block starts on the next line
+						if self.copy.is_some() {
+							return;
+						}
+					}
+					'\'' | '"' | '`' => {
+						let backslash_escapes =
+							c == '\'' && (!self.standard_strings || self.ends_with_escape_prefix());
+						self.push_code(c);
+						self.scan = Scan::Quoted {
+							quote: c,
+							backslash_escapes,
+						};
+					}
+					_ => self.push_code(c),
+				},
+			}
+		}
+
+		match self.scan {
+			Scan::Quoted { .. } => self.statement.push('\n'),
+			_ if !self.statement.is_empty() => self.statement.push(' '),
+			_ => {}
+		}
+	}
+
+	/// Parse a final statement that is missing its semicolon
+	pub fn finish(&mut self) {
+		if matches!(self.scan, Scan::Quoted { .. }) {
+			self.statement.clear();
+			self.scan = Scan::Code;
+			self.skip(format!(
+				"Unterminated string in statement at line {}",
+				self.statement_line
+			));
+			return;
+		}
+		if !self.statement.trim().is_empty() {
+			self.end_statement();
+		}
+		self.copy = None;
+	}
+
+	fn push_code(&mut self, c: char) {
+		if self.statement.is_empty() {
+			if c.is_whitespace() {
+				return;
+			}
+			self.statement_line = self.line_number;
+		}
+		self.statement.push(c);
+	}
+
+	/// Whether the statement ends with PostgreSQL's `E` prefix for escaped strings
+	fn ends_with_escape_prefix(&self) -> bool {
+		let mut chars = self.statement.chars().rev();
+		matches!(chars.next(), Some('E' | 'e')) && !chars.next().is_some_and(is_word_char)
+	}
+
+	fn skip(&mut self, reason: String) {
+		self.events.push_back(SqlEvent::Skipped(reason));
+	}
+
+	fn end_statement(&mut self) {
+		let statement = mem::take(&mut self.statement);
+		let tokens = tokenize(&statement, self.standard_strings);
+		let Some(Token::Word(keyword)) = tokens.first() else {
+			return;
+		};
+
+		match keyword.to_ascii_uppercase().as_str() {
+			"CREATE" => self.create_table(&tokens[1..]),
+			"INSERT" | "REPLACE" => self.insert(&tokens[1..]),
+			"COPY" => self.copy_from_stdin(&tokens[1..]),
+			"SET" => self.set_option(&tokens[1..]),
+			_ => {}
+		}
+	}
+
+	fn create_table(&mut self, tokens: &[Token]) {
+		// CREATE [OR REPLACE] [TEMPORARY | UNLOGGED ...] TABLE [IF NOT EXISTS] name (...)
+		let Some(at) = tokens.iter().take(4).position(|t| t.is_keyword("TABLE")) else {
+			return;
+		};
+		let mut rest = &tokens[at + 1..];
+		if rest.len() >= 3 && rest[0].is_keyword("IF") && rest[2].is_keyword("EXISTS") {
+			rest = &rest[3..];
+		}
+		let Some((name, rest)) = table_name(rest) else {
+			return;
+		};
+		// CREATE TABLE ... AS SELECT has no column definitions to read
+		let Some((definitions, _)) = parenthesized(rest) else {
+			return;
+		};
+
+		let columns = definitions
+			.iter()
+			.filter_map(|definition| match definition.first()? {
+				Token::Ident(column) => Some(column.clone()),
+				Token::Word(column)
+					if !CONSTRAINT_KEYWORDS
+						.iter()
+						.any(|keyword| column.eq_ignore_ascii_case(keyword)) =>
+				{
+					Some(column.clone())
+				}
+				_ => None,
+			})
+			.collect();
+		self.schemas.insert(name, columns);
+	}
+
+	fn insert(&mut self, tokens: &[Token]) {
+		let line = self.statement_line;
+		// INSERT [LOW_PRIORITY | IGNORE ...] INTO name [(columns)] VALUES (...), (...)
+		let target = tokens
+			.iter()
+			.position(|t| t.is_keyword("INTO"))
+			.and_then(|at| table_name(&tokens[at + 1..]));
+		let Some((name, mut rest)) = target else {
+			self.skip(format!("Unparseable INSERT statement at line {}", line));
+			return;
+		};
+
+		let mut columns = None;
+		if let Some((list, after)) = parenthesized(rest) {
+			columns = Some(list.iter().filter_map(|t| column_name(t)).collect());
+			rest = after;
+		}
+		// pg_dump --inserts writes OVERRIDING SYSTEM VALUE for identity columns
+		if rest.len() >= 3 && rest[0].is_keyword("OVERRIDING") && rest[2].is_keyword("VALUE") {
+			rest = &rest[3..];
+		}
+		match rest.first() {
+			Some(t) if t.is_keyword("VALUES") || t.is_keyword("VALUE") => rest = &rest[1..],
+			_ => {
+				self.skip(format!(
+					"INSERT into {} at line {} has no VALUES list",
+					name, line
+				));
+				return;
+			}
+		}
+
+		let mut table = None;
+		loop {
+			let Some((values, after)) = parenthesized(rest) else {
+				self.skip(format!(
+					"Unparseable VALUES tuple in INSERT into {} at line {}",
+					name, line
+				));
+				return;
+			};
+			let row: Vec<String> = values.iter().map(|value| render_value(value)).collect();
+			let table =
+				table.get_or_insert_with(|| self.resolve_table(&name, columns.take(), row.len()));
+			emit_row(&mut self.current, &mut self.events, table, row);
+
+			// Anything after the last tuple (ON DUPLICATE KEY UPDATE, RETURNING) is ignored
+			match after.first() {
+				Some(Token::Symbol(',')) => rest = &after[1..],
+				_ => return,
+			}
+		}
+	}
+
+	fn copy_from_stdin(&mut self, tokens: &[Token]) {
+		let Some((name, mut rest)) = table_name(tokens) else {
+			return;
+		};
+		let mut columns = None;
+		if let Some((list, after)) = parenthesized(rest) {
+			columns = Some(list.iter().filter_map(|t| column_name(t)).collect());
+			rest = after;
+		}
+		// COPY ... TO and COPY from a server-side file carry no data in the dump
+		if rest.len() >= 2 && rest[0].is_keyword("FROM") && rest[1].is_keyword("STDIN") {
+			self.copy = Some(CopyBlock {
+				name,
+				columns,
+				table: None,
+			});
+		}
+	}
+
+	fn copy_line(&mut self, mut block: CopyBlock, line: &str) {
+		let row: Vec<String> = line.split('\t').map(unescape_copy_field).collect();
+		let table = match block.table.take() {
+			Some(table) => table,
+			None => self.resolve_table(&block.name, block.columns.take(), row.len()),
+		};
+		emit_row(&mut self.current, &mut self.events, &table, row);
+		block.table = Some(table);
+		self.copy = Some(block);
+	}
+
+	fn set_option(&mut self, tokens: &[Token]) {
+		// SET [SESSION | LOCAL] standard_conforming_strings = on
+		let Some(at) = tokens
+			.iter()
+			.position(|t| t.is_keyword("standard_conforming_strings"))
+		else {
+			return;
+		};
+		match tokens.last() {
+			Some(Token::Word(value) | Token::Str(value)) if at + 1 < tokens.len() => {
+				self.standard_strings = value.eq_ignore_ascii_case("on");
+			}
+			_ => {}
+		}
+	}
+
+	/// Table for a statement's rows, falling back to `CREATE TABLE` and then placeholder names
+	fn resolve_table(&self, name: &str, columns: Option<Vec<String>>, width: usize) -> SqlTable {
+		let columns = columns
+			.filter(|columns| !columns.is_empty())
+			.or_else(|| {
+				self.schemas
+					.get(name)
+					.filter(|columns| !columns.is_empty())
+					.cloned()
+			})
+			.unwrap_or_else(|| (1..=width).map(|i| format!("column_{}", i)).collect());
+		SqlTable {
+			name: name.to_string(),
+			columns,
+		}
+	}
+}
+
+/// Queue a row, preceded by its table when it differs from the previous row's
+fn emit_row(
+	current: &mut Option<SqlTable>,
+	events: &mut VecDeque<SqlEvent>,
+	table: &SqlTable,
+	row: Vec<String>,
+) {
+	if current.as_ref() != Some(table) {
+		*current = Some(table.clone());
+		events.push_back(SqlEvent::Table(table.clone()));
+	}
+	events.push_back(SqlEvent::Row(row));
+}
+
+/// Lexical token of a complete statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+	/// Keyword, unquoted identifier or number
+	Word(String),
+	/// Quoted identifier
+	Ident(String),
+	/// String literal, unescaped
+	Str(String),
+	/// Punctuation and operators
+	Symbol(char),
+}
+
+impl Token {
+	fn is_keyword(&self, keyword: &str) -> bool {
+		matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+	}
+}
+
+fn is_word_char(c: char) -> bool {
+	c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Split a statement into tokens
+///
+/// The scanner only ends statements outside quotes, so every literal here is terminated.
+fn tokenize(statement: &str, standard_strings: bool) -> Vec<Token> {
+	let mut tokens = Vec::new();
+	let mut chars = statement.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		match c {
+			c if c.is_whitespace() => {}
+			'\'' => {
+				// E'...' always uses backslash escapes; _utf8mb4'...' and N'...' are plain strings
+				let prefix = match tokens.last() {
+					Some(Token::Word(word))
+						if word.eq_ignore_ascii_case("E")
+							|| word.eq_ignore_ascii_case("N")
+							|| word.starts_with('_') =>
+					{
+						Some(word.eq_ignore_ascii_case("E"))
+					}
+					_ => None,
+				};
+				if prefix.is_some() {
+					tokens.pop();
+				}
+				let backslash_escapes = !standard_strings || prefix == Some(true);
+				tokens.push(Token::Str(read_quoted(&mut chars, '\'', backslash_escapes)));
+			}
+			'"' | '`' => tokens.push(Token::Ident(read_quoted(&mut chars, c, false))),
+			c if is_word_char(c) => {
+				let mut word = String::from(c);
+				while let Some(next) = chars.next_if(|&next| is_word_char(next)) {
+					word.push(next);
+				}
+				tokens.push(Token::Word(word));
+			}
+			c => tokens.push(Token::Symbol(c)),
+		}
+	}
+
+	tokens
+}
+
+/// Read a quoted literal after its opening quote, undoing doubled quotes and escapes
+fn read_quoted(
+	chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+	quote: char,
+	backslash_escapes: bool,
+) -> String {
+	let mut value = String::new();
+	while let Some(c) = chars.next() {
+		if c == quote {
+			match chars.next_if_eq(&quote) {
+				Some(doubled) => value.push(doubled),
+				None => break,
+			}
+		} else if c == '\\' && backslash_escapes {
+			match chars.next() {
+				Some(escaped) => value.push(unescape_char(escaped)),
+				None => value.push(c),
+			}
+		} else {
+			value.push(c);
+		}
+	}
+	value
+}
+
+/// Character for a backslash escape in a MySQL string or PostgreSQL `COPY` field
+fn unescape_char(escaped: char) -> char {
+	match escaped {
+		'0' => '\0',
+		'b' => '\u{8}',
+		'f' => '\u{c}',
+		'n' => '\n',
+		'r' => '\r',
+		't' => '\t',
+		'v' => '\u{b}',
+		'Z' => '\u{1a}',
+		other => other,
+	}
+}
+
+/// Field of a `COPY` text-format line, with `\N` (NULL) as an empty string
+fn unescape_copy_field(field: &str) -> String {
+	if field == "\\N" {
+		return String::new();
+	}
+	let mut value = String::with_capacity(field.len());
+	let mut chars = field.chars();
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' => match chars.next() {
+				Some(escaped) => value.push(unescape_char(escaped)),
+				None => value.push(c),
+			},
+			c => value.push(c),
+		}
+	}
+	value
+}
+
+/// Read a possibly schema-qualified table name
+fn table_name(tokens: &[Token]) -> Option<(String, &[Token])> {
+	let mut name = match tokens.first()? {
+		Token::Word(part) | Token::Ident(part) => part.clone(),
+		_ => return None,
+	};
+	let mut rest = &tokens[1..];
+	while let [
+		Token::Symbol('.'),
+		Token::Word(part) | Token::Ident(part),
+		after @ ..,
+	] = rest
+	{
+		name.push('.');
+		name.push_str(part);
+		rest = after;
+	}
+	Some((name, rest))
+}
+
+/// Column name from a column list entry
+fn column_name(tokens: &[Token]) -> Option<String> {
+	match tokens {
+		[Token::Word(name) | Token::Ident(name)] => Some(name.clone()),
+		_ => None,
+	}
+}
+
+/// Split a parenthesized list at its top-level commas
+///
+/// Returns the items and the tokens after the closing parenthesis, or None when
+/// the tokens do not start with a balanced list.
+fn parenthesized(tokens: &[Token]) -> Option<(Vec<&[Token]>, &[Token])> {
+	if tokens.first() != Some(&Token::Symbol('(')) {
+		return None;
+	}
+
+	let mut items = Vec::new();
+	let mut depth = 0usize;
+	let mut start = 1;
+	for (i, token) in tokens.iter().enumerate().skip(1) {
+		match token {
+			Token::Symbol('(') => depth += 1,
+			Token::Symbol(')') if depth == 0 => {
+				// An empty list has no items
+				if !(items.is_empty() && start == i) {
+					items.push(&tokens[start..i]);
+				}
+				return Some((items, &tokens[i + 1..]));
+			}
+			Token::Symbol(')') => depth -= 1,
+			Token::Symbol(',') if depth == 0 => {
+				items.push(&tokens[start..i]);
+				start = i + 1;
+			}
+			_ => {}
+		}
+	}
+	None
+}
+
+/// Text of a value in a `VALUES` tuple
+fn render_value(tokens: &[Token]) -> String {
+	match tokens {
+		[Token::Word(word)] if word.eq_ignore_ascii_case("NULL") => String::new(),
+		// A string, possibly with a PostgreSQL cast ('x'::text)
+		[Token::Str(value), ..] => value.clone(),
+		// Numbers, hex literals and expressions are kept as written
+		_ => {
+			let mut text = String::new();
+			let mut previous_word = false;
+			for token in tokens {
+				let is_word = matches!(token, Token::Word(_) | Token::Ident(_));
+				if is_word && previous_word {
+					text.push(' ');
+				}
+				match token {
+					Token::Word(word) | Token::Ident(word) => text.push_str(word),
+					Token::Str(value) => {
+						text.push('\'');
+						text.push_str(value);
+						text.push('\'');
+					}
+					Token::Symbol(c) => text.push(*c),
+				}
+				previous_word = is_word;
+			}
+			text
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn table(name: &str, columns: &[&str]) -> SqlEvent {
+		SqlEvent::Table(SqlTable {
+			name: name.to_string(),
+			columns: columns.iter().map(|c| c.to_string()).collect(),
+		})
+	}
+
+	fn row(values: &[&str]) -> SqlEvent {
+		SqlEvent::Row(values.iter().map(|v| v.to_string()).collect())
+	}
+
+	#[test]
+	fn test_mysqldump_multi_row_insert() {
+		let dump = r#"-- MySQL dump 10.13
+/*!40101 SET NAMES utf8mb4 */;
+DROP TABLE IF EXISTS `users`;
+CREATE TABLE `users` (
+  `id` int NOT NULL AUTO_INCREMENT,
+  `email` varchar(255) DEFAULT NULL,
+  `password` varchar(255) DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `email` (`email`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+LOCK TABLES `users` WRITE;
+INSERT INTO `users` VALUES (1,'alice@example.com','it\'s;a\\secret'),(2,'bob@example.com',NULL),
+(3,'carol@example.com','two\nlines');
+UNLOCK TABLES;
+"#;
+
+		assert_eq!(
+			SqlDumpParser::parse(dump),
+			vec![
+				table("users", &["id", "email", "password"]),
+				row(&["1", "alice@example.com", "it's;a\\secret"]),
+				row(&["2", "bob@example.com", ""]),
+				row(&["3", "carol@example.com", "two\nlines"]),
+			]
+		);
+	}
+
+	#[test]
+	fn test_pg_dump_copy_block() {
+		let dump = "SET standard_conforming_strings = on;\n\
+			CREATE TABLE public.accounts (\n    id integer NOT NULL,\n    login text,\n    \
+			note text\n);\n\
+			COPY public.accounts (id, login, note) FROM stdin;\n\
+			1\talice@example.com\tsemi;colon\n\
+			2\tbob@example.com\t\\N\n\
+			3\tcarol@example.com\ttab\\there\n\
+			\\.\n\
+			INSERT INTO public.accounts VALUES (4, 'dave@example.com', 'C:\\temp'), (5, \
+			'erin@example.com', E'esc\\'aped');\n";
+
+		assert_eq!(
+			SqlDumpParser::parse(dump),
+			vec![
+				table("public.accounts", &["id", "login", "note"]),
+				row(&["1", "alice@example.com", "semi;colon"]),
+				row(&["2", "bob@example.com", ""]),
+				row(&["3", "carol@example.com", "tab\there"]),
+				row(&["4", "dave@example.com", "C:\\temp"]),
+				row(&["5", "erin@example.com", "esc'aped"]),
+			]
+		);
+	}
+
+	#[test]
+	fn test_tables_are_separate_datasets() {
+		let dump = "INSERT INTO a (x, y) VALUES ('1', 'one');\n\
+			INSERT INTO b VALUES (-2.5, 'it''s', 0x1F, now());\n\
+			INSERT INTO a (x, y) VALUES ('3', 'three');\n";
+
+		assert_eq!(
+			SqlDumpParser::parse(dump),
+			vec![
+				table("a", &["x", "y"]),
+				row(&["1", "one"]),
+				table("b", &["column_1", "column_2", "column_3", "column_4"]),
+				row(&["-2.5", "it's", "0x1F", "now()"]),
+				table("a", &["x", "y"]),
+				row(&["3", "three"]),
+			]
+		);
+	}
+
+	#[test]
+	fn test_malformed_statements_are_skipped() {
+		let dump = "INSERT INTO t VALUES (1, 'a'), (2, 'b';\n\
+			INSERT INTO t SELECT * FROM u;\n\
+			INSERT INTO t VALUES (3, 'c');\n\
+			INSERT INTO t VALUES ('unterminated";
+
+		assert_eq!(
+			SqlDumpParser::parse(dump),
+			vec![
+				table("t", &["column_1", "column_2"]),
+				row(&["1", "a"]),
+				SqlEvent::Skipped(
+					"Unparseable VALUES tuple in INSERT into t at line 1".to_string()
+				),
+				SqlEvent::Skipped("INSERT into t at line 2 has no VALUES list".to_string()),
+				row(&["3", "c"]),
+				SqlEvent::Skipped("Unterminated string in statement at line 4".to_string()),
+			]
+		);
+	}
+}
@@ -0,0 +1,243 @@
+//! SQLite database files found in dumps
+//!
+//! Breach packages often contain `.db` or `.sqlite` files, which the text safety
+//! checks reject as binary. They are recognized by their header instead of their
+//! extension, opened read-only and immutable (no journal, WAL or lock files are
+//! created next to the working copy), and read one table at a time.
+
+use std::{fs::File, io, io::Read, path::Path};
+
+use rusqlite::{Connection, OpenFlags, types::ValueRef};
+use tokio::sync::mpsc;
+
+use crate::ingest::sql_dump::SqlTable;
+
+/// First 16 bytes of every SQLite 3 database file
+pub const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+
+/// Rows sent to the reader per message
+pub const ROWS_PER_MESSAGE: usize = 256;
+
+/// Output of [`read_tables`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqliteEvent {
+	/// The rows that follow belong to this table
+	Table(SqlTable),
+	/// Rows of the current table, with `NULL` as an empty string
+	Rows(Vec<Vec<String>>),
+	/// A table that could not be read, in full or in part
+	Skipped(String),
+}
+
+/// Whether a file starts with the SQLite database header
+pub fn is_sqlite_database(path: &Path) -> io::Result<bool> {
+	let mut file = File::open(path)?;
+	let mut magic = [0u8; SQLITE_MAGIC.len()];
+	match file.read_exact(&mut magic) {
+		Ok(()) => Ok(&magic == SQLITE_MAGIC),
+		Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+		Err(e) => Err(e),
+	}
+}
+
+/// Open a database file read-only, without creating any files beside it
+pub fn open_read_only(path: &Path) -> rusqlite::Result<Connection> {
+	let conn = Connection::open_with_flags(
+		format!("file:{}?immutable=1", uri_path(path)),
+		OpenFlags::SQLITE_OPEN_READ_ONLY
+			| OpenFlags::SQLITE_OPEN_URI
+			| OpenFlags::SQLITE_OPEN_NO_MUTEX,
+	)?;
+	// Views and triggers in an untrusted schema may not call functions with side effects
+	conn.pragma_update(None, "trusted_schema", false)?;
+	Ok(conn)
+}
+
+/// Names of the database's tables in schema order, without SQLite's internal tables
+pub fn list_tables(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+	let mut stmt = conn.prepare(
+		"SELECT name FROM sqlite_schema WHERE type = 'table' AND name NOT LIKE 'sqlite\\_%' \
+		 ESCAPE '\\' ORDER BY rowid",
+	)?;
+	stmt.query_map([], |row| row.get(0))?.collect()
+}
+
+/// Send every table's column names and rows to `sender`
+///
+/// Runs on a blocking thread. A table that fails to read is reported as
+/// [`SqliteEvent::Skipped`] and the next table is read. Stops early when the
+/// receiver is dropped.
+pub fn read_tables(conn: &Connection, sender: &mpsc::Sender<io::Result<SqliteEvent>>) {
+	let tables = match list_tables(conn) {
+		Ok(tables) => tables,
+		Err(e) => {
+			let _ = sender.blocking_send(Err(io::Error::other(e)));
+			return;
+		}
+	};
+
+	for name in tables {
+		match read_table(conn, &name, sender) {
+			Ok(true) => {}
+			Ok(false) => return,
+			Err(e) => {
+				let skipped = SqliteEvent::Skipped(format!("Could not read table {}: {}", name, e));
+				if sender.blocking_send(Ok(skipped)).is_err() {
+					return;
+				}
+			}
+		}
+	}
+}
+
+/// Send one table's column names and rows, returning false when the receiver is gone
+fn read_table(
+	conn: &Connection,
+	name: &str,
+	sender: &mpsc::Sender<io::Result<SqliteEvent>>,
+) -> rusqlite::Result<bool> {
+	let mut stmt = conn.prepare(&format!("SELECT * FROM {}", quote_identifier(name)))?;
+	let width = stmt.column_count();
+	let table = SqlTable {
+		name: name.to_string(),
+		columns: stmt
+			.column_names()
+			.into_iter()
+			.map(str::to_string)
+			.collect(),
+	};
+
+	let mut rows = stmt.query([])?;
+	let mut table = Some(table);
+	let mut batch = Vec::with_capacity(ROWS_PER_MESSAGE);
+	while let Some(row) = rows.next()? {
+		batch.push(
+			(0..width)
+				.map(|i| row.get_ref(i).map(value_text))
+				.collect::<rusqlite::Result<Vec<_>>>()?,
+		);
+		if batch.len() == ROWS_PER_MESSAGE && !send_rows(sender, &mut table, &mut batch) {
+			return Ok(false);
+		}
+	}
+	Ok(batch.is_empty() || send_rows(sender, &mut table, &mut batch))
+}
+
+/// Send a batch of rows, announcing the table with its first batch
+///
+/// Tables without rows are therefore never announced.
+fn send_rows(
+	sender: &mpsc::Sender<io::Result<SqliteEvent>>,
+	table: &mut Option<SqlTable>,
+	batch: &mut Vec<Vec<String>>,
+) -> bool {
+	if let Some(table) = table.take()
+		&& sender.blocking_send(Ok(SqliteEvent::Table(table))).is_err()
+	{
+		return false;
+	}
+	let rows = SqliteEvent::Rows(std::mem::take(batch));
+	sender.blocking_send(Ok(rows)).is_ok()
+}
+
+/// Text of a column value; blobs are hex encoded
+fn value_text(value: ValueRef<'_>) -> String {
+	match value {
+		ValueRef::Null => String::new(),
+		ValueRef::Integer(i) => i.to_string(),
+		ValueRef::Real(f) => f.to_string(),
+		ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned(),
+		ValueRef::Blob(blob) => hex::encode(blob),
+	}
+}
+
+/// Quote a table name for use in SQL
+fn quote_identifier(name: &str) -> String {
+	format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Percent-encode the characters that end the path part of an SQLite URI
+fn uri_path(path: &Path) -> String {
+	let mut encoded = String::new();
+	for c in path.to_string_lossy().chars() {
+		match c {
+			'%' => encoded.push_str("%25"),
+			'?' => encoded.push_str("%3f"),
+			'#' => encoded.push_str("%23"),
+			c => encoded.push(c),
+		}
+	}
+	encoded
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_database(path: &Path) {
+		let conn = Connection::open(path).unwrap();
+		conn.execute_batch(
+			"CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT, password BLOB, note TEXT);
+			 INSERT INTO users (email, password, note) VALUES
+			   ('alice@example.com', X'DEADBEEF', NULL),
+			   ('bob@example.com', X'00', 'x');
+			 CREATE TABLE \"odd \"\"name\" (score REAL);
+			 INSERT INTO \"odd \"\"name\" VALUES (1.5);
+			 CREATE TABLE empty (a TEXT);
+			 CREATE INDEX users_email ON users (email);",
+		)
+		.unwrap();
+	}
+
+	#[test]
+	fn test_is_sqlite_database() {
+		let dir = tempfile::tempdir().unwrap();
+		let db = dir.path().join("leak.bin");
+		create_database(&db);
+		let text = dir.path().join("notes.sqlite");
+		std::fs::write(&text, "SQLite format 2").unwrap();
+
+		assert!(is_sqlite_database(&db).unwrap());
+		assert!(!is_sqlite_database(&text).unwrap());
+	}
+
+	#[test]
+	fn test_read_tables_streams_rows_read_only() {
+		let dir = tempfile::tempdir().unwrap();
+		let db = dir.path().join("leak #1?.db");
+		create_database(&db);
+
+		let conn = open_read_only(&db).unwrap();
+		assert!(conn.execute("DELETE FROM users", []).is_err());
+
+		let (sender, mut receiver) = mpsc::channel(16);
+		read_tables(&conn, &sender);
+		drop(sender);
+		let mut events = Vec::new();
+		while let Some(event) = receiver.blocking_recv() {
+			events.push(event.unwrap());
+		}
+
+		let text = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+		assert_eq!(
+			events,
+			vec![
+				SqliteEvent::Table(SqlTable {
+					name: "users".to_string(),
+					columns: text(&["id", "email", "password", "note"]),
+				}),
+				SqliteEvent::Rows(vec![
+					text(&["1", "alice@example.com", "deadbeef", ""]),
+					text(&["2", "bob@example.com", "00", "x"]),
+				]),
+				SqliteEvent::Table(SqlTable {
+					name: "odd \"name".to_string(),
+					columns: text(&["score"]),
+				}),
+				SqliteEvent::Rows(vec![text(&["1.5"])]),
+			]
+		);
+		// Nothing was written next to the database
+		assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+	}
+}
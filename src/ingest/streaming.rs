@@ -1,15 +1,43 @@
 //! Streaming file handling for arbitrarily large files without loading into memory.
 //!
-//! This module implements streaming CSV/JSON parsing with async I/O to handle
+//! This module implements streaming CSV/TSV/JSON parsing with async I/O to handle
 //! files up to OS limits (GB/TB scale) without loading the entire file into memory.
+//! Each parser produces the same rows as its in-memory counterpart:
+//! - CSV records are split like [`CsvAdapter`](crate::ingest::adapters::CsvAdapter)
+//! - TSV lines are split on tabs
+//! - JSON arrays and JSON Lines are flattened like
+//!   [`json_to_rows`](crate::ingest::universal_parser::json_to_rows)
+//! - Combo lists are parsed like
+//!   [`ComboListAdapter`](crate::ingest::combo::ComboListAdapter)
+//! - SQL dumps are parsed like [`SqlDumpParser`], one table at a time
+//! - SQLite databases are read one table at a time on a blocking thread
+//!
+//! Invalid UTF-8 is replaced line by line, as a lossy conversion of the whole file would.
 //! Only one instance writes a file at a time (enforced at application level).
 
+use std::collections::VecDeque;
+use std::io;
 use std::path::Path;
+
+use serde_json::Value;
 use tokio::fs::File;
-use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::sync::mpsc;
+
+use crate::ingest::adapters::CsvRecordBuilder;
+use crate::ingest::combo::{self, ComboLineParser, SAMPLE_LINES};
+use crate::ingest::sql_dump::{SqlDumpParser, SqlEvent, SqlTable};
+use crate::ingest::sqlite::{self, SqliteEvent};
+use crate::ingest::universal_parser::{ArrayRowShape, json_to_rows};
+
+/// Read buffer size for the streaming parsers
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Most warnings kept per stream; later failures are only counted in `rows_failed`
+const MAX_WARNINGS: usize = 100;
 
 /// Result of streaming parse operation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct StreamStats {
 	/// Total rows successfully processed
 	pub rows_processed: u64,
@@ -17,73 +45,97 @@ pub struct StreamStats {
 	pub rows_failed: u64,
 	/// Total bytes read from file
 	pub bytes_read: u64,
-	/// Any warnings/errors encountered during parsing
+	/// Any warnings/errors encountered during parsing (the first [`MAX_WARNINGS`])
 	pub warnings: Vec<String>,
 }
 
+impl StreamStats {
+	/// Count a row that failed to parse
+	fn fail(&mut self, warning: String) {
+		self.rows_failed += 1;
+		if self.warnings.len() < MAX_WARNINGS {
+			self.warnings.push(warning);
+		}
+	}
+}
+
+/// Line reader shared by the line-based parsers
+struct LineReader {
+	reader: BufReader<File>,
+	buffer: Vec<u8>,
+}
+
+impl LineReader {
+	async fn open(path: &Path) -> io::Result<Self> {
+		let file = File::open(path).await?;
+		Ok(LineReader {
+			reader: BufReader::with_capacity(READ_BUFFER_SIZE, file),
+			buffer: Vec::with_capacity(4096),
+		})
+	}
+
+	/// Read the next line including its trailing `\n`, or None at EOF
+	async fn next_line(&mut self, stats: &mut StreamStats) -> io::Result<Option<String>> {
+		self.buffer.clear();
+		let bytes_read = self.reader.read_until(b'\n', &mut self.buffer).await?;
+		if bytes_read == 0 {
+			return Ok(None);
+		}
+		stats.bytes_read += bytes_read as u64;
+
+		let line = match String::from_utf8(std::mem::take(&mut self.buffer)) {
+			Ok(line) => line,
+			Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+		};
+		Ok(Some(line))
+	}
+}
+
 /// Streaming CSV parser that processes one row at a time
 pub struct StreamingCsvParser {
-	reader: BufReader<File>,
+	lines: LineReader,
+	builder: CsvRecordBuilder,
 	stats: StreamStats,
-	line_buffer: String,
+	finished: bool,
 }
 
 impl StreamingCsvParser {
 	/// Create a new streaming CSV parser from a file path
-	pub async fn new(path: &Path) -> std::io::Result<Self> {
-		let file = File::open(path).await?;
-		let reader = BufReader::new(file);
-
+	pub async fn new(path: &Path) -> io::Result<Self> {
 		Ok(StreamingCsvParser {
-			reader,
-			stats: StreamStats {
-				rows_processed: 0,
-				rows_failed: 0,
-				bytes_read: 0,
-				warnings: Vec::new(),
-			},
-			line_buffer: String::with_capacity(4096),
+			lines: LineReader::open(path).await?,
+			builder: CsvRecordBuilder::new(),
+			stats: StreamStats::default(),
+			finished: false,
 		})
 	}
 
-	/// Read and parse the next CSV row from the file
+	/// Read and parse the next CSV record from the file
 	/// Returns None when EOF is reached
-	pub async fn next_row(&mut self) -> std::io::Result<Option<Vec<String>>> {
-		loop {
-			self.line_buffer.clear();
-
-			let bytes_read = self.reader.read_line(&mut self.line_buffer).await?;
-			if bytes_read == 0 {
-				// EOF reached
-				return Ok(None);
-			}
-
-			self.stats.bytes_read += bytes_read as u64;
-
-			// Parse CSV line (simple implementation)
-			let line = self.line_buffer.trim_end();
-			if line.is_empty() {
-				// Skip empty lines
-				continue;
-			}
+	/// Quoted fields may span lines
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		if self.finished {
+			return Ok(None);
+		}
 
-			match parse_csv_line(line) {
-				Ok(fields) => {
-					self.stats.rows_processed += 1;
-					return Ok(Some(fields));
+		let record = loop {
+			match self.lines.next_line(&mut self.stats).await? {
+				Some(line) => {
+					if let Some(record) = self.builder.push_line(&line) {
+						break Some(record);
+					}
 				}
-				Err(e) => {
-					self.stats.rows_failed += 1;
-					self.stats.warnings.push(format!(
-						"Failed to parse CSV line {}: {}",
-						self.stats.rows_processed + self.stats.rows_failed,
-						e
-					));
-					// Continue to next row on error
-					continue;
+				None => {
+					self.finished = true;
+					break self.builder.finish();
 				}
 			}
+		};
+
+		if record.is_some() {
+			self.stats.rows_processed += 1;
 		}
+		Ok(record)
 	}
 
 	/// Get current parsing statistics
@@ -97,87 +149,89 @@ impl StreamingCsvParser {
 	}
 }
 
-/// Streaming JSON Lines parser (one JSON object per line)
+/// Streaming TSV parser (one row per line, fields split on tabs)
+pub struct StreamingTsvParser {
+	lines: LineReader,
+	stats: StreamStats,
+}
+
+impl StreamingTsvParser {
+	/// Create a new streaming TSV parser from a file path
+	pub async fn new(path: &Path) -> io::Result<Self> {
+		Ok(StreamingTsvParser {
+			lines: LineReader::open(path).await?,
+			stats: StreamStats::default(),
+		})
+	}
+
+	/// Read and split the next line from the file
+	/// Returns None when EOF is reached
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		let Some(line) = self.lines.next_line(&mut self.stats).await? else {
+			return Ok(None);
+		};
+
+		// Same line endings as `str::lines`: `\n` or `\r\n`
+		let line = match line.strip_suffix('\n') {
+			Some(rest) => rest.strip_suffix('\r').unwrap_or(rest),
+			None => &line,
+		};
+		self.stats.rows_processed += 1;
+		Ok(Some(line.split('\t').map(str::to_string).collect()))
+	}
+
+	/// Get current parsing statistics
+	pub fn stats(&self) -> &StreamStats {
+		&self.stats
+	}
+
+	/// Consume parser and return final statistics
+	pub fn into_stats(self) -> StreamStats {
+		self.stats
+	}
+}
+
+/// Streaming JSON Lines parser (one JSON value per line)
 pub struct StreamingJsonLinesParser {
-	reader: BufReader<File>,
+	lines: LineReader,
 	stats: StreamStats,
-	line_buffer: String,
+	line_number: u64,
 }
 
 impl StreamingJsonLinesParser {
 	/// Create a new streaming JSON Lines parser from a file path
-	pub async fn new(path: &Path) -> std::io::Result<Self> {
-		let file = File::open(path).await?;
-		let reader = BufReader::new(file);
-
+	pub async fn new(path: &Path) -> io::Result<Self> {
 		Ok(StreamingJsonLinesParser {
-			reader,
-			stats: StreamStats {
-				rows_processed: 0,
-				rows_failed: 0,
-				bytes_read: 0,
-				warnings: Vec::new(),
-			},
-			line_buffer: String::with_capacity(4096),
+			lines: LineReader::open(path).await?,
+			stats: StreamStats::default(),
+			line_number: 0,
 		})
 	}
 
 	/// Read and parse the next JSON Line from the file
 	/// Returns None when EOF is reached
-	/// Each line should contain a complete JSON object
-	pub async fn next_row(&mut self) -> std::io::Result<Option<Vec<String>>> {
-		loop {
-			self.line_buffer.clear();
-
-			let bytes_read = self.reader.read_line(&mut self.line_buffer).await?;
-			if bytes_read == 0 {
-				// EOF reached
-				return Ok(None);
-			}
-
-			self.stats.bytes_read += bytes_read as u64;
-
-			let line = self.line_buffer.trim();
+	/// Blank lines are skipped; lines that are not valid JSON are counted and skipped
+	pub async fn next_value(&mut self) -> io::Result<Option<Value>> {
+		while let Some(line) = self.lines.next_line(&mut self.stats).await? {
+			self.line_number += 1;
+			let line = line.trim();
 			if line.is_empty() {
-				// Skip empty lines
 				continue;
 			}
 
-			match serde_json::from_str::<serde_json::Value>(line) {
+			match serde_json::from_str::<Value>(line) {
 				Ok(value) => {
-					let fields = match value {
-						serde_json::Value::Object(obj) => {
-							// Convert JSON object to flat string array
-							obj.iter().map(|(k, v)| format!("{}:{}", k, v)).collect()
-						}
-						serde_json::Value::Array(arr) => {
-							// Convert JSON array to string array
-							arr.iter().map(|v| v.to_string()).collect()
-						}
-						_ => {
-							self.stats.rows_failed += 1;
-							self.stats.warnings.push(format!(
-								"Line {} is not JSON object or array",
-								self.stats.rows_processed + self.stats.rows_failed
-							));
-							continue;
-						}
-					};
 					self.stats.rows_processed += 1;
-					return Ok(Some(fields));
+					return Ok(Some(value));
 				}
 				Err(e) => {
-					self.stats.rows_failed += 1;
-					self.stats.warnings.push(format!(
-						"Failed to parse JSON on line {}: {}",
-						self.stats.rows_processed + self.stats.rows_failed,
-						e
-					));
-					// Continue to next row on error
-					continue;
+					let warning =
+						format!("Failed to parse JSON on line {}: {}", self.line_number, e);
+					self.stats.fail(warning);
 				}
 			}
 		}
+		Ok(None)
 	}
 
 	/// Get current parsing statistics
@@ -191,128 +245,253 @@ impl StreamingJsonLinesParser {
 	}
 }
 
-/// Streaming JSON array parser (for compact JSON arrays)
+/// Streaming combo-list parser (see [`crate::ingest::combo`])
+///
+/// The first [`SAMPLE_LINES`] lines are buffered to infer the file's layout.
+/// Rows follow the [`COMBO_HEADER`](crate::ingest::combo::COMBO_HEADER) row, which
+/// is emitted before the first recognized line.
+pub struct StreamingComboParser {
+	lines: LineReader,
+	/// Sampled lines not yet returned
+	sample: VecDeque<String>,
+	parser: ComboLineParser,
+	stats: StreamStats,
+	line_number: u64,
+	header_sent: bool,
+	/// Row to return after the header
+	pending: Option<Vec<String>>,
+}
+
+impl StreamingComboParser {
+	/// Create a new streaming combo-list parser from a file path
+	pub async fn new(path: &Path) -> io::Result<Self> {
+		let mut lines = LineReader::open(path).await?;
+		let mut stats = StreamStats::default();
+		let mut sample = VecDeque::new();
+		while sample.len() < SAMPLE_LINES {
+			match lines.next_line(&mut stats).await? {
+				Some(line) => sample.push_back(line),
+				None => break,
+			}
+		}
+
+		Ok(StreamingComboParser {
+			parser: ComboLineParser::from_sample(sample.iter().map(String::as_str)),
+			lines,
+			sample,
+			stats,
+			line_number: 0,
+			header_sent: false,
+			pending: None,
+		})
+	}
+
+	/// Read and parse the next recognized line from the file
+	/// Returns None when EOF is reached
+	/// Blank lines are skipped; unrecognized lines are counted and skipped
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		if let Some(row) = self.pending.take() {
+			return Ok(Some(row));
+		}
+
+		loop {
+			let line = match self.sample.pop_front() {
+				Some(line) => line,
+				None => match self.lines.next_line(&mut self.stats).await? {
+					Some(line) => line,
+					None => return Ok(None),
+				},
+			};
+			self.line_number += 1;
+
+			if let Some(row) = self.parser.parse_line(&line) {
+				self.stats.rows_processed += 1;
+				if self.header_sent {
+					return Ok(Some(row));
+				}
+				self.header_sent = true;
+				self.pending = Some(row);
+				return Ok(Some(combo::header_row()));
+			}
+			if !line.trim().is_empty() {
+				// Lines hold credentials, so only their number is reported
+				let warning = format!("Unrecognized combo-list line {}", self.line_number);
+				self.stats.fail(warning);
+			}
+		}
+	}
+
+	/// Get current parsing statistics
+	pub fn stats(&self) -> &StreamStats {
+		&self.stats
+	}
+
+	/// Consume parser and return final statistics
+	pub fn into_stats(self) -> StreamStats {
+		self.stats
+	}
+}
+
+/// Position of [`StreamingJsonArrayParser`] in the document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArrayPosition {
+	/// Nothing read yet
+	Start,
+	/// Inside the top-level array, before the next item
+	InArray,
+	/// Past the end of the document
+	Done,
+}
+
+/// Streaming JSON array parser
+///
+/// Yields the items of a top-level JSON array one at a time. Any other
+/// document (an object or a single value) is read whole and yielded once.
 pub struct StreamingJsonArrayParser {
 	reader: BufReader<File>,
 	stats: StreamStats,
-	buffer: String,
-	in_array: bool,
-	brace_depth: i32,
+	position: ArrayPosition,
+	is_array: bool,
+	/// Bytes of the item being read
+	item: Vec<u8>,
 }
 
 impl StreamingJsonArrayParser {
 	/// Create a new streaming JSON array parser from a file path
-	pub async fn new(path: &Path) -> std::io::Result<Self> {
+	pub async fn new(path: &Path) -> io::Result<Self> {
 		let file = File::open(path).await?;
-		let reader = BufReader::new(file);
-
 		Ok(StreamingJsonArrayParser {
-			reader,
-			stats: StreamStats {
-				rows_processed: 0,
-				rows_failed: 0,
-				bytes_read: 0,
-				warnings: Vec::new(),
-			},
-			buffer: String::with_capacity(8192),
-			in_array: false,
-			brace_depth: 0,
+			reader: BufReader::with_capacity(READ_BUFFER_SIZE, file),
+			stats: StreamStats::default(),
+			position: ArrayPosition::Start,
+			is_array: false,
+			item: Vec::with_capacity(4096),
 		})
 	}
 
-	/// Read and parse the next JSON object from the array
-	/// Returns None when EOF or end of array is reached
-	pub async fn next_row(&mut self) -> std::io::Result<Option<Vec<String>>> {
-		loop {
-			let mut line = String::new();
+	/// Whether the document is a top-level array (known after the first value is read)
+	pub fn is_array(&self) -> bool {
+		self.is_array
+	}
 
-			// Read until we find a complete JSON object
-			let bytes_read = self.reader.read_line(&mut line).await?;
-			if bytes_read == 0 && self.buffer.is_empty() {
-				return Ok(None);
+	/// Read and parse the next item of the array
+	/// Returns None when the end of the array is reached
+	/// Malformed JSON anywhere in the document is an `InvalidData` error
+	pub async fn next_value(&mut self) -> io::Result<Option<Value>> {
+		match self.position {
+			ArrayPosition::Done => Ok(None),
+			ArrayPosition::Start => {
+				if self.skip_whitespace().await? != Some(b'[') {
+					return self.read_document().await.map(Some);
+				}
+				self.reader.consume(1);
+				self.stats.bytes_read += 1;
+				self.is_array = true;
+				self.position = ArrayPosition::InArray;
+				self.read_item().await
 			}
+			ArrayPosition::InArray => self.read_item().await,
+		}
+	}
+
+	/// Skip whitespace and peek at the next byte
+	async fn skip_whitespace(&mut self) -> io::Result<Option<u8>> {
+		loop {
+			let buf = self.reader.fill_buf().await?;
+			let Some(skip) = buf.iter().position(|b| !is_json_whitespace(*b)) else {
+				if buf.is_empty() {
+					return Ok(None);
+				}
+				let len = buf.len();
+				self.reader.consume(len);
+				self.stats.bytes_read += len as u64;
+				continue;
+			};
+			let next = buf[skip];
+			self.reader.consume(skip);
+			self.stats.bytes_read += skip as u64;
+			return Ok(Some(next));
+		}
+	}
+
+	/// Read a document that is not an array as a single value
+	async fn read_document(&mut self) -> io::Result<Value> {
+		self.position = ArrayPosition::Done;
+		let mut data = Vec::new();
+		let bytes_read = self.reader.read_to_end(&mut data).await?;
+		self.stats.bytes_read += bytes_read as u64;
+		let value = parse_json_bytes(&data)?;
+		self.stats.rows_processed += 1;
+		Ok(value)
+	}
 
-			self.stats.bytes_read += bytes_read as u64;
+	/// Read the bytes of the next item, up to the `,` or `]` that ends it
+	async fn read_item(&mut self) -> io::Result<Option<Value>> {
+		self.item.clear();
+		let mut depth: i64 = 0;
+		let mut in_string = false;
+		let mut escaped = false;
 
-			// Build up buffer to find complete JSON objects
-			for ch in line.chars() {
-				match ch {
-					'[' if !self.in_array => {
-						self.in_array = true;
-					}
-					']' if self.in_array && self.brace_depth == 0 => {
-						// End of array
-						if !self.buffer.is_empty()
-							&& let Some(result) = self.try_parse_buffer()
-						{
-							return result;
-						}
-						return Ok(None);
-					}
-					'{' => {
-						self.brace_depth += 1;
-						self.buffer.push(ch);
-					}
-					'}' => {
-						self.buffer.push(ch);
-						self.brace_depth -= 1;
-						if self.brace_depth == 0
-							&& !self.buffer.is_empty()
-							&& let Some(result) = self.try_parse_buffer()
-						{
-							return result;
-						}
-					}
-					',' if self.brace_depth == 0 && !self.buffer.is_empty() => {
-						// End of current object
-						if let Some(result) = self.try_parse_buffer() {
-							return result;
-						}
+		let end = loop {
+			let buf = self.reader.fill_buf().await?;
+			if buf.is_empty() {
+				return Err(invalid_json("EOF while parsing a list"));
+			}
+
+			let mut end = None;
+			for (i, &b) in buf.iter().enumerate() {
+				if in_string {
+					if escaped {
+						escaped = false;
+					} else if b == b'\\' {
+						escaped = true;
+					} else if b == b'"' {
+						in_string = false;
 					}
-					ch if self.brace_depth > 0 || (self.in_array && ch != '[' && ch != ']') => {
-						if !ch.is_whitespace() || self.brace_depth > 0 {
-							self.buffer.push(ch);
-						}
+					continue;
+				}
+				match b {
+					b'"' => in_string = true,
+					b'{' | b'[' => depth += 1,
+					b'}' | b']' if depth > 0 => depth -= 1,
+					b',' | b']' if depth == 0 => {
+						end = Some((i, b));
+						break;
 					}
 					_ => {}
 				}
 			}
 
-			if bytes_read == 0
-				&& !self.buffer.is_empty()
-				&& let Some(result) = self.try_parse_buffer()
-			{
-				return result;
+			let used = end.map_or(buf.len(), |(i, _)| i + 1);
+			let item_len = end.map_or(buf.len(), |(i, _)| i);
+			self.item.extend_from_slice(&buf[..item_len]);
+			self.reader.consume(used);
+			self.stats.bytes_read += used as u64;
+			if let Some((_, delimiter)) = end {
+				break delimiter;
+			}
+		};
+
+		if end == b']' {
+			self.finish_document().await?;
+			// `[]` has no items
+			if self.stats.rows_processed == 0 && self.item.iter().all(|b| is_json_whitespace(*b)) {
+				return Ok(None);
 			}
 		}
+
+		let value = parse_json_bytes(&self.item)?;
+		self.stats.rows_processed += 1;
+		Ok(Some(value))
 	}
 
-	/// Try to parse the buffered JSON object, returning None if parsing fails
-	/// (to continue to next object) or Some(result) if successful/complete
-	fn try_parse_buffer(&mut self) -> Option<std::io::Result<Option<Vec<String>>>> {
-		match serde_json::from_str::<serde_json::Value>(&self.buffer) {
-			Ok(value) => {
-				let fields = match value {
-					serde_json::Value::Object(obj) => {
-						obj.iter().map(|(k, v)| format!("{}:{}", k, v)).collect()
-					}
-					serde_json::Value::Array(arr) => arr.iter().map(|v| v.to_string()).collect(),
-					_ => vec![value.to_string()],
-				};
-				self.buffer.clear();
-				self.stats.rows_processed += 1;
-				Some(Ok(Some(fields)))
-			}
-			Err(e) => {
-				self.buffer.clear();
-				self.stats.rows_failed += 1;
-				self.stats
-					.warnings
-					.push(format!("Failed to parse JSON object: {}", e));
-				// Return None to continue parsing
-				None
-			}
+	/// Check that only whitespace follows the closing `]`
+	async fn finish_document(&mut self) -> io::Result<()> {
+		self.position = ArrayPosition::Done;
+		if self.skip_whitespace().await?.is_some() {
+			return Err(invalid_json("trailing characters after the array"));
 		}
+		Ok(())
 	}
 
 	/// Get current parsing statistics
@@ -326,99 +505,628 @@ impl StreamingJsonArrayParser {
 	}
 }
 
-/// Parse a single CSV line with proper quote handling
+/// JSON whitespace as defined by RFC 8259
+fn is_json_whitespace(b: u8) -> bool {
+	matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn invalid_json(message: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Parse JSON after replacing invalid UTF-8, as the in-memory path does
+fn parse_json_bytes(data: &[u8]) -> io::Result<Value> {
+	serde_json::from_str(&String::from_utf8_lossy(data))
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Source of JSON values for [`StreamingJsonRows`]
+enum JsonValues {
+	Array(StreamingJsonArrayParser),
+	Lines(StreamingJsonLinesParser),
+}
+
+impl JsonValues {
+	async fn open(path: &Path, json_lines: bool) -> io::Result<Self> {
+		Ok(if json_lines {
+			JsonValues::Lines(StreamingJsonLinesParser::new(path).await?)
+		} else {
+			JsonValues::Array(StreamingJsonArrayParser::new(path).await?)
+		})
+	}
+
+	async fn next_value(&mut self) -> io::Result<Option<Value>> {
+		match self {
+			JsonValues::Array(parser) => parser.next_value().await,
+			JsonValues::Lines(parser) => parser.next_value().await,
+		}
+	}
+
+	/// JSON Lines files are treated as an array of their lines
+	fn is_array(&self) -> bool {
+		match self {
+			JsonValues::Array(parser) => parser.is_array(),
+			JsonValues::Lines(_) => true,
+		}
+	}
+
+	fn stats(&self) -> &StreamStats {
+		match self {
+			JsonValues::Array(parser) => parser.stats(),
+			JsonValues::Lines(parser) => parser.stats(),
+		}
+	}
+}
+
+/// Rows of a JSON or JSON Lines file, flattened like
+/// [`json_to_rows`](crate::ingest::universal_parser::json_to_rows)
 ///
-/// Handles:
-/// - Simple comma-separated values
-/// - Quoted fields containing commas or quotes
-/// - Escaped quotes within quoted fields ("" -> ")
-fn parse_csv_line(line: &str) -> Result<Vec<String>, String> {
-	let mut fields = Vec::new();
-	let mut current_field = String::new();
-	let mut in_quotes = false;
-	let mut chars = line.chars().peekable();
-
-	while let Some(ch) = chars.next() {
-		match ch {
-			'"' => {
-				if in_quotes {
-					// Check for escaped quote ("")
-					if chars.peek() == Some(&'"') {
-						current_field.push('"');
-						chars.next();
-					} else {
-						in_quotes = false;
+/// Opening the file reads it once to validate it and collect the columns of
+/// arrays of objects; rows are then produced from a second pass.
+pub struct StreamingJsonRows {
+	values: JsonValues,
+	/// Shape of the array items, or None when the document was not an array
+	shape: Option<ArrayRowShape>,
+	/// Rows ready to be returned before reading further items
+	pending: VecDeque<Vec<String>>,
+}
+
+impl StreamingJsonRows {
+	/// Open a JSON document (`json_lines = false`) or JSON Lines file
+	pub async fn open(path: &Path, json_lines: bool) -> io::Result<Self> {
+		let mut values = JsonValues::open(path, json_lines).await?;
+		let Some(first) = values.next_value().await? else {
+			return Ok(StreamingJsonRows {
+				values,
+				shape: None,
+				pending: VecDeque::new(),
+			});
+		};
+
+		if !values.is_array() {
+			return Ok(StreamingJsonRows {
+				values,
+				shape: None,
+				pending: json_to_rows(&first).into(),
+			});
+		}
+
+		let mut shape = ArrayRowShape::for_first(&first);
+		shape.observe(&first);
+		while let Some(item) = values.next_value().await? {
+			shape.observe(&item);
+		}
+		if shape.is_empty() {
+			return Ok(StreamingJsonRows {
+				values,
+				shape: None,
+				pending: VecDeque::new(),
+			});
+		}
+
+		Ok(StreamingJsonRows {
+			values: JsonValues::open(path, json_lines).await?,
+			pending: shape.header().into_iter().collect(),
+			shape: Some(shape),
+		})
+	}
+
+	/// Whether the document produces any rows
+	pub fn has_rows(&self) -> bool {
+		self.shape.is_some() || !self.pending.is_empty()
+	}
+
+	/// Produce the next row
+	/// Returns None when EOF is reached
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		if let Some(row) = self.pending.pop_front() {
+			return Ok(Some(row));
+		}
+		let Some(shape) = &self.shape else {
+			return Ok(None);
+		};
+		Ok(self.values.next_value().await?.map(|item| shape.row(&item)))
+	}
+
+	/// Get current parsing statistics
+	pub fn stats(&self) -> &StreamStats {
+		self.values.stats()
+	}
+}
+
+/// Streaming SQL dump parser (see [`crate::ingest::sql_dump`])
+///
+/// A dump holds one dataset per table. [`next_table`](Self::next_table) moves to
+/// the next table, and [`next_row`](Self::next_row) then returns its column names
+/// followed by its rows, and None at the end of the table.
+pub struct StreamingSqlDumpParser {
+	lines: LineReader,
+	parser: SqlDumpParser,
+	stats: StreamStats,
+	/// Column names of the current table, until they are returned as its first row
+	header: Option<Vec<String>>,
+	/// Table reached while reading the rows of the previous one
+	next_table: Option<SqlTable>,
+	eof: bool,
+}
+
+impl StreamingSqlDumpParser {
+	/// Create a new streaming SQL dump parser from a file path
+	pub async fn new(path: &Path) -> io::Result<Self> {
+		Ok(StreamingSqlDumpParser {
+			lines: LineReader::open(path).await?,
+			parser: SqlDumpParser::new(),
+			stats: StreamStats::default(),
+			header: None,
+			next_table: None,
+			eof: false,
+		})
+	}
+
+	/// Move to the next table with rows, skipping any rows left in the current one
+	/// Returns None when EOF is reached
+	pub async fn next_table(&mut self) -> io::Result<Option<SqlTable>> {
+		let table = match self.next_table.take() {
+			Some(table) => Some(table),
+			None => loop {
+				match self.next_event().await? {
+					Some(SqlEvent::Table(table)) => break Some(table),
+					Some(_) => {}
+					None => break None,
+				}
+			},
+		};
+		self.header = table.as_ref().map(|table| table.columns.clone());
+		Ok(table)
+	}
+
+	/// Read the next row of the current table
+	/// Returns None at the end of the table
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		if let Some(header) = self.header.take() {
+			return Ok(Some(header));
+		}
+		if self.next_table.is_some() {
+			return Ok(None);
+		}
+
+		match self.next_event().await? {
+			Some(SqlEvent::Row(row)) => {
+				self.stats.rows_processed += 1;
+				Ok(Some(row))
+			}
+			Some(SqlEvent::Table(table)) => {
+				self.next_table = Some(table);
+				Ok(None)
+			}
+			Some(SqlEvent::Skipped(_)) | None => Ok(None),
+		}
+	}
+
+	/// Read lines until the parser has a table or row, counting skipped statements
+	async fn next_event(&mut self) -> io::Result<Option<SqlEvent>> {
+		loop {
+			match self.parser.next_event() {
+				Some(SqlEvent::Skipped(reason)) => self.stats.fail(reason),
+				Some(event) => return Ok(Some(event)),
+				None if self.eof => return Ok(None),
+				None => match self.lines.next_line(&mut self.stats).await? {
+					Some(line) => self.parser.push_line(&line),
+					None => {
+						self.eof = true;
+						self.parser.finish();
 					}
-				} else if current_field.is_empty() {
-					in_quotes = true;
-				} else {
-					return Err("Quote encountered mid-field".to_string());
+				},
+			}
+		}
+	}
+
+	/// Get current parsing statistics
+	pub fn stats(&self) -> &StreamStats {
+		&self.stats
+	}
+}
+
+/// Row batches buffered between the SQLite reading thread and its reader
+const SQLITE_CHANNEL_CAPACITY: usize = 4;
+
+/// Streaming SQLite database reader (see [`crate::ingest::sqlite`])
+///
+/// Tables are read like [`StreamingSqlDumpParser`]'s: [`next_table`](Self::next_table)
+/// moves to the next table, and [`next_row`](Self::next_row) then returns its
+/// column names followed by its rows, and None at the end of the table.
+pub struct StreamingSqliteReader {
+	receiver: mpsc::Receiver<io::Result<SqliteEvent>>,
+	/// Rows of the current table received but not yet returned
+	rows: std::vec::IntoIter<Vec<String>>,
+	stats: StreamStats,
+	/// Column names of the current table, until they are returned as its first row
+	header: Option<Vec<String>>,
+	/// Table reached while reading the rows of the previous one
+	next_table: Option<SqlTable>,
+}
+
+impl StreamingSqliteReader {
+	/// Open a database read-only and start reading its tables
+	///
+	/// Fails when the file is not a readable SQLite database.
+	pub async fn open(path: &Path) -> io::Result<Self> {
+		let path = path.to_path_buf();
+		let conn = tokio::task::spawn_blocking(move || {
+			let conn = sqlite::open_read_only(&path)?;
+			sqlite::list_tables(&conn)?;
+			Ok::<_, rusqlite::Error>(conn)
+		})
+		.await
+		.map_err(io::Error::other)?
+		.map_err(io::Error::other)?;
+
+		let (sender, receiver) = mpsc::channel(SQLITE_CHANNEL_CAPACITY);
+		tokio::task::spawn_blocking(move || sqlite::read_tables(&conn, &sender));
+
+		Ok(StreamingSqliteReader {
+			receiver,
+			rows: Vec::new().into_iter(),
+			stats: StreamStats::default(),
+			header: None,
+			next_table: None,
+		})
+	}
+
+	/// Move to the next table with rows, skipping any rows left in the current one
+	/// Returns None after the last table
+	pub async fn next_table(&mut self) -> io::Result<Option<SqlTable>> {
+		let table = match self.next_table.take() {
+			Some(table) => Some(table),
+			None => loop {
+				match self.next_event().await? {
+					Some(SqliteEvent::Table(table)) => break Some(table),
+					Some(_) => {}
+					None => break None,
 				}
+			},
+		};
+		self.rows = Vec::new().into_iter();
+		self.header = table.as_ref().map(|table| table.columns.clone());
+		Ok(table)
+	}
+
+	/// Read the next row of the current table
+	/// Returns None at the end of the table
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		if let Some(header) = self.header.take() {
+			return Ok(Some(header));
+		}
+
+		loop {
+			if let Some(row) = self.rows.next() {
+				self.stats.rows_processed += 1;
+				return Ok(Some(row));
+			}
+			if self.next_table.is_some() {
+				return Ok(None);
 			}
-			',' if !in_quotes => {
-				fields.push(current_field.clone());
-				current_field.clear();
+			match self.next_event().await? {
+				Some(SqliteEvent::Rows(rows)) => self.rows = rows.into_iter(),
+				Some(SqliteEvent::Table(table)) => {
+					self.next_table = Some(table);
+					return Ok(None);
+				}
+				Some(SqliteEvent::Skipped(_)) | None => return Ok(None),
 			}
-			_ => {
-				current_field.push(ch);
+		}
+	}
+
+	/// Receive the next table or rows, counting tables that could not be read
+	async fn next_event(&mut self) -> io::Result<Option<SqliteEvent>> {
+		loop {
+			match self.receiver.recv().await.transpose()? {
+				Some(SqliteEvent::Skipped(reason)) => self.stats.fail(reason),
+				event => return Ok(event),
 			}
 		}
 	}
 
-	if in_quotes {
-		return Err("Unterminated quoted field".to_string());
+	/// Get current parsing statistics
+	pub fn stats(&self) -> &StreamStats {
+		&self.stats
+	}
+}
+
+/// Tables from either of the multi-table readers
+pub enum TableStream {
+	Sql(Box<StreamingSqlDumpParser>),
+	Sqlite(StreamingSqliteReader),
+}
+
+impl TableStream {
+	/// Move to the next table with rows
+	/// Returns None after the last table
+	pub async fn next_table(&mut self) -> io::Result<Option<SqlTable>> {
+		match self {
+			TableStream::Sql(parser) => parser.next_table().await,
+			TableStream::Sqlite(reader) => reader.next_table().await,
+		}
+	}
+
+	/// Read the next row of the current table, starting with its column names
+	/// Returns None at the end of the table
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		match self {
+			TableStream::Sql(parser) => parser.next_row().await,
+			TableStream::Sqlite(reader) => reader.next_row().await,
+		}
+	}
+
+	/// Get current parsing statistics
+	pub fn stats(&self) -> &StreamStats {
+		match self {
+			TableStream::Sql(parser) => parser.stats(),
+			TableStream::Sqlite(reader) => reader.stats(),
+		}
+	}
+}
+
+/// Rows read incrementally for the ingest pipeline
+pub trait RowSource {
+	/// Read the next row
+	/// Returns None at the end of the rows
+	fn next_row(&mut self) -> impl Future<Output = io::Result<Option<Vec<String>>>> + Send;
+
+	/// Get current parsing statistics
+	fn stats(&self) -> &StreamStats;
+}
+
+impl RowSource for RowStream {
+	fn next_row(&mut self) -> impl Future<Output = io::Result<Option<Vec<String>>>> + Send {
+		RowStream::next_row(self)
+	}
+
+	fn stats(&self) -> &StreamStats {
+		RowStream::stats(self)
+	}
+}
+
+impl RowSource for TableStream {
+	fn next_row(&mut self) -> impl Future<Output = io::Result<Option<Vec<String>>>> + Send {
+		TableStream::next_row(self)
+	}
+
+	fn stats(&self) -> &StreamStats {
+		TableStream::stats(self)
+	}
+}
+
+/// Rows from any of the streaming parsers
+pub enum RowStream {
+	Csv(StreamingCsvParser),
+	Tsv(StreamingTsvParser),
+	Json(StreamingJsonRows),
+	Combo(StreamingComboParser),
+}
+
+impl RowStream {
+	/// Read the next row
+	/// Returns None when EOF is reached
+	pub async fn next_row(&mut self) -> io::Result<Option<Vec<String>>> {
+		match self {
+			RowStream::Csv(parser) => parser.next_row().await,
+			RowStream::Tsv(parser) => parser.next_row().await,
+			RowStream::Json(rows) => rows.next_row().await,
+			RowStream::Combo(parser) => parser.next_row().await,
+		}
 	}
 
-	fields.push(current_field);
-	Ok(fields)
+	/// Get current parsing statistics
+	pub fn stats(&self) -> &StreamStats {
+		match self {
+			RowStream::Csv(parser) => parser.stats(),
+			RowStream::Tsv(parser) => parser.stats(),
+			RowStream::Json(rows) => rows.stats(),
+			RowStream::Combo(parser) => parser.stats(),
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::ingest::adapters::{CsvAdapter, FormatAdapter};
+
+	async fn write_file(
+		dir: &tempfile::TempDir,
+		name: &str,
+		contents: &[u8],
+	) -> std::path::PathBuf {
+		let path = dir.path().join(name);
+		tokio::fs::write(&path, contents).await.unwrap();
+		path
+	}
 
-	#[test]
-	fn test_parse_simple_csv_line() {
-		let line = "field1,field2,field3";
-		let result = parse_csv_line(line).unwrap();
-		assert_eq!(result, vec!["field1", "field2", "field3"]);
+	async fn collect(mut stream: RowStream) -> Vec<Vec<String>> {
+		let mut rows = Vec::new();
+		while let Some(row) = stream.next_row().await.unwrap() {
+			rows.push(row);
+		}
+		rows
 	}
 
-	#[test]
-	fn test_parse_csv_with_quotes() {
-		let line = r#""quoted field","normal field","field with, comma""#;
-		let result = parse_csv_line(line).unwrap();
-		assert_eq!(result[0], "quoted field");
-		assert_eq!(result[1], "normal field");
-		assert_eq!(result[2], "field with, comma");
+	#[tokio::test]
+	async fn test_csv_stream_matches_adapter() {
+		let dir = tempfile::tempdir().unwrap();
+		let csv = "a,b,c\r\n\"quoted field\",normal,\"field with, comma\"\n\n\"multi\nline\",d,\"with \"\"quote\"\"\"\nfield1,,field3";
+		let path = write_file(&dir, "dump.csv", csv.as_bytes()).await;
+
+		let parser = StreamingCsvParser::new(&path).await.unwrap();
+		let rows = collect(RowStream::Csv(parser)).await;
+
+		assert_eq!(rows, CsvAdapter::new().parse(csv));
+		assert_eq!(rows[1][2], "field with, comma");
+		assert_eq!(rows[3][0], "multi\nline");
+		assert_eq!(rows[3][2], "with \"quote\"");
+		assert_eq!(rows[4], vec!["field1", "", "field3"]);
 	}
 
-	#[test]
-	fn test_parse_csv_with_escaped_quotes() {
-		let line = r#""field with ""escaped"" quotes",normal"#;
-		let result = parse_csv_line(line).unwrap();
-		assert_eq!(result[0], "field with \"escaped\" quotes");
-		assert_eq!(result[1], "normal");
+	#[tokio::test]
+	async fn test_csv_stream_replaces_invalid_utf8() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = write_file(&dir, "dump.csv", b"user\xFF,secret\n").await;
+
+		let mut parser = StreamingCsvParser::new(&path).await.unwrap();
+		let row = parser.next_row().await.unwrap().unwrap();
+		assert_eq!(row, vec!["user\u{FFFD}", "secret"]);
+		assert!(parser.next_row().await.unwrap().is_none());
+		assert_eq!(parser.stats().bytes_read, 13);
 	}
 
-	#[test]
-	fn test_parse_csv_empty_field() {
-		let line = "field1,,field3";
-		let result = parse_csv_line(line).unwrap();
-		assert_eq!(result, vec!["field1", "", "field3"]);
+	#[tokio::test]
+	async fn test_tsv_stream_splits_lines_like_str_lines() {
+		let dir = tempfile::tempdir().unwrap();
+		let tsv = "email\tpassword\r\nalice@example.com\thunter2\n\nbob@example.com\tletmein";
+		let path = write_file(&dir, "dump.tsv", tsv.as_bytes()).await;
+
+		let parser = StreamingTsvParser::new(&path).await.unwrap();
+		let rows = collect(RowStream::Tsv(parser)).await;
+
+		let expected: Vec<Vec<String>> = tsv
+			.lines()
+			.map(|line| line.split('\t').map(str::to_string).collect())
+			.collect();
+		assert_eq!(rows, expected);
 	}
 
-	#[test]
-	fn test_parse_csv_error_unterminated_quote() {
-		let line = r#""unterminated quote,field2"#;
-		let result = parse_csv_line(line);
-		assert!(result.is_err());
+	#[tokio::test]
+	async fn test_combo_stream_matches_adapter() {
+		use crate::ingest::combo::ComboListAdapter;
+
+		let dir = tempfile::tempdir().unwrap();
+		let combo = "alice@example.com:hunter2\r\n\nnot a credential\nbob;pa;ss\nhttps://example.com/login:carol:pw\n";
+		let path = write_file(&dir, "combo.txt", combo.as_bytes()).await;
+
+		let parser = StreamingComboParser::new(&path).await.unwrap();
+		let rows = collect(RowStream::Combo(parser)).await;
+
+		assert_eq!(rows, ComboListAdapter::new().parse(combo));
+		assert_eq!(rows.len(), 4);
+
+		let mut parser = StreamingComboParser::new(&path).await.unwrap();
+		while parser.next_row().await.unwrap().is_some() {}
+		assert_eq!(parser.stats().rows_failed, 1);
+		assert_eq!(
+			parser.stats().warnings,
+			vec!["Unrecognized combo-list line 3"]
+		);
 	}
 
-	#[test]
-	fn test_parse_csv_error_mid_field_quote() {
-		let line = r#"field"with"quote,field2"#;
-		let result = parse_csv_line(line);
-		assert!(result.is_err());
+	#[tokio::test]
+	async fn test_sql_dump_stream_reads_tables_in_turn() {
+		let dir = tempfile::tempdir().unwrap();
+		let dump = "CREATE TABLE `users` (`id` int, `email` varchar(64));\n\
+			INSERT INTO `users` VALUES (1,'alice@example.com'),(2,'bob@example.com');\n\
+			INSERT INTO `users` VALUES (3,'broken';\n\
+			COPY logins (who, ip) FROM stdin;\n\
+			carol@example.com\t10.0.0.1\n\
+			\\.\n";
+		let path = write_file(&dir, "dump.sql", dump.as_bytes()).await;
+		let mut parser = StreamingSqlDumpParser::new(&path).await.unwrap();
+
+		let mut tables = Vec::new();
+		while let Some(table) = parser.next_table().await.unwrap() {
+			let mut rows = Vec::new();
+			while let Some(row) = parser.next_row().await.unwrap() {
+				rows.push(row);
+			}
+			tables.push((table.name, rows));
+		}
+
+		assert_eq!(
+			tables,
+			vec![
+				(
+					"users".to_string(),
+					vec![
+						vec!["id".to_string(), "email".to_string()],
+						vec!["1".to_string(), "alice@example.com".to_string()],
+						vec!["2".to_string(), "bob@example.com".to_string()],
+					]
+				),
+				(
+					"logins".to_string(),
+					vec![
+						vec!["who".to_string(), "ip".to_string()],
+						vec!["carol@example.com".to_string(), "10.0.0.1".to_string()],
+					]
+				),
+			]
+		);
+		assert_eq!(parser.stats().rows_processed, 3);
+		assert_eq!(parser.stats().rows_failed, 1);
+	}
+
+	#[tokio::test]
+	async fn test_json_stream_matches_json_to_rows() {
+		let dir = tempfile::tempdir().unwrap();
+		let documents = [
+			r#"[{"email": "a@example.com", "user": {"name": "A, \"x\" ]"}}, {"email": "b@example.com", "pass": "p"}]"#,
+			r#" [ ["a@example.com", 1], ["b@example.com", [2, 3]] ] "#,
+			r#"["a@example.com", {"x": 1}]"#,
+			r#"{"email": "a@example.com", "nested": {"k": "v"}}"#,
+			"42",
+		];
+
+		for document in documents {
+			let path = write_file(&dir, "dump.json", document.as_bytes()).await;
+			let rows = StreamingJsonRows::open(&path, false).await.unwrap();
+			assert!(rows.has_rows());
+
+			let expected = json_to_rows(&serde_json::from_str(document).unwrap());
+			assert_eq!(
+				collect(RowStream::Json(rows)).await,
+				expected,
+				"{}",
+				document
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_json_stream_rejects_malformed_documents() {
+		let dir = tempfile::tempdir().unwrap();
+		for document in [
+			r#"[{"a": 1}, {"a": }]"#,
+			r#"[{"a": 1}"#,
+			r#"[1, 2] x"#,
+			"[1,,2]",
+		] {
+			let path = write_file(&dir, "dump.json", document.as_bytes()).await;
+			let result = StreamingJsonRows::open(&path, false).await;
+			assert!(result.is_err(), "{}", document);
+		}
+
+		for document in ["[]", "  [ ] ", "[{}]"] {
+			let path = write_file(&dir, "dump.json", document.as_bytes()).await;
+			let rows = StreamingJsonRows::open(&path, false).await.unwrap();
+			assert!(!rows.has_rows(), "{}", document);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_json_lines_stream_skips_bad_lines() {
+		let dir = tempfile::tempdir().unwrap();
+		let jsonl = "{\"email\": \"a@example.com\"}\n\nnot json\n{\"email\": \"b@example.com\", \"pass\": \"p\"}\n";
+		let path = write_file(&dir, "dump.jsonl", jsonl.as_bytes()).await;
+
+		let mut rows = StreamingJsonRows::open(&path, true).await.unwrap();
+		let expected = json_to_rows(&serde_json::json!([
+			{"email": "a@example.com"},
+			{"email": "b@example.com", "pass": "p"}
+		]));
+
+		for row in expected {
+			assert_eq!(rows.next_row().await.unwrap(), Some(row));
+		}
+		assert!(rows.next_row().await.unwrap().is_none());
+		assert_eq!(rows.stats().rows_failed, 1);
+		assert!(rows.stats().warnings[0].contains("line 3"));
 	}
 }
@@ -22,33 +22,23 @@ use serde_json::json;
 pub fn json_to_rows(value: &Value) -> Vec<Vec<String>> {
 	match value {
 		Value::Array(arr) => {
-			if arr.is_empty() {
+			let Some(first) = arr.first() else {
 				return Vec::new();
-			}
+			};
 
-			// Check what type of array elements we have
-			match &arr[0] {
-				Value::Object(_) => {
-					// Array of objects - extract all keys, convert to rows
-					flatten_object_array(arr)
-				}
-				Value::Array(_) => {
-					// Array of arrays - convert directly
-					arr.iter()
-						.map(|item| {
-							if let Value::Array(row) = item {
-								row.iter().map(value_to_string).collect()
-							} else {
-								vec![value_to_string(item)]
-							}
-						})
-						.collect()
-				}
-				_ => {
-					// Array of primitives - each becomes a row with one column
-					arr.iter().map(|v| vec![value_to_string(v)]).collect()
-				}
+			let mut shape = ArrayRowShape::for_first(first);
+			for item in arr {
+				shape.observe(item);
+			}
+			if shape.is_empty() {
+				return Vec::new();
 			}
+
+			shape
+				.header()
+				.into_iter()
+				.chain(arr.iter().map(|item| shape.row(item)))
+				.collect()
 		}
 		Value::Object(_) => {
 			// Single object - convert to one row
@@ -61,43 +51,68 @@ pub fn json_to_rows(value: &Value) -> Vec<Vec<String>> {
 	}
 }
 
-/// Flatten an array of objects into rows
-/// All objects are normalized to same columns
-fn flatten_object_array(arr: &[Value]) -> Vec<Vec<String>> {
-	if arr.is_empty() {
-		return Vec::new();
-	}
+/// How the items of a top-level JSON array become rows, decided by its first item
+///
+/// Lets streaming readers build the same rows as [`json_to_rows`] one item at a
+/// time: every item is [`observe`](Self::observe)d first, then turned into rows.
+#[derive(Debug, Clone)]
+pub enum ArrayRowShape {
+	/// Array of objects - all objects are normalized to the same columns,
+	/// the union of their keys, which also form the header row
+	Objects(std::collections::BTreeSet<String>),
+	/// Array of arrays - convert directly
+	Arrays,
+	/// Array of primitives - each becomes a row with one column
+	Primitives,
+}
 
-	// Collect all possible keys across all objects
-	let mut all_keys = std::collections::BTreeSet::new();
-	for item in arr {
-		if let Value::Object(map) = item {
-			collect_object_keys(map, "", &mut all_keys);
+impl ArrayRowShape {
+	/// Pick the shape from the array's first item
+	pub fn for_first(first: &Value) -> Self {
+		match first {
+			Value::Object(_) => ArrayRowShape::Objects(std::collections::BTreeSet::new()),
+			Value::Array(_) => ArrayRowShape::Arrays,
+			_ => ArrayRowShape::Primitives,
 		}
 	}
 
-	let keys: Vec<String> = all_keys.into_iter().collect();
-	if keys.is_empty() {
-		return Vec::new();
+	/// Record the columns an item contributes
+	pub fn observe(&mut self, item: &Value) {
+		if let (ArrayRowShape::Objects(keys), Value::Object(map)) = (self, item) {
+			collect_object_keys(map, "", keys);
+		}
 	}
 
-	// Convert each object to a row with all keys
-	let mut rows = Vec::new();
-
-	// Add header row
-	rows.push(keys.clone());
+	/// Whether the array produces no rows (objects without any keys)
+	pub fn is_empty(&self) -> bool {
+		matches!(self, ArrayRowShape::Objects(keys) if keys.is_empty())
+	}
 
-	// Add data rows
-	for item in arr {
-		let mut row = Vec::new();
-		for key in &keys {
-			let value = get_nested_value(item, key);
-			row.push(value_to_string(&value));
+	/// Header row listing the columns, for arrays of objects
+	pub fn header(&self) -> Option<Vec<String>> {
+		match self {
+			ArrayRowShape::Objects(keys) => Some(keys.iter().cloned().collect()),
+			_ => None,
 		}
-		rows.push(row);
 	}
 
-	rows
+	/// Convert one item into a row
+	pub fn row(&self, item: &Value) -> Vec<String> {
+		match self {
+			ArrayRowShape::Objects(keys) => keys
+				.iter()
+				.map(|key| value_to_string(&get_nested_value(item, key)))
+				.collect(),
+			ArrayRowShape::Arrays => {
+				if let Value::Array(row) = item {
+					row.iter().map(value_to_string).collect()
+				} else {
+					vec![value_to_string(item)]
+				}
+			}
+			ArrayRowShape::Primitives => vec![value_to_string(item)],
+		}
+	}
 }
 
 /// Recursively collect all keys from an object
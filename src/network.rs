@@ -14,4 +14,4 @@ pub mod tls;
 pub use oauth::{OAuthError, OAuthProvider, OAuthToken};
 pub use peer_discovery::{Peer, PeerRegistry};
 pub use peer_sync::SyncManager;
-pub use tls::{TlsError, create_tls_server_config};
+pub use tls::{TlsError, TlsSettings, build_server_config, create_tls_server_config};
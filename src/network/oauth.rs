@@ -2,13 +2,25 @@
 //!
 //! Provides secure token acquisition and validation for API authentication.
 //! Supports token caching with automatic refresh and scope validation.
+//!
+//! Incoming bearer tokens are validated either locally, as JWTs signed by a key
+//! from the identity provider's cached JWKS, or remotely via RFC 7662 token
+//! introspection. Issuer, audience, expiry and scopes are checked in both cases
+//! and the caller is returned as a [`Principal`].
 
 use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{
+	Algorithm, DecodingKey, Validation,
+	jwk::{Jwk, JwkSet},
+};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc, time::Instant};
 use thiserror::Error;
 use tokio::sync::RwLock;
 
+/// Minimum time between JWKS refreshes triggered by an unknown key ID
+const JWKS_MIN_REFRESH_SECS: u64 = 30;
+
 /// OAuth errors
 #[derive(Debug, Error)]
 pub enum OAuthError {
@@ -29,6 +41,12 @@ pub enum OAuthError {
 
 	#[error("HTTP error: {0}")]
 	HttpError(String),
+
+	#[error("Invalid token: {0}")]
+	InvalidToken(String),
+
+	#[error("Token validation is not configured")]
+	ValidationNotConfigured,
 }
 
 /// OAuth token response from authorization server
@@ -47,6 +65,103 @@ struct CachedToken {
 	expires_at: DateTime<Utc>,
 }
 
+/// Authenticated caller derived from a validated access token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+	/// Token subject (falls back to the client ID for client credentials tokens)
+	pub subject: String,
+	/// OAuth client the token was issued to
+	pub client_id: Option<String>,
+	/// Token issuer
+	pub issuer: Option<String>,
+	/// Scopes granted to the token
+	pub scopes: Vec<String>,
+	/// Token expiry
+	pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Principal {
+	/// Whether the token was granted `scope`
+	pub fn has_scope(&self, scope: &str) -> bool {
+		self.scopes.iter().any(|s| s == scope)
+	}
+
+	/// Fail with the first of `required_scopes` the token was not granted
+	pub fn require_scopes(&self, required_scopes: &[&str]) -> Result<(), OAuthError> {
+		match required_scopes.iter().find(|s| !self.has_scope(s)) {
+			Some(missing) => Err(OAuthError::InvalidScope(missing.to_string())),
+			None => Ok(()),
+		}
+	}
+}
+
+/// How incoming access tokens are validated
+#[derive(Debug, Clone, Default)]
+pub struct TokenValidation {
+	/// Expected `iss` claim
+	pub issuer: Option<String>,
+	/// Expected `aud` claim
+	pub audience: Option<String>,
+	/// JWKS endpoint used to verify JWT access tokens locally
+	pub jwks_uri: Option<String>,
+	/// RFC 7662 introspection endpoint used for opaque tokens
+	pub introspection_endpoint: Option<String>,
+	/// How long a fetched JWKS is reused before it is fetched again
+	pub jwks_cache_ttl_secs: u64,
+	/// Allowed clock skew when checking expiry
+	pub leeway_secs: u64,
+}
+
+/// JWKS with the time it was fetched
+#[derive(Debug, Clone)]
+struct CachedJwks {
+	keys: JwkSet,
+	/// `None` for keys supplied directly, which never expire
+	fetched_at: Option<Instant>,
+}
+
+/// JWT claims used to build a [`Principal`]
+#[derive(Debug, Deserialize)]
+struct AccessTokenClaims {
+	sub: Option<String>,
+	iss: Option<String>,
+	exp: Option<i64>,
+	client_id: Option<String>,
+	azp: Option<String>,
+	scope: Option<String>,
+	/// Scopes as an array (Okta, Azure AD)
+	scp: Option<Vec<String>>,
+}
+
+/// RFC 7662 token introspection response
+#[derive(Debug, Deserialize)]
+struct IntrospectionResponse {
+	active: bool,
+	scope: Option<String>,
+	client_id: Option<String>,
+	sub: Option<String>,
+	iss: Option<String>,
+	aud: Option<Audience>,
+	exp: Option<i64>,
+}
+
+/// `aud` may be a single string or an array
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Audience {
+	One(String),
+	Many(Vec<String>),
+}
+
+impl Audience {
+	fn contains(&self, audience: &str) -> bool {
+		match self {
+			Audience::One(aud) => aud == audience,
+			Audience::Many(auds) => auds.iter().any(|a| a == audience),
+		}
+	}
+}
+
 /// OAuth 2.0 Client Credentials Flow provider
 pub struct OAuthProvider {
 	client_id: String,
@@ -54,6 +169,8 @@ pub struct OAuthProvider {
 	token_endpoint: String,
 	scope: String,
 	cached_token: Arc<RwLock<Option<CachedToken>>>,
+	validation: TokenValidation,
+	jwks: Arc<RwLock<Option<CachedJwks>>>,
 	http_client: reqwest::Client,
 }
 
@@ -71,10 +188,27 @@ impl OAuthProvider {
 			token_endpoint,
 			scope,
 			cached_token: Arc::new(RwLock::new(None)),
+			validation: TokenValidation::default(),
+			jwks: Arc::new(RwLock::new(None)),
 			http_client: reqwest::Client::new(),
 		}
 	}
 
+	/// Configure how incoming access tokens are validated
+	pub fn with_validation(mut self, validation: TokenValidation) -> Self {
+		self.validation = validation;
+		self
+	}
+
+	/// Verify JWTs against a fixed key set instead of fetching `jwks_uri`
+	pub fn with_jwks(mut self, keys: JwkSet) -> Self {
+		self.jwks = Arc::new(RwLock::new(Some(CachedJwks {
+			keys,
+			fetched_at: None,
+		})));
+		self
+	}
+
 	/// Get a valid access token, refreshing if necessary
 	pub async fn get_access_token(&self) -> Result<String, OAuthError> {
 		// Check if cached token is still valid
@@ -148,6 +282,247 @@ impl OAuthProvider {
 		}
 		Ok(())
 	}
+
+	/// Validate an incoming access token and return the authenticated caller
+	///
+	/// JWTs are verified locally when a JWKS is available; other tokens are
+	/// introspected. The token must carry every scope in `required_scopes`.
+	pub async fn validate_token(
+		&self,
+		token: &str,
+		required_scopes: &[&str],
+	) -> Result<Principal, OAuthError> {
+		let has_jwks = self.validation.jwks_uri.is_some() || self.jwks.read().await.is_some();
+		let looks_like_jwt = token.split('.').count() == 3;
+
+		let principal = if has_jwks && looks_like_jwt {
+			self.verify_jwt(token).await?
+		} else if self.validation.introspection_endpoint.is_some() {
+			self.introspect(token).await?
+		} else if has_jwks {
+			return Err(OAuthError::InvalidToken("not a JWT".to_string()));
+		} else {
+			return Err(OAuthError::ValidationNotConfigured);
+		};
+
+		principal.require_scopes(required_scopes)?;
+		Ok(principal)
+	}
+
+	/// Verify a JWT signature and claims against the JWKS
+	async fn verify_jwt(&self, token: &str) -> Result<Principal, OAuthError> {
+		let header = jsonwebtoken::decode_header(token)
+			.map_err(|e| OAuthError::InvalidToken(e.to_string()))?;
+		let jwk = self.find_jwk(header.kid.as_deref()).await?;
+
+		// The key, not the token, decides which algorithm is acceptable
+		let algorithm = match jwk.common.key_algorithm {
+			Some(alg) => Algorithm::from_str(&alg.to_string())
+				.map_err(|e| OAuthError::InvalidToken(e.to_string()))?,
+			None => header.alg,
+		};
+		if header.alg != algorithm {
+			return Err(OAuthError::InvalidToken(format!(
+				"algorithm {:?} does not match key",
+				header.alg
+			)));
+		}
+
+		let key =
+			DecodingKey::from_jwk(&jwk).map_err(|e| OAuthError::InvalidToken(e.to_string()))?;
+		let mut validation = Validation::new(algorithm);
+		validation.leeway = self.validation.leeway_secs;
+		// `iss` and `aud` are only compared when present, so require the configured ones
+		let mut required = vec!["exp"];
+		if let Some(issuer) = &self.validation.issuer {
+			validation.set_issuer(&[issuer]);
+			required.push("iss");
+		}
+		match &self.validation.audience {
+			Some(audience) => {
+				validation.set_audience(&[audience]);
+				required.push("aud");
+			}
+			None => validation.validate_aud = false,
+		}
+		validation.set_required_spec_claims(&required);
+
+		let claims = jsonwebtoken::decode::<AccessTokenClaims>(token, &key, &validation)
+			.map_err(|e| match e.kind() {
+				jsonwebtoken::errors::ErrorKind::ExpiredSignature => OAuthError::TokenExpired,
+				_ => OAuthError::InvalidToken(e.to_string()),
+			})?
+			.claims;
+
+		let client_id = claims.client_id.or(claims.azp);
+		let scopes = match (claims.scope, claims.scp) {
+			(Some(scope), _) => split_scopes(&scope),
+			(None, Some(scp)) => scp,
+			(None, None) => Vec::new(),
+		};
+
+		Ok(Principal {
+			subject: subject_of(claims.sub, &client_id)?,
+			client_id,
+			issuer: claims.iss,
+			scopes,
+			expires_at: claims.exp.and_then(|exp| DateTime::from_timestamp(exp, 0)),
+		})
+	}
+
+	/// Find the signing key for `kid`, refreshing the JWKS when it is stale or the key is unknown
+	async fn find_jwk(&self, kid: Option<&str>) -> Result<Jwk, OAuthError> {
+		let ttl = std::time::Duration::from_secs(self.validation.jwks_cache_ttl_secs);
+		let cached = self.jwks.read().await.clone();
+
+		if let Some(cached) = &cached {
+			let fresh = cached.fetched_at.is_none_or(|at| at.elapsed() < ttl);
+			if fresh && let Some(jwk) = select_jwk(&cached.keys, kid) {
+				return Ok(jwk);
+			}
+			// Unknown key: allow a refresh for key rotation, but not on every request
+			if let Some(at) = cached.fetched_at
+				&& fresh && at.elapsed().as_secs() < JWKS_MIN_REFRESH_SECS
+			{
+				return Err(OAuthError::InvalidToken("unknown signing key".to_string()));
+			}
+		}
+
+		let Some(jwks_uri) = &self.validation.jwks_uri else {
+			return Err(OAuthError::InvalidToken("unknown signing key".to_string()));
+		};
+		let keys = match self.fetch_jwks(jwks_uri).await {
+			Ok(keys) => keys,
+			// Keep using stale keys while the identity provider is unreachable
+			Err(e) => {
+				return cached
+					.and_then(|cached| select_jwk(&cached.keys, kid))
+					.ok_or(e);
+			}
+		};
+		let jwk = select_jwk(&keys, kid);
+
+		*self.jwks.write().await = Some(CachedJwks {
+			keys,
+			fetched_at: Some(Instant::now()),
+		});
+
+		jwk.ok_or_else(|| OAuthError::InvalidToken("unknown signing key".to_string()))
+	}
+
+	/// Fetch the identity provider's JWKS
+	async fn fetch_jwks(&self, jwks_uri: &str) -> Result<JwkSet, OAuthError> {
+		let response = self
+			.http_client
+			.get(jwks_uri)
+			.timeout(std::time::Duration::from_secs(10))
+			.send()
+			.await
+			.map_err(|e| OAuthError::HttpError(e.to_string()))?;
+
+		if !response.status().is_success() {
+			return Err(OAuthError::HttpError(format!(
+				"JWKS request failed: HTTP {}",
+				response.status()
+			)));
+		}
+
+		response
+			.json::<JwkSet>()
+			.await
+			.map_err(|e| OAuthError::HttpError(format!("Invalid JWKS: {}", e)))
+	}
+
+	/// Validate an opaque token with RFC 7662 introspection
+	async fn introspect(&self, token: &str) -> Result<Principal, OAuthError> {
+		let endpoint = self
+			.validation
+			.introspection_endpoint
+			.as_deref()
+			.ok_or(OAuthError::ValidationNotConfigured)?;
+
+		let response = self
+			.http_client
+			.post(endpoint)
+			.basic_auth(&self.client_id, Some(&self.client_secret))
+			.form(&[("token", token), ("token_type_hint", "access_token")])
+			.timeout(std::time::Duration::from_secs(10))
+			.send()
+			.await
+			.map_err(|e| OAuthError::HttpError(e.to_string()))?;
+
+		if !response.status().is_success() {
+			return Err(OAuthError::HttpError(format!(
+				"Introspection request failed: HTTP {}",
+				response.status()
+			)));
+		}
+
+		let introspection = response
+			.json::<IntrospectionResponse>()
+			.await
+			.map_err(|e| OAuthError::InvalidTokenResponse(e.to_string()))?;
+
+		if !introspection.active {
+			return Err(OAuthError::InvalidToken("token is not active".to_string()));
+		}
+
+		let expires_at = introspection
+			.exp
+			.and_then(|exp| DateTime::from_timestamp(exp, 0));
+		if let Some(expires_at) = expires_at
+			&& expires_at + Duration::seconds(self.validation.leeway_secs as i64) < Utc::now()
+		{
+			return Err(OAuthError::TokenExpired);
+		}
+
+		if let Some(issuer) = &self.validation.issuer
+			&& introspection.iss.as_ref() != Some(issuer)
+		{
+			return Err(OAuthError::InvalidToken("issuer mismatch".to_string()));
+		}
+
+		if let Some(audience) = &self.validation.audience
+			&& !introspection
+				.aud
+				.as_ref()
+				.is_some_and(|aud| aud.contains(audience))
+		{
+			return Err(OAuthError::InvalidToken("audience mismatch".to_string()));
+		}
+
+		Ok(Principal {
+			subject: subject_of(introspection.sub, &introspection.client_id)?,
+			client_id: introspection.client_id,
+			issuer: introspection.iss,
+			scopes: introspection
+				.scope
+				.as_deref()
+				.map(split_scopes)
+				.unwrap_or_default(),
+			expires_at,
+		})
+	}
+}
+
+/// Pick the key matching `kid`, or the only signing key when the token has no `kid`
+fn select_jwk(keys: &JwkSet, kid: Option<&str>) -> Option<Jwk> {
+	match kid {
+		Some(kid) => keys.find(kid).cloned(),
+		None if keys.keys.len() == 1 => keys.keys.first().cloned(),
+		None => None,
+	}
+}
+
+/// Split a space-delimited scope string
+fn split_scopes(scope: &str) -> Vec<String> {
+	scope.split_whitespace().map(str::to_string).collect()
+}
+
+/// The token subject, falling back to the client ID
+fn subject_of(sub: Option<String>, client_id: &Option<String>) -> Result<String, OAuthError> {
+	sub.or_else(|| client_id.clone())
+		.ok_or_else(|| OAuthError::InvalidToken("token has no subject".to_string()))
 }
 
 #[cfg(test)]
@@ -190,4 +565,225 @@ mod tests {
 
 		assert!(provider.validate_scope(&["admin"]).is_err());
 	}
+
+	/// RSA signing key shared by the mock identity provider tests
+	static SIGNING_KEY: once_cell::sync::Lazy<openssl::rsa::Rsa<openssl::pkey::Private>> =
+		once_cell::sync::Lazy::new(|| openssl::rsa::Rsa::generate(2048).expect("rsa key"));
+
+	const ISSUER: &str = "https://idp.test";
+	const AUDIENCE: &str = "dumptruck";
+
+	fn base64url(bytes: &[u8]) -> String {
+		openssl::base64::encode_block(bytes)
+			.trim_end_matches('=')
+			.replace('+', "-")
+			.replace('/', "_")
+	}
+
+	fn test_jwks() -> serde_json::Value {
+		serde_json::json!({
+			"keys": [{
+				"kty": "RSA",
+				"kid": "test-key",
+				"alg": "RS256",
+				"use": "sig",
+				"n": base64url(&SIGNING_KEY.n().to_vec()),
+				"e": base64url(&SIGNING_KEY.e().to_vec()),
+			}]
+		})
+	}
+
+	fn sign(kid: &str, claims: serde_json::Value) -> String {
+		let mut header = jsonwebtoken::Header::new(Algorithm::RS256);
+		header.kid = Some(kid.to_string());
+		let key = jsonwebtoken::EncodingKey::from_rsa_pem(
+			&SIGNING_KEY.private_key_to_pem().expect("pem"),
+		)
+		.expect("encoding key");
+		jsonwebtoken::encode(&header, &claims, &key).expect("token")
+	}
+
+	fn claims(aud: &str, iss: &str, exp_offset: i64) -> serde_json::Value {
+		serde_json::json!({
+			"sub": "analyst@example.com",
+			"client_id": "cli",
+			"iss": iss,
+			"aud": aud,
+			"exp": Utc::now().timestamp() + exp_offset,
+			"scope": "read:dumps write:dumps",
+		})
+	}
+
+	/// Serve a JWKS and an introspection endpoint on a local port
+	async fn mock_idp() -> String {
+		use axum::{Form, Json, Router, routing::get, routing::post};
+		use std::collections::HashMap;
+
+		let app = Router::new()
+			.route("/jwks", get(|| async { Json(test_jwks()) }))
+			.route(
+				"/introspect",
+				post(|Form(form): Form<HashMap<String, String>>| async move {
+					Json(match form.get("token").map(String::as_str) {
+						Some("opaque-good") => serde_json::json!({
+							"active": true,
+							"sub": "svc-ingest",
+							"client_id": "svc-ingest",
+							"iss": ISSUER,
+							"aud": [AUDIENCE, "other"],
+							"exp": Utc::now().timestamp() + 300,
+							"scope": "read:dumps",
+						}),
+						_ => serde_json::json!({ "active": false }),
+					})
+				}),
+			);
+
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+			.await
+			.expect("bind");
+		let addr = listener.local_addr().expect("addr");
+		tokio::spawn(async move {
+			let _ = axum::serve(listener, app).await;
+		});
+		format!("http://{}", addr)
+	}
+
+	fn validating_provider(idp: &str) -> OAuthProvider {
+		OAuthProvider::new(
+			"client_id".to_string(),
+			"client_secret".to_string(),
+			format!("{}/token", idp),
+			"read".to_string(),
+		)
+		.with_validation(TokenValidation {
+			issuer: Some(ISSUER.to_string()),
+			audience: Some(AUDIENCE.to_string()),
+			jwks_uri: Some(format!("{}/jwks", idp)),
+			introspection_endpoint: Some(format!("{}/introspect", idp)),
+			jwks_cache_ttl_secs: 3600,
+			leeway_secs: 0,
+		})
+	}
+
+	#[tokio::test]
+	async fn test_validate_jwt_against_jwks() {
+		let idp = mock_idp().await;
+		let provider = validating_provider(&idp);
+		let token = sign("test-key", claims(AUDIENCE, ISSUER, 300));
+
+		let principal = provider
+			.validate_token(&token, &["read:dumps"])
+			.await
+			.expect("valid token");
+		assert_eq!(principal.subject, "analyst@example.com");
+		assert_eq!(principal.client_id.as_deref(), Some("cli"));
+		assert!(principal.has_scope("write:dumps"));
+
+		assert!(matches!(
+			provider.validate_token(&token, &["admin"]).await,
+			Err(OAuthError::InvalidScope(scope)) if scope == "admin"
+		));
+	}
+
+	#[tokio::test]
+	async fn test_validate_jwt_rejects_bad_claims() {
+		let idp = mock_idp().await;
+		let provider = validating_provider(&idp);
+
+		for token in [
+			sign("test-key", claims("someone-else", ISSUER, 300)),
+			sign("test-key", claims(AUDIENCE, "https://evil.test", 300)),
+			sign("rotated-key", claims(AUDIENCE, ISSUER, 300)),
+		] {
+			assert!(matches!(
+				provider.validate_token(&token, &[]).await,
+				Err(OAuthError::InvalidToken(_))
+			));
+		}
+
+		let expired = sign("test-key", claims(AUDIENCE, ISSUER, -300));
+		assert!(matches!(
+			provider.validate_token(&expired, &[]).await,
+			Err(OAuthError::TokenExpired)
+		));
+	}
+
+	#[tokio::test]
+	async fn test_validate_jwt_requires_configured_issuer_and_audience() {
+		let idp = mock_idp().await;
+		let provider = validating_provider(&idp);
+
+		for missing in ["iss", "aud"] {
+			let mut claims = claims(AUDIENCE, ISSUER, 300);
+			claims.as_object_mut().expect("object").remove(missing);
+			let token = sign("test-key", claims);
+			assert!(
+				matches!(
+					provider.validate_token(&token, &[]).await,
+					Err(OAuthError::InvalidToken(_))
+				),
+				"token without {} was accepted",
+				missing
+			);
+		}
+
+		// Neither claim is needed when no issuer or audience is configured
+		let provider = OAuthProvider::new(
+			"client_id".to_string(),
+			"client_secret".to_string(),
+			format!("{}/token", idp),
+			"read".to_string(),
+		)
+		.with_validation(TokenValidation {
+			issuer: None,
+			audience: None,
+			jwks_uri: Some(format!("{}/jwks", idp)),
+			introspection_endpoint: None,
+			jwks_cache_ttl_secs: 3600,
+			leeway_secs: 0,
+		});
+		let mut claims = claims(AUDIENCE, ISSUER, 300);
+		let object = claims.as_object_mut().expect("object");
+		object.remove("iss");
+		object.remove("aud");
+		assert!(
+			provider
+				.validate_token(&sign("test-key", claims), &[])
+				.await
+				.is_ok()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_validate_opaque_token_via_introspection() {
+		let idp = mock_idp().await;
+		let provider = validating_provider(&idp);
+
+		let principal = provider
+			.validate_token("opaque-good", &["read:dumps"])
+			.await
+			.expect("active token");
+		assert_eq!(principal.subject, "svc-ingest");
+
+		assert!(matches!(
+			provider.validate_token("opaque-revoked", &[]).await,
+			Err(OAuthError::InvalidToken(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_validate_token_requires_configuration() {
+		let provider = OAuthProvider::new(
+			"client_id".to_string(),
+			"client_secret".to_string(),
+			"https://oauth.example.com/token".to_string(),
+			"read".to_string(),
+		);
+
+		assert!(matches!(
+			provider.validate_token("anything", &[]).await,
+			Err(OAuthError::ValidationNotConfigured)
+		));
+	}
 }
@@ -1,11 +1,15 @@
 //! TLS 1.3+ configuration with hardened security settings.
 //!
 //! Enforces TLS 1.3+ only, certificate validation, and ALPN negotiation for HTTP/2.
+//! Optionally verifies client certificates (mutual TLS) against a CA bundle.
 
+use rustls::RootCertStore;
 use rustls::pki_types::{CertificateDer, PrivateKeyDer};
-use rustls::server::ServerConfig;
+use rustls::server::{ServerConfig, WebPkiClientVerifier};
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
 use thiserror::Error;
 
 /// Initialize rustls crypto provider (must be called once at startup)
@@ -95,10 +99,66 @@ fn load_private_key(key_path: &Path) -> Result<PrivateKeyDer<'static>, TlsError>
 	))
 }
 
+/// Files and client authentication policy for the server's TLS configuration
+#[derive(Debug, Clone, Default)]
+pub struct TlsSettings {
+	pub cert_path: String,
+	pub key_path: String,
+	/// CA bundle for client certificates; `None` disables mutual TLS
+	pub client_ca_path: Option<String>,
+	/// Accept clients without a certificate when mutual TLS is enabled
+	pub client_auth_optional: bool,
+}
+
+/// Modification times of the TLS files, used to detect when they change
+pub type TlsFileStamp = Vec<Option<SystemTime>>;
+
+impl TlsSettings {
+	/// Current modification times of the certificate, key and client CA files
+	pub fn file_stamp(&self) -> TlsFileStamp {
+		[
+			Some(&self.cert_path),
+			Some(&self.key_path),
+			self.client_ca_path.as_ref(),
+		]
+		.into_iter()
+		.flatten()
+		.map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+		.collect()
+	}
+}
+
+/// Load the CA certificates that client certificates must chain to
+fn load_client_roots(ca_path: &Path) -> Result<RootCertStore, TlsError> {
+	let mut roots = RootCertStore::empty();
+	for cert in load_certificates(ca_path)? {
+		roots
+			.add(cert)
+			.map_err(|e| TlsError::InvalidCertificate(format!("{}: {}", ca_path.display(), e)))?;
+	}
+
+	if roots.is_empty() {
+		return Err(TlsError::InvalidCertificate(
+			"No certificates found in client CA file".to_string(),
+		));
+	}
+
+	Ok(roots)
+}
+
 /// Create TLS 1.3+ server configuration
 pub fn create_tls_server_config(cert_path: &str, key_path: &str) -> Result<ServerConfig, TlsError> {
-	let cert_path = Path::new(cert_path);
-	let key_path = Path::new(key_path);
+	build_server_config(&TlsSettings {
+		cert_path: cert_path.to_string(),
+		key_path: key_path.to_string(),
+		..Default::default()
+	})
+}
+
+/// Create TLS 1.3+ server configuration, verifying client certificates if configured
+pub fn build_server_config(settings: &TlsSettings) -> Result<ServerConfig, TlsError> {
+	let cert_path = Path::new(&settings.cert_path);
+	let key_path = Path::new(&settings.key_path);
 
 	// Load certificate and private key
 	let certs = load_certificates(cert_path)?;
@@ -111,8 +171,25 @@ pub fn create_tls_server_config(cert_path: &str, key_path: &str) -> Result<Serve
 	}
 
 	// Create TLS config - rustls defaults to TLS 1.3+ only
-	let mut config = ServerConfig::builder()
-		.with_no_client_auth()
+	let builder = ServerConfig::builder();
+	let builder = match &settings.client_ca_path {
+		Some(ca_path) => {
+			let roots = load_client_roots(Path::new(ca_path))?;
+			let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+			let verifier = if settings.client_auth_optional {
+				verifier.allow_unauthenticated()
+			} else {
+				verifier
+			};
+			builder.with_client_cert_verifier(
+				verifier
+					.build()
+					.map_err(|e| TlsError::ConfigurationError(e.to_string()))?,
+			)
+		}
+		None => builder.with_no_client_auth(),
+	};
+	let mut config = builder
 		.with_single_cert(certs, key)
 		.map_err(|e| TlsError::ConfigurationError(e.to_string()))?;
 
@@ -138,4 +215,113 @@ mod tests {
 		let error = TlsError::ConfigurationError("Test error".to_string());
 		assert_eq!(error.to_string(), "Failed to create TLS config: Test error");
 	}
+
+	/// Write a self-signed EC certificate and key (PEM) to `dir`, returning their paths
+	fn write_self_signed(dir: &Path, name: &str) -> (String, String) {
+		use openssl::{
+			asn1::Asn1Time,
+			bn::BigNum,
+			ec::{EcGroup, EcKey},
+			hash::MessageDigest,
+			nid::Nid,
+			pkey::PKey,
+			x509::{X509, X509NameBuilder, extension::BasicConstraints},
+		};
+
+		let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).expect("group");
+		let key = PKey::from_ec_key(EcKey::generate(&group).expect("ec key")).expect("key");
+		let mut subject = X509NameBuilder::new().expect("name");
+		subject.append_entry_by_text("CN", name).expect("cn");
+		let subject = subject.build();
+
+		let mut cert = X509::builder().expect("builder");
+		cert.set_version(2).expect("version");
+		cert.set_serial_number(
+			&BigNum::from_u32(1)
+				.expect("bn")
+				.to_asn1_integer()
+				.expect("serial"),
+		)
+		.expect("serial");
+		cert.set_subject_name(&subject).expect("subject");
+		cert.set_issuer_name(&subject).expect("issuer");
+		cert.set_pubkey(&key).expect("pubkey");
+		cert.set_not_before(&Asn1Time::days_from_now(0).expect("time"))
+			.expect("not before");
+		cert.set_not_after(&Asn1Time::days_from_now(1).expect("time"))
+			.expect("not after");
+		cert.append_extension(BasicConstraints::new().critical().ca().build().expect("bc"))
+			.expect("extension");
+		cert.sign(&key, MessageDigest::sha256()).expect("sign");
+
+		let cert_path = dir.join(format!("{}.crt", name));
+		let key_path = dir.join(format!("{}.key", name));
+		fs::write(&cert_path, cert.build().to_pem().expect("pem")).expect("write cert");
+		fs::write(&key_path, key.private_key_to_pem_pkcs8().expect("pem")).expect("write key");
+		(
+			cert_path.to_string_lossy().to_string(),
+			key_path.to_string_lossy().to_string(),
+		)
+	}
+
+	#[test]
+	fn test_build_server_config_with_client_auth() {
+		init_crypto_provider();
+		let dir = tempfile::tempdir().expect("tempdir");
+		let (cert_path, key_path) = write_self_signed(dir.path(), "server");
+		let (ca_path, _) = write_self_signed(dir.path(), "client-ca");
+
+		create_tls_server_config(&cert_path, &key_path).expect("server config");
+
+		let mut settings = TlsSettings {
+			cert_path,
+			key_path,
+			client_ca_path: Some(ca_path),
+			client_auth_optional: false,
+		};
+		let config = build_server_config(&settings).expect("mtls config");
+		assert_eq!(config.alpn_protocols[0], b"h2".to_vec());
+
+		settings.client_auth_optional = true;
+		build_server_config(&settings).expect("optional mtls config");
+
+		// A CA file without certificates must not silently disable client auth
+		let empty_ca = dir.path().join("empty.pem");
+		fs::write(&empty_ca, "").expect("write");
+		settings.client_ca_path = Some(empty_ca.to_string_lossy().to_string());
+		assert!(matches!(
+			build_server_config(&settings),
+			Err(TlsError::InvalidCertificate(_))
+		));
+
+		settings.client_ca_path =
+			Some(dir.path().join("missing.pem").to_string_lossy().to_string());
+		assert!(matches!(
+			build_server_config(&settings),
+			Err(TlsError::CertificateReadError(_))
+		));
+	}
+
+	#[test]
+	fn test_file_stamp_tracks_changes() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let (cert_path, key_path) = write_self_signed(dir.path(), "server");
+		let settings = TlsSettings {
+			cert_path: cert_path.clone(),
+			key_path,
+			..Default::default()
+		};
+
+		let before = settings.file_stamp();
+		assert_eq!(before.len(), 2);
+		assert!(before.iter().all(Option::is_some));
+
+		let file = fs::File::options()
+			.write(true)
+			.open(&cert_path)
+			.expect("open");
+		file.set_modified(SystemTime::now() + std::time::Duration::from_secs(60))
+			.expect("touch");
+		assert_ne!(settings.file_stamp(), before);
+	}
 }
@@ -6,25 +6,29 @@
 mod addresses;
 mod aliases;
 mod breaches;
+mod credentials;
 mod metadata;
 mod rows;
 mod schema;
 mod similarity;
 
 pub use addresses::{
-	get_credentials_for_address, insert_address_alternate, insert_address_credential_canonical,
-	insert_canonical_address, lookup_canonical_by_alternate,
+	CredentialSighting, canonical_address_exists, get_address_alternates, get_address_seen_range,
+	get_credential_sightings, get_credentials_for_address, insert_address_alternate,
+	insert_address_credential_canonical, insert_canonical_address, lookup_canonical_by_alternate,
 };
 pub use aliases::{get_alias_relationships, insert_alias_relationship};
 pub use breaches::{
-	BreachRecord, get_address_neighbors, insert_address_breach, record_address_cooccurrence,
+	BreachRecord, StoredBreach, get_address_breaches, get_address_neighbors, insert_address_breach,
+	record_address_cooccurrence,
 };
+pub use credentials::{DigestAlgorithm, get_credential_range, insert_credential_digest};
 pub use metadata::{
 	CustodyRecord, get_anomalies_for_file, get_high_risk_anomalies, insert_anomaly_score,
 	insert_custody_record, insert_file_metadata,
 };
 pub use rows::RowData;
-pub use schema::create_schema;
+pub use schema::{SCHEMA_VERSION, create_schema, schema_version};
 pub use similarity::{
 	cosine_similarity, find_duplicate_address, find_similar_addresses, update_address_embedding,
 };
@@ -37,6 +41,8 @@ use std::{
 
 use rusqlite::Connection;
 
+use crate::core::metrics;
+
 /// Trait for storage backends used in examples.
 pub trait StorageAdapter {
 	fn store_row(&mut self, row: &[String]) -> std::io::Result<()>;
@@ -101,6 +107,36 @@ pub trait StorageAdapter {
 		Ok(vec![])
 	}
 
+	/// Check whether a canonical address has been recorded.
+	fn canonical_address_exists(&mut self, canonical_hash: &str) -> std::io::Result<bool> {
+		let _ = canonical_hash;
+		Ok(false)
+	}
+
+	/// Get the alternate hashes that map to a canonical address.
+	fn get_address_alternates(&mut self, canonical_hash: &str) -> std::io::Result<Vec<String>> {
+		let _ = canonical_hash;
+		Ok(vec![])
+	}
+
+	/// Get the (first_seen, last_seen) timestamps of a canonical address.
+	fn get_address_seen_range(
+		&mut self,
+		canonical_hash: &str,
+	) -> std::io::Result<Option<(String, String)>> {
+		let _ = canonical_hash;
+		Ok(None)
+	}
+
+	/// Get every canonical address a credential hash has been seen with.
+	fn get_credential_sightings(
+		&mut self,
+		credential_hash: &str,
+	) -> std::io::Result<Vec<CredentialSighting>> {
+		let _ = credential_hash;
+		Ok(vec![])
+	}
+
 	/// Record a co-occurrence edge between two canonical addresses.
 	/// Addresses should be in canonical order (hash_1 < hash_2) to avoid duplicates.
 	/// Returns Ok(true) if newly inserted, Ok(false) if already existed (count incremented).
@@ -167,6 +203,33 @@ pub trait StorageAdapter {
 		Ok(false)
 	}
 
+	/// Count an occurrence of a plaintext credential by its SHA-1 and NTLM digests for range queries.
+	fn insert_credential_digest(
+		&mut self,
+		credential_hash: &str,
+		sha1_hash: &str,
+		ntlm_hash: &str,
+	) -> std::io::Result<bool> {
+		let _ = (credential_hash, sha1_hash, ntlm_hash);
+		Ok(false)
+	}
+
+	/// Get (suffix, occurrence_count) for every credential digest starting with `prefix`.
+	fn get_credential_range(
+		&mut self,
+		prefix: &str,
+		algorithm: DigestAlgorithm,
+	) -> std::io::Result<Vec<(String, i64)>> {
+		let _ = (prefix, algorithm);
+		Ok(vec![])
+	}
+
+	/// Get breach data stored for a canonical address.
+	fn get_address_breaches(&mut self, canonical_hash: &str) -> std::io::Result<Vec<StoredBreach>> {
+		let _ = canonical_hash;
+		Ok(vec![])
+	}
+
 	// ========== Stage 13: Storage Enhancement Methods ==========
 
 	/// Insert file metadata (Stage 1: Evidence Preservation)
@@ -254,6 +317,10 @@ impl SqliteStorage {
 	pub fn new(db_path: &str, dataset: Option<String>) -> std::io::Result<Self> {
 		let conn = Connection::open(db_path).map_err(io::Error::other)?;
 
+		// Server workers open their own connections; wait on locks instead of failing fast
+		conn.busy_timeout(std::time::Duration::from_secs(5))
+			.map_err(io::Error::other)?;
+
 		// Create schema if not exists
 		schema::create_schema(&conn)?;
 
@@ -450,7 +517,7 @@ impl StorageAdapter for SqliteStorage {
 			fields_text: &fields_text,
 		};
 
-		rows::store_row(&self.conn, &data)
+		metrics::observe_sqlite_write("row", || rows::store_row(&self.conn, &data))
 	}
 
 	fn contains_hash(&mut self, hash: &str) -> std::io::Result<bool> {
@@ -517,8 +584,8 @@ impl StorageAdapter for SqliteStorage {
 			serde_json::Value::String(cred_hash.to_string()),
 		]);
 		let fields_text = serde_json::to_string(&fields).map_err(io::Error::other)?;
-		self.conn
-			.execute(
+		metrics::observe_sqlite_write("address_credential_row", || {
+			self.conn.execute(
 				"INSERT INTO normalized_rows (dataset, event_type, address_hash, credential_hash, \
 				 file_id, fields) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
 				rusqlite::params![
@@ -530,7 +597,8 @@ impl StorageAdapter for SqliteStorage {
 					&fields_text,
 				],
 			)
-			.map_err(io::Error::other)?;
+		})
+		.map_err(io::Error::other)?;
 		Ok(())
 	}
 
@@ -540,12 +608,14 @@ impl StorageAdapter for SqliteStorage {
 		address_text: &str,
 		normalized_form: &str,
 	) -> std::io::Result<bool> {
-		addresses::insert_canonical_address(
-			&self.conn,
-			canonical_hash,
-			address_text,
-			normalized_form,
-		)
+		metrics::observe_sqlite_write("canonical_address", || {
+			addresses::insert_canonical_address(
+				&self.conn,
+				canonical_hash,
+				address_text,
+				normalized_form,
+			)
+		})
 	}
 
 	fn insert_address_alternate(
@@ -554,12 +624,14 @@ impl StorageAdapter for SqliteStorage {
 		alternate_hash: &str,
 		alternate_form: &str,
 	) -> std::io::Result<bool> {
-		addresses::insert_address_alternate(
-			&self.conn,
-			canonical_hash,
-			alternate_hash,
-			alternate_form,
-		)
+		metrics::observe_sqlite_write("address_alternate", || {
+			addresses::insert_address_alternate(
+				&self.conn,
+				canonical_hash,
+				alternate_hash,
+				alternate_form,
+			)
+		})
 	}
 
 	fn lookup_canonical_by_alternate(
@@ -574,7 +646,13 @@ impl StorageAdapter for SqliteStorage {
 		canonical_hash: &str,
 		credential_hash: &str,
 	) -> std::io::Result<bool> {
-		addresses::insert_address_credential_canonical(&self.conn, canonical_hash, credential_hash)
+		metrics::observe_sqlite_write("address_credential", || {
+			addresses::insert_address_credential_canonical(
+				&self.conn,
+				canonical_hash,
+				credential_hash,
+			)
+		})
 	}
 
 	fn get_credentials_for_address(
@@ -584,6 +662,28 @@ impl StorageAdapter for SqliteStorage {
 		addresses::get_credentials_for_address(&self.conn, canonical_hash)
 	}
 
+	fn canonical_address_exists(&mut self, canonical_hash: &str) -> std::io::Result<bool> {
+		addresses::canonical_address_exists(&self.conn, canonical_hash)
+	}
+
+	fn get_address_alternates(&mut self, canonical_hash: &str) -> std::io::Result<Vec<String>> {
+		addresses::get_address_alternates(&self.conn, canonical_hash)
+	}
+
+	fn get_address_seen_range(
+		&mut self,
+		canonical_hash: &str,
+	) -> std::io::Result<Option<(String, String)>> {
+		addresses::get_address_seen_range(&self.conn, canonical_hash)
+	}
+
+	fn get_credential_sightings(
+		&mut self,
+		credential_hash: &str,
+	) -> std::io::Result<Vec<CredentialSighting>> {
+		addresses::get_credential_sightings(&self.conn, credential_hash)
+	}
+
 	fn record_address_cooccurrence(
 		&mut self,
 		canonical_hash_1: &str,
@@ -595,7 +695,9 @@ impl StorageAdapter for SqliteStorage {
 		} else {
 			(canonical_hash_2, canonical_hash_1)
 		};
-		breaches::record_address_cooccurrence(&self.conn, h1, h2)
+		metrics::observe_sqlite_write("address_cooccurrence", || {
+			breaches::record_address_cooccurrence(&self.conn, h1, h2)
+		})
 	}
 
 	fn get_address_neighbors(
@@ -654,7 +756,32 @@ impl StorageAdapter for SqliteStorage {
 	}
 
 	fn insert_address_breach(&mut self, record: &BreachRecord<'_>) -> std::io::Result<bool> {
-		breaches::insert_address_breach(&self.conn, record)
+		metrics::observe_sqlite_write("address_breach", || {
+			breaches::insert_address_breach(&self.conn, record)
+		})
+	}
+
+	fn get_address_breaches(&mut self, canonical_hash: &str) -> std::io::Result<Vec<StoredBreach>> {
+		breaches::get_address_breaches(&self.conn, canonical_hash)
+	}
+
+	fn insert_credential_digest(
+		&mut self,
+		credential_hash: &str,
+		sha1_hash: &str,
+		ntlm_hash: &str,
+	) -> std::io::Result<bool> {
+		metrics::observe_sqlite_write("credential_digest", || {
+			credentials::insert_credential_digest(&self.conn, credential_hash, sha1_hash, ntlm_hash)
+		})
+	}
+
+	fn get_credential_range(
+		&mut self,
+		prefix: &str,
+		algorithm: DigestAlgorithm,
+	) -> std::io::Result<Vec<(String, i64)>> {
+		credentials::get_credential_range(&self.conn, prefix, algorithm)
 	}
 
 	fn insert_file_metadata(
@@ -664,17 +791,21 @@ impl StorageAdapter for SqliteStorage {
 		sha256_hash: &str,
 		file_size: i64,
 	) -> std::io::Result<bool> {
-		metadata::insert_file_metadata(
-			&self.conn,
-			file_id,
-			original_filename,
-			sha256_hash,
-			file_size,
-		)
+		metrics::observe_sqlite_write("file_metadata", || {
+			metadata::insert_file_metadata(
+				&self.conn,
+				file_id,
+				original_filename,
+				sha256_hash,
+				file_size,
+			)
+		})
 	}
 
 	fn insert_custody_record(&mut self, record: &CustodyRecord<'_>) -> std::io::Result<bool> {
-		metadata::insert_custody_record(&self.conn, record)
+		metrics::observe_sqlite_write("custody_record", || {
+			metadata::insert_custody_record(&self.conn, record)
+		})
 	}
 
 	fn insert_alias_relationship(
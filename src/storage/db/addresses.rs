@@ -3,6 +3,7 @@
 use std::io;
 
 use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
 
 /// Insert a canonical address.
 pub fn insert_canonical_address(
@@ -88,3 +89,95 @@ pub fn get_credentials_for_address(
 
 	Ok(creds)
 }
+
+/// Check whether a canonical address exists.
+pub fn canonical_address_exists(conn: &Connection, canonical_hash: &str) -> io::Result<bool> {
+	conn.query_row(
+		"SELECT 1 FROM canonical_addresses WHERE canonical_hash = ?1",
+		rusqlite::params![canonical_hash],
+		|_| Ok(()),
+	)
+	.optional()
+	.map(|found| found.is_some())
+	.map_err(io::Error::other)
+}
+
+/// Get the alternate hashes recorded for a canonical address.
+pub fn get_address_alternates(conn: &Connection, canonical_hash: &str) -> io::Result<Vec<String>> {
+	let mut stmt = conn
+		.prepare(
+			"SELECT alternate_hash FROM address_alternates WHERE canonical_hash = ?1 ORDER BY \
+			 created_at, alternate_hash",
+		)
+		.map_err(io::Error::other)?;
+
+	let alternates = stmt
+		.query_map(rusqlite::params![canonical_hash], |row| row.get(0))
+		.map_err(io::Error::other)?
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(io::Error::other)?;
+
+	Ok(alternates)
+}
+
+/// Get when a canonical address was first and last seen, across the address,
+/// its alternates and its credentials.
+pub fn get_address_seen_range(
+	conn: &Connection,
+	canonical_hash: &str,
+) -> io::Result<Option<(String, String)>> {
+	conn.query_row(
+		"SELECT MIN(first_seen), MAX(last_seen) FROM ( \
+		 SELECT created_at AS first_seen, created_at AS last_seen FROM canonical_addresses \
+		 WHERE canonical_hash = ?1 \
+		 UNION ALL SELECT created_at, created_at FROM address_alternates WHERE canonical_hash = ?1 \
+		 UNION ALL SELECT first_seen_at, last_seen_at FROM address_credentials \
+		 WHERE canonical_hash = ?1)",
+		rusqlite::params![canonical_hash],
+		|row| {
+			Ok(row
+				.get::<_, Option<String>>(0)?
+				.zip(row.get::<_, Option<String>>(1)?))
+		},
+	)
+	.map_err(io::Error::other)
+}
+
+/// A canonical address a credential was seen with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CredentialSighting {
+	/// Canonical hash of the address
+	pub canonical_hash: String,
+	/// Number of times the pair was recorded
+	pub occurrence_count: i64,
+	pub first_seen: String,
+	pub last_seen: String,
+}
+
+/// Get every canonical address a credential hash was seen with.
+pub fn get_credential_sightings(
+	conn: &Connection,
+	credential_hash: &str,
+) -> io::Result<Vec<CredentialSighting>> {
+	let mut stmt = conn
+		.prepare(
+			"SELECT canonical_hash, occurrence_count, first_seen_at, last_seen_at FROM \
+			 address_credentials WHERE credential_hash = ?1 ORDER BY first_seen_at, canonical_hash",
+		)
+		.map_err(io::Error::other)?;
+
+	let sightings = stmt
+		.query_map(rusqlite::params![credential_hash], |row| {
+			Ok(CredentialSighting {
+				canonical_hash: row.get(0)?,
+				occurrence_count: row.get(1)?,
+				first_seen: row.get(2)?,
+				last_seen: row.get(3)?,
+			})
+		})
+		.map_err(io::Error::other)?
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(io::Error::other)?;
+
+	Ok(sightings)
+}
@@ -1,7 +1,9 @@
 //! Co-occurrence and breach data management.
 
 use rusqlite::Connection;
+use serde::Serialize;
 use std::io;
+use utoipa::ToSchema;
 
 /// Parameters for inserting a breach record into the database.
 #[derive(Debug, Clone)]
@@ -93,3 +95,51 @@ pub fn insert_address_breach(conn: &Connection, record: &BreachRecord<'_>) -> io
 
 	Ok(rows > 0)
 }
+
+/// Breach data stored for a canonical address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct StoredBreach {
+	pub breach_name: String,
+	pub breach_title: Option<String>,
+	pub breach_domain: Option<String>,
+	pub breach_date: Option<String>,
+	pub pwn_count: Option<i32>,
+	pub is_verified: bool,
+	pub is_fabricated: bool,
+	pub is_sensitive: bool,
+	pub is_retired: bool,
+}
+
+/// Get breach data for canonical address.
+pub fn get_address_breaches(
+	conn: &Connection,
+	canonical_hash: &str,
+) -> io::Result<Vec<StoredBreach>> {
+	let mut stmt = conn
+		.prepare(
+			"SELECT breach_name, breach_title, breach_domain, breach_date, pwn_count, \
+			 is_verified, is_fabricated, is_sensitive, is_retired FROM address_breaches \
+			 WHERE canonical_hash = ?1 ORDER BY breach_date DESC, breach_name",
+		)
+		.map_err(io::Error::other)?;
+
+	let breaches = stmt
+		.query_map(rusqlite::params![canonical_hash], |row| {
+			Ok(StoredBreach {
+				breach_name: row.get(0)?,
+				breach_title: row.get(1)?,
+				breach_domain: row.get(2)?,
+				breach_date: row.get(3)?,
+				pwn_count: row.get(4)?,
+				is_verified: row.get(5)?,
+				is_fabricated: row.get(6)?,
+				is_sensitive: row.get(7)?,
+				is_retired: row.get(8)?,
+			})
+		})
+		.map_err(io::Error::other)?
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(io::Error::other)?;
+
+	Ok(breaches)
+}
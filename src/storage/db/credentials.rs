@@ -0,0 +1,70 @@
+//! Credential digests for k-anonymity range queries.
+
+use std::io;
+
+use rusqlite::Connection;
+
+/// Digest algorithms that can be range-queried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+	Sha1,
+	Ntlm,
+}
+
+/// Record an occurrence of a plaintext credential by its SHA-1 and NTLM digests.
+///
+/// Digests are uppercase hex, keyed by SHA-1, and linked to the credential hash
+/// used by the rest of the schema. Each call counts one more occurrence of the
+/// exact credential; returns true the first time it is seen.
+pub fn insert_credential_digest(
+	conn: &Connection,
+	credential_hash: &str,
+	sha1_hash: &str,
+	ntlm_hash: &str,
+) -> io::Result<bool> {
+	let occurrences: i64 = conn
+		.query_row(
+			"INSERT INTO credential_digests (sha1_hash, ntlm_hash, credential_hash) \
+			 VALUES (?1, ?2, ?3) ON CONFLICT (sha1_hash) DO UPDATE SET occurrence_count = \
+			 occurrence_count + 1 RETURNING occurrence_count",
+			rusqlite::params![sha1_hash, ntlm_hash, credential_hash],
+			|row| row.get(0),
+		)
+		.map_err(io::Error::other)?;
+
+	Ok(occurrences == 1)
+}
+
+/// Get the suffixes and occurrence counts of every digest starting with `prefix`.
+///
+/// `prefix` must be uppercase hex. The count is how often that exact credential
+/// was seen; `credential_hash` is shared by credentials that only differ in case
+/// or Unicode form, so it is not used for counting.
+pub fn get_credential_range(
+	conn: &Connection,
+	prefix: &str,
+	algorithm: DigestAlgorithm,
+) -> io::Result<Vec<(String, i64)>> {
+	let column = match algorithm {
+		DigestAlgorithm::Sha1 => "sha1_hash",
+		DigestAlgorithm::Ntlm => "ntlm_hash",
+	};
+	let sql = format!(
+		"SELECT substr({column}, ?3), occurrence_count FROM credential_digests \
+		 WHERE {column} >= ?1 AND {column} < ?2 ORDER BY {column}"
+	);
+
+	// Every uppercase hex digest with the prefix sorts below prefix + 'G'
+	let upper = format!("{}G", prefix);
+	let mut stmt = conn.prepare(&sql).map_err(io::Error::other)?;
+	let range = stmt
+		.query_map(
+			rusqlite::params![prefix, upper, prefix.len() as i64 + 1],
+			|row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+		)
+		.map_err(io::Error::other)?
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(io::Error::other)?;
+
+	Ok(range)
+}
@@ -4,14 +4,25 @@ use std::io;
 
 use rusqlite::Connection;
 
+/// Schema version recorded in `PRAGMA user_version` once the schema is created
+pub const SCHEMA_VERSION: i32 = 1;
+
 /// Initialize the SQLite schema with all required tables and indexes.
 pub fn create_schema(conn: &Connection) -> io::Result<()> {
 	for statement in sql_statements() {
 		conn.execute(statement, []).map_err(io::Error::other)?;
 	}
+	conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+		.map_err(io::Error::other)?;
 	Ok(())
 }
 
+/// Read the schema version of a database (0 if the schema was never created)
+pub fn schema_version(conn: &Connection) -> io::Result<i32> {
+	conn.pragma_query_value(None, "user_version", |row| row.get(0))
+		.map_err(io::Error::other)
+}
+
 /// Get SQL statements for schema creation.
 fn sql_statements() -> Vec<&'static str> {
 	vec![
@@ -115,11 +126,21 @@ fn sql_statements() -> Vec<&'static str> {
 			PRIMARY KEY (file_id, subject_hash, anomaly_type),
 			FOREIGN KEY (file_id) REFERENCES file_metadata(file_id)
 		)",
+		"CREATE TABLE IF NOT EXISTS credential_digests (
+			sha1_hash TEXT PRIMARY KEY,
+			ntlm_hash TEXT NOT NULL,
+			credential_hash TEXT NOT NULL,
+			occurrence_count INTEGER NOT NULL DEFAULT 1,
+			created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+		)",
 		"CREATE INDEX IF NOT EXISTS idx_normalized_address_hash ON normalized_rows(address_hash)",
 		"CREATE INDEX IF NOT EXISTS idx_normalized_credential_hash ON \
 		 normalized_rows(credential_hash)",
 		"CREATE INDEX IF NOT EXISTS idx_address_alternates_hash ON \
 		 address_alternates(alternate_hash)",
+		"CREATE INDEX IF NOT EXISTS idx_credential_digests_ntlm ON credential_digests(ntlm_hash)",
+		"CREATE INDEX IF NOT EXISTS idx_address_credentials_credential ON \
+		 address_credentials(credential_hash)",
 		"CREATE INDEX IF NOT EXISTS idx_breaches_canonical ON address_breaches(canonical_hash)",
 		"CREATE INDEX IF NOT EXISTS idx_cooccurrence_both ON \
 		 address_cooccurrence(canonical_hash_1, canonical_hash_2)",
@@ -1,15 +1,31 @@
 //! Job tracking and async processing for Dumptruck server.
 //!
 //! Manages ingest jobs with status tracking, result storage, and cleanup.
+//! Jobs are kept in memory or, so they survive restarts, in a `jobs` table in
+//! the SQLite database. Database calls block (up to the busy timeout when
+//! another process holds the write lock), so they run on tokio's blocking
+//! thread pool rather than on the async workers serving the API.
 
+use crate::api::output::IngestResult;
 use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock, broadcast};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// Attempts a job gets before it is dead-lettered
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubles with every further attempt
+const RETRY_BASE_DELAY_SECS: i64 = 30;
+
+/// Upper bound on the delay between retries
+const RETRY_MAX_DELAY_SECS: i64 = 3600;
+
 /// Job errors
 #[derive(Debug, Error)]
 pub enum JobError {
@@ -24,6 +40,9 @@ pub enum JobError {
 
 	#[error("Job processing failed: {0}")]
 	ProcessingFailed(String),
+
+	#[error("Job storage error: {0}")]
+	Storage(String),
 }
 
 /// Job status
@@ -40,6 +59,32 @@ pub enum JobStatus {
 	Failed,
 	/// Job cancelled
 	Cancelled,
+	/// Gave up after exhausting all attempts
+	#[serde(rename = "dead_lettered")]
+	DeadLettered,
+}
+
+impl JobStatus {
+	/// Every status, in lifecycle order
+	pub const ALL: [JobStatus; 6] = [
+		JobStatus::Queued,
+		JobStatus::Processing,
+		JobStatus::Completed,
+		JobStatus::Failed,
+		JobStatus::Cancelled,
+		JobStatus::DeadLettered,
+	];
+
+	/// Whether the job will not change status again
+	pub fn is_terminal(&self) -> bool {
+		matches!(
+			self,
+			JobStatus::Completed
+				| JobStatus::Failed
+				| JobStatus::Cancelled
+				| JobStatus::DeadLettered
+		)
+	}
 }
 
 impl std::fmt::Display for JobStatus {
@@ -50,10 +95,25 @@ impl std::fmt::Display for JobStatus {
 			JobStatus::Completed => write!(f, "completed"),
 			JobStatus::Failed => write!(f, "failed"),
 			JobStatus::Cancelled => write!(f, "cancelled"),
+			JobStatus::DeadLettered => write!(f, "dead_lettered"),
 		}
 	}
 }
 
+/// One attempt to deliver a job's completion callback
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CallbackDelivery {
+	/// 1-based attempt number
+	pub attempt: u32,
+	pub attempted_at: DateTime<Utc>,
+	/// HTTP status returned by the callback endpoint, if it answered
+	pub status_code: Option<u16>,
+	/// Why the attempt failed, if it did
+	pub error: Option<String>,
+	/// Whether the endpoint acknowledged the callback with a 2xx status
+	pub delivered: bool,
+}
+
 /// Job metadata and progress
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Job {
@@ -64,9 +124,47 @@ pub struct Job {
 	pub completed_at: Option<DateTime<Utc>>,
 	pub filename: String,
 	pub file_size_bytes: u64,
+	/// Server-side location of the uploaded file, if the job owns one
+	pub file_path: Option<String>,
+	/// Resolved location of a file queued from the import directory
+	#[serde(default)]
+	pub import_path: Option<String>,
+	/// Hex-encoded SHA-256 of the uploaded file, computed by the server
+	pub sha256: Option<String>,
+	/// Evidence file ID recorded for the uploaded file
+	pub file_id: Option<String>,
+	/// Subject of the principal that submitted the job
+	pub owner: Option<String>,
+	/// Worker that claimed the job for processing
+	#[serde(default)]
+	pub worker_id: Option<usize>,
+	/// Higher priorities are claimed first
+	#[serde(default)]
+	pub priority: i32,
+	/// Number of times processing has started
+	#[serde(default)]
+	pub attempts: u32,
+	/// Attempts allowed before the job is dead-lettered
+	#[serde(default = "default_max_attempts")]
+	pub max_attempts: u32,
+	/// Earliest time a retried job may be claimed again
+	#[serde(default)]
+	pub next_retry_at: Option<DateTime<Utc>>,
+	/// URL notified when the job reaches a terminal state
+	#[serde(default)]
+	pub callback_url: Option<String>,
+	/// Attempts to deliver the completion callback, oldest first
+	#[serde(default)]
+	pub callback_deliveries: Vec<CallbackDelivery>,
 	pub rows_processed: usize,
 	pub error_message: Option<String>,
 	pub progress_percentage: u32,
+	/// Analysis result, populated once the job completes
+	pub result: Option<IngestResult>,
+}
+
+fn default_max_attempts() -> u32 {
+	DEFAULT_MAX_ATTEMPTS
 }
 
 impl Job {
@@ -80,12 +178,85 @@ impl Job {
 			completed_at: None,
 			filename,
 			file_size_bytes,
+			file_path: None,
+			import_path: None,
+			sha256: None,
+			file_id: None,
+			owner: None,
+			worker_id: None,
+			priority: 0,
+			attempts: 0,
+			max_attempts: DEFAULT_MAX_ATTEMPTS,
+			next_retry_at: None,
+			callback_url: None,
+			callback_deliveries: Vec::new(),
 			rows_processed: 0,
 			error_message: None,
 			progress_percentage: 0,
+			result: None,
 		}
 	}
 
+	/// Create a new job for a file stored on the server
+	pub fn for_stored_file(
+		filename: String,
+		file_path: String,
+		file_size_bytes: u64,
+		sha256: String,
+	) -> Self {
+		let mut job = Self::new(filename, file_size_bytes);
+		job.file_path = Some(file_path);
+		job.sha256 = Some(sha256);
+		job
+	}
+
+	/// Create a new job for a file in the server's import directory
+	pub fn for_import(filename: String, import_path: String, file_size_bytes: u64) -> Self {
+		let mut job = Self::new(filename, file_size_bytes);
+		job.import_path = Some(import_path);
+		job
+	}
+
+	/// Record the principal that submitted the job
+	pub fn owned_by(mut self, owner: &str) -> Self {
+		self.owner = Some(owner.to_string());
+		self
+	}
+
+	/// Set the job's priority (higher is claimed first)
+	pub fn with_priority(mut self, priority: i32) -> Self {
+		self.priority = priority;
+		self
+	}
+
+	/// Notify `callback_url` once the job finishes
+	pub fn with_callback(mut self, callback_url: Option<String>) -> Self {
+		self.callback_url = callback_url;
+		self
+	}
+
+	/// Whether the completion callback still has to be sent
+	///
+	/// True once the job is terminal, has a callback URL and no delivery has
+	/// been attempted yet.
+	pub fn callback_pending(&self) -> bool {
+		self.status.is_terminal()
+			&& self.callback_url.is_some()
+			&& self.callback_deliveries.is_empty()
+	}
+
+	/// Whether the job may be claimed at `now`
+	pub fn is_claimable(&self, now: DateTime<Utc>) -> bool {
+		self.status == JobStatus::Queued && self.next_retry_at.is_none_or(|at| at <= now)
+	}
+
+	/// Path of the file the worker should ingest
+	///
+	/// The client-supplied filename is never used as a path.
+	pub fn source_path(&self) -> Option<&str> {
+		self.file_path.as_deref().or(self.import_path.as_deref())
+	}
+
 	/// Mark job as processing
 	pub fn start_processing(&mut self) -> Result<(), JobError> {
 		if self.status != JobStatus::Queued {
@@ -96,6 +267,8 @@ impl Job {
 		}
 		self.status = JobStatus::Processing;
 		self.started_at = Some(Utc::now());
+		self.attempts += 1;
+		self.next_retry_at = None;
 		Ok(())
 	}
 
@@ -114,6 +287,13 @@ impl Job {
 		Ok(())
 	}
 
+	/// Mark job as completed with the analysis result
+	pub fn complete_with_result(&mut self, result: IngestResult) -> Result<(), JobError> {
+		self.complete(result.rows_processed)?;
+		self.result = Some(result);
+		Ok(())
+	}
+
 	/// Mark job as failed
 	pub fn fail(&mut self, error: String) -> Result<(), JobError> {
 		if self.status != JobStatus::Processing {
@@ -128,6 +308,53 @@ impl Job {
 		Ok(())
 	}
 
+	/// Schedule a retry after a transient failure, or dead-letter the job once
+	/// its attempts are exhausted
+	///
+	/// The delay doubles with each attempt, starting at 30 seconds and capped at an hour.
+	pub fn retry_or_dead_letter(&mut self, error: String) -> Result<(), JobError> {
+		if self.attempts >= self.max_attempts {
+			return self.dead_letter(error);
+		}
+
+		self.requeue()?;
+		let exponent = self.attempts.saturating_sub(1).min(16);
+		let delay = (RETRY_BASE_DELAY_SECS << exponent).min(RETRY_MAX_DELAY_SECS);
+		self.next_retry_at = Some(Utc::now() + chrono::Duration::seconds(delay));
+		self.error_message = Some(error);
+		Ok(())
+	}
+
+	/// Give up on the job for good
+	pub fn dead_letter(&mut self, error: String) -> Result<(), JobError> {
+		if self.status != JobStatus::Processing {
+			return Err(JobError::InvalidState(format!(
+				"Cannot dead-letter job in {} state",
+				self.status
+			)));
+		}
+		self.status = JobStatus::DeadLettered;
+		self.error_message = Some(error);
+		self.completed_at = Some(Utc::now());
+		Ok(())
+	}
+
+	/// Return an interrupted job to the queue so it is processed from the start
+	pub fn requeue(&mut self) -> Result<(), JobError> {
+		if self.status != JobStatus::Processing {
+			return Err(JobError::InvalidState(format!(
+				"Cannot requeue job in {} state",
+				self.status
+			)));
+		}
+		self.status = JobStatus::Queued;
+		self.started_at = None;
+		self.worker_id = None;
+		self.rows_processed = 0;
+		self.progress_percentage = 0;
+		Ok(())
+	}
+
 	/// Update progress
 	pub fn update_progress(&mut self, rows_processed: usize, total_rows: usize) {
 		self.rows_processed = rows_processed;
@@ -137,70 +364,295 @@ impl Job {
 	}
 }
 
-/// In-memory job queue
+/// Schema for the durable job queue
+const JOBS_SCHEMA: &[&str] = &[
+	"CREATE TABLE IF NOT EXISTS jobs (
+		id TEXT PRIMARY KEY,
+		status TEXT NOT NULL,
+		owner TEXT,
+		priority INTEGER NOT NULL DEFAULT 0,
+		next_retry_at INTEGER,
+		created_at INTEGER NOT NULL,
+		data TEXT NOT NULL
+	)",
+	"CREATE INDEX IF NOT EXISTS idx_jobs_claim ON jobs(status, priority DESC, created_at)",
+	"CREATE INDEX IF NOT EXISTS idx_jobs_owner_created ON jobs(owner, created_at)",
+];
+
+/// Where the queue keeps its jobs
+enum Backend {
+	/// In-process map; jobs are lost on restart
+	Memory(RwLock<HashMap<String, Job>>),
+	/// `jobs` table in the SQLite database; jobs survive restarts
+	Sqlite(Arc<Mutex<Connection>>),
+}
+
+/// Number of job updates buffered for slow event subscribers
+const JOB_EVENTS_CAPACITY: usize = 1024;
+
+/// Job queue backed by memory or by the SQLite database
 pub struct JobQueue {
-	jobs: Arc<RwLock<HashMap<String, Job>>>,
+	backend: Backend,
+	/// Wakes idle workers when a job becomes available
+	job_available: Notify,
+	/// Publishes a snapshot of every job after it changes
+	events: broadcast::Sender<Job>,
 }
 
 impl JobQueue {
-	/// Create a new job queue
+	/// Create a new in-memory job queue
 	pub fn new() -> Self {
 		Self {
-			jobs: Arc::new(RwLock::new(HashMap::new())),
+			backend: Backend::Memory(RwLock::new(HashMap::new())),
+			job_available: Notify::new(),
+			events: broadcast::channel(JOB_EVENTS_CAPACITY).0,
 		}
 	}
 
+	/// Open the durable job queue in the SQLite database at `db_path`
+	pub fn open_sqlite(db_path: &str) -> Result<Self, JobError> {
+		let conn = Connection::open(db_path).map_err(storage_error)?;
+		conn.busy_timeout(std::time::Duration::from_secs(5))
+			.map_err(storage_error)?;
+		for statement in JOBS_SCHEMA {
+			conn.execute(statement, []).map_err(storage_error)?;
+		}
+
+		Ok(Self {
+			backend: Backend::Sqlite(Arc::new(Mutex::new(conn))),
+			job_available: Notify::new(),
+			events: broadcast::channel(JOB_EVENTS_CAPACITY).0,
+		})
+	}
+
 	/// Create and enqueue a new job
 	pub async fn enqueue(
 		&self,
 		filename: String,
 		file_size_bytes: u64,
 	) -> Result<String, JobError> {
-		let job = Job::new(filename, file_size_bytes);
+		self.enqueue_job(Job::new(filename, file_size_bytes)).await
+	}
+
+	/// Subscribe to snapshots of jobs as they are enqueued and updated
+	pub fn subscribe(&self) -> broadcast::Receiver<Job> {
+		self.events.subscribe()
+	}
+
+	/// Enqueue a prepared job
+	pub async fn enqueue_job(&self, job: Job) -> Result<String, JobError> {
 		let job_id = job.id.clone();
+		let snapshot = job.clone();
 
-		let mut jobs = self.jobs.write().await;
-		if jobs.contains_key(&job_id) {
-			return Err(JobError::AlreadyExists(job_id));
+		match &self.backend {
+			Backend::Memory(jobs) => {
+				let mut jobs = jobs.write().await;
+				if jobs.contains_key(&job_id) {
+					return Err(JobError::AlreadyExists(job_id));
+				}
+				jobs.insert(job_id.clone(), job);
+			}
+			Backend::Sqlite(conn) => {
+				let id = job_id.clone();
+				blocking(conn, move |conn| match sqlite_insert(conn, &job) {
+					Err(rusqlite::Error::SqliteFailure(e, _))
+						if e.code == rusqlite::ErrorCode::ConstraintViolation =>
+					{
+						Err(JobError::AlreadyExists(id))
+					}
+					result => result.map_err(storage_error),
+				})
+				.await?;
+			}
 		}
 
-		jobs.insert(job_id.clone(), job);
+		self.job_available.notify_one();
+		let _ = self.events.send(snapshot);
 		Ok(job_id)
 	}
 
+	/// Atomically claim the next job for `worker_id`, marking it as processing
+	///
+	/// Jobs are claimed by descending priority, then oldest first; retries are
+	/// skipped until their backoff has elapsed. Returns `None` when no job is ready.
+	pub async fn claim_next(&self, worker_id: usize) -> Result<Option<Job>, JobError> {
+		let claim = move |job: &mut Job| {
+			job.start_processing()?;
+			job.worker_id = Some(worker_id);
+			Ok(())
+		};
+
+		let claimed = match &self.backend {
+			Backend::Memory(jobs) => {
+				let now = Utc::now();
+				let mut jobs = jobs.write().await;
+				let next = jobs
+					.values_mut()
+					.filter(|job| job.is_claimable(now))
+					.min_by(|a, b| {
+						(std::cmp::Reverse(a.priority), a.created_at, &a.id).cmp(&(
+							std::cmp::Reverse(b.priority),
+							b.created_at,
+							&b.id,
+						))
+					});
+
+				match next {
+					Some(job) => {
+						claim(job)?;
+						Some(job.clone())
+					}
+					None => None,
+				}
+			}
+			Backend::Sqlite(conn) => {
+				blocking(conn, move |conn| sqlite_claim_next(conn, claim)).await?
+			}
+		};
+
+		if let Some(job) = &claimed {
+			let _ = self.events.send(job.clone());
+		}
+		Ok(claimed)
+	}
+
+	/// Wait until a job may be available, or until `timeout` elapses
+	///
+	/// The timeout bounds how long jobs queued by another process go unnoticed.
+	pub async fn wait_for_job(&self, timeout: std::time::Duration) {
+		let _ = tokio::time::timeout(timeout, self.job_available.notified()).await;
+	}
+
 	/// Get job status
 	pub async fn get_job(&self, job_id: &str) -> Result<Job, JobError> {
-		let jobs = self.jobs.read().await;
-		jobs.get(job_id)
-			.cloned()
-			.ok_or_else(|| JobError::NotFound(job_id.to_string()))
+		let job = match &self.backend {
+			Backend::Memory(jobs) => jobs.read().await.get(job_id).cloned(),
+			Backend::Sqlite(conn) => {
+				let id = job_id.to_string();
+				blocking(conn, move |conn| sqlite_get(conn, &id)).await?
+			}
+		};
+		job.ok_or_else(|| JobError::NotFound(job_id.to_string()))
+	}
+
+	/// List jobs with pagination, newest first
+	///
+	/// When `owner` is given only that owner's jobs are listed and counted.
+	pub async fn list_jobs(
+		&self,
+		owner: Option<&str>,
+		offset: usize,
+		limit: usize,
+	) -> Result<(Vec<Job>, usize), JobError> {
+		match &self.backend {
+			Backend::Memory(jobs) => {
+				let jobs = jobs.read().await;
+
+				let mut jobs_vec: Vec<_> = jobs
+					.values()
+					.filter(|job| owner.is_none() || job.owner.as_deref() == owner)
+					.cloned()
+					.collect();
+				let total = jobs_vec.len();
+				jobs_vec.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+
+				let paginated: Vec<_> = jobs_vec.into_iter().skip(offset).take(limit).collect();
+
+				Ok((paginated, total))
+			}
+			Backend::Sqlite(conn) => {
+				let owner = owner.map(str::to_string);
+				blocking(conn, move |conn| {
+					sqlite_list(conn, owner.as_deref(), offset, limit)
+				})
+				.await
+			}
+		}
 	}
 
-	/// List all jobs with pagination
-	pub async fn list_jobs(&self, offset: usize, limit: usize) -> (Vec<Job>, usize) {
-		let jobs = self.jobs.read().await;
-		let total = jobs.len();
+	/// Finished jobs whose completion callback has not been attempted yet
+	pub async fn pending_callbacks(&self) -> Result<Vec<Job>, JobError> {
+		let jobs: Vec<Job> = match &self.backend {
+			Backend::Memory(jobs) => jobs.read().await.values().cloned().collect(),
+			Backend::Sqlite(conn) => {
+				blocking(conn, |conn| {
+					let mut stmt = conn
+						.prepare(
+							"SELECT data FROM jobs \
+							 WHERE json_extract(data, '$.callback_url') IS NOT NULL",
+						)
+						.map_err(storage_error)?;
+					let rows: Vec<String> = stmt
+						.query_map([], |row| row.get(0))
+						.and_then(Iterator::collect)
+						.map_err(storage_error)?;
+					rows.iter().map(|data| decode_job(data)).collect()
+				})
+				.await?
+			}
+		};
 
-		let mut jobs_vec: Vec<_> = jobs.values().cloned().collect();
-		jobs_vec.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+		Ok(jobs.into_iter().filter(Job::callback_pending).collect())
+	}
 
-		let paginated: Vec<_> = jobs_vec.into_iter().skip(offset).take(limit).collect();
+	/// Count jobs in each status (every status is listed, including empty ones)
+	pub async fn count_by_status(&self) -> Result<Vec<(JobStatus, usize)>, JobError> {
+		let mut counts: HashMap<String, usize> = HashMap::new();
+		match &self.backend {
+			Backend::Memory(jobs) => {
+				for job in jobs.read().await.values() {
+					*counts.entry(job.status.to_string()).or_default() += 1;
+				}
+			}
+			Backend::Sqlite(conn) => {
+				counts = blocking(conn, |conn| {
+					let mut stmt = conn
+						.prepare("SELECT status, COUNT(*) FROM jobs GROUP BY status")
+						.map_err(storage_error)?;
+					stmt.query_map([], |row| {
+						Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+					})
+					.and_then(Iterator::collect)
+					.map_err(storage_error)
+				})
+				.await?;
+			}
+		}
 
-		(paginated, total)
+		Ok(JobStatus::ALL
+			.into_iter()
+			.map(|status| {
+				(
+					status,
+					counts.get(&status.to_string()).copied().unwrap_or(0),
+				)
+			})
+			.collect())
 	}
 
 	/// Update job status
 	pub async fn update_job<F>(&self, job_id: &str, update: F) -> Result<Job, JobError>
 	where
-		F: FnOnce(&mut Job) -> Result<(), JobError>,
+		F: FnOnce(&mut Job) -> Result<(), JobError> + Send + 'static,
 	{
-		let mut jobs = self.jobs.write().await;
-		let job = jobs
-			.get_mut(job_id)
-			.ok_or_else(|| JobError::NotFound(job_id.to_string()))?;
+		let job = match &self.backend {
+			Backend::Memory(jobs) => {
+				let mut jobs = jobs.write().await;
+				let job = jobs
+					.get_mut(job_id)
+					.ok_or_else(|| JobError::NotFound(job_id.to_string()))?;
+
+				update(job)?;
+				job.clone()
+			}
+			Backend::Sqlite(conn) => {
+				let id = job_id.to_string();
+				blocking(conn, move |conn| sqlite_update(conn, &id, update)).await?
+			}
+		};
 
-		update(job)?;
-		Ok(job.clone())
+		let _ = self.events.send(job.clone());
+		Ok(job)
 	}
 
 	/// Cancel a job
@@ -218,6 +670,63 @@ impl JobQueue {
 		})
 		.await
 	}
+
+	/// Recover jobs left in `Processing` by a previous run of the server
+	///
+	/// Jobs whose input file is still present are re-queued unless they were on
+	/// their final attempt; the rest are failed or dead-lettered. Returns the
+	/// number of jobs re-queued and not re-queued.
+	pub async fn recover_interrupted(&self) -> Result<(usize, usize), JobError> {
+		let interrupted: Vec<String> = match &self.backend {
+			Backend::Memory(jobs) => jobs
+				.read()
+				.await
+				.values()
+				.filter(|job| job.status == JobStatus::Processing)
+				.map(|job| job.id.clone())
+				.collect(),
+			Backend::Sqlite(conn) => {
+				blocking(conn, |conn| {
+					let mut stmt = conn
+						.prepare("SELECT id FROM jobs WHERE status = ?1")
+						.map_err(storage_error)?;
+					stmt.query_map([JobStatus::Processing.to_string()], |row| row.get(0))
+						.and_then(Iterator::collect)
+						.map_err(storage_error)
+				})
+				.await?
+			}
+		};
+
+		let (mut requeued, mut failed) = (0, 0);
+		for job_id in interrupted {
+			let job = self
+				.update_job(&job_id, |job| {
+					if job.attempts >= job.max_attempts {
+						job.dead_letter(
+							"Interrupted by server restart on its final attempt".to_string(),
+						)
+					} else if job
+						.source_path()
+						.is_some_and(|path| std::path::Path::new(path).exists())
+					{
+						job.requeue()
+					} else {
+						job.fail("Interrupted by server restart; input file is gone".to_string())
+					}
+				})
+				.await?;
+
+			if job.status == JobStatus::Queued {
+				requeued += 1;
+				self.job_available.notify_one();
+			} else {
+				failed += 1;
+			}
+		}
+
+		Ok((requeued, failed))
+	}
 }
 
 impl Default for JobQueue {
@@ -226,6 +735,160 @@ impl Default for JobQueue {
 	}
 }
 
+fn storage_error(e: impl std::fmt::Display) -> JobError {
+	JobError::Storage(e.to_string())
+}
+
+fn lock(conn: &Mutex<Connection>) -> Result<MutexGuard<'_, Connection>, JobError> {
+	conn.lock().map_err(storage_error)
+}
+
+/// Run `f` with the database connection on the blocking thread pool
+async fn blocking<T, F>(conn: &Arc<Mutex<Connection>>, f: F) -> Result<T, JobError>
+where
+	T: Send + 'static,
+	F: FnOnce(&mut Connection) -> Result<T, JobError> + Send + 'static,
+{
+	let conn = conn.clone();
+	tokio::task::spawn_blocking(move || f(&mut *lock(&conn)?))
+		.await
+		.map_err(storage_error)?
+}
+
+fn sqlite_insert(conn: &Connection, job: &Job) -> rusqlite::Result<()> {
+	let data = serde_json::to_string(job)
+		.map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+	conn.execute(
+		"INSERT INTO jobs (id, status, owner, priority, next_retry_at, created_at, data) \
+		 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+		params![
+			job.id,
+			job.status.to_string(),
+			job.owner,
+			job.priority,
+			job.next_retry_at.map(|at| at.timestamp_micros()),
+			job.created_at.timestamp_micros(),
+			data
+		],
+	)?;
+	Ok(())
+}
+
+fn sqlite_get(conn: &Connection, job_id: &str) -> Result<Option<Job>, JobError> {
+	let data: Option<String> = conn
+		.query_row("SELECT data FROM jobs WHERE id = ?1", [job_id], |row| {
+			row.get(0)
+		})
+		.optional()
+		.map_err(storage_error)?;
+	data.map(|data| decode_job(&data)).transpose()
+}
+
+fn sqlite_list(
+	conn: &Connection,
+	owner: Option<&str>,
+	offset: usize,
+	limit: usize,
+) -> Result<(Vec<Job>, usize), JobError> {
+	let total: i64 = conn
+		.query_row(
+			"SELECT COUNT(*) FROM jobs WHERE ?1 IS NULL OR owner = ?1",
+			[owner],
+			|row| row.get(0),
+		)
+		.map_err(storage_error)?;
+
+	let mut stmt = conn
+		.prepare(
+			"SELECT data FROM jobs WHERE ?1 IS NULL OR owner = ?1 \
+			 ORDER BY created_at DESC LIMIT ?2 OFFSET ?3",
+		)
+		.map_err(storage_error)?;
+	let rows: Vec<String> = stmt
+		.query_map(params![owner, limit as i64, offset as i64], |row| {
+			row.get(0)
+		})
+		.and_then(Iterator::collect)
+		.map_err(storage_error)?;
+
+	let jobs = rows
+		.iter()
+		.map(|data| decode_job(data))
+		.collect::<Result<_, _>>()?;
+	Ok((jobs, total as usize))
+}
+
+fn sqlite_update<F>(conn: &mut Connection, job_id: &str, update: F) -> Result<Job, JobError>
+where
+	F: FnOnce(&mut Job) -> Result<(), JobError>,
+{
+	// IMMEDIATE takes the write lock up front so read-modify-write is atomic across processes
+	let tx = conn
+		.transaction_with_behavior(TransactionBehavior::Immediate)
+		.map_err(storage_error)?;
+
+	let mut job = sqlite_get(&tx, job_id)?.ok_or_else(|| JobError::NotFound(job_id.to_string()))?;
+	update(&mut job)?;
+
+	sqlite_write(&tx, &job)?;
+	tx.commit().map_err(storage_error)?;
+
+	Ok(job)
+}
+
+fn sqlite_claim_next<F>(conn: &mut Connection, claim: F) -> Result<Option<Job>, JobError>
+where
+	F: FnOnce(&mut Job) -> Result<(), JobError>,
+{
+	// Select and update in one IMMEDIATE transaction so no two workers claim the same job
+	let tx = conn
+		.transaction_with_behavior(TransactionBehavior::Immediate)
+		.map_err(storage_error)?;
+
+	let data: Option<String> = tx
+		.query_row(
+			"SELECT data FROM jobs \
+			 WHERE status = ?1 AND (next_retry_at IS NULL OR next_retry_at <= ?2) \
+			 ORDER BY priority DESC, created_at, rowid LIMIT 1",
+			params![JobStatus::Queued.to_string(), Utc::now().timestamp_micros()],
+			|row| row.get(0),
+		)
+		.optional()
+		.map_err(storage_error)?;
+	let Some(data) = data else {
+		return Ok(None);
+	};
+
+	let mut job = decode_job(&data)?;
+	claim(&mut job)?;
+	sqlite_write(&tx, &job)?;
+	tx.commit().map_err(storage_error)?;
+
+	Ok(Some(job))
+}
+
+fn sqlite_write(conn: &Connection, job: &Job) -> Result<(), JobError> {
+	let data = serde_json::to_string(job).map_err(storage_error)?;
+	conn.execute(
+		"UPDATE jobs SET status = ?2, owner = ?3, priority = ?4, next_retry_at = ?5, data = ?6 \
+		 WHERE id = ?1",
+		params![
+			job.id,
+			job.status.to_string(),
+			job.owner,
+			job.priority,
+			job.next_retry_at.map(|at| at.timestamp_micros()),
+			data
+		],
+	)
+	.map_err(storage_error)?;
+	Ok(())
+}
+
+fn decode_job(data: &str) -> Result<Job, JobError> {
+	serde_json::from_str(data).map_err(storage_error)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -250,6 +913,28 @@ mod tests {
 		assert_eq!(job.rows_processed, 100);
 	}
 
+	#[test]
+	fn test_job_source_path_prefers_stored_file() {
+		let job = Job::new("/data/dump.csv".to_string(), 10);
+		assert_eq!(job.source_path(), None);
+
+		let job = Job::for_import(
+			"dump.csv".to_string(),
+			"/srv/imports/dump.csv".to_string(),
+			10,
+		);
+		assert_eq!(job.source_path(), Some("/srv/imports/dump.csv"));
+
+		let job = Job::for_stored_file(
+			"dump.csv".to_string(),
+			"/tmp/dumptruck/upload_1_dump.csv".to_string(),
+			10,
+			"abc".to_string(),
+		);
+		assert_eq!(job.source_path(), Some("/tmp/dumptruck/upload_1_dump.csv"));
+		assert_eq!(job.sha256.as_deref(), Some("abc"));
+	}
+
 	#[test]
 	fn test_job_invalid_state_transition() {
 		let mut job = Job::new("test.csv".to_string(), 1024);
@@ -282,11 +967,57 @@ mod tests {
 			.await
 			.expect("enqueue 2 failed");
 
-		let (jobs, total) = queue.list_jobs(0, 10).await;
+		let (jobs, total) = queue.list_jobs(None, 0, 10).await.expect("list failed");
 		assert_eq!(jobs.len(), 2);
 		assert_eq!(total, 2);
 	}
 
+	#[tokio::test]
+	async fn test_job_queue_list_filters_by_owner() {
+		let queue = JobQueue::new();
+		for owner in ["alice", "bob", "alice"] {
+			queue
+				.enqueue_job(Job::new("dump.csv".to_string(), 10).owned_by(owner))
+				.await
+				.expect("enqueue failed");
+		}
+
+		let (jobs, total) = queue
+			.list_jobs(Some("alice"), 0, 1)
+			.await
+			.expect("list failed");
+		assert_eq!(jobs.len(), 1);
+		assert_eq!(total, 2);
+		assert_eq!(jobs[0].owner.as_deref(), Some("alice"));
+
+		let (_, total) = queue.list_jobs(None, 0, 10).await.expect("list failed");
+		assert_eq!(total, 3);
+	}
+
+	#[tokio::test]
+	async fn test_count_by_status() {
+		for queue in [
+			JobQueue::new(),
+			JobQueue::open_sqlite(":memory:").expect("sqlite queue"),
+		] {
+			let first = queue
+				.enqueue("a.csv".to_string(), 1)
+				.await
+				.expect("enqueue");
+			queue
+				.enqueue("b.csv".to_string(), 1)
+				.await
+				.expect("enqueue");
+			queue.cancel_job(&first).await.expect("cancel");
+
+			let counts = queue.count_by_status().await.expect("counts");
+			assert_eq!(counts.len(), JobStatus::ALL.len());
+			assert!(counts.contains(&(JobStatus::Queued, 1)));
+			assert!(counts.contains(&(JobStatus::Cancelled, 1)));
+			assert!(counts.contains(&(JobStatus::Completed, 0)));
+		}
+	}
+
 	#[tokio::test]
 	async fn test_job_queue_update() {
 		let queue = JobQueue::new();
@@ -303,4 +1034,358 @@ mod tests {
 		let job = queue.get_job(&job_id).await.expect("get failed");
 		assert_eq!(job.status, JobStatus::Processing);
 	}
+
+	#[tokio::test]
+	async fn test_sqlite_queue_survives_reopen() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let db_path = dir.path().join("jobs.db");
+		let db_path = db_path.to_str().expect("path");
+
+		let job_id = {
+			let queue = JobQueue::open_sqlite(db_path).expect("open");
+			let job_id = queue
+				.enqueue_job(Job::new("dump.csv".to_string(), 10).owned_by("alice"))
+				.await
+				.expect("enqueue failed");
+			queue
+				.update_job(&job_id, |job| {
+					job.start_processing()?;
+					job.update_progress(5, 10);
+					Ok(())
+				})
+				.await
+				.expect("update failed");
+			job_id
+		};
+
+		let queue = JobQueue::open_sqlite(db_path).expect("reopen");
+		let job = queue.get_job(&job_id).await.expect("get failed");
+		assert_eq!(job.status, JobStatus::Processing);
+		assert_eq!(job.rows_processed, 5);
+		assert_eq!(job.owner.as_deref(), Some("alice"));
+
+		let (jobs, total) = queue
+			.list_jobs(Some("alice"), 0, 10)
+			.await
+			.expect("list failed");
+		assert_eq!(total, 1);
+		assert_eq!(jobs[0].id, job_id);
+		assert!(matches!(
+			queue.get_job("missing").await,
+			Err(JobError::NotFound(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_sqlite_calls_do_not_block_the_runtime() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let db_path = dir.path().join("jobs.db");
+		let queue = Arc::new(JobQueue::open_sqlite(db_path.to_str().expect("path")).expect("open"));
+		let job_id = queue
+			.enqueue("dump.csv".to_string(), 10)
+			.await
+			.expect("enqueue failed");
+
+		// Another process holds the write lock, so the update waits for it
+		let blocker = Connection::open(&db_path).expect("open");
+		blocker.execute_batch("BEGIN IMMEDIATE").expect("lock");
+		let update = tokio::spawn({
+			let queue = queue.clone();
+			let job_id = job_id.clone();
+			async move {
+				queue
+					.update_job(&job_id, |job| {
+						job.priority = 5;
+						Ok(())
+					})
+					.await
+			}
+		});
+
+		// The single-threaded runtime keeps running other tasks meanwhile
+		let started = std::time::Instant::now();
+		tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+		assert!(started.elapsed() < std::time::Duration::from_secs(1));
+		assert!(!update.is_finished());
+
+		blocker.execute_batch("COMMIT").expect("unlock");
+		let job = update.await.expect("task").expect("update");
+		assert_eq!(job.priority, 5);
+	}
+
+	#[tokio::test]
+	async fn test_recover_interrupted_jobs() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let input = dir.path().join("dump.csv");
+		std::fs::write(&input, "email\n").expect("write input");
+		let queue = JobQueue::open_sqlite(dir.path().join("jobs.db").to_str().expect("path"))
+			.expect("open");
+
+		let mut ids = Vec::new();
+		for path in [input.clone(), dir.path().join("gone.csv")] {
+			let job = Job::for_import(
+				"dump.csv".to_string(),
+				path.to_string_lossy().to_string(),
+				6,
+			);
+			let job_id = queue.enqueue_job(job).await.expect("enqueue failed");
+			queue
+				.update_job(&job_id, |job| job.start_processing())
+				.await
+				.expect("start failed");
+			ids.push(job_id);
+		}
+
+		assert_eq!(queue.recover_interrupted().await.expect("recover"), (1, 1));
+
+		let requeued = queue.get_job(&ids[0]).await.expect("get failed");
+		assert_eq!(requeued.status, JobStatus::Queued);
+		assert!(requeued.started_at.is_none());
+		let failed = queue.get_job(&ids[1]).await.expect("get failed");
+		assert_eq!(failed.status, JobStatus::Failed);
+		assert!(failed.error_message.is_some());
+	}
+
+	#[tokio::test]
+	async fn test_claim_next_is_fifo_and_exclusive() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let sqlite = JobQueue::open_sqlite(dir.path().join("jobs.db").to_str().expect("path"))
+			.expect("open");
+
+		for queue in [JobQueue::new(), sqlite] {
+			let mut ids = Vec::new();
+			for name in ["first.csv", "second.csv", "third.csv"] {
+				ids.push(
+					queue
+						.enqueue(name.to_string(), 10)
+						.await
+						.expect("enqueue failed"),
+				);
+			}
+
+			let queue = std::sync::Arc::new(queue);
+			let claims = (0..4).map(|worker_id| {
+				let queue = queue.clone();
+				tokio::spawn(async move { queue.claim_next(worker_id).await })
+			});
+			let mut claimed: Vec<Job> = Vec::new();
+			for claim in claims {
+				if let Some(job) = claim.await.expect("join").expect("claim failed") {
+					claimed.push(job);
+				}
+			}
+
+			assert_eq!(claimed.len(), 3);
+			let mut claimed_ids: Vec<_> = claimed.iter().map(|job| job.id.clone()).collect();
+			claimed_ids.sort();
+			ids.sort();
+			assert_eq!(claimed_ids, ids);
+			assert!(
+				claimed
+					.iter()
+					.all(|job| job.status == JobStatus::Processing)
+			);
+			assert!(claimed.iter().all(|job| job.worker_id.is_some()));
+			assert!(queue.claim_next(9).await.expect("claim failed").is_none());
+		}
+	}
+
+	#[tokio::test]
+	async fn test_claim_next_takes_oldest_first() {
+		let queue = JobQueue::new();
+		let first = queue
+			.enqueue("first.csv".to_string(), 10)
+			.await
+			.expect("enqueue failed");
+		queue
+			.enqueue("second.csv".to_string(), 10)
+			.await
+			.expect("enqueue failed");
+
+		let job = queue
+			.claim_next(0)
+			.await
+			.expect("claim failed")
+			.expect("job");
+		assert_eq!(job.id, first);
+		assert_eq!(job.worker_id, Some(0));
+	}
+
+	#[tokio::test]
+	async fn test_wait_for_job_wakes_on_enqueue() {
+		let queue = std::sync::Arc::new(JobQueue::new());
+		let waiter = {
+			let queue = queue.clone();
+			tokio::spawn(async move {
+				let started = std::time::Instant::now();
+				queue.wait_for_job(std::time::Duration::from_secs(30)).await;
+				started.elapsed()
+			})
+		};
+
+		tokio::task::yield_now().await;
+		queue
+			.enqueue("dump.csv".to_string(), 10)
+			.await
+			.expect("enqueue failed");
+
+		let waited = waiter.await.expect("join");
+		assert!(waited < std::time::Duration::from_secs(5));
+	}
+
+	#[tokio::test]
+	async fn test_claim_next_prefers_higher_priority() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let sqlite = JobQueue::open_sqlite(dir.path().join("jobs.db").to_str().expect("path"))
+			.expect("open");
+
+		for queue in [JobQueue::new(), sqlite] {
+			queue
+				.enqueue("backfill.csv".to_string(), 10)
+				.await
+				.expect("enqueue failed");
+			let urgent = queue
+				.enqueue_job(Job::new("incident.csv".to_string(), 10).with_priority(10))
+				.await
+				.expect("enqueue failed");
+
+			let job = queue
+				.claim_next(0)
+				.await
+				.expect("claim failed")
+				.expect("job");
+			assert_eq!(job.id, urgent);
+		}
+	}
+
+	#[test]
+	fn test_retry_backoff_then_dead_letter() {
+		let mut job = Job::new("dump.csv".to_string(), 10);
+		job.max_attempts = 2;
+
+		job.start_processing().expect("start");
+		job.retry_or_dead_letter("database is locked".to_string())
+			.expect("retry");
+		assert_eq!(job.status, JobStatus::Queued);
+		assert_eq!(job.attempts, 1);
+		let retry_at = job.next_retry_at.expect("retry scheduled");
+		assert!(retry_at > Utc::now() + chrono::Duration::seconds(25));
+		assert!(!job.is_claimable(Utc::now()));
+		assert!(job.is_claimable(retry_at));
+
+		job.start_processing().expect("start");
+		job.retry_or_dead_letter("database is locked".to_string())
+			.expect("dead letter");
+		assert_eq!(job.status, JobStatus::DeadLettered);
+		assert_eq!(job.attempts, 2);
+		assert!(job.completed_at.is_some());
+	}
+
+	#[tokio::test]
+	async fn test_claim_next_waits_for_retry_backoff() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let queue = JobQueue::open_sqlite(dir.path().join("jobs.db").to_str().expect("path"))
+			.expect("open");
+		let job_id = queue
+			.enqueue("dump.csv".to_string(), 10)
+			.await
+			.expect("enqueue failed");
+
+		queue
+			.claim_next(0)
+			.await
+			.expect("claim failed")
+			.expect("job");
+		queue
+			.update_job(&job_id, |job| {
+				job.retry_or_dead_letter("database is locked".to_string())
+			})
+			.await
+			.expect("retry failed");
+
+		assert!(queue.claim_next(0).await.expect("claim failed").is_none());
+
+		queue
+			.update_job(&job_id, |job| {
+				job.next_retry_at = Some(Utc::now() - chrono::Duration::seconds(1));
+				Ok(())
+			})
+			.await
+			.expect("update failed");
+		let job = queue
+			.claim_next(0)
+			.await
+			.expect("claim failed")
+			.expect("job");
+		assert_eq!(job.attempts, 2);
+	}
+
+	#[tokio::test]
+	async fn test_job_queue_publishes_updates() {
+		let queue = JobQueue::new();
+		let mut events = queue.subscribe();
+
+		let job_id = queue
+			.enqueue("test.csv".to_string(), 1024)
+			.await
+			.expect("enqueue failed");
+		queue
+			.update_job(&job_id, |j| {
+				j.update_progress(5, 10);
+				Ok(())
+			})
+			.await
+			.expect("update failed");
+
+		let job = events.recv().await.expect("enqueue event");
+		assert_eq!(job.status, JobStatus::Queued);
+		let job = events.recv().await.expect("progress event");
+		assert_eq!(job.id, job_id);
+		assert_eq!(job.rows_processed, 5);
+	}
+
+	#[tokio::test]
+	async fn test_pending_callbacks_lists_finished_unattempted_jobs() {
+		let dir = tempfile::tempdir().expect("tempdir");
+		let db_path = dir.path().join("jobs.db");
+		let queue = JobQueue::open_sqlite(db_path.to_str().expect("path")).expect("open");
+
+		let hook = || Some("https://example.com/hook".to_string());
+		queue
+			.enqueue_job(Job::new("queued.csv".to_string(), 10).with_callback(hook()))
+			.await
+			.expect("enqueue failed");
+		let pending = queue
+			.enqueue_job(Job::new("pending.csv".to_string(), 10).with_callback(hook()))
+			.await
+			.expect("enqueue failed");
+		let attempted = queue
+			.enqueue_job(Job::new("attempted.csv".to_string(), 10).with_callback(hook()))
+			.await
+			.expect("enqueue failed");
+		let plain = queue
+			.enqueue_job(Job::new("plain.csv".to_string(), 10))
+			.await
+			.expect("enqueue failed");
+		for job_id in [&pending, &attempted, &plain] {
+			queue.cancel_job(job_id).await.expect("cancel failed");
+		}
+		queue
+			.update_job(&attempted, |j| {
+				j.callback_deliveries.push(CallbackDelivery {
+					attempt: 1,
+					attempted_at: Utc::now(),
+					status_code: Some(204),
+					error: None,
+					delivered: true,
+				});
+				Ok(())
+			})
+			.await
+			.expect("update failed");
+
+		let jobs = queue.pending_callbacks().await.expect("scan failed");
+		let ids: Vec<_> = jobs.iter().map(|j| j.id.as_str()).collect();
+		assert_eq!(ids, vec![pending.as_str()]);
+	}
 }
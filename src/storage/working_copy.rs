@@ -46,7 +46,7 @@ pub enum WorkingCopyError {
 /// Working copy manager for isolated file processing
 pub struct WorkingCopyManager {
 	working_dir: PathBuf,
-	_verify_noexec: bool,
+	verify_noexec: bool,
 	verbose: u32,
 }
 
@@ -104,7 +104,7 @@ impl WorkingCopyManager {
 
 		let manager = WorkingCopyManager {
 			working_dir: working_dir.to_path_buf(),
-			_verify_noexec: verify_noexec,
+			verify_noexec,
 			verbose,
 		};
 
@@ -123,7 +123,7 @@ impl WorkingCopyManager {
 	/// This check attempts to create a test file and verify it cannot be executed.
 	/// On systems without noexec support or where execution is not prevented,
 	/// this will return an error.
-	fn verify_noexec_mount(&self) -> Result<(), WorkingCopyError> {
+	pub fn verify_noexec_mount(&self) -> Result<(), WorkingCopyError> {
 		// NoExec verification is only meaningful on Unix systems
 		#[cfg(unix)]
 		{
@@ -291,6 +291,38 @@ impl WorkingCopyManager {
 		&self.working_dir
 	}
 
+	/// Whether `path` is a file inside the working directory
+	///
+	/// Both sides are canonicalized, so `..` components and symbolic links
+	/// cannot make an outside file look like it belongs to the manager.
+	pub fn contains(&self, path: &Path) -> bool {
+		match (self.working_dir.canonicalize(), path.canonicalize()) {
+			(Ok(working_dir), Ok(path)) => path != working_dir && path.starts_with(working_dir),
+			_ => false,
+		}
+	}
+
+	/// Whether the working directory is required to be mounted noexec
+	pub fn verifies_noexec(&self) -> bool {
+		self.verify_noexec
+	}
+
+	/// Bytes available to unprivileged users on the working directory's filesystem
+	#[cfg(unix)]
+	pub fn available_space(&self) -> io::Result<u64> {
+		let stat = rustix::fs::statvfs(&self.working_dir)?;
+		Ok(stat.f_bavail.saturating_mul(stat.f_frsize))
+	}
+
+	/// Bytes available on the working directory's filesystem (unknown on this platform)
+	#[cfg(not(unix))]
+	pub fn available_space(&self) -> io::Result<u64> {
+		Err(io::Error::new(
+			io::ErrorKind::Unsupported,
+			"free space is not available on this platform",
+		))
+	}
+
 	/// Clean up all files in the working directory
 	///
 	/// This is used for cleanup between operations or at shutdown.
@@ -388,6 +420,24 @@ mod tests {
 		assert_ne!(copy1, copy2);
 	}
 
+	#[test]
+	fn test_contains_canonicalizes_paths() {
+		let temp_dir = TempDir::new().unwrap();
+		let work_dir = temp_dir.path().join("work");
+		let manager = WorkingCopyManager::new(&work_dir, false, 0).unwrap();
+
+		let inside = work_dir.join("copy.csv");
+		fs::write(&inside, "data").unwrap();
+		let outside = temp_dir.path().join("outside.csv");
+		fs::write(&outside, "data").unwrap();
+
+		assert!(manager.contains(&inside));
+		assert!(!manager.contains(&outside));
+		assert!(!manager.contains(&work_dir.join("..").join("outside.csv")));
+		assert!(!manager.contains(&work_dir.join("missing.csv")));
+		assert!(!manager.contains(&work_dir));
+	}
+
 	#[test]
 	fn test_cleanup() {
 		let temp_dir = TempDir::new().unwrap();